@@ -54,7 +54,7 @@ pub trait GCBox: PartialEq {
     fn new_invalid() -> Self;
 }
 
-pub struct Allocator<T> {
+pub struct Allocator<T: GCBox> {
     /// Allocated number of objects in current page.
     used_in_current: usize,
     /// Total allocated objects.
@@ -78,6 +78,8 @@ pub struct Allocator<T> {
     /// Flag whether GC is enabled or not.
     pub gc_enabled: bool,
     pub malloc_threshold: usize,
+    /// Root set for GC at allocation. GC never runs at allocation while this is None.
+    root: Option<*const dyn GCRoot<T>>,
 }
 
 impl<T: GCBox> Allocator<T> {
@@ -98,9 +100,17 @@ impl<T: GCBox> Allocator<T> {
             alloc_flag: false,
             gc_enabled: true,
             malloc_threshold: MALLOC_THRESHOLD,
+            root: None,
         }
     }
 
+    ///
+    /// Set the root set for GC at allocation.
+    ///
+    pub(crate) fn set_root(&mut self, root: Option<*const dyn GCRoot<T>>) {
+        self.root = root;
+    }
+
     #[cfg(not(feature = "gc-stress"))]
     #[inline(always)]
     pub(crate) fn is_allocated(&self) -> bool {
@@ -199,8 +209,7 @@ impl<T: GCBox> Allocator<T> {
         self.print_mark();
     }
 
-    #[allow(unused)]
-    pub fn check_gc(&mut self, root: &impl GCRoot<T>) {
+    pub fn check_gc(&mut self, root: &(impl GCRoot<T> + ?Sized)) {
         let malloced = MALLOC_AMOUNT.load(std::sync::atomic::Ordering::SeqCst);
         #[cfg(not(feature = "gc-stress"))]
         {
@@ -213,7 +222,7 @@ impl<T: GCBox> Allocator<T> {
         self.gc(root);
     }
 
-    pub(crate) fn gc(&mut self, root: &impl GCRoot<T>) {
+    pub(crate) fn gc(&mut self, root: &(impl GCRoot<T> + ?Sized)) {
         if !self.gc_enabled {
             return;
         }
@@ -241,7 +250,7 @@ impl<T: GCBox> Allocator<T> {
             eprintln!("free list: {}", self.free_list_count);
         }
         self.alloc_flag = false;
-        //self.count += 1;
+        self.count += 1;
         let malloced = MALLOC_AMOUNT.load(std::sync::atomic::Ordering::SeqCst);
         self.malloc_threshold = malloced + MALLOC_THRESHOLD;
         #[cfg(any(feature = "trace", feature = "gc-debug"))]
@@ -271,6 +280,67 @@ impl<T: GCBox> Allocator<T> {
         }
         is_marked
     }
+
+    ///
+    /// Check whether *ptr* points to an allocated cell in the heap pages.
+    ///
+    pub(crate) fn is_heap_ptr(&self, ptr: *const T) -> bool {
+        let page_ptr = PageRef::from_inner(ptr as *mut T);
+        let used = if self.current == page_ptr {
+            self.used_in_current
+        } else if self.pages.iter().any(|heap| *heap == page_ptr) {
+            DATA_LEN
+        } else {
+            return false;
+        };
+        let offset = ptr as usize - page_ptr.get_data_ptr(0) as usize;
+        offset % GCBOX_SIZE == 0 && offset / GCBOX_SIZE < used
+    }
+}
+
+impl<T: GCBox + GC<T>> Allocator<T> {
+    ///
+    /// Safe point at allocation.
+    ///
+    /// *new* is the object just allocated, which is not reachable from the root set yet.
+    ///
+    pub(crate) fn check_gc_on_alloc(&mut self, new: *const T) {
+        if let Some(root) = self.root {
+            let root = AllocRoot {
+                root: unsafe { &*root },
+                new: unsafe { &*new },
+            };
+            self.check_gc(&root);
+        }
+    }
+}
+
+struct AllocRoot<'a, T: GCBox> {
+    root: &'a dyn GCRoot<T>,
+    new: &'a T,
+}
+
+impl<'a, T: GCBox + GC<T>> GC<T> for AllocRoot<'a, T> {
+    fn mark(&self, alloc: &mut Allocator<T>) {
+        self.root.mark(alloc);
+        self.new.mark(alloc);
+    }
+}
+
+impl<'a, T: GCBox + GC<T>> GCRoot<T> for AllocRoot<'a, T> {
+    fn startup_flag(&self) -> bool {
+        self.root.startup_flag()
+    }
+}
+
+///
+/// Execute *f* with GC disabled.
+///
+pub(crate) fn without_gc<R>(f: impl FnOnce() -> R) -> R {
+    let enabled = ALLOC.with(|alloc| std::mem::replace(&mut alloc.borrow_mut().gc_enabled, false));
+    let res = f();
+    ALLOC.with(|alloc| alloc.borrow_mut().gc_enabled = enabled);
+    res
 }
 
 impl<T: GCBox> Allocator<T> {
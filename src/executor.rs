@@ -2,14 +2,18 @@ use super::*;
 
 mod builtins;
 mod bytecodegen;
+mod fiber;
 mod globals;
 mod inst;
 mod op;
 pub use builtins::*;
 use bytecodegen::*;
+pub use fiber::*;
 pub use globals::*;
 use inst::*;
 use op::*;
+use std::cell::{Cell, RefCell};
+use std::rc::{Rc, Weak};
 
 type Result<T> = std::result::Result<T, MonorubyErr>;
 pub type BuiltinFn =
@@ -53,6 +57,155 @@ impl CFP {
     fn bp(&self) -> *const usize {
         unsafe { self.0.add(OFFSET_CFP as usize / 8) as _ }
     }
+
+    fn func_id(&self) -> FuncId {
+        Meta::new(unsafe { *self.bp().sub(OFFSET_META as usize / 8) as u64 }).func_id()
+    }
+
+    ///
+    /// Set *val* to the register *reg* of this frame.
+    ///
+    fn set_register(&self, reg: usize, val: Value) {
+        unsafe { *(self.bp().sub(OFFSET_SELF as usize / 8 + reg) as *mut Value) = val }
+    }
+
+    ///
+    /// Mark the block, *self* and registers of this frame.
+    ///
+    /// Registers may hold stale values (e.g. slots linked to xmm registers in JIT code, or
+    /// temporary registers not yet initialized), so only the values pointing to live
+    /// objects are marked.
+    ///
+    fn mark(&self, alloc: &mut Allocator<RValue>) {
+        let bp = self.bp();
+        let meta = Meta::new(unsafe { *bp.sub(OFFSET_META as usize / 8) as u64 });
+        let reg_num = match meta.kind() {
+            // reg_num of native functions holds the number of arguments.
+            2 => meta.reg_num().max(0) + 1,
+            _ => meta.reg_num().max(0),
+        } as usize;
+        mark_slot(alloc, unsafe { *bp.sub(OFFSET_BLOCK as usize / 8) } as u64);
+        for r in 0..reg_num {
+            let v = unsafe { *bp.sub(OFFSET_SELF as usize / 8 + r) };
+            mark_slot(alloc, v as u64);
+        }
+        // outer frames may have been moved to the heap.
+        let outer = unsafe { *bp.sub(OFFSET_OUTER as usize / 8) };
+        mark_env(alloc, outer);
+    }
+}
+
+///
+/// Environment captured by a Proc.
+///
+/// This holds the address of the *outer* slot of the frame in which the Proc was created.
+/// While the frame is alive, the address points to the stack. When the frame exits,
+/// the frame is moved to the heap, and the address is updated to point to the heap frame.
+///
+/// The heap frame is owned by the environment, and freed when all the Procs and the inner
+/// environments sharing it are dropped.
+///
+#[derive(Clone)]
+pub struct Env(Rc<EnvInner>);
+
+struct EnvInner {
+    /// the address of the *outer* slot of the frame.
+    outer: Cell<usize>,
+    /// the frame moved to the heap.
+    heap_frame: Cell<Option<Box<[usize]>>>,
+    /// the environment of the outer frame, which must live as long as this frame.
+    parent: RefCell<Option<Env>>,
+}
+
+impl std::fmt::Debug for Env {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Env({:016x})", self.0.outer.get())
+    }
+}
+
+impl GC<RValue> for Env {
+    fn mark(&self, alloc: &mut Allocator<RValue>) {
+        mark_env(alloc, self.0.outer.get());
+    }
+}
+
+impl Env {
+    fn new(outer: usize) -> Self {
+        Self(Rc::new(EnvInner {
+            outer: Cell::new(outer),
+            heap_frame: Cell::new(None),
+            parent: RefCell::new(None),
+        }))
+    }
+
+    ///
+    /// Get the environment which owns the heap frame of *outer_slot*.
+    ///
+    /// The address of the environment is stored just above the *cfp* slot of the heap frame.
+    ///
+    fn from_heap_frame(outer_slot: usize) -> Self {
+        let bp = outer_slot + OFFSET_OUTER as usize;
+        let ptr = unsafe { *(bp as *const *const EnvInner) };
+        unsafe {
+            Rc::increment_strong_count(ptr);
+            Self(Rc::from_raw(ptr))
+        }
+    }
+
+    fn set_parent(&self, parent: Env) {
+        *self.0.parent.borrow_mut() = Some(parent);
+    }
+
+    ///
+    /// Move *frame* to the heap, and make the environment point to it.
+    ///
+    /// *frame* holds the slots from the lowest address to the *cfp* slot. Returns the address
+    /// of the *outer* slot of the heap frame.
+    ///
+    fn set_heap_frame(&self, mut frame: Vec<usize>) -> usize {
+        let len = frame.len();
+        frame.push(Rc::as_ptr(&self.0) as usize);
+        let mut frame = frame.into_boxed_slice();
+        let heap_bp = frame.as_mut_ptr() as usize + len * 8;
+        self.0.heap_frame.set(Some(frame));
+        let outer_slot = heap_bp - OFFSET_OUTER as usize;
+        self.0.outer.set(outer_slot);
+        outer_slot
+    }
+
+    ///
+    /// Get *CFP* of the frame which is used as the outer frame of the block.
+    ///
+    pub(crate) fn outer_cfp(&self) -> CFP {
+        CFP((self.0.outer.get() + (OFFSET_OUTER - OFFSET_CFP) as usize) as _)
+    }
+}
+
+///
+/// Mark the blocks, *self*s and registers of the outer frames.
+///
+/// *outer* is the address of the *outer* slot of the innermost frame.
+///
+fn mark_env(alloc: &mut Allocator<RValue>, outer: usize) {
+    let mut outer = outer as *const usize;
+    while !outer.is_null() {
+        let meta = Meta::new(unsafe { *outer.sub(1) } as u64);
+        mark_slot(alloc, unsafe { *outer.sub(2) } as u64);
+        for r in 0..meta.reg_num().max(0) as usize {
+            mark_slot(alloc, unsafe { *outer.sub(3 + r) } as u64);
+        }
+        outer = unsafe { *outer } as _;
+    }
+}
+
+fn mark_slot(alloc: &mut Allocator<RValue>, v: u64) {
+    if v == 0 || v & 0b111 != 0 || !alloc.is_heap_ptr(v as *const RValue) {
+        return;
+    }
+    let rvalue = unsafe { &*(v as *const RValue) };
+    if rvalue.is_valid() {
+        rvalue.mark(alloc);
+    }
 }
 
 ///
@@ -63,6 +216,68 @@ impl CFP {
 pub struct Executor {
     pub cfp: CFP,
     lexical_class: Vec<ClassId>,
+    /// the visibility of methods defined by `def` in each class context (parallel to
+    /// *lexical_class*), and whether they are defined as module functions.
+    def_visibility: Vec<(Visibility, bool)>,
+    /// frames captured by Procs, which are moved to the heap when they exit.
+    /// (shared with Fibers, whose stacks may be freed while holding captured frames)
+    escaped: Rc<RefCell<HashMap<usize, EscapedFrame>>>,
+    /// temporary values held by native functions, which are marked as GC roots.
+    temp_stack: Vec<Value>,
+    /// Arrays collecting the values yielded in `collect_each`.
+    collectors: Vec<Collector>,
+    /// blocks called with the indexes in `each_with_index_by`.
+    indexers: Vec<Indexer>,
+    /// Fibers being resumed. (innermost last)
+    fibers: Vec<Value>,
+}
+
+///
+/// An Array which collects the values yielded to the block given by a native function.
+///
+struct Collector {
+    /// bp of the frame of the native function.
+    bp: usize,
+    ary: Value,
+    /// the iteration is stopped when *ary* reaches this length.
+    limit: Option<usize>,
+}
+
+///
+/// A block which is called with the index by the block given by a native function.
+///
+struct Indexer {
+    /// bp of the frame of the native function.
+    bp: usize,
+    /// the block given to the native function.
+    block: Value,
+    index: i64,
+}
+
+///
+/// A frame on the stack captured by Procs.
+///
+struct EscapedFrame {
+    /// the original return address of the frame.
+    ret_addr: usize,
+    /// the environment shared by the Procs.
+    env: Env,
+    /// the environments of the heap frames pointing to this frame.
+    dependents: Vec<Weak<EnvInner>>,
+}
+
+impl GC<RValue> for Executor {
+    fn mark(&self, alloc: &mut Allocator<RValue>) {
+        let mut cfp = self.cfp;
+        while !cfp.is_null() {
+            cfp.mark(alloc);
+            cfp = cfp.next();
+        }
+        self.temp_stack.iter().for_each(|v| v.mark(alloc));
+        self.collectors.iter().for_each(|c| c.ary.mark(alloc));
+        self.indexers.iter().for_each(|i| i.block.mark(alloc));
+        self.fibers.iter().for_each(|f| f.mark(alloc));
+    }
 }
 
 impl Executor {
@@ -77,7 +292,9 @@ impl Executor {
         globals.dump_bc();
 
         let entry_point = globals.codegen.entry_point;
+        let prev_executor = globals.set_gc_root(self);
         let res = entry_point(self, globals, main_data);
+        globals.set_gc_root(prev_executor);
         globals.flush_stdout();
         #[cfg(feature = "log-jit")]
         {
@@ -123,12 +340,38 @@ impl Executor {
 
     pub(crate) fn push_class_context(&mut self, class_id: ClassId) {
         self.lexical_class.push(class_id);
+        self.def_visibility.push((Visibility::Public, false));
     }
 
     pub(crate) fn pop_class_context(&mut self) -> Option<ClassId> {
+        self.def_visibility.pop();
         self.lexical_class.pop()
     }
 
+    ///
+    /// Get the visibility of methods defined by `def` in the current class context, and
+    /// whether they are defined as module functions.
+    ///
+    /// Methods defined at the top level are private.
+    ///
+    pub(crate) fn get_def_visibility(&self) -> (Visibility, bool) {
+        self.def_visibility
+            .last()
+            .cloned()
+            .unwrap_or((Visibility::Private, false))
+    }
+
+    ///
+    /// Set the visibility of methods defined by `def` in the current class context.
+    ///
+    /// This has no effect at the top level.
+    ///
+    pub(crate) fn set_def_visibility(&mut self, visibility: Visibility, module_function: bool) {
+        if let Some(last) = self.def_visibility.last_mut() {
+            *last = (visibility, module_function);
+        }
+    }
+
     pub(crate) fn get_class_context(&self) -> ClassId {
         self.lexical_class.last().cloned().unwrap_or(OBJECT_CLASS)
     }
@@ -136,6 +379,214 @@ impl Executor {
     pub(crate) fn class_context_stack(&self) -> &[ClassId] {
         &self.lexical_class
     }
+
+    ///
+    /// Get the lexical class context of the function *func_id*. (innermost last)
+    ///
+    pub(crate) fn lexical_context(&self, globals: &Globals, func_id: FuncId) -> Vec<ClassId> {
+        globals
+            .func
+            .get_lexical_context(func_id)
+            .unwrap_or_else(|| self.lexical_class.clone())
+    }
+
+    ///
+    /// Get the lexical class context of the caller of the current native function.
+    ///
+    pub(crate) fn caller_lexical_context(&self, globals: &Globals) -> Vec<ClassId> {
+        let func_id = self.cfp.next().func_id();
+        self.lexical_context(globals, func_id)
+    }
+}
+
+impl Executor {
+    ///
+    /// Get the address of the register which holds `$~` for the frame *cfp*.
+    ///
+    /// `$~` is local to the method frame, so the outer frames of blocks are followed.
+    /// Returns None if the frame is a native function or its method does not use `$~`.
+    ///
+    fn special_var_addr(&self, globals: &Globals, cfp: CFP) -> Option<*mut Value> {
+        let mut outer = unsafe { cfp.bp().sub(OFFSET_OUTER as usize / 8) };
+        loop {
+            let next = unsafe { *outer };
+            if next == 0 {
+                break;
+            }
+            outer = next as *const usize;
+        }
+        let meta = Meta::new(unsafe { *outer.sub(1) } as u64);
+        let reg = globals.func[meta.func_id()]
+            .is_ruby_func()?
+            .special_var_reg()?;
+        Some(unsafe { outer.sub(3 + reg) } as *mut Value)
+    }
+
+    ///
+    /// Get `$~` of the current Ruby frame.
+    ///
+    pub(crate) fn get_last_match(&self, globals: &Globals) -> Value {
+        self.last_match_of(globals, self.cfp)
+    }
+
+    ///
+    /// Get `$~` of the caller of the current native function.
+    ///
+    pub(crate) fn caller_last_match(&self, globals: &Globals) -> Value {
+        self.last_match_of(globals, self.cfp.next())
+    }
+
+    fn last_match_of(&self, globals: &Globals, cfp: CFP) -> Value {
+        match self.special_var_addr(globals, cfp) {
+            Some(addr) => unsafe { *addr },
+            None => Value::nil(),
+        }
+    }
+
+    ///
+    /// Set `$~` of the caller of the current native function.
+    ///
+    pub(crate) fn set_last_match(&self, globals: &Globals, val: Value) {
+        if let Some(addr) = self.special_var_addr(globals, self.cfp.next()) {
+            unsafe { *addr = val };
+        }
+    }
+}
+
+impl Executor {
+    ///
+    /// Get FuncId of the block and CFP of its outer frame from the block handler *bh*.
+    ///
+    pub(crate) fn decode_block_handler(&self, bh: i64) -> (FuncId, CFP) {
+        Self::decode_block_handler_from(self.cfp, bh)
+    }
+
+    ///
+    /// Decode the block handler *bh* which was given to the frame of *cfp*.
+    ///
+    fn decode_block_handler_from(cfp: CFP, bh: i64) -> (FuncId, CFP) {
+        let func_id = FuncId(u32::try_from((bh as u64) >> 16).unwrap());
+        let mut cfp = cfp;
+        for _ in 0..bh as i16 as u16 {
+            cfp = cfp.next();
+        }
+        (func_id, cfp)
+    }
+
+    ///
+    /// Get FuncId of the function running in the current frame.
+    ///
+    pub(crate) fn current_func_id(&self) -> FuncId {
+        self.cfp.func_id()
+    }
+
+    ///
+    /// Generate a Proc object from *block_handler*.
+    ///
+    /// If *block_handler* is already a Proc, it is returned as it is.
+    ///
+    pub(crate) fn generate_proc(
+        &mut self,
+        globals: &Globals,
+        block_handler: Value,
+        is_lambda: bool,
+    ) -> Value {
+        let bh = match block_handler.try_fixnum() {
+            Some(bh) => bh,
+            None => return block_handler,
+        };
+        let (func_id, outer_cfp) = self.decode_block_handler(bh);
+        let env = self.escape_frame(globals, outer_cfp);
+        Value::new_proc(ProcInner::new(func_id, env, is_lambda))
+    }
+
+    ///
+    /// Mark the frame of *cfp* and its outer frames as captured, and get the environment.
+    ///
+    /// The return addresses of the captured frames are replaced with the escape trampoline,
+    /// so that the frames are moved to the heap when they exit.
+    ///
+    fn escape_frame(&mut self, globals: &Globals, cfp: CFP) -> Env {
+        let mut bp = cfp.bp() as usize;
+        let mut env: Option<Env> = None;
+        // the innermost environment created in this loop, whose parent is not set yet.
+        let mut inner: Option<Env> = None;
+        loop {
+            let outer_slot = bp - OFFSET_OUTER as usize;
+            if let Some(frame) = self.escaped.borrow().get(&bp) {
+                if let Some(inner) = inner {
+                    inner.set_parent(frame.env.clone());
+                }
+                env.get_or_insert(frame.env.clone());
+                break;
+            }
+            let ret_addr = unsafe { &mut *((bp + 8) as *mut usize) };
+            let frame_env = Env::new(outer_slot);
+            self.escaped.borrow_mut().insert(
+                bp,
+                EscapedFrame {
+                    ret_addr: *ret_addr,
+                    env: frame_env.clone(),
+                    dependents: vec![],
+                },
+            );
+            *ret_addr = globals.codegen.escape_trampoline;
+            if let Some(inner) = inner.replace(frame_env.clone()) {
+                inner.set_parent(frame_env.clone());
+            }
+            env.get_or_insert(frame_env);
+            let outer = unsafe { *(outer_slot as *const usize) };
+            if outer == 0 {
+                break;
+            }
+            if Meta::new(unsafe { *((outer - 8) as *const usize) } as u64).is_on_heap() {
+                inner.unwrap().set_parent(Env::from_heap_frame(outer));
+                break;
+            }
+            bp = outer + OFFSET_OUTER as usize;
+        }
+        self.set_fibers_escaped();
+        env.unwrap()
+    }
+
+    ///
+    /// Move the exiting frame *bp* to the heap, and return the original return address.
+    ///
+    pub(crate) fn exit_escaped_frame(&mut self, bp: usize) -> usize {
+        exit_escaped_frame(&mut self.escaped.borrow_mut(), bp)
+    }
+}
+
+///
+/// Move the frame *bp* captured by Procs to the heap, and return the original return address.
+///
+fn exit_escaped_frame(escaped: &mut HashMap<usize, EscapedFrame>, bp: usize) -> usize {
+    let EscapedFrame {
+        ret_addr,
+        env,
+        dependents,
+    } = escaped.remove(&bp).unwrap();
+    let mut meta = Meta::new(unsafe { *((bp - OFFSET_META as usize) as *const u64) });
+    // cfp, outer, meta, block, and registers.
+    let len = 4 + meta.reg_num().max(0) as usize;
+    let mut frame: Vec<usize> = (1..=len)
+        .rev()
+        .map(|i| unsafe { *((bp - 8 * i) as *const usize) })
+        .collect();
+    meta.set_on_heap();
+    frame[len - OFFSET_META as usize / 8] = meta.get() as usize;
+    let outer_slot = env.set_heap_frame(frame);
+    // the heap frames which are still alive are redirected to the new heap frame.
+    for dep in dependents.iter().filter_map(|dep| dep.upgrade()) {
+        unsafe { *(dep.outer.get() as *mut usize) = outer_slot };
+    }
+    let outer = unsafe { *(outer_slot as *const usize) };
+    if outer != 0 {
+        if let Some(frame) = escaped.get_mut(&(outer + OFFSET_OUTER as usize)) {
+            frame.dependents.push(Rc::downgrade(&env.0));
+        }
+    }
+    ret_addr
 }
 
 impl Executor {
@@ -147,8 +598,9 @@ impl Executor {
         globals: &mut Globals,
         site_id: ConstSiteId,
     ) -> Option<Value> {
-        let current = self.class_context_stack();
-        globals.find_constant(site_id, current)
+        let func_id = globals.func[site_id].func_id;
+        let current = self.lexical_context(globals, func_id);
+        globals.find_constant(site_id, &current)
     }
 
     pub(crate) fn set_constant(&self, globals: &mut Globals, name: IdentId, val: Value) {
@@ -172,11 +624,201 @@ impl Executor {
         method: IdentId,
         receiver: Value,
         args: &[Value],
+    ) -> Option<Value> {
+        self.invoke_method_with_block(globals, method, receiver, args, None)
+    }
+
+    ///
+    /// Invoke method for *receiver* and *method* with the block handler *block*.
+    ///
+    pub(crate) fn invoke_method_with_block(
+        &mut self,
+        globals: &mut Globals,
+        method: IdentId,
+        receiver: Value,
+        args: &[Value],
+        block: Option<Value>,
     ) -> Option<Value> {
         let len = args.len();
-        let func_id = globals.find_method_checked(receiver, method, len)?;
+        let func_id = match globals.find_method_checked(receiver, method, len) {
+            Some(func_id) => func_id,
+            None => return self.invoke_method_missing(globals, method, receiver, args, block),
+        };
+        let data = globals.compile_on_demand(func_id) as *const _;
+        (globals.codegen.method_invoker)(
+            self,
+            globals,
+            data,
+            receiver,
+            args.as_ptr(),
+            args.len(),
+            block,
+        )
+    }
+
+    ///
+    /// Invoke `method_missing` of *receiver* with the name *method* and *args*, after the
+    /// lookup of *method* failed.
+    ///
+    /// If the lookup failed for other reasons than that the method was not found or not
+    /// accessible, or `method_missing` is not overridden, the error is kept and None is returned.
+    ///
+    pub(crate) fn invoke_method_missing(
+        &mut self,
+        globals: &mut Globals,
+        method: IdentId,
+        receiver: Value,
+        args: &[Value],
+        block: Option<Value>,
+    ) -> Option<Value> {
+        if !globals.is_method_missing_error() {
+            return None;
+        }
+        let func_id = globals.find_method(receiver, IdentId::_METHOD_MISSING)?;
+        // the default method_missing is a builtin, which raises the error.
+        if globals.func[func_id].is_ruby_func().is_none() {
+            return None;
+        }
+        globals.take_error();
+        let mut mm_args = vec![Value::new_symbol(method)];
+        mm_args.extend_from_slice(args);
+        globals.check_arg(func_id, mm_args.len())?;
         let data = globals.compile_on_demand(func_id) as *const _;
-        (globals.codegen.method_invoker)(self, globals, data, receiver, args.as_ptr(), args.len())
+        (globals.codegen.method_invoker)(
+            self,
+            globals,
+            data,
+            receiver,
+            mm_args.as_ptr(),
+            mm_args.len(),
+            block,
+        )
+    }
+
+    ///
+    /// Call `each` of *receiver* and collect the yielded values into an Array.
+    ///
+    /// Multiple values yielded at once are packed into an Array.
+    /// If *limit* is given, the iteration is stopped when *limit* values were collected.
+    ///
+    /// This must be called from a native function, and the frame of the native function
+    /// is used as the outer frame of the block.
+    ///
+    pub(crate) fn collect_each(
+        &mut self,
+        globals: &mut Globals,
+        receiver: Value,
+        limit: Option<usize>,
+    ) -> Option<Value> {
+        let ary = Value::new_array_from_vec(vec![]);
+        if limit == Some(0) {
+            return Some(ary);
+        }
+        let bp = self.cfp.bp() as usize;
+        self.collectors.push(Collector { bp, ary, limit });
+        let func_id = globals.func.enum_yielder();
+        let block = Value::new_integer(((func_id.0 as i64) << 16) + 1);
+        let res = self.invoke_method_with_block(globals, IdentId::EACH, receiver, &[], Some(block));
+        self.collectors.pop();
+        match res {
+            Some(_) => Some(ary),
+            // the iteration was stopped by the block.
+            None if globals.method_return_target() == Some(bp) => {
+                globals.take_error();
+                Some(ary)
+            }
+            None => None,
+        }
+    }
+
+    ///
+    /// Push *val* to the collector of the outer frame of the current block.
+    ///
+    /// If the collector reached its limit, an error which unwinds frames up to
+    /// the outer frame is set, and None is returned.
+    ///
+    pub(crate) fn push_collected(&mut self, globals: &mut Globals, val: Value) -> Option<Value> {
+        let outer =
+            unsafe { *self.cfp.bp().sub(OFFSET_OUTER as usize / 8) } + OFFSET_OUTER as usize;
+        let collector = self
+            .collectors
+            .iter_mut()
+            .rev()
+            .find(|c| c.bp == outer)
+            .unwrap();
+        collector.ary.as_array_mut().push(val);
+        if Some(collector.ary.as_array().len()) == collector.limit {
+            globals.set_error(MonorubyErr::method_return(Value::nil(), collector.bp));
+            return None;
+        }
+        Some(Value::nil())
+    }
+
+    ///
+    /// Call *method* of *receiver* with a block which calls *block* with the yielded value
+    /// and its index starting from *offset*.
+    ///
+    /// Multiple values yielded at once are packed into an Array.
+    /// This must be called from a native function, and *block* is the block given to it.
+    ///
+    pub(crate) fn each_with_index_by(
+        &mut self,
+        globals: &mut Globals,
+        receiver: Value,
+        method: IdentId,
+        block: Value,
+        offset: i64,
+    ) -> Option<Value> {
+        let bp = self.cfp.bp() as usize;
+        self.indexers.push(Indexer {
+            bp,
+            block,
+            index: offset,
+        });
+        let func_id = globals.func.index_yielder();
+        let handler = Value::new_integer(((func_id.0 as i64) << 16) + 1);
+        let res = self.invoke_method_with_block(globals, method, receiver, &[], Some(handler));
+        self.indexers.pop();
+        res
+    }
+
+    ///
+    /// Call the block of the indexer of the outer frame of the current block with *val* and
+    /// the index.
+    ///
+    pub(crate) fn yield_with_index(&mut self, globals: &mut Globals, val: Value) -> Option<Value> {
+        let outer =
+            unsafe { *self.cfp.bp().sub(OFFSET_OUTER as usize / 8) } + OFFSET_OUTER as usize;
+        let indexer = self
+            .indexers
+            .iter_mut()
+            .rev()
+            .find(|i| i.bp == outer)
+            .unwrap();
+        let index = Value::new_integer(indexer.index);
+        indexer.index += 1;
+        let block = indexer.block;
+        // the block handler is relative to the frame of the native function.
+        let data = match block.try_fixnum() {
+            Some(bh) => {
+                let cfp = CFP((outer - OFFSET_CFP as usize) as _);
+                let (func_id, outer_cfp) = Self::decode_block_handler_from(cfp, bh);
+                BlockData {
+                    outer_cfp,
+                    func_data: globals.compile_on_demand(func_id) as _,
+                }
+            }
+            None => globals.get_block_data(block, self),
+        };
+        let args = [val, index];
+        (globals.codegen.block_invoker)(
+            self,
+            globals,
+            &data as _,
+            Value::nil(),
+            args.as_ptr(),
+            args.len(),
+        )
     }
 
     ///
@@ -200,6 +842,19 @@ impl Executor {
         )
     }
 
+    ///
+    /// Execute *f* with *val* held as a GC root.
+    ///
+    /// Native functions use this to keep objects which are not reachable from
+    /// the stack (e.g. an array under construction) alive while invoking blocks.
+    ///
+    pub(crate) fn with_temp<R>(&mut self, val: Value, f: impl FnOnce(&mut Self) -> R) -> R {
+        self.temp_stack.push(val);
+        let res = f(self);
+        self.temp_stack.pop();
+        res
+    }
+
     ///
     /// Invoke func with *args*: Args.
     ///
@@ -265,9 +920,11 @@ impl std::ops::Add<u16> for SlotId {
 /// kind:   0 VM
 ///         1 JIT
 ///         2 NATIVE
+///         3 HEAP (a frame moved to the heap)
 ///
 /// mode:   0 method
 ///         1 class def
+///         2 lambda
 /// ~~~
 ///
 #[derive(Clone, Copy, PartialEq, Eq, Default)]
@@ -288,11 +945,13 @@ impl std::fmt::Debug for Meta {
                 0 => "VM",
                 1 => "JIT",
                 2 => "NATIVE",
+                3 => "HEAP",
                 _ => "INVALID",
             },
             match self.mode() {
                 0 => "method",
                 1 => "class_def",
+                2 => "lambda",
                 _ => "INVALID",
             },
             self.func_id(),
@@ -366,6 +1025,28 @@ impl Meta {
         self.kind = 1;
     }
 
+    ///
+    /// Set HEAP flag in Meta.
+    ///
+    pub(crate) fn set_on_heap(&mut self) {
+        self.kind = 3;
+    }
+
+    pub(crate) fn is_on_heap(&self) -> bool {
+        self.kind == 3
+    }
+
+    ///
+    /// Set lambda flag in Meta.
+    ///
+    pub(crate) fn set_lambda(&mut self) {
+        self.mode = 2;
+    }
+
+    pub(crate) fn is_lambda(&self) -> bool {
+        self.mode == 2
+    }
+
     ///
     /// Set the number of registers in Meta.
     ///
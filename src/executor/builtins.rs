@@ -2,15 +2,32 @@ use super::*;
 
 mod array;
 mod class;
+mod comparable;
+mod complex;
+mod enumerable;
+mod enumerator;
+mod exception;
+mod fiber;
 mod file;
 mod float;
+mod gc;
+mod hash;
 mod integer;
 mod math;
+mod numeric;
 mod object;
+mod procedure;
 mod range;
+mod rational;
+mod regexp;
 mod string;
+mod struct_class;
+mod symbol;
 mod time;
 
+pub(crate) use enumerable::enum_yielder;
+pub(crate) use enumerator::{fiber_yielder, index_yielder};
+pub(crate) use symbol::call_symbol;
 pub use time::TimeInfo;
 
 //
@@ -24,10 +41,16 @@ pub(crate) fn init_builtins(globals: &mut Globals) {
             .define_builtin_class("Object", OBJECT_CLASS, None, OBJECT_CLASS)
             .as_class()
     );
+    assert_eq!(
+        MODULE_CLASS,
+        globals
+            .define_builtin_class_under_obj("Module", MODULE_CLASS)
+            .as_class()
+    );
     assert_eq!(
         CLASS_CLASS,
         globals
-            .define_builtin_class_under_obj("Class", CLASS_CLASS)
+            .define_builtin_class("Class", CLASS_CLASS, MODULE_CLASS, OBJECT_CLASS)
             .as_class()
     );
     assert_eq!(
@@ -48,16 +71,34 @@ pub(crate) fn init_builtins(globals: &mut Globals) {
             .define_builtin_class_under_obj("FalseClass", FALSE_CLASS)
             .as_class()
     );
+    assert_eq!(
+        NUMERIC_CLASS,
+        globals
+            .define_builtin_class_under_obj("Numeric", NUMERIC_CLASS)
+            .as_class()
+    );
     assert_eq!(
         INTEGER_CLASS,
         globals
-            .define_builtin_class_under_obj("Integer", INTEGER_CLASS)
+            .define_builtin_class("Integer", INTEGER_CLASS, NUMERIC_CLASS, OBJECT_CLASS)
             .as_class()
     );
     assert_eq!(
         FLOAT_CLASS,
         globals
-            .define_builtin_class_under_obj("Float", FLOAT_CLASS)
+            .define_builtin_class("Float", FLOAT_CLASS, NUMERIC_CLASS, OBJECT_CLASS)
+            .as_class()
+    );
+    assert_eq!(
+        RATIONAL_CLASS,
+        globals
+            .define_builtin_class("Rational", RATIONAL_CLASS, NUMERIC_CLASS, OBJECT_CLASS)
+            .as_class()
+    );
+    assert_eq!(
+        COMPLEX_CLASS,
+        globals
+            .define_builtin_class("Complex", COMPLEX_CLASS, NUMERIC_CLASS, OBJECT_CLASS)
             .as_class()
     );
     assert_eq!(
@@ -90,19 +131,304 @@ pub(crate) fn init_builtins(globals: &mut Globals) {
             .define_builtin_class_under_obj("Range", RANGE_CLASS)
             .as_class()
     );
-    let math_class = globals.define_class_under_obj("Math").as_class();
+    assert_eq!(
+        EXCEPTION_CLASS,
+        globals
+            .define_builtin_class_under_obj("Exception", EXCEPTION_CLASS)
+            .as_class()
+    );
+    assert_eq!(
+        STANDARD_ERROR_CLASS,
+        globals
+            .define_builtin_class(
+                "StandardError",
+                STANDARD_ERROR_CLASS,
+                EXCEPTION_CLASS,
+                OBJECT_CLASS
+            )
+            .as_class()
+    );
+    assert_eq!(
+        RUNTIME_ERROR_CLASS,
+        globals
+            .define_builtin_class(
+                "RuntimeError",
+                RUNTIME_ERROR_CLASS,
+                STANDARD_ERROR_CLASS,
+                OBJECT_CLASS
+            )
+            .as_class()
+    );
+    assert_eq!(
+        NAME_ERROR_CLASS,
+        globals
+            .define_builtin_class(
+                "NameError",
+                NAME_ERROR_CLASS,
+                STANDARD_ERROR_CLASS,
+                OBJECT_CLASS
+            )
+            .as_class()
+    );
+    assert_eq!(
+        NO_METHOD_ERROR_CLASS,
+        globals
+            .define_builtin_class(
+                "NoMethodError",
+                NO_METHOD_ERROR_CLASS,
+                NAME_ERROR_CLASS,
+                OBJECT_CLASS
+            )
+            .as_class()
+    );
+    assert_eq!(
+        ZERO_DIVISION_ERROR_CLASS,
+        globals
+            .define_builtin_class(
+                "ZeroDivisionError",
+                ZERO_DIVISION_ERROR_CLASS,
+                STANDARD_ERROR_CLASS,
+                OBJECT_CLASS
+            )
+            .as_class()
+    );
+    assert_eq!(
+        TYPE_ERROR_CLASS,
+        globals
+            .define_builtin_class(
+                "TypeError",
+                TYPE_ERROR_CLASS,
+                STANDARD_ERROR_CLASS,
+                OBJECT_CLASS
+            )
+            .as_class()
+    );
+    assert_eq!(
+        INDEX_ERROR_CLASS,
+        globals
+            .define_builtin_class(
+                "IndexError",
+                INDEX_ERROR_CLASS,
+                STANDARD_ERROR_CLASS,
+                OBJECT_CLASS
+            )
+            .as_class()
+    );
+    assert_eq!(
+        FROZEN_ERROR_CLASS,
+        globals
+            .define_builtin_class(
+                "FrozenError",
+                FROZEN_ERROR_CLASS,
+                RUNTIME_ERROR_CLASS,
+                OBJECT_CLASS
+            )
+            .as_class()
+    );
+    assert_eq!(
+        SCRIPT_ERROR_CLASS,
+        globals
+            .define_builtin_class(
+                "ScriptError",
+                SCRIPT_ERROR_CLASS,
+                EXCEPTION_CLASS,
+                OBJECT_CLASS
+            )
+            .as_class()
+    );
+    assert_eq!(
+        LOAD_ERROR_CLASS,
+        globals
+            .define_builtin_class(
+                "LoadError",
+                LOAD_ERROR_CLASS,
+                SCRIPT_ERROR_CLASS,
+                OBJECT_CLASS
+            )
+            .as_class()
+    );
+    assert_eq!(
+        ARGUMENT_ERROR_CLASS,
+        globals
+            .define_builtin_class(
+                "ArgumentError",
+                ARGUMENT_ERROR_CLASS,
+                STANDARD_ERROR_CLASS,
+                OBJECT_CLASS
+            )
+            .as_class()
+    );
+    assert_eq!(
+        RANGE_ERROR_CLASS,
+        globals
+            .define_builtin_class(
+                "RangeError",
+                RANGE_ERROR_CLASS,
+                STANDARD_ERROR_CLASS,
+                OBJECT_CLASS
+            )
+            .as_class()
+    );
+    assert_eq!(
+        HASH_CLASS,
+        globals
+            .define_builtin_class_under_obj("Hash", HASH_CLASS)
+            .as_class()
+    );
+    assert_eq!(
+        KEY_ERROR_CLASS,
+        globals
+            .define_builtin_class("KeyError", KEY_ERROR_CLASS, INDEX_ERROR_CLASS, OBJECT_CLASS)
+            .as_class()
+    );
+    assert_eq!(
+        COMPARABLE_MODULE,
+        globals
+            .define_builtin_module_under_obj("Comparable", COMPARABLE_MODULE)
+            .as_class()
+    );
+    assert_eq!(
+        ENUMERABLE_MODULE,
+        globals
+            .define_builtin_module_under_obj("Enumerable", ENUMERABLE_MODULE)
+            .as_class()
+    );
+    assert_eq!(
+        PROC_CLASS,
+        globals
+            .define_builtin_class_under_obj("Proc", PROC_CLASS)
+            .as_class()
+    );
+    assert_eq!(
+        LOCAL_JUMP_ERROR_CLASS,
+        globals
+            .define_builtin_class(
+                "LocalJumpError",
+                LOCAL_JUMP_ERROR_CLASS,
+                STANDARD_ERROR_CLASS,
+                OBJECT_CLASS
+            )
+            .as_class()
+    );
+    assert_eq!(
+        REGEXP_CLASS,
+        globals
+            .define_builtin_class_under_obj("Regexp", REGEXP_CLASS)
+            .as_class()
+    );
+    assert_eq!(
+        MATCHDATA_CLASS,
+        globals
+            .define_builtin_class_under_obj("MatchData", MATCHDATA_CLASS)
+            .as_class()
+    );
+    assert_eq!(
+        REGEXP_ERROR_CLASS,
+        globals
+            .define_builtin_class(
+                "RegexpError",
+                REGEXP_ERROR_CLASS,
+                STANDARD_ERROR_CLASS,
+                OBJECT_CLASS
+            )
+            .as_class()
+    );
+    assert_eq!(
+        FIBER_CLASS,
+        globals
+            .define_builtin_class_under_obj("Fiber", FIBER_CLASS)
+            .as_class()
+    );
+    assert_eq!(
+        FIBER_ERROR_CLASS,
+        globals
+            .define_builtin_class(
+                "FiberError",
+                FIBER_ERROR_CLASS,
+                STANDARD_ERROR_CLASS,
+                OBJECT_CLASS
+            )
+            .as_class()
+    );
+    assert_eq!(
+        ENUMERATOR_CLASS,
+        globals
+            .define_builtin_class_under_obj("Enumerator", ENUMERATOR_CLASS)
+            .as_class()
+    );
+    assert_eq!(
+        YIELDER_CLASS,
+        globals
+            .define_builtin_class("Yielder", YIELDER_CLASS, OBJECT_CLASS, ENUMERATOR_CLASS)
+            .as_class()
+    );
+    assert_eq!(
+        LAZY_CLASS,
+        globals
+            .define_builtin_class("Lazy", LAZY_CLASS, ENUMERATOR_CLASS, ENUMERATOR_CLASS)
+            .as_class()
+    );
+    assert_eq!(
+        STOP_ITERATION_CLASS,
+        globals
+            .define_builtin_class(
+                "StopIteration",
+                STOP_ITERATION_CLASS,
+                INDEX_ERROR_CLASS,
+                OBJECT_CLASS
+            )
+            .as_class()
+    );
+    assert_eq!(
+        STRUCT_CLASS,
+        globals
+            .define_builtin_class_under_obj("Struct", STRUCT_CLASS)
+            .as_class()
+    );
+    for class_id in [NUMERIC_CLASS, STRING_CLASS, SYMBOL_CLASS] {
+        globals
+            .include_module(class_id, COMPARABLE_MODULE, false)
+            .unwrap();
+    }
+    for class_id in [
+        ARRAY_CLASS,
+        HASH_CLASS,
+        RANGE_CLASS,
+        ENUMERATOR_CLASS,
+        STRUCT_CLASS,
+    ] {
+        globals
+            .include_module(class_id, ENUMERABLE_MODULE, false)
+            .unwrap();
+    }
+    let math_class = globals.define_module("Math", OBJECT_CLASS).as_class();
     let file_class = globals.define_class_under_obj("File").as_class();
+    let gc_class = globals.define_module("GC", OBJECT_CLASS).as_class();
 
     object::init(globals);
+    numeric::init(globals);
     integer::init(globals);
     float::init(globals);
+    rational::init(globals);
+    complex::init(globals);
     class::init(globals);
+    comparable::init(globals);
+    enumerable::init(globals);
+    enumerator::init(globals);
+    exception::init(globals);
+    fiber::init(globals);
     string::init(globals);
+    struct_class::init(globals);
+    symbol::init(globals);
     array::init(globals);
+    hash::init(globals);
+    procedure::init(globals);
     range::init(globals);
+    regexp::init(globals);
     time::init(globals);
     file::init(globals, file_class);
     math::init(globals, math_class);
+    gc::init(globals, gc_class);
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -115,3 +441,35 @@ impl std::ops::Index<usize> for Arg {
         unsafe { &*self.0.sub(index) }
     }
 }
+
+impl Arg {
+    ///
+    /// Get the *len* arguments as a Vec.
+    ///
+    pub fn to_vec(&self, len: usize) -> Vec<Value> {
+        (0..len).map(|i| self[i]).collect()
+    }
+
+    ///
+    /// Get the block handler in the *index*-th argument slot.
+    ///
+    /// The slot holds 0 if no block was given.
+    ///
+    pub fn block_handler(&self, index: usize) -> Option<Value> {
+        unsafe { *(self.0.sub(index) as *const Option<Value>) }
+    }
+
+    ///
+    /// Split keyword arguments off *len* arguments.
+    ///
+    /// Keyword arguments are passed as a keyword Hash in the last argument.
+    /// Return the number of positional arguments and the keyword Hash if exists.
+    ///
+    pub fn keywords(&self, len: usize) -> (usize, Option<Value>) {
+        if len != 0 && self[len - 1].is_hash().map_or(false, |h| h.is_keyword()) {
+            (len - 1, Some(self[len - 1]))
+        } else {
+            (len, None)
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use crate::*;
+use std::cmp::Ordering;
 
 //
 // Array class
@@ -11,6 +12,39 @@ pub(super) fn init(globals: &mut Globals) {
     globals.define_builtin_func(ARRAY_CLASS, "+", add, 1);
     globals.define_builtin_func(ARRAY_CLASS, "<<", shl, 1);
     globals.define_builtin_func(ARRAY_CLASS, "[]=", index_assign, 2);
+    globals.define_builtin_func(ARRAY_CLASS, "[]", index, -1);
+    globals.define_builtin_func(ARRAY_CLASS, "slice", index, -1);
+    globals.define_builtin_func(ARRAY_CLASS, "==", eq, 1);
+    globals.define_builtin_func(ARRAY_CLASS, "empty?", empty, 0);
+    globals.define_builtin_func(ARRAY_CLASS, "first", first, -1);
+    globals.define_builtin_func(ARRAY_CLASS, "last", last, -1);
+    globals.define_builtin_func(ARRAY_CLASS, "push", push, -1);
+    globals.define_builtin_func(ARRAY_CLASS, "pop", pop, -1);
+    globals.define_builtin_func(ARRAY_CLASS, "shift", shift, -1);
+    globals.define_builtin_func(ARRAY_CLASS, "unshift", unshift, -1);
+    globals.define_builtin_func(ARRAY_CLASS, "include?", include, 1);
+    globals.define_builtin_func(ARRAY_CLASS, "index", index_of, -1);
+    globals.define_builtin_func(ARRAY_CLASS, "reverse", reverse, 0);
+    globals.define_builtin_func(ARRAY_CLASS, "compact", compact, 0);
+    globals.define_builtin_func(ARRAY_CLASS, "flatten", flatten, -1);
+    globals.define_builtin_func(ARRAY_CLASS, "zip", zip, -1);
+    globals.define_builtin_func(ARRAY_CLASS, "join", join, -1);
+    globals.define_builtin_func(ARRAY_CLASS, "uniq", uniq, 0);
+    globals.define_builtin_func(ARRAY_CLASS, "dup", dup, 0);
+    globals.define_builtin_func(ARRAY_CLASS, "each", each, 0);
+    globals.define_builtin_func(ARRAY_CLASS, "each_with_index", each_with_index, 0);
+    globals.define_builtin_func(ARRAY_CLASS, "map", map, 0);
+    globals.define_builtin_func(ARRAY_CLASS, "collect", map, 0);
+    globals.define_builtin_func(ARRAY_CLASS, "select", select, 0);
+    globals.define_builtin_func(ARRAY_CLASS, "filter", select, 0);
+    globals.define_builtin_func(ARRAY_CLASS, "reject", reject, 0);
+    globals.define_builtin_func(ARRAY_CLASS, "inject", inject, -1);
+    globals.define_builtin_func(ARRAY_CLASS, "reduce", inject, -1);
+    globals.define_builtin_func(ARRAY_CLASS, "sum", sum, -1);
+    globals.define_builtin_func(ARRAY_CLASS, "min", min, 0);
+    globals.define_builtin_func(ARRAY_CLASS, "max", max, 0);
+    globals.define_builtin_func(ARRAY_CLASS, "sort", sort, 0);
+    globals.define_builtin_func(ARRAY_CLASS, "sort_by", sort_by, 0);
 }
 
 /// ### Array.new
@@ -116,6 +150,992 @@ extern "C" fn index_assign(
     }
 }
 
+/// ### Array#[]
+/// - self[nth] -> object | nil
+/// - self[start, length] -> Array | nil
+/// - self[range] -> Array | nil
+/// - slice(nth) -> object | nil
+/// - slice(start, length) -> Array | nil
+/// - slice(range) -> Array | nil
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Array/i/=5b=5d.html]
+extern "C" fn index(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let ary = self_val.as_array();
+    match len {
+        1 => {
+            let idx = arg[0];
+            if let Some(i) = idx.try_fixnum() {
+                return ary.get_index(i);
+            }
+            if let Some(range) = idx.is_range() {
+                let start = range.start.expect_integer(globals)?;
+                let end = if range.end.is_nil() {
+                    ary.len() as i64
+                } else {
+                    let end = range.end.expect_integer(globals)?;
+                    let end = if end < 0 { end + ary.len() as i64 } else { end };
+                    if range.exclude_end() {
+                        end
+                    } else {
+                        end + 1
+                    }
+                };
+                let start_pos = if start < 0 {
+                    start + ary.len() as i64
+                } else {
+                    start
+                };
+                return Some(ary.get_elements(start, std::cmp::max(end - start_pos, 0)));
+            }
+            globals.err_no_implict_conv(idx, INTEGER_CLASS);
+            None
+        }
+        2 => {
+            let start = arg[0].expect_integer(globals)?;
+            let len = arg[1].expect_integer(globals)?;
+            Some(ary.get_elements(start, len))
+        }
+        _ => {
+            globals.err_wrong_number_of_arguments_range(len, 1..=2);
+            None
+        }
+    }
+}
+
+/// ### Array#==
+/// - self == other -> bool
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Array/i/=3d=3d.html]
+extern "C" fn eq(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let other = arg[0];
+    if other.is_array().is_none() {
+        return Some(Value::bool(false));
+    }
+    let mut i = 0;
+    loop {
+        let (lhs, rhs) = match (self_val.as_array().get(i), other.as_array().get(i)) {
+            (Some(lhs), Some(rhs)) => (*lhs, *rhs),
+            (None, None) => return Some(Value::bool(true)),
+            _ => return Some(Value::bool(false)),
+        };
+        if !vm.eq_values(globals, lhs, rhs)? {
+            return Some(Value::bool(false));
+        }
+        i += 1;
+    }
+}
+
+/// ### Array#empty?
+/// - empty? -> bool
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Array/i/empty=3f.html]
+extern "C" fn empty(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(Value::bool(self_val.as_array().is_empty()))
+}
+
+/// ### Array#first
+/// - first -> object | nil
+/// - first(n) -> Array
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Array/i/first.html]
+extern "C" fn first(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let ary = self_val.as_array();
+    match len {
+        0 => Some(ary.first().cloned().unwrap_or_default()),
+        1 => {
+            let n = expect_size(globals, arg[0])?.min(ary.len());
+            Some(Value::new_array_from_vec(ary[..n].to_vec()))
+        }
+        _ => {
+            globals.err_wrong_number_of_arguments_range(len, 0..=1);
+            None
+        }
+    }
+}
+
+/// ### Array#last
+/// - last -> object | nil
+/// - last(n) -> Array
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Array/i/last.html]
+extern "C" fn last(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let ary = self_val.as_array();
+    match len {
+        0 => Some(ary.last().cloned().unwrap_or_default()),
+        1 => {
+            let n = expect_size(globals, arg[0])?.min(ary.len());
+            Some(Value::new_array_from_vec(ary[ary.len() - n..].to_vec()))
+        }
+        _ => {
+            globals.err_wrong_number_of_arguments_range(len, 0..=1);
+            None
+        }
+    }
+}
+
+/// ### Array#push
+/// - push(*obj) -> self
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Array/i/append.html]
+extern "C" fn push(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    mut self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    self_val.as_array_mut().extend(arg.to_vec(len));
+    Some(self_val)
+}
+
+/// ### Array#pop
+/// - pop -> object | nil
+/// - pop(n) -> Array
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Array/i/pop.html]
+extern "C" fn pop(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    mut self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let ary = self_val.as_array_mut();
+    match len {
+        0 => Some(ary.pop().unwrap_or_default()),
+        1 => {
+            let n = expect_size(globals, arg[0])?.min(ary.len());
+            let ary_len = ary.len();
+            Some(Value::new_array_from_vec(ary.drain(ary_len - n..ary_len)))
+        }
+        _ => {
+            globals.err_wrong_number_of_arguments_range(len, 0..=1);
+            None
+        }
+    }
+}
+
+/// ### Array#shift
+/// - shift -> object | nil
+/// - shift(n) -> Array
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Array/i/shift.html]
+extern "C" fn shift(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    mut self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let ary = self_val.as_array_mut();
+    match len {
+        0 => {
+            if ary.is_empty() {
+                Some(Value::nil())
+            } else {
+                Some(ary.drain(0..1)[0])
+            }
+        }
+        1 => {
+            let n = expect_size(globals, arg[0])?.min(ary.len());
+            Some(Value::new_array_from_vec(ary.drain(0..n)))
+        }
+        _ => {
+            globals.err_wrong_number_of_arguments_range(len, 0..=1);
+            None
+        }
+    }
+}
+
+/// ### Array#unshift
+/// - unshift(*obj) -> self
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Array/i/prepend.html]
+extern "C" fn unshift(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    mut self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    self_val.as_array_mut().insert_slice(0, &arg.to_vec(len));
+    Some(self_val)
+}
+
+/// ### Array#include?
+/// - include?(val) -> bool
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Array/i/include=3f.html]
+extern "C" fn include(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let mut i = 0;
+    while let Some(elem) = self_val.as_array().get(i).cloned() {
+        if vm.eq_values(globals, elem, arg[0])? {
+            return Some(Value::bool(true));
+        }
+        i += 1;
+    }
+    Some(Value::bool(false))
+}
+
+/// ### Array#index
+/// - index(val) -> Integer | nil
+/// - index {|item| ...} -> Integer | nil
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Array/i/find_index.html]
+extern "C" fn index_of(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    if len > 1 {
+        globals.err_wrong_number_of_arguments_range(len, 0..=1);
+        return None;
+    }
+    let block = if len == 0 {
+        Some(globals.expect_block(block)?)
+    } else {
+        None
+    };
+    let mut i = 0;
+    while let Some(elem) = self_val.as_array().get(i).cloned() {
+        let found = match block {
+            Some(block) => vm
+                .invoke_block(globals, block, self_val, &[elem])?
+                .as_bool(),
+            None => vm.eq_values(globals, elem, arg[0])?,
+        };
+        if found {
+            return Some(Value::new_integer(i as i64));
+        }
+        i += 1;
+    }
+    Some(Value::nil())
+}
+
+/// ### Array#reverse
+/// - reverse -> Array
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Array/i/reverse.html]
+extern "C" fn reverse(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let v = self_val.as_array().iter().rev().cloned().collect();
+    Some(Value::new_array_from_vec(v))
+}
+
+/// ### Array#compact
+/// - compact -> Array
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Array/i/compact.html]
+extern "C" fn compact(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let mut ary = self_val.as_array().clone();
+    ary.retain(|v| !v.is_nil());
+    Some(Value::new_array(ary))
+}
+
+/// ### Array#flatten
+/// - flatten(lv = nil) -> Array
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Array/i/flatten.html]
+extern "C" fn flatten(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let depth = match len {
+        0 => None,
+        1 if arg[0].is_nil() => None,
+        1 => match arg[0].expect_integer(globals)? {
+            d if d < 0 => None,
+            d => Some(d as usize),
+        },
+        _ => {
+            globals.err_wrong_number_of_arguments_range(len, 0..=1);
+            return None;
+        }
+    };
+    let mut res = vec![];
+    flatten_into(self_val.as_array(), depth, &mut res);
+    Some(Value::new_array_from_vec(res))
+}
+
+fn flatten_into(ary: &[Value], depth: Option<usize>, res: &mut Vec<Value>) {
+    for v in ary {
+        match v.is_array() {
+            Some(inner) if depth != Some(0) => flatten_into(inner, depth.map(|d| d - 1), res),
+            _ => res.push(*v),
+        }
+    }
+}
+
+/// ### Array#zip
+/// - zip(*lists) -> [[object]]
+/// - zip(*lists) {|v| ...} -> nil
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Array/i/zip.html]
+extern "C" fn zip(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    let others = arg.to_vec(len);
+    for other in &others {
+        if other.is_array().is_none() {
+            globals.err_no_implict_conv(*other, ARRAY_CLASS);
+            return None;
+        }
+    }
+    let mut res = Value::new_array_from_vec(vec![]);
+    vm.with_temp(res, |vm| {
+        let mut i = 0;
+        while let Some(elem) = self_val.as_array().get(i).cloned() {
+            let mut tuple = vec![elem];
+            tuple.extend(
+                others
+                    .iter()
+                    .map(|other| other.as_array().get(i).cloned().unwrap_or_default()),
+            );
+            res.as_array_mut().push(Value::new_array_from_vec(tuple));
+            i += 1;
+        }
+        match block {
+            Some(block) => {
+                for tuple in res.as_array().to_vec() {
+                    vm.invoke_block(globals, block, self_val, &[tuple])?;
+                }
+                Some(Value::nil())
+            }
+            None => Some(res),
+        }
+    })
+}
+
+/// ### Array#join
+/// - join(sep = "") -> String
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Array/i/join.html]
+extern "C" fn join(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let sep = match len {
+        0 => vec![],
+        1 => match arg[0].unpack() {
+            RV::String(s) => s.to_vec(),
+            RV::Nil => vec![],
+            _ => {
+                globals.err_no_implict_conv(arg[0], STRING_CLASS);
+                return None;
+            }
+        },
+        _ => {
+            globals.err_wrong_number_of_arguments_range(len, 0..=1);
+            return None;
+        }
+    };
+    let mut res = vec![];
+    join_into(globals, self_val.as_array(), &sep, &mut res);
+    Some(Value::new_string_from_slice(&res))
+}
+
+fn join_into(globals: &Globals, ary: &[Value], sep: &[u8], res: &mut Vec<u8>) {
+    for (i, v) in ary.iter().enumerate() {
+        if i != 0 {
+            res.extend_from_slice(sep);
+        }
+        match v.is_array() {
+            Some(inner) => join_into(globals, inner, sep, res),
+            None if v.is_nil() => {}
+            None => res.extend(globals.val_tobytes(*v)),
+        }
+    }
+}
+
+/// ### Array#uniq
+/// - uniq -> Array
+/// - uniq {|item| ... } -> Array
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Array/i/uniq.html]
+extern "C" fn uniq(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    let mut set = Value::new_hash(HashInner::new());
+    vm.with_temp(set, |vm| {
+        let mut res = Value::new_array_from_vec(vec![]);
+        vm.with_temp(res, |vm| {
+            let mut i = 0;
+            while let Some(elem) = self_val.as_array().get(i).cloned() {
+                let key = match block {
+                    Some(block) => vm.invoke_block(globals, block, self_val, &[elem])?,
+                    None => elem,
+                };
+                if set.as_hash().get(vm, globals, key)?.is_none() {
+                    set.as_hash_mut().insert(vm, globals, key, Value::nil())?;
+                    res.as_array_mut().push(elem);
+                }
+                i += 1;
+            }
+            Some(res)
+        })
+    })
+}
+
+/// ### Object#dup
+/// - dup -> Array
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Object/i/clone.html]
+extern "C" fn dup(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let class_id = self_val.get_real_class_id(globals);
+    Some(Value::new_array_with_class(
+        self_val.as_array().to_vec(),
+        class_id,
+    ))
+}
+
+/// ### Array#each
+/// - each {|item| .... } -> self
+/// - each -> Enumerator
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Array/i/each.html]
+extern "C" fn each(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    let block = match block {
+        Some(block) => block,
+        None => return Some(Value::new_enumerator(self_val, "each", vec![])),
+    };
+    let mut i = 0;
+    while let Some(elem) = self_val.as_array().get(i).cloned() {
+        vm.invoke_block(globals, block, self_val, &[elem])?;
+        i += 1;
+    }
+    Some(self_val)
+}
+
+/// ### Enumerable#each_with_index
+/// - each_with_index {|item, index| ... } -> self
+/// - each_with_index -> Enumerator
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Enumerable/i/each_with_index.html]
+extern "C" fn each_with_index(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    let block = match block {
+        Some(block) => block,
+        None => return Some(Value::new_enumerator(self_val, "each_with_index", vec![])),
+    };
+    let mut i = 0;
+    while let Some(elem) = self_val.as_array().get(i).cloned() {
+        let idx = Value::new_integer(i as i64);
+        vm.invoke_block(globals, block, self_val, &[elem, idx])?;
+        i += 1;
+    }
+    Some(self_val)
+}
+
+/// ### Array#map
+/// - collect {|item| ..} -> [object]
+/// - map {|item| ..} -> [object]
+/// - map -> Enumerator
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Array/i/collect.html]
+extern "C" fn map(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    let block = match block {
+        Some(block) => block,
+        None => return Some(Value::new_enumerator(self_val, "map", vec![])),
+    };
+    let mut res = Value::new_array_from_vec(vec![]);
+    vm.with_temp(res, |vm| {
+        let mut i = 0;
+        while let Some(elem) = self_val.as_array().get(i).cloned() {
+            let v = vm.invoke_block(globals, block, self_val, &[elem])?;
+            res.as_array_mut().push(v);
+            i += 1;
+        }
+        Some(res)
+    })
+}
+
+/// ### Array#select
+/// - select {|item| ... } -> [object]
+/// - filter {|item| ... } -> [object]
+/// - select -> Enumerator
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Array/i/filter.html]
+extern "C" fn select(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    let block = match block {
+        Some(block) => block,
+        None => return Some(Value::new_enumerator(self_val, "select", vec![])),
+    };
+    filter_by(vm, globals, self_val, block, true)
+}
+
+/// ### Array#reject
+/// - reject {|item| ... } -> [object]
+/// - reject -> Enumerator
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Array/i/reject.html]
+extern "C" fn reject(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    let block = match block {
+        Some(block) => block,
+        None => return Some(Value::new_enumerator(self_val, "reject", vec![])),
+    };
+    filter_by(vm, globals, self_val, block, false)
+}
+
+///
+/// Collect the elements of *self_val* for which *block* returns *expected* truthiness.
+///
+fn filter_by(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    block: Value,
+    expected: bool,
+) -> Option<Value> {
+    let mut res = Value::new_array_from_vec(vec![]);
+    vm.with_temp(res, |vm| {
+        let mut i = 0;
+        while let Some(elem) = self_val.as_array().get(i).cloned() {
+            if vm
+                .invoke_block(globals, block, self_val, &[elem])?
+                .as_bool()
+                == expected
+            {
+                res.as_array_mut().push(elem);
+            }
+            i += 1;
+        }
+        Some(res)
+    })
+}
+
+/// ### Enumerable#inject
+/// - inject(init = self.first) {|result, item| ... } -> object
+/// - inject(sym) -> object
+/// - inject(init, sym) -> object
+/// - reduce(init = self.first) {|result, item| ... } -> object
+/// - reduce(sym) -> object
+/// - reduce(init, sym) -> object
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Enumerable/i/inject.html]
+extern "C" fn inject(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    let (init, op) = match (len, block) {
+        (0, Some(_)) => (None, None),
+        (1, Some(_)) => (Some(arg[0]), None),
+        (1, None) => (None, Some(arg[0])),
+        (2, _) => (Some(arg[0]), Some(arg[1])),
+        (0, None) => {
+            globals.err_local_jump("no block given".to_string());
+            return None;
+        }
+        _ => {
+            globals.err_wrong_number_of_arguments_range(len, 0..=2);
+            return None;
+        }
+    };
+    let op = match op {
+        Some(op) => match op.unpack() {
+            RV::Symbol(id) => Some(id),
+            RV::String(s) => Some(IdentId::get_ident_id_from_string(
+                String::from_utf8_lossy(s).into_owned(),
+            )),
+            _ => {
+                globals.err_is_not_symbol_nor_string(op);
+                return None;
+            }
+        },
+        None => None,
+    };
+    let (mut acc, mut i) = match init {
+        Some(init) => (init, 0),
+        None => match self_val.as_array().first() {
+            Some(first) => (*first, 1),
+            None => return Some(Value::nil()),
+        },
+    };
+    while let Some(elem) = self_val.as_array().get(i).cloned() {
+        acc = match op {
+            Some(op) => vm.binary_op(globals, op, acc, elem)?,
+            None => vm.invoke_block(globals, block.unwrap(), self_val, &[acc, elem])?,
+        };
+        i += 1;
+    }
+    Some(acc)
+}
+
+/// ### Array#sum
+/// - sum(init = 0) -> object
+/// - sum(init = 0) {|e| expr } -> object
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Array/i/sum.html]
+extern "C" fn sum(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    let mut acc = match len {
+        0 => Value::new_integer(0),
+        1 => arg[0],
+        _ => {
+            globals.err_wrong_number_of_arguments_range(len, 0..=1);
+            return None;
+        }
+    };
+    // (sum, compensation) for the Kahan-Babuska summation of floats.
+    let mut float_sum: Option<(f64, f64)> = None;
+    let mut i = 0;
+    while let Some(elem) = self_val.as_array().get(i).cloned() {
+        i += 1;
+        let v = match block {
+            Some(block) => vm.invoke_block(globals, block, self_val, &[elem])?,
+            None => elem,
+        };
+        if float_sum.is_none() {
+            if let (RV::Integer(_) | RV::Float(_), RV::Float(_)) = (acc.unpack(), v.unpack()) {
+                float_sum = Some((as_f64(acc).unwrap(), 0.0));
+            }
+        }
+        if let Some((f, c)) = &mut float_sum {
+            if let Some(x) = as_f64(v) {
+                let t = *f + x;
+                if f.is_finite() && x.is_finite() {
+                    if f.abs() >= x.abs() {
+                        *c += (*f - t) + x;
+                    } else {
+                        *c += (x - t) + *f;
+                    }
+                }
+                *f = t;
+                continue;
+            }
+            acc = Value::new_float(*f + *c);
+            float_sum = None;
+        }
+        acc = vm.binary_op(globals, IdentId::_ADD, acc, v)?;
+    }
+    if let Some((f, c)) = float_sum {
+        acc = Value::new_float(f + c);
+    }
+    Some(acc)
+}
+
+fn as_f64(v: Value) -> Option<f64> {
+    match v.unpack() {
+        RV::Integer(i) => Some(i as f64),
+        RV::Float(f) => Some(f),
+        _ => None,
+    }
+}
+
+/// ### Array#min
+/// - min -> object | nil
+/// - min {|a, b| ... } -> object | nil
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Array/i/min.html]
+extern "C" fn min(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    find_extreme(vm, globals, self_val, block, Ordering::Less)
+}
+
+/// ### Array#max
+/// - max -> object | nil
+/// - max {|a, b| ... } -> object | nil
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Array/i/max.html]
+extern "C" fn max(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    find_extreme(vm, globals, self_val, block, Ordering::Greater)
+}
+
+///
+/// Find the first element *e* of *self_val* such that no other element compares to *e* as *ord*.
+///
+fn find_extreme(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    block: Option<Value>,
+    ord: Ordering,
+) -> Option<Value> {
+    let mut res = match self_val.as_array().first() {
+        Some(first) => *first,
+        None => return Some(Value::nil()),
+    };
+    let mut i = 1;
+    while let Some(elem) = self_val.as_array().get(i).cloned() {
+        if compare(vm, globals, self_val, block, elem, res)? == ord {
+            res = elem;
+        }
+        i += 1;
+    }
+    Some(res)
+}
+
+/// ### Array#sort
+/// - sort -> Array
+/// - sort {|a, b| ... } -> Array
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Array/i/sort.html]
+extern "C" fn sort(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    let res = Value::new_array_from_vec(self_val.as_array().to_vec());
+    vm.with_temp(res, |vm| {
+        let mut v = res.as_array().to_vec();
+        merge_sort(vm, globals, &mut v, &mut |vm, globals, lhs, rhs| {
+            compare(vm, globals, self_val, block, lhs, rhs)
+        })?;
+        Some(Value::new_array_from_vec(v))
+    })
+}
+
+/// ### Enumerable#sort_by
+/// - sort_by {|item| ... } -> [object]
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Enumerable/i/sort_by.html]
+extern "C" fn sort_by(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    let block = globals.expect_block(block)?;
+    let elems = Value::new_array_from_vec(self_val.as_array().to_vec());
+    vm.with_temp(elems, |vm| {
+        let mut keys = Value::new_array_from_vec(vec![]);
+        vm.with_temp(keys, |vm| {
+            for elem in elems.as_array().to_vec() {
+                let key = vm.invoke_block(globals, block, self_val, &[elem])?;
+                keys.as_array_mut().push(key);
+            }
+            let mut indices: Vec<usize> = (0..keys.as_array().len()).collect();
+            merge_sort(vm, globals, &mut indices, &mut |vm, globals, lhs, rhs| {
+                let (lhs, rhs) = (keys.as_array()[lhs], keys.as_array()[rhs]);
+                vm.compare_values(globals, lhs, rhs)
+            })?;
+            let v = indices.into_iter().map(|i| elems.as_array()[i]).collect();
+            Some(Value::new_array_from_vec(v))
+        })
+    })
+}
+
+///
+/// Compare *lhs* and *rhs* with *block*, or with `<=>` if no block was given.
+///
+fn compare(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    block: Option<Value>,
+    lhs: Value,
+    rhs: Value,
+) -> Option<Ordering> {
+    let block = match block {
+        Some(block) => block,
+        None => return vm.compare_values(globals, lhs, rhs),
+    };
+    let res = vm.invoke_block(globals, block, self_val, &[lhs, rhs])?;
+    match res.try_fixnum() {
+        Some(i) => Some(i.cmp(&0)),
+        None => {
+            let msg = format!(
+                "comparison of {} with {} failed",
+                lhs.get_real_class_name(globals),
+                rhs.get_real_class_name(globals)
+            );
+            globals.err_argument(&msg);
+            None
+        }
+    }
+}
+
+///
+/// Sort *v* with the comparison function *cmp* by stable merge sort.
+///
+/// Unlike `slice::sort_by`, *cmp* may call Ruby methods and may fail.
+///
+fn merge_sort<T: Copy>(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    v: &mut [T],
+    cmp: &mut impl FnMut(&mut Executor, &mut Globals, T, T) -> Option<Ordering>,
+) -> Option<()> {
+    if v.len() <= 1 {
+        return Some(());
+    }
+    let mid = v.len() / 2;
+    merge_sort(vm, globals, &mut v[..mid], cmp)?;
+    merge_sort(vm, globals, &mut v[mid..], cmp)?;
+    let mut merged = Vec::with_capacity(v.len());
+    let (mut i, mut j) = (0, mid);
+    while i < mid && j < v.len() {
+        if cmp(vm, globals, v[j], v[i])? == Ordering::Less {
+            merged.push(v[j]);
+            j += 1;
+        } else {
+            merged.push(v[i]);
+            i += 1;
+        }
+    }
+    merged.extend_from_slice(&v[i..mid]);
+    merged.extend_from_slice(&v[j..]);
+    v.copy_from_slice(&merged);
+    Some(())
+}
+
+fn expect_size(globals: &mut Globals, v: Value) -> Option<usize> {
+    let n = v.expect_integer(globals)?;
+    if n < 0 {
+        globals.err_argument("negative array size");
+        return None;
+    }
+    Some(n as usize)
+}
+
 #[cfg(test)]
 mod test {
     use super::tests::*;
@@ -170,6 +1190,123 @@ mod test {
         run_test(r##"a = [1,2,3]; a.<<(10); a"##);
     }
 
+    #[test]
+    fn test_array_eq() {
+        run_test(r##"[1, 2, [3, "a"]] == [1, 2, [3, "a"]]"##);
+        run_test(r##"[1, 2, 3] == [1, 2]"##);
+        run_test(r##"[1, 2.0, :c, nil] == [1.0, 2, :c, nil]"##);
+        run_test(r##"[1, 2] == 3"##);
+        run_test(r##"[[1, 2] != [1, 2], [] != [nil]]"##);
+    }
+
+    #[test]
+    fn test_array_access() {
+        run_test(r##"a = [1, 2, 3, 4, 5]; [a[1], a[-1], a[7], a.slice(2)]"##);
+        run_test(r##"a = [1, 2, 3, 4, 5]; [a[1, 2], a[-3, 2], a[5, 1], a[6, 1], a.slice(0, 10)]"##);
+        run_test(
+            r##"a = [1, 2, 3, 4, 5]; [a[1..3], a[1...3], a[-2..-1], a[3..1], a[5..7], a[6..7]]"##,
+        );
+        run_test(
+            r##"a = [1, 2, 3]; [a.first, a.last, a.first(2), a.last(2), a.first(5), [].first, [].last]"##,
+        );
+        run_test(r##"[[].empty?, [1].empty?]"##);
+        run_test_error(r##"[1, 2].first(-1)"##);
+    }
+
+    #[test]
+    fn test_array_push_pop() {
+        run_test(r##"a = [1, 2]; a.push(3, 4); a.push; a"##);
+        run_test(r##"a = [1, 2, 3, 4]; [a.pop, a.pop(2), a, [].pop]"##);
+        run_test(r##"a = [1, 2, 3, 4]; [a.shift, a.shift(2), a, [].shift]"##);
+        run_test(r##"a = [3, 4]; a.unshift(1, 2); a.unshift; a"##);
+    }
+
+    #[test]
+    fn test_array_search() {
+        run_test(
+            r##"a = [1, "x", [2], nil]; [a.include?("x"), a.include?([2]), a.include?(nil), a.include?(3)]"##,
+        );
+        run_test(r##"a = [4, 5, 6, 5]; [a.index(5), a.index(7), a.index { |x| x > 4 }]"##);
+    }
+
+    #[test]
+    fn test_array_transform() {
+        run_test(r##"[1, 2, 3].reverse"##);
+        run_test(r##"[1, nil, 2, nil, nil].compact"##);
+        run_test(r##"[1, [2, [3, [4]]], 5].flatten"##);
+        run_test(r##"[1, [2, [3, [4]]], 5].flatten(1)"##);
+        run_test(r##"[1, 2, 3].zip([4, 5, 6], [7, 8])"##);
+        run_test(r##"a = []; [1, 2].zip([3, 4]) { |x| a << x }; a"##);
+        run_test(r##"[1, "a", :b, 2.5, nil, [3, [4]]].join"##);
+        run_test(r##"[1, 2, [3, 4]].join(", ")"##);
+        run_test(r##"[1, 2, 1, "a", "a", [1], [1], 3].uniq"##);
+        run_test(r##"[1, 2, 3, 4, 5].uniq { |x| x % 3 }"##);
+        run_test(r##"a = [1, 2]; b = a.dup; b << 3; [a, b]"##);
+    }
+
+    #[test]
+    fn test_array_each() {
+        run_test(r##"a = []; [1, 2, 3].each { |x| a << x * 2 }; a"##);
+        run_test(r##"x = [1, 2, 3]; y = x.each { |v| }; y << 4; x"##);
+        run_test(r##"a = []; [4, 5, 6].each_with_index { |x, i| a << x * i }; a"##);
+        run_test(r##"a = []; [[1, 2], [3, 4]].each { |x, y| a << x + y }; a"##);
+        run_test_error(r##"[1, 2].each"##);
+    }
+
+    #[test]
+    fn test_array_map_select() {
+        run_test(r##"[1, 2, 3].map { |x| x * x }"##);
+        run_test(r##"[1, 2, 3].collect { |x| "#{x}!" }"##);
+        run_test(r##"[1, 2, 3, 4, 5].select { |x| x % 2 == 1 }"##);
+        run_test(r##"[1, 2, 3, 4, 5].filter { |x| x > 3 }"##);
+        run_test(r##"[1, 2, 3, 4, 5].reject { |x| x % 2 == 1 }"##);
+    }
+
+    #[test]
+    fn test_array_inject() {
+        run_test(r##"[1, 2, 3, 4].inject { |acc, x| acc * x }"##);
+        run_test(r##"[1, 2, 3, 4].inject(10) { |acc, x| acc + x }"##);
+        run_test(r##"[1, 2, 3, 4].reduce(:+)"##);
+        run_test(r##"[1, 2, 3, 4].reduce(2, :*)"##);
+        run_test(r##"["a", "b", "c"].inject(:+)"##);
+        run_test(r##"[[].inject(:+), [5].inject { |a, b| a + b }]"##);
+    }
+
+    #[test]
+    fn test_array_sum() {
+        run_test(r##"[1, 2, 3].sum"##);
+        run_test(r##"[1, 2, 3].sum(10)"##);
+        run_test(r##"[1, 2, 3].sum { |x| x * 10 }"##);
+        run_test(r##"[0.1, 0.2, 0.3].sum"##);
+        run_test(r##"[1, 0.1, 2, 0.2].sum"##);
+        run_test(r##"[3.0, 1e100, -1e100].sum"##);
+        run_test(r##"["a", "b"].sum("")"##);
+        run_test(r##"[[1], [2, 3]].sum([])"##);
+        run_test(r##"[].sum"##);
+    }
+
+    #[test]
+    fn test_array_min_max() {
+        run_test(r##"[[3, 1, 4, 1, 5].min, [3, 1, 4, 1, 5].max, [].min, [].max]"##);
+        run_test(r##"[2.5, 1, 3].max"##);
+        run_test(r##"["banana", "apple", "cherry"].min"##);
+        run_test(r##"[[1, 1, 1], [2], [3, 3]].max { |a, b| a.size - b.size }"##);
+        run_test(r##"[[1, 1, 1], [2], [3, 3]].min { |a, b| a.size - b.size }"##);
+        run_test_error(r##"[1, "a"].max"##);
+    }
+
+    #[test]
+    fn test_array_sort() {
+        run_test(r##"[3, 1, 4, 1, 5, 9, 2, 6].sort"##);
+        run_test(r##"[3.5, 1, 2.5, -4].sort"##);
+        run_test(r##"["pear", "apple", "fig"].sort"##);
+        run_test(r##"[3, 1, 4, 1, 5, 9, 2, 6].sort { |a, b| b - a }"##);
+        run_test(r##"[[1, 2, 3], [1], [1, 2]].sort_by { |x| x.size }"##);
+        run_test(r##"[3, 1, 2].sort_by { |x| -x }"##);
+        run_test(r##"a = [3, 1, 2]; a.sort; a"##);
+        run_test_error(r##"[1, "a"].sort"##);
+    }
+
     #[test]
     fn test_array_index() {
         run_test(
@@ -1,19 +1,41 @@
 use crate::*;
 
 //
-// Class class
+// Module / Class class
 //
 
 pub(super) fn init(globals: &mut Globals) {
+    globals.define_builtin_func(MODULE_CLASS, "to_s", tos, 0);
+    globals.define_builtin_func(MODULE_CLASS, "name", tos, 0);
+    globals.define_builtin_func(MODULE_CLASS, "===", teq, 1);
+    globals.define_builtin_func(MODULE_CLASS, "ancestors", ancestors, 0);
+    globals.define_builtin_func(MODULE_CLASS, "include?", include_, 1);
+    globals.define_builtin_func(MODULE_CLASS, "include", include, -1);
+    globals.define_builtin_func(MODULE_CLASS, "prepend", prepend, -1);
+    globals.define_builtin_func(MODULE_CLASS, "constants", constants, 0);
+    globals.define_builtin_func(MODULE_CLASS, "const_get", const_get, -1);
+    globals.define_builtin_func(MODULE_CLASS, "const_set", const_set, 2);
+    globals.define_builtin_func(MODULE_CLASS, "const_defined?", const_defined, -1);
+    globals.define_builtin_singleton_func(MODULE_CLASS, "nesting", nesting, 0);
+    globals.define_builtin_func(MODULE_CLASS, "instance_methods", instance_methods, 0);
+    globals.define_builtin_func(MODULE_CLASS, "attr_reader", attr_reader, -1);
+    globals.define_builtin_func(MODULE_CLASS, "attr_writer", attr_writer, -1);
+    globals.define_builtin_func(MODULE_CLASS, "attr_accessor", attr_accessor, -1);
+    globals.define_builtin_func(MODULE_CLASS, "public", public, -1);
+    globals.define_builtin_func(MODULE_CLASS, "private", private, -1);
+    globals.define_builtin_func(MODULE_CLASS, "protected", protected, -1);
+    globals.define_builtin_func(MODULE_CLASS, "module_function", module_function, -1);
+    globals.define_builtin_func(MODULE_CLASS, "public_class_method", public_class_method, -1);
+    globals.define_builtin_func(
+        MODULE_CLASS,
+        "private_class_method",
+        private_class_method,
+        -1,
+    );
+    globals.define_builtin_func(MODULE_CLASS, "private_constant", private_constant, -1);
     globals.define_builtin_func(CLASS_CLASS, "new", new, -1);
     globals.define_builtin_func(CLASS_CLASS, "superclass", superclass, 0);
     globals.define_builtin_func(CLASS_CLASS, "allocate", allocate, 0);
-    globals.define_builtin_func(CLASS_CLASS, "to_s", tos, 0);
-    globals.define_builtin_func(CLASS_CLASS, "constants", constants, 0);
-    globals.define_builtin_func(CLASS_CLASS, "instance_methods", instance_methods, 0);
-    globals.define_builtin_func(CLASS_CLASS, "attr_reader", attr_reader, -1);
-    globals.define_builtin_func(CLASS_CLASS, "attr_writer", attr_writer, -1);
-    globals.define_builtin_func(CLASS_CLASS, "attr_accessor", attr_accessor, -1);
 }
 
 /// ### Class#new
@@ -22,7 +44,7 @@ pub(super) fn init(globals: &mut Globals) {
 /// [https://docs.ruby-lang.org/ja/latest/method/Class/i/new.html]
 ///
 /// !! We must call Object#initialize.
-extern "C" fn new(
+pub(super) extern "C" fn new(
     vm: &mut Executor,
     globals: &mut Globals,
     self_val: Value,
@@ -64,21 +86,27 @@ extern "C" fn superclass(
 /// [https://docs.ruby-lang.org/ja/latest/method/Class/i/allocate.html]
 extern "C" fn allocate(
     _vm: &mut Executor,
-    _globals: &mut Globals,
+    globals: &mut Globals,
     self_val: Value,
     _arg: Arg,
     _len: usize,
     _: Option<Value>,
 ) -> Option<Value> {
     let class_id = self_val.as_class();
-    let obj = Value::new_object(class_id);
+    let obj = if globals.is_subclass_of(class_id, EXCEPTION_CLASS) {
+        let msg = class_id.get_name(globals);
+        Value::new_exception(class_id, MonorubyErr::runtimeerr(msg))
+    } else {
+        Value::new_object(class_id)
+    };
     Some(obj)
 }
 
-/// ### Class#to_s
+/// ### Module#to_s
 /// - to_s -> String
+/// - name -> String
 ///
-/// [https://docs.ruby-lang.org/ja/latest/method/Object/i/to_s.html]
+/// [https://docs.ruby-lang.org/ja/latest/method/Module/i/to_s.html]
 extern "C" fn tos(
     _vm: &mut Executor,
     globals: &mut Globals,
@@ -92,6 +120,132 @@ extern "C" fn tos(
     Some(res)
 }
 
+/// ### Module#===
+/// - self === obj -> bool
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Module/i/=3d=3d=3d.html]
+extern "C" fn teq(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let class_id = self_val.as_class();
+    let obj_class = arg[0].get_real_class_id(globals);
+    Some(Value::bool(globals.is_subclass_of(obj_class, class_id)))
+}
+
+/// ### Module#ancestors
+/// - ancestors -> [Class, Module]
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Module/i/ancestors.html]
+extern "C" fn ancestors(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let class_id = self_val.as_class();
+    let v = globals
+        .ancestors(class_id)
+        .into_iter()
+        .map(|id| id.get_obj(globals))
+        .collect();
+    Some(Value::new_array_from_vec(v))
+}
+
+/// ### Module#include?
+/// - include?(module) -> bool
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Module/i/include=3f.html]
+extern "C" fn include_(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let class_id = self_val.as_class();
+    let module = expect_module_arg(globals, arg[0])?;
+    let res = class_id != module && globals.ancestors(class_id).contains(&module);
+    Some(Value::bool(res))
+}
+
+/// ### Module#include
+/// - include(*mod) -> self
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Module/i/include.html]
+extern "C" fn include(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    append_modules(globals, self_val.as_class(), arg, len, false)?;
+    Some(self_val)
+}
+
+/// ### Module#prepend
+/// - prepend(*modules) -> self
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Module/i/prepend.html]
+extern "C" fn prepend(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    append_modules(globals, self_val.as_class(), arg, len, true)?;
+    Some(self_val)
+}
+
+///
+/// Include (or prepend if *prepend* is true) the modules in *arg* to *class_id*.
+///
+/// The modules are processed from the last one, so that the first one comes first in the ancestors.
+///
+pub(super) fn append_modules(
+    globals: &mut Globals,
+    class_id: ClassId,
+    arg: Arg,
+    len: usize,
+    prepend: bool,
+) -> Option<()> {
+    if len == 0 {
+        globals.err_wrong_number_of_arguments_range(len, 1..=usize::MAX);
+        return None;
+    }
+    let modules = (0..len)
+        .map(|i| expect_module_arg(globals, arg[i]))
+        .collect::<Option<Vec<_>>>()?;
+    for module in modules.into_iter().rev() {
+        globals.include_module(class_id, module, prepend)?;
+    }
+    Some(())
+}
+
+fn expect_module_arg(globals: &mut Globals, val: Value) -> Option<ClassId> {
+    match val.is_module() {
+        Some(module) => Some(module),
+        None => {
+            let class_name = val.get_real_class_name(globals);
+            globals.set_error(MonorubyErr::typeerr(format!(
+                "wrong argument type {class_name} (expected Module)"
+            )));
+            None
+        }
+    }
+}
+
 /// ### Module#constants
 /// - constants(inherit = true) -> [Symbol]
 ///
@@ -113,6 +267,150 @@ extern "C" fn constants(
     Some(Value::new_array_from_vec(v))
 }
 
+/// ### Module#const_get
+/// - const_get(name, inherit = true) -> object
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Module/i/const_get.html]
+extern "C" fn const_get(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    if !(1..=2).contains(&len) {
+        globals.err_wrong_number_of_arguments_range(len, 1..=2);
+        return None;
+    }
+    let inherit = len == 1 || arg[1].as_bool();
+    let path = arg[0].expect_symbol_or_string(globals)?;
+    let path = IdentId::get_name(path);
+    let mut class_id = if path.starts_with("::") {
+        OBJECT_CLASS
+    } else {
+        self_val.as_class()
+    };
+    let names = path
+        .trim_start_matches("::")
+        .split("::")
+        .collect::<Vec<_>>();
+    let (last, prefix) = names.split_last().unwrap();
+    for name in prefix {
+        let name = check_const_name(globals, name)?;
+        class_id = lookup_const(globals, class_id, name, inherit)?
+            .expect_class_or_module(name, globals)?;
+    }
+    let name = check_const_name(globals, last)?;
+    lookup_const(globals, class_id, name, inherit)
+}
+
+/// ### Module#const_set
+/// - const_set(name, value) -> object
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Module/i/const_set.html]
+extern "C" fn const_set(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let name = arg[0].expect_symbol_or_string(globals)?;
+    let name = check_const_name(globals, &IdentId::get_name(name))?;
+    globals.set_constant(self_val.as_class(), name, arg[1]);
+    Some(arg[1])
+}
+
+/// ### Module#const_defined?
+/// - const_defined?(name, inherit = true) -> bool
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Module/i/const_defined=3f.html]
+extern "C" fn const_defined(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    if !(1..=2).contains(&len) {
+        globals.err_wrong_number_of_arguments_range(len, 1..=2);
+        return None;
+    }
+    let inherit = len == 1 || arg[1].as_bool();
+    let name = arg[0].expect_symbol_or_string(globals)?;
+    let name = check_const_name(globals, &IdentId::get_name(name))?;
+    let class_id = self_val.as_class();
+    let res = if inherit {
+        globals.search_constant(class_id, name).is_some()
+            || globals.get_constant(OBJECT_CLASS, name).is_some()
+    } else {
+        globals.get_constant(class_id, name).is_some()
+    };
+    Some(Value::bool(res))
+}
+
+/// ### Module.nesting
+/// - nesting -> [Class, Module]
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Module/s/nesting.html]
+extern "C" fn nesting(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    _self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let v = vm
+        .caller_lexical_context(globals)
+        .into_iter()
+        .rev()
+        .map(|id| id.get_obj(globals))
+        .collect();
+    Some(Value::new_array_from_vec(v))
+}
+
+///
+/// Check whether *name* is a valid constant name, and return its IdentId.
+///
+fn check_const_name(globals: &mut Globals, name: &str) -> Option<IdentId> {
+    let id = IdentId::get_ident_id(name);
+    match name.chars().next() {
+        Some(c) if c.is_ascii_uppercase() => Some(id),
+        _ => {
+            globals.err_wrong_constant_name(id);
+            None
+        }
+    }
+}
+
+///
+/// Get a constant with *name* in the class of *class_id*.
+///
+/// If *inherit* is true, the ancestors of the class and Object are also searched.
+///
+fn lookup_const(
+    globals: &mut Globals,
+    class_id: ClassId,
+    name: IdentId,
+    inherit: bool,
+) -> Option<Value> {
+    let res = if inherit {
+        globals
+            .search_constant(class_id, name)
+            .or_else(|| globals.get_constant(OBJECT_CLASS, name))
+    } else {
+        globals.get_constant(class_id, name)
+    };
+    if res.is_none() {
+        globals.err_uninitialized_constant(name);
+    }
+    res
+}
+
 /// ### Module#instance_methods
 /// - instance_methods(inherited_too = true) -> [Symbol]
 ///
@@ -143,7 +441,7 @@ extern "C" fn instance_methods(
 ///
 /// [https://docs.ruby-lang.org/ja/latest/method/Module/i/attr_reader.html]
 extern "C" fn attr_reader(
-    _vm: &mut Executor,
+    vm: &mut Executor,
     globals: &mut Globals,
     self_val: Value,
     arg: Arg,
@@ -155,6 +453,7 @@ extern "C" fn attr_reader(
     for i in 0..len {
         let arg_name = arg[i].expect_symbol_or_string(globals)?;
         let method_name = globals.define_attr_reader(class_id, arg_name);
+        apply_def_visibility(vm, globals, class_id, method_name)?;
         res.push(Value::new_symbol(method_name));
     }
     Some(Value::new_array_from_vec(res))
@@ -165,7 +464,7 @@ extern "C" fn attr_reader(
 ///
 /// [https://docs.ruby-lang.org/ja/latest/method/Module/i/attr_writer.html]
 extern "C" fn attr_writer(
-    _vm: &mut Executor,
+    vm: &mut Executor,
     globals: &mut Globals,
     self_val: Value,
     arg: Arg,
@@ -177,6 +476,7 @@ extern "C" fn attr_writer(
     for i in 0..len {
         let arg_name = arg[i].expect_symbol_or_string(globals)?;
         let method_name = globals.define_attr_writer(class_id, arg_name);
+        apply_def_visibility(vm, globals, class_id, method_name)?;
         res.push(Value::new_symbol(method_name));
     }
     Some(Value::new_array_from_vec(res))
@@ -187,7 +487,7 @@ extern "C" fn attr_writer(
 ///
 /// [https://docs.ruby-lang.org/ja/latest/method/Module/i/attr_accessor.html]
 extern "C" fn attr_accessor(
-    _vm: &mut Executor,
+    vm: &mut Executor,
     globals: &mut Globals,
     self_val: Value,
     arg: Arg,
@@ -199,13 +499,231 @@ extern "C" fn attr_accessor(
     for i in 0..len {
         let arg_name = arg[i].expect_symbol_or_string(globals)?;
         let method_name = globals.define_attr_reader(class_id, arg_name);
+        apply_def_visibility(vm, globals, class_id, method_name)?;
         res.push(Value::new_symbol(method_name));
         let method_name = globals.define_attr_writer(class_id, arg_name);
+        apply_def_visibility(vm, globals, class_id, method_name)?;
         res.push(Value::new_symbol(method_name));
     }
     Some(Value::new_array_from_vec(res))
 }
 
+///
+/// Apply the visibility of the current class body to the method *name* defined by `attr_*`.
+///
+fn apply_def_visibility(
+    vm: &Executor,
+    globals: &mut Globals,
+    class_id: ClassId,
+    name: IdentId,
+) -> Option<()> {
+    if vm.get_class_context() != class_id {
+        return Some(());
+    }
+    match vm.get_def_visibility() {
+        (Visibility::Public, false) => Some(()),
+        (_, true) => globals.change_method_visibility(class_id, name, Visibility::Private),
+        (visibility, false) => globals.change_method_visibility(class_id, name, visibility),
+    }
+}
+
+///
+/// Collect the method names in *arg* for `public`, `private` and so on.
+///
+/// An Array of names is also accepted.
+///
+fn method_names(globals: &mut Globals, arg: Arg, len: usize) -> Option<Vec<IdentId>> {
+    let mut names = vec![];
+    for i in 0..len {
+        match arg[i].is_array() {
+            Some(ary) => {
+                for v in ary.iter() {
+                    names.push(v.expect_symbol_or_string(globals)?);
+                }
+            }
+            None => names.push(arg[i].expect_symbol_or_string(globals)?),
+        }
+    }
+    Some(names)
+}
+
+///
+/// Return value of `public`, `private` and so on.
+///
+/// nil for no argument, the argument itself for one argument, and an Array of the arguments
+/// for multiple arguments.
+///
+fn visibility_result(arg: Arg, len: usize) -> Value {
+    match len {
+        0 => Value::nil(),
+        1 => arg[0],
+        _ => Value::new_array_from_vec(arg.to_vec(len)),
+    }
+}
+
+///
+/// Set the visibility of the methods in *arg* of the class *self_val*.
+///
+/// Without arguments, set the visibility of methods defined afterwards in the class body.
+///
+fn set_visibility(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    visibility: Visibility,
+) -> Option<Value> {
+    let class_id = self_val.as_class();
+    if len == 0 {
+        if vm.get_class_context() == class_id {
+            vm.set_def_visibility(visibility, false);
+        }
+    } else {
+        for name in method_names(globals, arg, len)? {
+            globals.change_method_visibility(class_id, name, visibility)?;
+        }
+    }
+    Some(visibility_result(arg, len))
+}
+
+/// ### Module#public
+/// - public(*name) -> nil | Symbol | [Symbol]
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Module/i/public.html]
+extern "C" fn public(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    set_visibility(vm, globals, self_val, arg, len, Visibility::Public)
+}
+
+/// ### Module#private
+/// - private(*name) -> nil | Symbol | [Symbol]
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Module/i/private.html]
+extern "C" fn private(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    set_visibility(vm, globals, self_val, arg, len, Visibility::Private)
+}
+
+/// ### Module#protected
+/// - protected(*name) -> nil | Symbol | [Symbol]
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Module/i/protected.html]
+extern "C" fn protected(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    set_visibility(vm, globals, self_val, arg, len, Visibility::Protected)
+}
+
+/// ### Module#module_function
+/// - module_function(*name) -> nil | Symbol | [Symbol]
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Module/i/module_function.html]
+extern "C" fn module_function(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let class_id = self_val.as_class();
+    if !globals.is_module(class_id) {
+        globals.set_error(MonorubyErr::typeerr(
+            "module_function must be called for modules".to_string(),
+        ));
+        return None;
+    }
+    if len == 0 {
+        if vm.get_class_context() == class_id {
+            vm.set_def_visibility(Visibility::Private, true);
+        }
+    } else {
+        let singleton = globals.get_singleton_id(class_id);
+        for name in method_names(globals, arg, len)? {
+            globals.change_method_visibility(class_id, name, Visibility::Private)?;
+            let func_id = globals.find_method_for_class(class_id, name).unwrap();
+            globals.add_method(singleton, name, func_id);
+        }
+    }
+    Some(visibility_result(arg, len))
+}
+
+/// ### Module#public_class_method
+/// - public_class_method(*name) -> self
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Module/i/public_class_method.html]
+extern "C" fn public_class_method(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let singleton = globals.get_singleton_id(self_val.as_class());
+    for name in method_names(globals, arg, len)? {
+        globals.change_method_visibility(singleton, name, Visibility::Public)?;
+    }
+    Some(self_val)
+}
+
+/// ### Module#private_class_method
+/// - private_class_method(*name) -> self
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Module/i/private_class_method.html]
+extern "C" fn private_class_method(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let singleton = globals.get_singleton_id(self_val.as_class());
+    for name in method_names(globals, arg, len)? {
+        globals.change_method_visibility(singleton, name, Visibility::Private)?;
+    }
+    Some(self_val)
+}
+
+/// ### Module#private_constant
+/// - private_constant(*name) -> self
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Module/i/private_constant.html]
+extern "C" fn private_constant(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let class_id = self_val.as_class();
+    for i in 0..len {
+        let name = arg[i].expect_symbol_or_string(globals)?;
+        globals.set_private_constant(class_id, name)?;
+    }
+    Some(self_val)
+}
+
 #[cfg(test)]
 mod test {
     use super::tests::*;
@@ -213,6 +731,9 @@ mod test {
     #[test]
     fn test_class() {
         run_test("Time.superclass.to_s");
+        run_test("Integer === 3");
+        run_test("Object === 3.7");
+        run_test("String === :a");
         run_test(
             r#"
         class A
@@ -235,6 +756,138 @@ mod test {
         );
     }
 
+    #[test]
+    fn module() {
+        run_test(
+            r#"
+        module M
+          C = 100
+          def f
+            C + 1
+          end
+        end
+        class A
+          include M
+        end
+        [A.new.f, M::C, A.include?(M), M === A.new, A.new.is_a?(M), M.to_s]
+        "#,
+        );
+        run_test(
+            r#"
+        module M
+          def f; "M"; end
+        end
+        module M
+          def g; "g"; end
+        end
+        class A; include M; end
+        [A.new.f, A.new.g]
+        "#,
+        );
+        run_test("[Comparable === 3, Enumerable === [], Comparable === [], Integer.include?(Comparable)]");
+        run_test("[3.kind_of?(Comparable), 3.is_a?(Integer), 3.is_a?(String)]");
+    }
+
+    #[test]
+    fn module_include_prepend() {
+        run_test(
+            r#"
+        module M1; def f; "M1"; end; def g; "M1"; end; end
+        module M2; def f; "M2"; end; def g; "M2"; end; def h; "M2"; end; end
+        module M3; def f; "M3"; end; def h; "M3"; end; end
+        class A; include M3; def f; "A"; end; end
+        class A; include M1, M2; end
+        a = A.ancestors
+        o = A.new
+        [o.f, o.g, o.h, a[0], a[1], a[2], a[3], a[4]]
+        "#,
+        );
+        run_test(
+            r#"
+        module P; def f; "P"; end; end
+        class A; def f; "A"; end; end
+        x = A.new.f
+        class A; prepend P; end
+        a = A.ancestors
+        [x, A.new.f, a[0], a[1]]
+        "#,
+        );
+        run_test(
+            r#"
+        module M; def f; 42; end; end
+        class A; end
+        a = A.new
+        a.extend(M)
+        [a.f, a.is_a?(M), A.new.is_a?(M), a.singleton_class.include?(M)]
+        "#,
+        );
+        run_test(
+            r#"
+        module M; def m; 1; end; end
+        class A; def f; m; end; end
+        r = []
+        begin; A.new.f; rescue NoMethodError; r << :err; end
+        class A; include M; end
+        r << A.new.f
+        r
+        "#,
+        );
+    }
+
+    #[test]
+    fn module_error() {
+        run_test_error(
+            r#"
+        class M; end
+        module M; end
+        "#,
+        );
+        run_test_error(
+            r#"
+        class A; include 3; end
+        "#,
+        );
+        run_test_error(
+            r#"
+        module M; end
+        M.include(M)
+        "#,
+        );
+    }
+
+    #[test]
+    fn const_get() {
+        run_test(
+            r#"
+        module M
+          X = 1
+          class A
+            Y = 2
+          end
+        end
+        class B
+          include M
+        end
+        [
+          M.const_get(:X), B.const_get("X"), M.const_get("A::Y"), Object.const_get("M::A::Y"),
+          M::A.const_get(:String), M.const_defined?(:X), B.const_defined?(:X),
+          B.const_defined?(:X, false), M.const_defined?(:Z)
+        ]
+        "#,
+        );
+        run_test(
+            r#"
+        class C; end
+        r = C.const_set(:W, 100)
+        [r, C::W, C.const_get(:W), C.constants]
+        "#,
+        );
+        run_test_error("Object.const_get(:Foo)");
+        run_test_error("Object.const_get(:foo)");
+        run_test_error("Object.const_set(:foo, 1)");
+        run_test_error("Comparable.const_get(:X, false)");
+    }
+
     #[test]
     fn attr_reader() {
         run_test(
@@ -325,4 +978,216 @@ mod test {
         "#,
         );
     }
+
+    #[test]
+    fn visibility() {
+        run_test_with_prelude(
+            r#"
+        a = A.new
+        res = [a.pub, a.call_priv, a.call_self_priv, a.respond_to?(:priv), a.respond_to?(:priv, true)]
+        begin
+          a.priv
+        rescue NoMethodError => e
+          res << e.class
+        end
+        begin
+          a.priv2
+        rescue NoMethodError => e
+          res << e.class
+        end
+        res << a.pub2
+        res
+        "#,
+            r#"
+        class A
+          def pub; 1; end
+          def call_priv; priv + 1; end
+          def call_self_priv; self.priv + 2; end
+          private
+          def priv; 10; end
+          def priv2; 20; end
+          public
+          def pub2; priv2; end
+        end
+        "#,
+        );
+        run_test_with_prelude(
+            r#"
+        a = A.new
+        res = [a.f, a.g]
+        begin
+          a.h
+        rescue NoMethodError => e
+          res << e.class
+        end
+        res
+        "#,
+            r#"
+        class A
+          def f; g + h; end
+          def g; 1; end
+          def h; 2; end
+          private :h
+          attr_reader :j
+          private :j
+        end
+        "#,
+        );
+        run_test_with_prelude(
+            r#"
+        a = Money.new(3); b = Money.new(5)
+        res = [a.cmp(b), b.cmp(a)]
+        begin
+          a.amount
+        rescue NoMethodError => e
+          res << e.class
+        end
+        res
+        "#,
+            r#"
+        class Money
+          def initialize(amount); @amount = amount; end
+          def cmp(other); amount <=> other.amount; end
+          protected
+          def amount; @amount; end
+        end
+        "#,
+        );
+        run_test_with_prelude(
+            r#"
+        a = Money.new(3)
+        res = []
+        50.times do |i|
+          caller = i.even? ? Money.new(i) : i
+          begin
+            res << caller.peek(a)
+          rescue NoMethodError => e
+            res << e.class
+          end
+        end
+        res
+        "#,
+            r#"
+        class Money
+          def initialize(amount); @amount = amount; end
+          protected
+          def amount; @amount; end
+        end
+        class Object
+          def peek(m); m.amount; end
+        end
+        "#,
+        );
+    }
+
+    #[test]
+    fn visibility_inherited() {
+        run_test_with_prelude(
+            r#"
+        res = [B.new.call_f]
+        begin
+          B.new.f
+        rescue NoMethodError => e
+          res << e.class
+        end
+        res << A.new.f
+        begin
+          C.new
+        rescue NoMethodError => e
+          res << e.class
+        end
+        res << C.create.class
+        res
+        "#,
+            r#"
+        class A
+          def f; 42; end
+        end
+        class B < A
+          private :f
+          def call_f; f; end
+        end
+        class C
+          def self.create; new; end
+          private_class_method :new
+        end
+        "#,
+        );
+    }
+
+    #[test]
+    fn module_function() {
+        run_test_with_prelude(
+            r#"
+        res = [M.f, M.g, C.new.h]
+        begin
+          C.new.f
+        rescue NoMethodError => e
+          res << e.class
+        end
+        res
+        "#,
+            r#"
+        module M
+          module_function
+          def f; 1; end
+          def g; f + 1; end
+        end
+        module N
+          def k; 5; end
+          module_function :k
+        end
+        class C
+          include M
+          def h; f + g + N.k; end
+        end
+        "#,
+        );
+    }
+
+    #[test]
+    fn private_constant() {
+        run_test_with_prelude(
+            r#"
+        res = [A.get]
+        begin
+          A::SECRET
+        rescue NameError => e
+          res << e.class
+        end
+        res << A::OPEN
+        "#,
+            r#"
+        class A
+          SECRET = 42
+          OPEN = 7
+          private_constant :SECRET
+          def self.get; SECRET; end
+        end
+        "#,
+        );
+    }
+
+    #[test]
+    fn visibility_error() {
+        run_test_error(
+            r#"
+        class A
+          private
+          def f; end
+        end
+        A.new.f
+        "#,
+        );
+        run_test_error(
+            r#"
+        def foo; end
+        1.foo
+        "#,
+        );
+        run_test_error("class A; def f; end; end; A.new.initialize");
+        run_test_error("class A; private :g; end");
+        run_test_error("class A; C = 1; private_constant :C; end; A::C");
+        run_test_error("class A; module_function; end");
+    }
 }
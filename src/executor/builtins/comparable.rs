@@ -0,0 +1,179 @@
+use std::cmp::Ordering;
+
+use crate::*;
+
+//
+// Comparable module
+//
+
+pub(super) fn init(globals: &mut Globals) {
+    globals.define_builtin_func(COMPARABLE_MODULE, "==", eq, 1);
+    globals.define_builtin_func(COMPARABLE_MODULE, "<", lt, 1);
+    globals.define_builtin_func(COMPARABLE_MODULE, "<=", le, 1);
+    globals.define_builtin_func(COMPARABLE_MODULE, ">", gt, 1);
+    globals.define_builtin_func(COMPARABLE_MODULE, ">=", ge, 1);
+    globals.define_builtin_func(COMPARABLE_MODULE, "between?", between, 2);
+    globals.define_builtin_func(COMPARABLE_MODULE, "clamp", clamp, -1);
+}
+
+/// ### Comparable#==
+/// - self == other -> bool
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Comparable/i/=3d=3d.html]
+extern "C" fn eq(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    if self_val == arg[0] {
+        return Some(Value::bool(true));
+    }
+    if globals.find_method(self_val, IdentId::_CMP).is_none() {
+        return Some(Value::bool(false));
+    }
+    let res = vm.invoke_method(globals, IdentId::_CMP, self_val, &[arg[0]])?;
+    Some(Value::bool(res.try_fixnum() == Some(0)))
+}
+
+macro_rules! cmp_op {
+    ($(($name:ident, $($ord:pat_param)|+)),+) => {
+        $(
+            extern "C" fn $name(
+                vm: &mut Executor,
+                globals: &mut Globals,
+                self_val: Value,
+                arg: Arg,
+                _len: usize,
+                _: Option<Value>,
+            ) -> Option<Value> {
+                let ord = vm.compare_values(globals, self_val, arg[0])?;
+                Some(Value::bool(matches!(ord, $($ord)|+)))
+            }
+        )+
+    };
+}
+
+cmp_op!(
+    (lt, Ordering::Less),
+    (le, Ordering::Less | Ordering::Equal),
+    (gt, Ordering::Greater),
+    (ge, Ordering::Greater | Ordering::Equal)
+);
+
+/// ### Comparable#between?
+/// - between?(min, max) -> bool
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Comparable/i/between=3f.html]
+extern "C" fn between(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let b = vm.compare_values(globals, self_val, arg[0])? != Ordering::Less
+        && vm.compare_values(globals, self_val, arg[1])? != Ordering::Greater;
+    Some(Value::bool(b))
+}
+
+/// ### Comparable#clamp
+/// - clamp(min, max) -> object
+/// - clamp(range) -> object
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Comparable/i/clamp.html]
+extern "C" fn clamp(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let (min, max) = match len {
+        1 => {
+            let range = match arg[0].is_range() {
+                Some(range) => range,
+                None => {
+                    globals.err_no_implict_conv(arg[0], RANGE_CLASS);
+                    return None;
+                }
+            };
+            if range.exclude_end() && !range.end.is_nil() {
+                globals.err_argument("cannot clamp with an exclusive range");
+                return None;
+            }
+            (range.start, range.end)
+        }
+        2 => (arg[0], arg[1]),
+        _ => {
+            globals.err_wrong_number_of_arguments_range(len, 1..=2);
+            return None;
+        }
+    };
+    if !min.is_nil() && !max.is_nil() && vm.compare_values(globals, min, max)? == Ordering::Greater
+    {
+        globals.err_argument("min argument must be less than or equal to max argument");
+        return None;
+    }
+    if !min.is_nil() {
+        match vm.compare_values(globals, self_val, min)? {
+            Ordering::Equal => return Some(self_val),
+            Ordering::Less => return Some(min),
+            Ordering::Greater => {}
+        }
+    }
+    if !max.is_nil() && vm.compare_values(globals, self_val, max)? == Ordering::Greater {
+        return Some(max);
+    }
+    Some(self_val)
+}
+
+#[cfg(test)]
+mod test {
+    use super::tests::*;
+
+    const PRELUDE: &str = r##"
+        class Ver
+          include Comparable
+          attr_reader :v
+          def initialize(v)
+            @v = v
+          end
+          def <=>(other)
+            other.is_a?(Ver) ? @v <=> other.v : nil
+          end
+        end
+        "##;
+
+    #[test]
+    fn comparable() {
+        run_test_with_prelude(
+            "a = Ver.new(1); b = Ver.new(2); [a < b, a <= b, a > b, a >= b, a == b, a == Ver.new(1), a == 1]",
+            PRELUDE,
+        );
+        run_test_with_prelude(
+            "[Ver.new(2).between?(Ver.new(1), Ver.new(3)), Ver.new(5).between?(Ver.new(1), Ver.new(3))]",
+            PRELUDE,
+        );
+        run_test_with_prelude(
+            "[Ver.new(0), Ver.new(2), Ver.new(5)].map { |x| x.clamp(Ver.new(1), Ver.new(3)).v }",
+            PRELUDE,
+        );
+        run_test_error(&format!("{PRELUDE}; Ver.new(1) < 1"));
+        run_test_error(&format!(
+            "{PRELUDE}; Ver.new(1).clamp(Ver.new(3), Ver.new(1))"
+        ));
+    }
+
+    #[test]
+    fn comparable_builtin() {
+        run_test("[3.clamp(1, 2), 0.clamp(1, 2), 1.5.clamp(1, 2), 5.clamp(Range.new(nil, 3)), 5.clamp(Range.new(7, nil))]");
+        run_test(r#"["b".clamp("a", "c"), "z".clamp("a", "c"), "b".between?("a", "c")]"#);
+        run_test("[5.between?(1, 10), 5.between?(6, 10)]");
+        run_test_error("5.clamp(1...3)");
+    }
+}
@@ -0,0 +1,834 @@
+use num::Zero;
+
+use crate::*;
+
+//
+// Complex class
+//
+
+pub(super) fn init(globals: &mut Globals) {
+    globals.define_builtin_func(OBJECT_CLASS, "Complex", kernel_complex, -1);
+    globals.define_builtin_singleton_func(COMPLEX_CLASS, "rectangular", rectangular, -1);
+    globals.define_builtin_singleton_func(COMPLEX_CLASS, "rect", rectangular, -1);
+    globals.define_builtin_singleton_func(COMPLEX_CLASS, "polar", polar, -1);
+    let i = Value::new_complex(Value::new_integer(0), Value::new_integer(1));
+    globals.set_constant(COMPLEX_CLASS, IdentId::get_ident_id("I"), i);
+    for class_id in [INTEGER_CLASS, FLOAT_CLASS, RATIONAL_CLASS] {
+        globals.define_builtin_func(class_id, "i", numeric_i, 0);
+    }
+    globals.define_builtin_func(NUMERIC_CLASS, "real", numeric_real, 0);
+    globals.define_builtin_func(NUMERIC_CLASS, "imaginary", numeric_imaginary, 0);
+    globals.define_builtin_func(NUMERIC_CLASS, "imag", numeric_imaginary, 0);
+    globals.define_builtin_func(NUMERIC_CLASS, "conjugate", numeric_real, 0);
+    globals.define_builtin_func(NUMERIC_CLASS, "conj", numeric_real, 0);
+    globals.define_builtin_func(NUMERIC_CLASS, "real?", numeric_real_, 0);
+    globals.define_builtin_func(NUMERIC_CLASS, "abs2", numeric_abs2, 0);
+    globals.define_builtin_func(NUMERIC_CLASS, "arg", numeric_arg, 0);
+    globals.define_builtin_func(NUMERIC_CLASS, "angle", numeric_arg, 0);
+    globals.define_builtin_func(NUMERIC_CLASS, "phase", numeric_arg, 0);
+    globals.define_builtin_func(NUMERIC_CLASS, "rectangular", numeric_rectangular, 0);
+    globals.define_builtin_func(NUMERIC_CLASS, "rect", numeric_rectangular, 0);
+    globals.define_builtin_func(NUMERIC_CLASS, "polar", numeric_polar, 0);
+
+    globals.define_builtin_func(COMPLEX_CLASS, "real", real, 0);
+    globals.define_builtin_func(COMPLEX_CLASS, "imaginary", imaginary, 0);
+    globals.define_builtin_func(COMPLEX_CLASS, "imag", imaginary, 0);
+    globals.define_builtin_func(COMPLEX_CLASS, "real?", real_, 0);
+    globals.define_builtin_func(COMPLEX_CLASS, "==", eq, 1);
+    globals.define_builtin_func(COMPLEX_CLASS, "===", eq, 1);
+    globals.define_builtin_func(COMPLEX_CLASS, "<=>", cmp, 1);
+    globals.define_builtin_func(COMPLEX_CLASS, "-@", neg, 0);
+    globals.define_builtin_func(COMPLEX_CLASS, "coerce", coerce, 1);
+    globals.define_builtin_func(COMPLEX_CLASS, "abs", abs, 0);
+    globals.define_builtin_func(COMPLEX_CLASS, "magnitude", abs, 0);
+    globals.define_builtin_func(COMPLEX_CLASS, "abs2", abs2, 0);
+    globals.define_builtin_func(COMPLEX_CLASS, "arg", arg, 0);
+    globals.define_builtin_func(COMPLEX_CLASS, "angle", arg, 0);
+    globals.define_builtin_func(COMPLEX_CLASS, "phase", arg, 0);
+    globals.define_builtin_func(COMPLEX_CLASS, "conjugate", conjugate, 0);
+    globals.define_builtin_func(COMPLEX_CLASS, "conj", conjugate, 0);
+    globals.define_builtin_func(COMPLEX_CLASS, "rectangular", rect, 0);
+    globals.define_builtin_func(COMPLEX_CLASS, "rect", rect, 0);
+    globals.define_builtin_func(COMPLEX_CLASS, "polar", to_polar, 0);
+    globals.define_builtin_func(COMPLEX_CLASS, "zero?", zero_, 0);
+    globals.define_builtin_func(COMPLEX_CLASS, "finite?", finite_, 0);
+    globals.define_builtin_func(COMPLEX_CLASS, "infinite?", infinite_, 0);
+    globals.define_builtin_func(COMPLEX_CLASS, "to_f", tof, 0);
+    globals.define_builtin_func(COMPLEX_CLASS, "to_i", toi, 0);
+    globals.define_builtin_func(COMPLEX_CLASS, "to_r", tor, 0);
+    globals.define_builtin_func(COMPLEX_CLASS, "to_s", tos, 0);
+    globals.define_builtin_func(COMPLEX_CLASS, "inspect", inspect, 0);
+    // Complex numbers are not ordered.
+    for name in ["positive?", "negative?", "<", "<=", ">", ">="] {
+        globals.define_builtin_func(COMPLEX_CLASS, name, undefined, -1);
+    }
+}
+
+///
+/// Check that *val* is a real number (Integer, Float or Rational).
+///
+fn expect_real(globals: &mut Globals, val: Value) -> Option<Value> {
+    if val.is_numeric() || val.is_rational().is_some() {
+        return Some(val);
+    }
+    let msg = match val.unpack() {
+        RV::Nil | RV::Bool(_) => globals.val_inspect(val),
+        _ => val.get_real_class_name(globals),
+    };
+    globals.set_error(MonorubyErr::typeerr(format!(
+        "can't convert {} into Complex",
+        msg
+    )));
+    None
+}
+
+fn is_exact_zero(val: Value) -> bool {
+    match val.unpack() {
+        RV::Integer(i) => i == 0,
+        _ => val.is_rational().map_or(false, |r| r.is_zero()),
+    }
+}
+
+fn is_zero(val: Value) -> bool {
+    match val.unpack() {
+        RV::Float(f) => f == 0.0,
+        _ => is_exact_zero(val),
+    }
+}
+
+/// ### Kernel.#Complex
+/// - Complex(r, i = 0) -> Complex
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Kernel/m/Complex.html]
+extern "C" fn kernel_complex(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    if !(1..=2).contains(&len) {
+        globals.err_wrong_number_of_arguments_range(len, 1..=2);
+        return None;
+    }
+    if len == 1 && arg[0].is_complex().is_some() {
+        return Some(arg[0]);
+    }
+    rectangular(vm, globals, self_val, arg, len, None)
+}
+
+/// ### Complex.rectangular
+/// - rectangular(r, i = 0) -> Complex
+/// - rect(r, i = 0) -> Complex
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Complex/s/rect.html]
+extern "C" fn rectangular(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    _self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    if !(1..=2).contains(&len) {
+        globals.err_wrong_number_of_arguments_range(len, 1..=2);
+        return None;
+    }
+    let re = expect_real(globals, arg[0])?;
+    let im = if len == 2 {
+        expect_real(globals, arg[1])?
+    } else {
+        Value::new_integer(0)
+    };
+    Some(Value::new_complex(re, im))
+}
+
+/// ### Complex.polar
+/// - polar(r, theta = 0) -> Complex
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Complex/s/polar.html]
+extern "C" fn polar(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    _self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    if !(1..=2).contains(&len) {
+        globals.err_wrong_number_of_arguments_range(len, 1..=2);
+        return None;
+    }
+    let r = expect_real(globals, arg[0])?;
+    if len == 1 || is_exact_zero(arg[1]) {
+        return Some(Value::new_complex(r, Value::new_integer(0)));
+    }
+    let theta = expect_real(globals, arg[1])?.try_float().unwrap();
+    let r = r.try_float().unwrap();
+    Some(Value::new_complex(
+        Value::new_float(r * theta.cos()),
+        Value::new_float(r * theta.sin()),
+    ))
+}
+
+/// ### Numeric#i
+/// - i -> Complex
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Numeric/i/i.html]
+extern "C" fn numeric_i(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(Value::new_complex(Value::new_integer(0), self_val))
+}
+
+/// ### Numeric#real
+/// - real -> Numeric
+/// - conjugate -> Numeric
+/// - conj -> Numeric
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Numeric/i/real.html]
+extern "C" fn numeric_real(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(self_val)
+}
+
+/// ### Numeric#imaginary
+/// - imaginary -> 0
+/// - imag -> 0
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Numeric/i/imag.html]
+extern "C" fn numeric_imaginary(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    _self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(Value::new_integer(0))
+}
+
+/// ### Numeric#real?
+/// - real? -> bool
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Numeric/i/real=3f.html]
+extern "C" fn numeric_real_(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    _self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(Value::bool(true))
+}
+
+/// ### Numeric#abs2
+/// - abs2 -> Numeric
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Numeric/i/abs2.html]
+extern "C" fn numeric_abs2(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    vm.binary_op(globals, IdentId::_MUL, self_val, self_val)
+}
+
+/// ### Numeric#arg
+/// - arg -> 0 | Float
+/// - angle -> 0 | Float
+/// - phase -> 0 | Float
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Numeric/i/angle.html]
+extern "C" fn numeric_arg(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    if let RV::Float(f) = self_val.unpack() {
+        if f.is_nan() {
+            return Some(self_val);
+        }
+        if f.is_sign_negative() {
+            return Some(Value::new_float(std::f64::consts::PI));
+        }
+        return Some(Value::new_integer(0));
+    }
+    let negative = vm
+        .binary_op(globals, IdentId::_LT, self_val, Value::new_integer(0))?
+        .as_bool();
+    Some(if negative {
+        Value::new_float(std::f64::consts::PI)
+    } else {
+        Value::new_integer(0)
+    })
+}
+
+/// ### Numeric#rectangular
+/// - rectangular -> [Numeric, 0]
+/// - rect -> [Numeric, 0]
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Numeric/i/rect.html]
+extern "C" fn numeric_rectangular(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(Value::new_array_from_vec(vec![
+        self_val,
+        Value::new_integer(0),
+    ]))
+}
+
+/// ### Numeric#polar
+/// - polar -> [Numeric, Numeric]
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Numeric/i/polar.html]
+extern "C" fn numeric_polar(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let abs = vm.invoke_method(globals, IdentId::get_ident_id("abs"), self_val, &[])?;
+    let arg = numeric_arg(vm, globals, self_val, arg, len, None)?;
+    Some(Value::new_array_from_vec(vec![abs, arg]))
+}
+
+/// ### Complex#real
+/// - real -> Numeric
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Complex/i/real.html]
+extern "C" fn real(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(self_val.as_complex().re)
+}
+
+/// ### Complex#imaginary
+/// - imaginary -> Numeric
+/// - imag -> Numeric
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Complex/i/imag.html]
+extern "C" fn imaginary(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(self_val.as_complex().im)
+}
+
+/// ### Complex#real?
+/// - real? -> false
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Complex/i/real=3f.html]
+extern "C" fn real_(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    _self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(Value::bool(false))
+}
+
+/// ### Complex#==
+/// - self == other -> bool
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Complex/i/=3d=3d.html]
+extern "C" fn eq(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let (re, im) = {
+        let complex = self_val.as_complex();
+        (complex.re, complex.im)
+    };
+    let other = arg[0];
+    let b = if let Some(other) = other.is_complex() {
+        let (other_re, other_im) = (other.re, other.im);
+        vm.eq_values(globals, re, other_re)? && vm.eq_values(globals, im, other_im)?
+    } else if other.is_numeric() || other.is_rational().is_some() {
+        vm.eq_values(globals, re, other)? && is_zero(im)
+    } else {
+        vm.invoke_method(globals, IdentId::_EQ, other, &[self_val])?
+            .as_bool()
+    };
+    Some(Value::bool(b))
+}
+
+/// ### Complex#<=>
+/// - self <=> other -> -1 | 0 | 1 | nil
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Complex/i/=3c=3d=3e.html]
+extern "C" fn cmp(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let complex = self_val.as_complex();
+    let (re, im) = (complex.re, complex.im);
+    let other = match arg[0].is_complex() {
+        Some(other) if is_zero(other.im) => other.re,
+        Some(_) => return Some(Value::nil()),
+        None if arg[0].is_numeric() || arg[0].is_rational().is_some() => arg[0],
+        None => return Some(Value::nil()),
+    };
+    if !is_zero(im) {
+        return Some(Value::nil());
+    }
+    vm.invoke_method(globals, IdentId::_CMP, re, &[other])
+}
+
+/// ### Complex#-@
+/// - -self -> Complex
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Complex/i/-=40.html]
+extern "C" fn neg(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let complex = self_val.as_complex();
+    let (re, im) = (complex.re, complex.im);
+    let re = super::super::op::neg_value(vm, globals, re)?;
+    let im = super::super::op::neg_value(vm, globals, im)?;
+    Some(Value::new_complex(re, im))
+}
+
+/// ### Complex#coerce
+/// - coerce(other) -> [Complex, Complex]
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Complex/i/coerce.html]
+extern "C" fn coerce(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let other = arg[0];
+    let other = if other.is_complex().is_some() {
+        other
+    } else if other.is_numeric() || other.is_rational().is_some() {
+        Value::new_complex(other, Value::new_integer(0))
+    } else {
+        globals.err_cant_be_coerced(other, self_val);
+        return None;
+    };
+    Some(Value::new_array_from_vec(vec![other, self_val]))
+}
+
+/// ### Complex#abs
+/// - abs -> Numeric
+/// - magnitude -> Numeric
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Complex/i/abs.html]
+extern "C" fn abs(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let complex = self_val.as_complex();
+    let (re, im) = (complex.re, complex.im);
+    // If one part is zero, the absolute value of the other is returned without
+    // converting into Float, unless the zero part is a Float.
+    let other = if is_zero(re) {
+        Some((im, re))
+    } else if is_zero(im) {
+        Some((re, im))
+    } else {
+        None
+    };
+    if let Some((val, zero)) = other {
+        let abs = vm.invoke_method(globals, IdentId::get_ident_id("abs"), val, &[])?;
+        return Some(match zero.unpack() {
+            RV::Float(_) => Value::new_float(abs.try_float().unwrap()),
+            _ => abs,
+        });
+    }
+    let re = re.try_float().unwrap();
+    let im = im.try_float().unwrap();
+    Some(Value::new_float(re.hypot(im)))
+}
+
+/// ### Complex#abs2
+/// - abs2 -> Numeric
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Complex/i/abs2.html]
+extern "C" fn abs2(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let complex = self_val.as_complex();
+    let (re, im) = (complex.re, complex.im);
+    let re2 = vm.binary_op(globals, IdentId::_MUL, re, re)?;
+    let im2 = vm.binary_op(globals, IdentId::_MUL, im, im)?;
+    vm.binary_op(globals, IdentId::_ADD, re2, im2)
+}
+
+/// ### Complex#arg
+/// - arg -> Float
+/// - angle -> Float
+/// - phase -> Float
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Complex/i/angle.html]
+extern "C" fn arg(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let complex = self_val.as_complex();
+    let re = complex.re.try_float().unwrap();
+    let im = complex.im.try_float().unwrap();
+    Some(Value::new_float(im.atan2(re)))
+}
+
+/// ### Complex#conjugate
+/// - conjugate -> Complex
+/// - conj -> Complex
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Complex/i/conj.html]
+extern "C" fn conjugate(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let complex = self_val.as_complex();
+    let (re, im) = (complex.re, complex.im);
+    let im = super::super::op::neg_value(vm, globals, im)?;
+    Some(Value::new_complex(re, im))
+}
+
+/// ### Complex#rectangular
+/// - rectangular -> [Numeric, Numeric]
+/// - rect -> [Numeric, Numeric]
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Complex/i/rect.html]
+extern "C" fn rect(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let complex = self_val.as_complex();
+    Some(Value::new_array_from_vec(vec![complex.re, complex.im]))
+}
+
+/// ### Complex#polar
+/// - polar -> [Numeric, Float]
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Complex/i/polar.html]
+extern "C" fn to_polar(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg_: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let abs = abs(vm, globals, self_val, arg_, len, None)?;
+    let arg = arg(vm, globals, self_val, arg_, len, None)?;
+    Some(Value::new_array_from_vec(vec![abs, arg]))
+}
+
+/// ### Complex#zero?
+/// - zero? -> bool
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Numeric/i/zero=3f.html]
+extern "C" fn zero_(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let complex = self_val.as_complex();
+    Some(Value::bool(is_zero(complex.re) && is_zero(complex.im)))
+}
+
+/// ### Complex#finite?
+/// - finite? -> bool
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Complex/i/finite=3f.html]
+extern "C" fn finite_(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let complex = self_val.as_complex();
+    let b =
+        complex.re.try_float().unwrap().is_finite() && complex.im.try_float().unwrap().is_finite();
+    Some(Value::bool(b))
+}
+
+/// ### Complex#infinite?
+/// - infinite? -> nil | 1
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Complex/i/infinite=3f.html]
+extern "C" fn infinite_(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let complex = self_val.as_complex();
+    let b = complex.re.try_float().unwrap().is_infinite()
+        || complex.im.try_float().unwrap().is_infinite();
+    Some(if b {
+        Value::new_integer(1)
+    } else {
+        Value::nil()
+    })
+}
+
+///
+/// Get the real part of *val* for conversion into a real number.
+///
+/// Raise RangeError if the imaginary part is not an exact zero.
+///
+fn expect_real_part(globals: &mut Globals, val: Value, class_name: &str) -> Option<Value> {
+    let complex = val.as_complex();
+    if !is_exact_zero(complex.im) {
+        let msg = format!("can't convert {} into {}", globals.val_tos(val), class_name);
+        globals.set_error(MonorubyErr::range(msg));
+        return None;
+    }
+    Some(complex.re)
+}
+
+/// ### Complex#to_f
+/// - to_f -> Float
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Complex/i/to_f.html]
+extern "C" fn tof(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let re = expect_real_part(globals, self_val, "Float")?;
+    Some(Value::new_float(re.try_float().unwrap()))
+}
+
+/// ### Complex#to_i
+/// - to_i -> Integer
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Complex/i/to_i.html]
+extern "C" fn toi(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let re = expect_real_part(globals, self_val, "Integer")?;
+    vm.invoke_method(globals, IdentId::get_ident_id("to_i"), re, &[])
+}
+
+/// ### Complex#to_r
+/// - to_r -> Rational
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Complex/i/to_r.html]
+extern "C" fn tor(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let re = expect_real_part(globals, self_val, "Rational")?;
+    vm.invoke_method(globals, IdentId::get_ident_id("to_r"), re, &[])
+}
+
+/// ### Complex#to_s
+/// - to_s -> String
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Complex/i/to_s.html]
+extern "C" fn tos(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(Value::new_string(globals.val_tos(self_val)))
+}
+
+/// ### Complex#inspect
+/// - inspect -> String
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Complex/i/inspect.html]
+extern "C" fn inspect(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(Value::new_string(globals.val_inspect(self_val)))
+}
+
+///
+/// Methods of Numeric which are not defined for Complex.
+///
+extern "C" fn undefined(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let func_id = vm.current_func_id();
+    let name = IdentId::get_ident_id(globals.func[func_id].name().unwrap());
+    globals.err_method_not_found(name, self_val);
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::tests::*;
+
+    #[test]
+    fn complex_new() {
+        run_test(r#"Complex(1, 2).inspect"#);
+        run_test(
+            r#"[Complex(1, -2), Complex(1.5, 2), Complex(1, -0.0), Complex(3), Complex(0, Rational(1, 2)), Complex(Rational(-1, 3), Rational(-1, 2))].map(&:inspect)"#,
+        );
+        run_test(
+            r#"[Complex(1, 2).to_s, Complex(1, -2.5).to_s, Complex(0, Rational(1, 2)).to_s, Complex::I.inspect]"#,
+        );
+        run_test(
+            r#"[3.i.inspect, -2.5.i.inspect, Complex.rect(1, 2).inspect, Complex(Complex(1, 2)).inspect]"#,
+        );
+        run_test(
+            r#"[Complex(1, 2).real, Complex(1, 2).imaginary, Complex(1, 2.5).imag, Complex(1, 2).rect, Complex(1, 2).real?, 1.real?]"#,
+        );
+        run_test(r#"[1.real, 1.imag, 2.5.conj, 3.abs2, -3.arg, 3.arg, 2.rect]"#);
+        run_test(r#"[Complex(1, 2).class, Complex(1, 2).is_a?(Numeric)]"#);
+        run_test_error(r#"Complex("a", 1)"#);
+        run_test_error(r#"Complex(nil)"#);
+    }
+
+    #[test]
+    fn complex_arith() {
+        run_test(r#"(Complex(1, 2) + Complex(3, 4)).inspect"#);
+        run_test(
+            r#"[Complex(1, 2) - Complex(3, 4), Complex(1, 2) * Complex(3, 4), Complex(1, 2) + 1, 1 + Complex(1, 2), 3 - Complex(1, 2), 2 * Complex(1, 2), Complex(1, 2) * 1.5].map(&:inspect)"#,
+        );
+        run_test(
+            r#"[Complex(1, 2) / Complex(3, 4), Complex(4, 2) / 2, 1 / Complex(0, 1), Complex(1.0, 2) / 2].map(&:inspect)"#,
+        );
+        run_test(
+            r#"[Complex(1, 2) + Rational(1, 2), Rational(1, 2) * Complex(0, 2)].map(&:inspect)"#,
+        );
+        run_test(
+            r#"[Complex(1, 2) ** 2, Complex(1, 2) ** 0, Complex(1, 2) ** 3, Complex(1, 2) ** -1, Complex::I ** 2].map(&:inspect)"#,
+        );
+        run_test(
+            r#"[-Complex(1, 2), Complex(1, 2).conjugate, Complex(1, -2.5).conj].map(&:inspect)"#,
+        );
+        run_test(
+            r#"[Complex(3, 4).abs, Complex(3, 4).abs2, Complex(0, -3).abs, Complex(-2.5, 0).magnitude, Complex(1, 1).arg, Complex(-1, 0).angle]"#,
+        );
+        run_test(r#"((Complex(1, 2) ** 0.5) ** 2 - Complex(1, 2)).abs < 1e-10"#);
+        run_test(r#"(Complex.polar(2, 3.141592653589793 / 2) - Complex(0, 2)).abs < 1e-10"#);
+        run_test(r#"c = Complex(0, 0); 10.times { |i| c += Complex(i, -i) }; c.inspect"#);
+        run_test_error(r#"Complex(1, 2) / 0"#);
+        run_test_error(r#"Complex(1, 2) + "a""#);
+        run_test_error(r#"Complex(1, 2) < Complex(2, 3)"#);
+        run_test_error(r#"1 < Complex(2, 3)"#);
+        run_test_error(r#"Complex(1, 2) % 2"#);
+    }
+
+    #[test]
+    fn complex_cmp() {
+        run_test(
+            r#"[Complex(1, 2) == Complex(1, 2), Complex(1, 2) == Complex(1, 3), Complex(1, 0) == 1, 1 == Complex(1, 0), Complex(1.0, 0) == 1, 2.0 == Complex(2)]"#,
+        );
+        run_test(
+            r#"[Complex(1, 0) <=> 2, Complex(3) <=> Complex(2), Complex(1, 2) <=> 1, Complex(1) <=> "a"]"#,
+        );
+        run_test(
+            r#"[Complex(0, 0).zero?, Complex(0, 1).zero?, Complex(1, 2).finite?, Complex(1, Float::INFINITY).infinite?]"#,
+        );
+        run_test(
+            r#"[Complex(3, 0).to_i, Complex(2.5, 0).to_f, Complex(Rational(1, 2), 0).to_r.inspect]"#,
+        );
+        run_test(r#"h = { Complex(1, 2) => 1 }; h[Complex(1, 2)]"#);
+        run_test_error(r#"Complex(1, 2).to_f"#);
+        run_test_error(r#"Complex(1, 2).positive?"#);
+    }
+}
@@ -0,0 +1,687 @@
+use crate::*;
+
+//
+// Enumerable module
+//
+
+pub(super) fn init(globals: &mut Globals) {
+    globals.define_builtin_func(ENUMERABLE_MODULE, "to_a", to_a, 0);
+    globals.define_builtin_func(ENUMERABLE_MODULE, "entries", to_a, 0);
+    globals.define_builtin_func(ENUMERABLE_MODULE, "map", map, 0);
+    globals.define_builtin_func(ENUMERABLE_MODULE, "collect", map, 0);
+    globals.define_builtin_func(ENUMERABLE_MODULE, "select", select, 0);
+    globals.define_builtin_func(ENUMERABLE_MODULE, "filter", select, 0);
+    globals.define_builtin_func(ENUMERABLE_MODULE, "reject", reject, 0);
+    globals.define_builtin_func(ENUMERABLE_MODULE, "find", find, 0);
+    globals.define_builtin_func(ENUMERABLE_MODULE, "detect", find, 0);
+    globals.define_builtin_func(ENUMERABLE_MODULE, "include?", include, 1);
+    globals.define_builtin_func(ENUMERABLE_MODULE, "member?", include, 1);
+    globals.define_builtin_func(ENUMERABLE_MODULE, "sort", sort, 0);
+    globals.define_builtin_func(ENUMERABLE_MODULE, "sort_by", sort_by, 0);
+    globals.define_builtin_func(ENUMERABLE_MODULE, "min", min, 0);
+    globals.define_builtin_func(ENUMERABLE_MODULE, "max", max, 0);
+    globals.define_builtin_func(ENUMERABLE_MODULE, "sum", sum, -1);
+    globals.define_builtin_func(ENUMERABLE_MODULE, "inject", inject, -1);
+    globals.define_builtin_func(ENUMERABLE_MODULE, "reduce", inject, -1);
+    globals.define_builtin_func(ENUMERABLE_MODULE, "each_with_index", each_with_index, 0);
+    globals.define_builtin_func(ENUMERABLE_MODULE, "each_slice", each_slice, 1);
+    globals.define_builtin_func(ENUMERABLE_MODULE, "group_by", group_by, 0);
+    globals.define_builtin_func(ENUMERABLE_MODULE, "partition", partition, 0);
+    globals.define_builtin_func(ENUMERABLE_MODULE, "tally", tally, 0);
+    globals.define_builtin_func(ENUMERABLE_MODULE, "zip", zip, -1);
+    globals.define_builtin_func(ENUMERABLE_MODULE, "count", count, -1);
+    globals.define_builtin_func(ENUMERABLE_MODULE, "first", first, -1);
+    globals.define_builtin_func(ENUMERABLE_MODULE, "lazy", lazy, 0);
+}
+
+///
+/// The body of the block which `Executor::collect_each` passes to `each`.
+///
+/// The yielded values are pushed to the collector of the outer frame.
+///
+pub(crate) extern "C" fn enum_yielder(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    _self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let val = match len {
+        0 => Value::nil(),
+        1 => arg[0],
+        _ => Value::new_array_from_vec(arg.to_vec(len)),
+    };
+    vm.push_collected(globals, val)
+}
+
+///
+/// Get the block handler for passing *block* of the current native function to a callee.
+///
+pub(super) fn block_proxy(block: Option<Value>) -> Option<Value> {
+    block.map(|block| match block.try_fixnum() {
+        // the outer frame is one more frame away from the callee.
+        Some(bh) => Value::new_integer(bh + 1),
+        None => block,
+    })
+}
+
+///
+/// Collect the elements of *self_val*, and call the Array method *method* on them.
+///
+fn delegate_to_array(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    method: &str,
+    args: &[Value],
+    block: Option<Value>,
+) -> Option<Value> {
+    let ary = vm.collect_each(globals, self_val, None)?;
+    let method = IdentId::get_ident_id(method);
+    vm.with_temp(ary, |vm| {
+        vm.invoke_method_with_block(globals, method, ary, args, block_proxy(block))
+    })
+}
+
+/// ### Enumerable#to_a
+/// - to_a -> [object]
+/// - entries -> [object]
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Enumerable/i/entries.html]
+extern "C" fn to_a(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    vm.collect_each(globals, self_val, None)
+}
+
+/// ### Enumerable#map
+/// - map {|item| ... } -> [object]
+/// - collect {|item| ... } -> [object]
+/// - map -> Enumerator
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Enumerable/i/collect.html]
+extern "C" fn map(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    let block = match block {
+        Some(block) => block,
+        None => return Some(Value::new_enumerator(self_val, "map", vec![])),
+    };
+    delegate_to_array(vm, globals, self_val, "map", &[], Some(block))
+}
+
+/// ### Enumerable#select
+/// - select {|item| ... } -> [object]
+/// - filter {|item| ... } -> [object]
+/// - select -> Enumerator
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Enumerable/i/filter.html]
+extern "C" fn select(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    let block = match block {
+        Some(block) => block,
+        None => return Some(Value::new_enumerator(self_val, "select", vec![])),
+    };
+    delegate_to_array(vm, globals, self_val, "select", &[], Some(block))
+}
+
+/// ### Enumerable#reject
+/// - reject {|item| ... } -> [object]
+/// - reject -> Enumerator
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Enumerable/i/reject.html]
+extern "C" fn reject(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    let block = match block {
+        Some(block) => block,
+        None => return Some(Value::new_enumerator(self_val, "reject", vec![])),
+    };
+    delegate_to_array(vm, globals, self_val, "reject", &[], Some(block))
+}
+
+/// ### Enumerable#find
+/// - find {|item| ... } -> object | nil
+/// - detect {|item| ... } -> object | nil
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Enumerable/i/detect.html]
+extern "C" fn find(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    let block = globals.expect_block(block)?;
+    let ary = vm.collect_each(globals, self_val, None)?;
+    vm.with_temp(ary, |vm| {
+        for elem in ary.as_array().to_vec() {
+            if vm
+                .invoke_block(globals, block, self_val, &[elem])?
+                .as_bool()
+            {
+                return Some(elem);
+            }
+        }
+        Some(Value::nil())
+    })
+}
+
+/// ### Enumerable#include?
+/// - include?(val) -> bool
+/// - member?(val) -> bool
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Enumerable/i/include=3f.html]
+extern "C" fn include(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let ary = vm.collect_each(globals, self_val, None)?;
+    vm.with_temp(ary, |vm| {
+        for elem in ary.as_array().to_vec() {
+            if vm.eq_values(globals, elem, arg[0])? {
+                return Some(Value::bool(true));
+            }
+        }
+        Some(Value::bool(false))
+    })
+}
+
+/// ### Enumerable#sort
+/// - sort -> [object]
+/// - sort {|a, b| ... } -> [object]
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Enumerable/i/sort.html]
+extern "C" fn sort(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    delegate_to_array(vm, globals, self_val, "sort", &[], block)
+}
+
+/// ### Enumerable#sort_by
+/// - sort_by {|item| ... } -> [object]
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Enumerable/i/sort_by.html]
+extern "C" fn sort_by(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    globals.expect_block(block)?;
+    delegate_to_array(vm, globals, self_val, "sort_by", &[], block)
+}
+
+/// ### Enumerable#min
+/// - min -> object | nil
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Enumerable/i/min.html]
+extern "C" fn min(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    delegate_to_array(vm, globals, self_val, "min", &[], None)
+}
+
+/// ### Enumerable#max
+/// - max -> object | nil
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Enumerable/i/max.html]
+extern "C" fn max(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    delegate_to_array(vm, globals, self_val, "max", &[], None)
+}
+
+/// ### Enumerable#sum
+/// - sum(init = 0) -> object
+/// - sum(init = 0) {|obj| ... } -> object
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Enumerable/i/sum.html]
+extern "C" fn sum(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    delegate_to_array(vm, globals, self_val, "sum", &arg.to_vec(len), block)
+}
+
+/// ### Enumerable#inject
+/// - inject(init) {|result, item| ... } -> object
+/// - inject {|result, item| ... } -> object
+/// - reduce(init) {|result, item| ... } -> object
+/// - reduce {|result, item| ... } -> object
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Enumerable/i/inject.html]
+extern "C" fn inject(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    delegate_to_array(vm, globals, self_val, "inject", &arg.to_vec(len), block)
+}
+
+/// ### Enumerable#each_with_index
+/// - each_with_index {|(*args), idx| ... } -> self
+/// - each_with_index -> Enumerator
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Enumerable/i/each_with_index.html]
+extern "C" fn each_with_index(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    let block = match block {
+        Some(block) => block,
+        None => return Some(Value::new_enumerator(self_val, "each_with_index", vec![])),
+    };
+    let ary = vm.collect_each(globals, self_val, None)?;
+    vm.with_temp(ary, |vm| {
+        for (i, elem) in ary.as_array().to_vec().into_iter().enumerate() {
+            let idx = Value::new_integer(i as i64);
+            vm.invoke_block(globals, block, self_val, &[elem, idx])?;
+        }
+        Some(self_val)
+    })
+}
+
+/// ### Enumerable#each_slice
+/// - each_slice(n) {|list| ... } -> self
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Enumerable/i/each_slice.html]
+extern "C" fn each_slice(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    _len: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    let n = match arg[0].try_fixnum() {
+        Some(n) if n <= 0 => {
+            globals.err_argument("invalid slice size");
+            return None;
+        }
+        Some(n) => n as usize,
+        None => {
+            globals.err_no_implict_conv(arg[0], INTEGER_CLASS);
+            return None;
+        }
+    };
+    let block = globals.expect_block(block)?;
+    let ary = vm.collect_each(globals, self_val, None)?;
+    vm.with_temp(ary, |vm| {
+        for chunk in ary.as_array().to_vec().chunks(n) {
+            let slice = Value::new_array_from_vec(chunk.to_vec());
+            vm.invoke_block(globals, block, self_val, &[slice])?;
+        }
+        Some(self_val)
+    })
+}
+
+/// ### Enumerable#group_by
+/// - group_by {|obj| ... } -> Hash
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Enumerable/i/group_by.html]
+extern "C" fn group_by(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    let block = globals.expect_block(block)?;
+    let ary = vm.collect_each(globals, self_val, None)?;
+    let mut res = Value::new_hash(HashInner::new());
+    vm.with_temp(ary, |vm| {
+        vm.with_temp(res, |vm| {
+            for elem in ary.as_array().to_vec() {
+                let key = vm.invoke_block(globals, block, self_val, &[elem])?;
+                match res.as_hash().get(vm, globals, key)? {
+                    Some(mut group) => group.as_array_mut().push(elem),
+                    None => {
+                        let group = Value::new_array_from_vec(vec![elem]);
+                        res.as_hash_mut().insert(vm, globals, key, group)?;
+                    }
+                }
+            }
+            Some(res)
+        })
+    })
+}
+
+/// ### Enumerable#partition
+/// - partition {|item| ... } -> [[object], [object]]
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Enumerable/i/partition.html]
+extern "C" fn partition(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    let block = globals.expect_block(block)?;
+    let ary = vm.collect_each(globals, self_val, None)?;
+    vm.with_temp(ary, |vm| {
+        let mut t = vec![];
+        let mut f = vec![];
+        for elem in ary.as_array().to_vec() {
+            if vm
+                .invoke_block(globals, block, self_val, &[elem])?
+                .as_bool()
+            {
+                t.push(elem);
+            } else {
+                f.push(elem);
+            }
+        }
+        Some(Value::new_array_from_vec(vec![
+            Value::new_array_from_vec(t),
+            Value::new_array_from_vec(f),
+        ]))
+    })
+}
+
+/// ### Enumerable#tally
+/// - tally -> Hash
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Enumerable/i/tally.html]
+extern "C" fn tally(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let ary = vm.collect_each(globals, self_val, None)?;
+    let mut res = Value::new_hash(HashInner::new());
+    vm.with_temp(ary, |vm| {
+        vm.with_temp(res, |vm| {
+            for elem in ary.as_array().to_vec() {
+                let count = match res.as_hash().get(vm, globals, elem)? {
+                    Some(count) => count.try_fixnum().unwrap() + 1,
+                    None => 1,
+                };
+                res.as_hash_mut()
+                    .insert(vm, globals, elem, Value::new_integer(count))?;
+            }
+            Some(res)
+        })
+    })
+}
+
+/// ### Enumerable#zip
+/// - zip(*lists) -> [[object]]
+/// - zip(*lists) {|v1, v2, ...| ...} -> nil
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Enumerable/i/zip.html]
+extern "C" fn zip(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    delegate_to_array(vm, globals, self_val, "zip", &arg.to_vec(len), block)
+}
+
+/// ### Enumerable#count
+/// - count -> Integer
+/// - count(item) -> Integer
+/// - count {|obj| ... } -> Integer
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Enumerable/i/count.html]
+extern "C" fn count(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    if len > 1 {
+        globals.err_wrong_number_of_arguments_range(len, 0..=1);
+        return None;
+    }
+    let ary = vm.collect_each(globals, self_val, None)?;
+    vm.with_temp(ary, |vm| {
+        let mut count = 0;
+        for elem in ary.as_array().to_vec() {
+            let b = if len == 1 {
+                vm.eq_values(globals, elem, arg[0])?
+            } else if let Some(block) = block {
+                vm.invoke_block(globals, block, self_val, &[elem])?
+                    .as_bool()
+            } else {
+                true
+            };
+            if b {
+                count += 1;
+            }
+        }
+        Some(Value::new_integer(count))
+    })
+}
+
+/// ### Enumerable#first
+/// - first -> object | nil
+/// - first(n) -> Array
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Enumerable/i/first.html]
+extern "C" fn first(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    match len {
+        0 => {
+            let ary = vm.collect_each(globals, self_val, Some(1))?;
+            Some(ary.as_array().first().cloned().unwrap_or_default())
+        }
+        1 => {
+            let n = match arg[0].try_fixnum() {
+                Some(n) if n < 0 => {
+                    globals.err_argument("attempt to take negative size");
+                    return None;
+                }
+                Some(n) => n as usize,
+                None => {
+                    globals.err_no_implict_conv(arg[0], INTEGER_CLASS);
+                    return None;
+                }
+            };
+            vm.collect_each(globals, self_val, Some(n))
+        }
+        _ => {
+            globals.err_wrong_number_of_arguments_range(len, 0..=1);
+            None
+        }
+    }
+}
+
+/// ### Enumerable#lazy
+/// - lazy -> Enumerator::Lazy
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Enumerable/i/lazy.html]
+extern "C" fn lazy(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let inner = EnumeratorInner::new(self_val, IdentId::EACH, vec![]);
+    Some(Value::new_enumerator_with_class(inner, LAZY_CLASS))
+}
+
+#[cfg(test)]
+mod test {
+    use super::tests::*;
+
+    const PRELUDE: &str = r##"
+        class Foo
+          include Enumerable
+          def initialize(*a)
+            @a = a
+          end
+          def each
+            i = 0
+            while i < @a.size
+              yield @a[i]
+              i += 1
+            end
+            self
+          end
+        end
+        "##;
+
+    #[test]
+    fn enumerable() {
+        run_test_with_prelude("Foo.new(3, 1, 2).to_a", PRELUDE);
+        run_test_with_prelude("Foo.new(3, 1, 2).map { |x| x * 2 }", PRELUDE);
+        run_test_with_prelude("Foo.new(3, 1, 2).select { |x| x.odd? }", PRELUDE);
+        run_test_with_prelude("Foo.new(3, 1, 2).reject { |x| x.odd? }", PRELUDE);
+        run_test_with_prelude("Foo.new(3, 1, 2).find { |x| x < 3 }", PRELUDE);
+        run_test_with_prelude("Foo.new(3, 1, 2).detect { |x| x > 3 }", PRELUDE);
+        run_test_with_prelude(
+            "[Foo.new(3, 1, 2).include?(2), Foo.new(3, 1, 2).member?(5)]",
+            PRELUDE,
+        );
+        run_test_with_prelude(
+            "f = Foo.new(3, 1, 2); [f.sort, f.sort { |a, b| b <=> a }, f.min, f.max]",
+            PRELUDE,
+        );
+        run_test_with_prelude(
+            "f = Foo.new(3, 1, 2); [f.sum, f.sum(10), f.sum { |x| x * x }]",
+            PRELUDE,
+        );
+        run_test_with_prelude(
+            "f = Foo.new(3, 1, 2); [f.inject(:+), f.reduce(10) { |a, x| a * x }]",
+            PRELUDE,
+        );
+        run_test_with_prelude(
+            "a = []; Foo.new(3, 1, 2).each_with_index { |x, i| a << [x, i] }; a",
+            PRELUDE,
+        );
+        run_test_with_prelude(
+            "a = []; Foo.new(1, 2, 3, 4, 5).each_slice(2) { |s| a << s }; a",
+            PRELUDE,
+        );
+        run_test_with_prelude("Foo.new(1, 2, 3, 4, 5).group_by { |x| x % 3 }", PRELUDE);
+        run_test_with_prelude("Foo.new(1, 2, 3, 4, 5).partition { |x| x.even? }", PRELUDE);
+        run_test_with_prelude("Foo.new(:a, :b, :a, :c, :a).tally", PRELUDE);
+        run_test_with_prelude("Foo.new(1, 2, 3).zip([4, 5, 6], [7, 8])", PRELUDE);
+        run_test_with_prelude(
+            "f = Foo.new(1, 2, 2, 3); [f.count, f.count(2), f.count { |x| x > 1 }]",
+            PRELUDE,
+        );
+        run_test_with_prelude(
+            "f = Foo.new(1, 2, 3); [f.first, f.first(2), f.first(0), Foo.new.first]",
+            PRELUDE,
+        );
+        run_test_error(&format!("{PRELUDE}; Foo.new(1, 2).each_slice(0) {{}}"));
+        run_test_error(&format!("{PRELUDE}; Foo.new(1, 2).first(-1)"));
+    }
+
+    #[test]
+    fn enumerable_first() {
+        // `first` stops the iteration.
+        run_test(
+            r##"
+        class Inf
+          include Enumerable
+          def each
+            i = 0
+            while true
+              yield i
+              i += 1
+            end
+          end
+        end
+        [Inf.new.first, Inf.new.first(5)]
+        "##,
+        );
+        run_test(
+            r##"
+        class Multi
+          include Enumerable
+          def each
+            yield 1, 2
+            yield 3
+          end
+        end
+        [Multi.new.to_a, Multi.new.map { |a, b| b }]
+        "##,
+        );
+    }
+
+    #[test]
+    fn enumerable_builtin() {
+        run_test("h = {a: 1, b: 2, c: 3}; h.partition { |k, v| v.odd? }");
+        run_test("[1, 2, 1, 3].tally");
+        run_test("a = []; (1..10).each_slice(3) { |s| a << s }; a");
+        run_test("(1..6).group_by { |x| x % 2 }");
+        run_test("[(1..6).find { |x| x > 3 }, (1..6).count(&:even?)]");
+    }
+}
@@ -0,0 +1,633 @@
+use super::enumerable::block_proxy;
+use crate::*;
+
+//
+// Enumerator class
+//
+
+pub(super) fn init(globals: &mut Globals) {
+    globals.define_builtin_singleton_func(ENUMERATOR_CLASS, "new", enumerator_new, -1);
+    globals.define_builtin_func(ENUMERATOR_CLASS, "each", each, 0);
+    globals.define_builtin_func(ENUMERATOR_CLASS, "next", next, 0);
+    globals.define_builtin_func(ENUMERATOR_CLASS, "peek", peek, 0);
+    globals.define_builtin_func(ENUMERATOR_CLASS, "rewind", rewind, 0);
+    globals.define_builtin_func(ENUMERATOR_CLASS, "size", size, 0);
+    globals.define_builtin_func(ENUMERATOR_CLASS, "with_index", with_index, -1);
+    globals.define_builtin_func(YIELDER_CLASS, "<<", yielder_push, 1);
+    globals.define_builtin_func(YIELDER_CLASS, "yield", yielder_yield, -1);
+    globals.define_builtin_func(LAZY_CLASS, "each", lazy_each, 0);
+    globals.define_builtin_func(LAZY_CLASS, "map", lazy_map, 0);
+    globals.define_builtin_func(LAZY_CLASS, "collect", lazy_map, 0);
+    globals.define_builtin_func(LAZY_CLASS, "select", lazy_select, 0);
+    globals.define_builtin_func(LAZY_CLASS, "filter", lazy_select, 0);
+    globals.define_builtin_func(LAZY_CLASS, "reject", lazy_reject, 0);
+    globals.define_builtin_func(LAZY_CLASS, "take_while", lazy_take_while, 0);
+    globals.define_builtin_func(LAZY_CLASS, "take", lazy_take, 1);
+    globals.define_builtin_func(LAZY_CLASS, "force", force, 0);
+    globals.define_builtin_func(LAZY_CLASS, "to_a", force, 0);
+    globals.define_builtin_func(LAZY_CLASS, "lazy", lazy_lazy, 0);
+}
+
+///
+/// Pack the values yielded to a native block.
+///
+fn pack_args(arg: Arg, len: usize) -> Value {
+    match len {
+        0 => Value::nil(),
+        1 => arg[0],
+        _ => Value::new_array_from_vec(arg.to_vec(len)),
+    }
+}
+
+///
+/// The body of the block which the Fiber of an Enumerator passes to `each`.
+///
+/// The yielded values are passed to the resumer of the Fiber.
+///
+pub(crate) extern "C" fn fiber_yielder(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    _self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let val = pack_args(arg, len);
+    vm.fiber_yield(globals, val)
+}
+
+///
+/// The body of the block which `Executor::each_with_index_by` passes to the method.
+///
+/// The yielded values are passed to the block of the outer frame with indexes.
+///
+pub(crate) extern "C" fn index_yielder(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    _self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let val = pack_args(arg, len);
+    vm.yield_with_index(globals, val)
+}
+
+///
+/// Get the next value of the external iteration of the Enumerator *e*.
+///
+/// Returns Some(None) if the iteration reached an end.
+///
+fn enum_next(vm: &mut Executor, globals: &mut Globals, mut e: Value) -> Option<Option<Value>> {
+    if let Some(val) = e.as_enumerator_mut().peeked.take() {
+        return Some(Some(val));
+    }
+    let fiber = match e.as_enumerator().fiber {
+        Some(fiber) => fiber,
+        None => {
+            let fiber = Value::new_fiber(FiberBody::Enumerator(e));
+            e.as_enumerator_mut().fiber = Some(fiber);
+            fiber
+        }
+    };
+    if fiber.is_fiber().unwrap().state() == FiberState::Terminated {
+        return Some(None);
+    }
+    let val = vm.fiber_resume(globals, fiber, &[])?;
+    if fiber.is_fiber().unwrap().state() == FiberState::Terminated {
+        return Some(None);
+    }
+    Some(Some(val))
+}
+
+/// ### Enumerator.new
+/// - new(size = nil) {|y| ... } -> Enumerator
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Enumerator/s/new.html]
+extern "C" fn enumerator_new(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    _: Value,
+    arg: Arg,
+    len: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    let size = match len {
+        0 => None,
+        1 => Some(arg[0]),
+        _ => {
+            globals.err_wrong_number_of_arguments_range(len, 0..=1);
+            return None;
+        }
+    };
+    let block = globals.expect_block(block)?;
+    let proc = vm.generate_proc(globals, block, false);
+    let inner = EnumeratorInner::new_generator(proc, size);
+    Some(Value::new_enumerator_with_class(inner, ENUMERATOR_CLASS))
+}
+
+/// ### Enumerator#each
+/// - each {...} -> object
+/// - each -> self
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Enumerator/i/each.html]
+extern "C" fn each(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    let block = match block {
+        Some(block) => block,
+        None => return Some(self_val),
+    };
+    let e = self_val.as_enumerator();
+    if e.is_generator {
+        // the generator runs in a Fiber of a fresh Enumerator, so that the external
+        // iteration of self is not disturbed.
+        let gen = Value::new_enumerator_with_class(e.dup(), ENUMERATOR_CLASS);
+        return vm.with_temp(gen, |vm| {
+            while let Some(val) = enum_next(vm, globals, gen)? {
+                vm.invoke_block(globals, block, self_val, &[val])?;
+            }
+            Some(self_val)
+        });
+    }
+    let (obj, method, args) = (e.obj, e.method, e.args.clone());
+    vm.invoke_method_with_block(globals, method, obj, &args, block_proxy(Some(block)))
+}
+
+/// ### Enumerator#next
+/// - next -> object
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Enumerator/i/next.html]
+extern "C" fn next(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    match enum_next(vm, globals, self_val)? {
+        Some(val) => Some(val),
+        None => {
+            globals.err_stop_iteration();
+            None
+        }
+    }
+}
+
+/// ### Enumerator#peek
+/// - peek -> object
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Enumerator/i/peek.html]
+extern "C" fn peek(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    mut self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    match enum_next(vm, globals, self_val)? {
+        Some(val) => {
+            self_val.as_enumerator_mut().peeked = Some(val);
+            Some(val)
+        }
+        None => {
+            globals.err_stop_iteration();
+            None
+        }
+    }
+}
+
+/// ### Enumerator#rewind
+/// - rewind -> self
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Enumerator/i/rewind.html]
+extern "C" fn rewind(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    mut self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    self_val.as_enumerator_mut().rewind();
+    Some(self_val)
+}
+
+/// ### Enumerator#size
+/// - size -> Integer | Float::INFINITY | nil
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Enumerator/i/size.html]
+extern "C" fn size(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let e = self_val.as_enumerator();
+    if e.is_generator {
+        return Some(e.size.unwrap_or_default());
+    }
+    if !e.lazy_ops.is_empty() {
+        return Some(Value::nil());
+    }
+    let obj = e.obj;
+    match IdentId::get_name(e.method).as_str() {
+        "each" | "map" | "collect" | "select" | "filter" | "reject" | "each_with_index"
+        | "with_index" | "each_char" => {
+            let size = IdentId::get_ident_id("size");
+            if globals.find_method(obj, size).is_none() {
+                return Some(Value::nil());
+            }
+            vm.invoke_method(globals, size, obj, &[])
+        }
+        "times" => match obj.try_fixnum() {
+            Some(i) => Some(Value::new_integer(i.max(0))),
+            None => Some(Value::nil()),
+        },
+        "loop" => Some(Value::new_float(f64::INFINITY)),
+        _ => Some(Value::nil()),
+    }
+}
+
+/// ### Enumerator#with_index
+/// - with_index(offset = 0) {|(*args), idx| ... } -> object
+/// - with_index(offset = 0) -> Enumerator
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Enumerator/i/with_index.html]
+extern "C" fn with_index(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    let offset = match len {
+        0 => 0,
+        1 => match arg[0].unpack() {
+            RV::Nil => 0,
+            RV::Integer(i) => i,
+            _ => {
+                globals.err_no_implict_conv(arg[0], INTEGER_CLASS);
+                return None;
+            }
+        },
+        _ => {
+            globals.err_wrong_number_of_arguments_range(len, 0..=1);
+            return None;
+        }
+    };
+    match block {
+        Some(block) => vm.each_with_index_by(globals, self_val, IdentId::EACH, block, offset),
+        None => Some(Value::new_enumerator(
+            self_val,
+            "with_index",
+            arg.to_vec(len),
+        )),
+    }
+}
+
+/// ### Enumerator::Yielder#<<
+/// - self << object -> self
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Enumerator=3a=3aYielder/i/=3c=3c.html]
+extern "C" fn yielder_push(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    vm.fiber_yield(globals, arg[0])?;
+    Some(self_val)
+}
+
+/// ### Enumerator::Yielder#yield
+/// - yield(*object) -> object
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Enumerator=3a=3aYielder/i/yield.html]
+extern "C" fn yielder_yield(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    _self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let val = pack_args(arg, len);
+    vm.fiber_yield(globals, val)
+}
+
+///
+/// Apply *ops* of Enumerator::Lazy to *val*.
+///
+/// Returns the value to be yielded (None if it was filtered out), and whether the
+/// iteration must be stopped after this value.
+///
+fn apply_lazy_ops(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    ops: &[LazyOp],
+    taken: &mut [usize],
+    mut val: Value,
+) -> Option<(Option<Value>, bool)> {
+    for (i, op) in ops.iter().enumerate() {
+        match *op {
+            LazyOp::Map(proc) => val = vm.invoke_block(globals, proc, Value::nil(), &[val])?,
+            LazyOp::Select(proc) => {
+                if !vm
+                    .invoke_block(globals, proc, Value::nil(), &[val])?
+                    .as_bool()
+                {
+                    return Some((None, false));
+                }
+            }
+            LazyOp::Reject(proc) => {
+                if vm
+                    .invoke_block(globals, proc, Value::nil(), &[val])?
+                    .as_bool()
+                {
+                    return Some((None, false));
+                }
+            }
+            LazyOp::TakeWhile(proc) => {
+                if !vm
+                    .invoke_block(globals, proc, Value::nil(), &[val])?
+                    .as_bool()
+                {
+                    return Some((None, true));
+                }
+            }
+            LazyOp::Take(n) => {
+                taken[i] += 1;
+                if taken[i] == n {
+                    // the rest of the operations must be applied to this value.
+                    let (val, _) =
+                        apply_lazy_ops(vm, globals, &ops[i + 1..], &mut taken[i + 1..], val)?;
+                    return Some((val, true));
+                }
+            }
+        }
+    }
+    Some((Some(val), false))
+}
+
+/// ### Enumerator::Lazy#each
+/// - each {|item| ... } -> self
+/// - each -> self
+///
+/// [https://docs.ruby-lang.org/ja/latest/class/Enumerator=3a=3aLazy.html]
+extern "C" fn lazy_each(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    let block = match block {
+        Some(block) => block,
+        None => return Some(self_val),
+    };
+    let e = self_val.as_enumerator();
+    let ops = e.lazy_ops.clone();
+    if ops.iter().any(|op| matches!(op, LazyOp::Take(0))) {
+        return Some(self_val);
+    }
+    let mut taken = vec![0; ops.len()];
+    // the source is iterated externally, so that the iteration can be stopped at any time.
+    let src = EnumeratorInner::new(e.obj, e.method, e.args.clone());
+    let src = Value::new_enumerator_with_class(src, ENUMERATOR_CLASS);
+    vm.with_temp(src, |vm| {
+        while let Some(val) = enum_next(vm, globals, src)? {
+            let (val, stop) = apply_lazy_ops(vm, globals, &ops, &mut taken, val)?;
+            if let Some(val) = val {
+                vm.invoke_block(globals, block, self_val, &[val])?;
+            }
+            if stop {
+                break;
+            }
+        }
+        Some(self_val)
+    })
+}
+
+///
+/// Create a new Enumerator::Lazy which applies *op* after the operations of *self_val*.
+///
+fn lazy_push_op(self_val: Value, op: LazyOp) -> Value {
+    let mut inner = self_val.as_enumerator().dup();
+    inner.lazy_ops.push(op);
+    Value::new_enumerator_with_class(inner, LAZY_CLASS)
+}
+
+/// ### Enumerator::Lazy#map
+/// - map {|item| ... } -> Enumerator::Lazy
+/// - collect {|item| ... } -> Enumerator::Lazy
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Enumerator=3a=3aLazy/i/collect.html]
+extern "C" fn lazy_map(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    let block = globals.expect_block(block)?;
+    let proc = vm.generate_proc(globals, block, false);
+    Some(lazy_push_op(self_val, LazyOp::Map(proc)))
+}
+
+/// ### Enumerator::Lazy#select
+/// - select {|item| ... } -> Enumerator::Lazy
+/// - filter {|item| ... } -> Enumerator::Lazy
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Enumerator=3a=3aLazy/i/filter.html]
+extern "C" fn lazy_select(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    let block = globals.expect_block(block)?;
+    let proc = vm.generate_proc(globals, block, false);
+    Some(lazy_push_op(self_val, LazyOp::Select(proc)))
+}
+
+/// ### Enumerator::Lazy#reject
+/// - reject {|item| ... } -> Enumerator::Lazy
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Enumerator=3a=3aLazy/i/reject.html]
+extern "C" fn lazy_reject(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    let block = globals.expect_block(block)?;
+    let proc = vm.generate_proc(globals, block, false);
+    Some(lazy_push_op(self_val, LazyOp::Reject(proc)))
+}
+
+/// ### Enumerator::Lazy#take_while
+/// - take_while {|item| ... } -> Enumerator::Lazy
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Enumerator=3a=3aLazy/i/take_while.html]
+extern "C" fn lazy_take_while(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    let block = globals.expect_block(block)?;
+    let proc = vm.generate_proc(globals, block, false);
+    Some(lazy_push_op(self_val, LazyOp::TakeWhile(proc)))
+}
+
+/// ### Enumerator::Lazy#take
+/// - take(n) -> Enumerator::Lazy
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Enumerator=3a=3aLazy/i/take.html]
+extern "C" fn lazy_take(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let n = match arg[0].try_fixnum() {
+        Some(n) if n < 0 => {
+            globals.err_argument("attempt to take negative size");
+            return None;
+        }
+        Some(n) => n as usize,
+        None => {
+            globals.err_no_implict_conv(arg[0], INTEGER_CLASS);
+            return None;
+        }
+    };
+    Some(lazy_push_op(self_val, LazyOp::Take(n)))
+}
+
+/// ### Enumerator::Lazy#force
+/// - force -> Array
+/// - to_a -> Array
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Enumerator=3a=3aLazy/i/force.html]
+extern "C" fn force(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    vm.collect_each(globals, self_val, None)
+}
+
+/// ### Enumerator::Lazy#lazy
+/// - lazy -> self
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Enumerator=3a=3aLazy/i/lazy.html]
+extern "C" fn lazy_lazy(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(self_val)
+}
+
+#[cfg(test)]
+mod test {
+    use super::tests::*;
+
+    #[test]
+    fn enumerator_next() {
+        run_test("e = [1, 2, 3].each; [e.next, e.peek, e.next, e.next]");
+        run_test("e = [1, 2, 3].map; a = [e.next, e.next]; e.rewind; a << e.next; a");
+        run_test(r#"e = {a: 1, b: 2}.each; [e.next, e.next]"#);
+        run_test("e = 3.times; a = []; loop { a << e.next }; a");
+        run_test("e = (1..3).each; [e.next, e.next, e.next, e.size]");
+        run_test_error("e = [1].each; e.next; e.next");
+        run_test_error("e = [].each; e.peek");
+    }
+
+    #[test]
+    fn enumerator_generator() {
+        run_test(
+            r#"
+            e = Enumerator.new do |y|
+              y << 1 << 2
+              y.yield 3
+              y.yield 4, 5
+            end
+            [e.next, e.next, e.next, e.next, e.to_a, e.map { |x| x.to_s }]
+            "#,
+        );
+        run_test(
+            r#"
+            fib = Enumerator.new do |y|
+              a, b = 0, 1
+              loop do
+                y << a
+                a, b = b, a + b
+              end
+            end
+            [fib.first(10), fib.next, fib.next, fib.next, fib.first, Enumerator.new(3) { }.size]
+            "#,
+        );
+    }
+
+    #[test]
+    fn enumerator_with_index() {
+        run_test("%w(a b c).each.with_index(1).map { |s, i| s * i }");
+        run_test("%w(a b c).map.with_index { |s, i| s + i.to_s }");
+        run_test("[5, 6, 7].select.with_index { |x, i| i != 1 }");
+        run_test("[5, 6].each_with_index.map { |x, i| x * i }");
+        run_test("a = []; 3.times.with_index(10) { |x, i| a << x + i }; a");
+        run_test("[[1, 2].each.size, 4.times.size, loop.size, [1, 2].each.with_index.size]");
+    }
+
+    #[test]
+    fn enumerator_lazy() {
+        run_test(
+            r#"
+            e = Enumerator.new { |y| i = 0; loop { y << i; i += 1 } }
+            e.lazy.map { |x| x * 2 }.select { |x| x % 3 == 0 }.first(3)
+            "#,
+        );
+        run_test("(1..20).lazy.reject(&:even?).map { |x| x * x }.take(3).to_a");
+        run_test("[1, 2, 3, 4, 1].lazy.take_while { |x| x < 3 }.force");
+        run_test("a = []; r = [1, 2, 3].lazy.map { |x| a << x; x }.take(2).to_a; [r, a]");
+        run_test("[1, 2, 3].lazy.take(0).to_a");
+    }
+
+    #[test]
+    fn enumerator_inspect() {
+        run_test("[1, 2].each.inspect");
+        run_test("3.to_enum(:upto, 5).inspect");
+        run_test("[1, 2].each.with_index(1).inspect");
+        run_test("[1, 2].lazy.inspect");
+    }
+}
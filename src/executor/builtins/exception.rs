@@ -0,0 +1,180 @@
+use crate::*;
+
+//
+// Exception class
+//
+
+pub(super) fn init(globals: &mut Globals) {
+    globals.define_builtin_func(EXCEPTION_CLASS, "initialize", initialize, -1);
+    globals.define_builtin_func(EXCEPTION_CLASS, "message", message, 0);
+    globals.define_builtin_func(EXCEPTION_CLASS, "to_s", message, 0);
+    globals.define_builtin_func(EXCEPTION_CLASS, "backtrace", backtrace, 0);
+    globals.define_builtin_func(EXCEPTION_CLASS, "full_message", full_message, 0);
+}
+
+/// ### Exception#initialize
+/// - new(error_message = nil) -> Exception
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Exception/s/new.html]
+extern "C" fn initialize(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    mut self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    if len > 1 {
+        globals.err_wrong_number_of_arguments_range(len, 0..=1);
+        return None;
+    }
+    if self_val.is_exception().is_none() {
+        globals.set_error(MonorubyErr::typeerr(
+            "exception object expected".to_string(),
+        ));
+        return None;
+    }
+    if len == 1 && arg[0] != Value::nil() {
+        let msg = globals.val_tos(arg[0]);
+        *self_val.as_exception_mut() = MonorubyErr::runtimeerr(msg);
+    }
+    Some(Value::nil())
+}
+
+/// ### Exception#message
+/// - message -> String
+/// - to_s -> String
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Exception/i/message.html]
+extern "C" fn message(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _: Arg,
+    _: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let msg = self_val.is_exception().unwrap().get_error_message(globals);
+    Some(Value::new_string(msg))
+}
+
+/// ### Exception#backtrace
+/// - backtrace -> [String] | nil
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Exception/i/backtrace.html]
+extern "C" fn backtrace(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _: Arg,
+    _: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let err = self_val.is_exception().unwrap();
+    if err.loc.is_empty() {
+        return Some(Value::nil());
+    }
+    let v = err
+        .get_backtrace()
+        .into_iter()
+        .map(Value::new_string)
+        .collect();
+    Some(Value::new_array_from_vec(v))
+}
+
+/// ### Exception#full_message
+/// - full_message -> String
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Exception/i/full_message.html]
+///
+/// !! Options (highlight:, order:) are not supported.
+extern "C" fn full_message(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _: Arg,
+    _: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let err = self_val.is_exception().unwrap();
+    let msg = err.get_error_message(globals);
+    let class_name = self_val.get_real_class_name(globals);
+    let mut backtrace = err.get_backtrace().into_iter();
+    let mut res = match backtrace.next() {
+        Some(loc) => format!("{loc}: {msg} ({class_name})"),
+        None => format!("{msg} ({class_name})"),
+    };
+    for loc in backtrace {
+        res += &format!("\n\tfrom {loc}");
+    }
+    Some(Value::new_string(res))
+}
+
+#[cfg(test)]
+mod test {
+    use super::tests::*;
+
+    #[test]
+    fn exception_new() {
+        run_test(r#"Exception.new.message"#);
+        run_test(r#"StandardError.new("woo").message"#);
+        run_test(r#"RuntimeError.new("woo").to_s"#);
+        run_test(r#"ArgumentError.new.backtrace"#);
+        run_test(r#"IndexError.new("woo").class.superclass.to_s"#);
+    }
+
+    #[test]
+    fn exception_class() {
+        run_test(
+            r#"
+            res = []
+            begin; 1/0; rescue => e; res << e.class.to_s << e.message; end
+            begin; 1.foo; rescue => e; res << e.class.to_s; end
+            begin; Integer("woo"); rescue => e; res << e.class.to_s; end
+            begin; [1][-3] = 1; rescue IndexError => e; res << e.class.to_s; end
+            begin; Foo; rescue NameError => e; res << e.class.to_s; end
+            begin; 3.instance_variable_set(:@a, 1); rescue => e; res << e.class.to_s; end
+            begin; 100000.chr; rescue RangeError => e; res << e.class.to_s; end
+            res
+        "#,
+        );
+        run_test(
+            r#"
+            [
+              NoMethodError.superclass.to_s, ZeroDivisionError.superclass.to_s,
+              TypeError.superclass.to_s, IndexError.superclass.to_s,
+              FrozenError.superclass.to_s, LoadError.superclass.to_s,
+              ArgumentError.superclass.to_s, RangeError.superclass.to_s
+            ]
+        "#,
+        );
+    }
+
+    #[test]
+    fn exception_user_defined() {
+        run_test(
+            r#"
+            class MyError < StandardError; end
+            begin
+              raise MyError, "my error"
+            rescue MyError => e
+              [e.class.to_s, e.message, e.backtrace.class.to_s]
+            end
+        "#,
+        );
+        run_test(
+            r#"
+            class MyError2 < StandardError
+              def initialize(msg = "default message")
+                @msg = msg
+              end
+            end
+            begin
+              raise MyError2
+            rescue StandardError => e
+              [e.class.to_s, e.message]
+            end
+        "#,
+        );
+    }
+}
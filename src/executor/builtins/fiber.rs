@@ -0,0 +1,204 @@
+use crate::*;
+
+//
+// Fiber class
+//
+
+pub(super) fn init(globals: &mut Globals) {
+    globals.define_builtin_singleton_func(FIBER_CLASS, "new", fiber_new, 0);
+    globals.define_builtin_singleton_func(FIBER_CLASS, "yield", fiber_yield, -1);
+    globals.define_builtin_func(FIBER_CLASS, "resume", resume, -1);
+    globals.define_builtin_func(FIBER_CLASS, "alive?", alive, 0);
+}
+
+/// ### Fiber.new
+/// - new {|*args| ... } -> Fiber
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Fiber/s/new.html]
+extern "C" fn fiber_new(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    _: Value,
+    _: Arg,
+    _: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    let block = match block {
+        Some(block) => block,
+        None => {
+            globals.err_argument("tried to create Proc object without a block");
+            return None;
+        }
+    };
+    let proc = vm.generate_proc(globals, block, false);
+    Some(Value::new_fiber(FiberBody::Proc(proc)))
+}
+
+/// ### Fiber.yield
+/// - yield(*arg = nil) -> object
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Fiber/s/yield.html]
+extern "C" fn fiber_yield(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    _: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let val = match len {
+        0 => Value::nil(),
+        1 => arg[0],
+        _ => Value::new_array_from_vec(arg.to_vec(len)),
+    };
+    vm.fiber_yield(globals, val)
+}
+
+/// ### Fiber#resume
+/// - resume(*arg = nil) -> object
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Fiber/i/resume.html]
+extern "C" fn resume(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    vm.fiber_resume(globals, self_val, &arg.to_vec(len))
+}
+
+/// ### Fiber#alive?
+/// - alive? -> bool
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Fiber/i/alive=3f.html]
+extern "C" fn alive(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _: Arg,
+    _: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let state = self_val.is_fiber().unwrap().state();
+    Some(Value::bool(state != FiberState::Terminated))
+}
+
+#[cfg(test)]
+mod test {
+    use super::tests::*;
+
+    #[test]
+    fn fiber() {
+        run_test(
+            r#"
+            f = Fiber.new do |x|
+              y = Fiber.yield(x * 2)
+              z = Fiber.yield(y + 1)
+              z * 10
+            end
+            [f.resume(1), f.resume(5), f.alive?, f.resume(7), f.alive?]
+            "#,
+        );
+        run_test(
+            r#"
+            f = Fiber.new { Fiber.yield(1, 2); Fiber.yield; :done }
+            [f.resume, f.resume, f.resume]
+            "#,
+        );
+        run_test(
+            r#"
+            fib = Fiber.new do
+              a, b = 0, 1
+              loop do
+                Fiber.yield(a)
+                a, b = b, a + b
+              end
+            end
+            res = []
+            30.times { res << fib.resume }
+            res
+            "#,
+        );
+    }
+
+    #[test]
+    fn fiber_nested() {
+        run_test(
+            r#"
+            res = []
+            inner = Fiber.new { res << :i1; Fiber.yield; res << :i2 }
+            outer = Fiber.new { res << :o1; inner.resume; Fiber.yield; res << :o2; inner.resume; res << :o3 }
+            outer.resume
+            res << :m
+            outer.resume
+            res
+            "#,
+        );
+        run_test(
+            r#"
+            def gen(n)
+              Fiber.new { n.times { |i| Fiber.yield(i * i) }; nil }
+            end
+            f = gen(5)
+            a = []
+            while v = f.resume
+              a << v
+            end
+            a
+            "#,
+        );
+    }
+
+    #[test]
+    fn fiber_gc() {
+        run_test(
+            r#"
+            fibers = (0...20).map do |i|
+              Fiber.new do
+                a = []
+                loop { a << "s#{i}" * 10; Fiber.yield(a.size) }
+              end
+            end
+            r = 0
+            200.times { fibers.each { |f| r += f.resume } }
+            GC.start
+            [r, fibers[3].resume]
+            "#,
+        );
+        run_test(
+            r#"
+            procs = []
+            5.times do |i|
+              f = Fiber.new do
+                x = i * 10
+                procs << proc { x += 1 }
+                Fiber.yield
+              end
+              f.resume
+            end
+            GC.start
+            procs.map { |pr| pr.call } + procs.map { |pr| pr.call }
+            "#,
+        );
+    }
+
+    #[test]
+    fn fiber_error() {
+        run_test_error("Fiber.yield(1)");
+        run_test_error("f = Fiber.new { 1 }; f.resume; f.resume");
+        run_test_error("f = Fiber.new { f.resume }; f.resume");
+        run_test_error("f = Fiber.new { raise 'err' }; f.resume");
+        run_test(
+            r#"
+            f = Fiber.new { raise ArgumentError }
+            begin
+              f.resume
+            rescue ArgumentError
+              [f.alive?, :rescued]
+            end
+            "#,
+        );
+    }
+}
@@ -1,3 +1,6 @@
+use num::{BigInt, Integer, Signed, Zero};
+
+use super::numeric::{float_divmod, float_to_integer};
 use crate::*;
 
 //
@@ -7,6 +10,34 @@ use crate::*;
 pub(super) fn init(globals: &mut Globals) {
     globals.define_builtin_func(FLOAT_CLASS, "to_i", toi, 0);
     globals.define_builtin_func(FLOAT_CLASS, "to_f", tof, 0);
+    globals.define_builtin_func(FLOAT_CLASS, "round", round, -1);
+    globals.define_builtin_func(FLOAT_CLASS, "floor", floor, -1);
+    globals.define_builtin_func(FLOAT_CLASS, "ceil", ceil, -1);
+    globals.define_builtin_func(FLOAT_CLASS, "truncate", truncate, -1);
+    globals.define_builtin_func(FLOAT_CLASS, "nan?", nan_, 0);
+    globals.define_builtin_func(FLOAT_CLASS, "infinite?", infinite_, 0);
+    globals.define_builtin_func(FLOAT_CLASS, "finite?", finite_, 0);
+    globals.define_builtin_func(FLOAT_CLASS, "abs", abs, 0);
+    globals.define_builtin_func(FLOAT_CLASS, "magnitude", abs, 0);
+    globals.define_builtin_func(FLOAT_CLASS, "divmod", divmod, 1);
+    for (name, val) in [
+        ("INFINITY", f64::INFINITY),
+        ("NAN", f64::NAN),
+        ("EPSILON", f64::EPSILON),
+        ("MAX", f64::MAX),
+        ("MIN", f64::MIN_POSITIVE),
+    ] {
+        globals.set_constant(
+            FLOAT_CLASS,
+            IdentId::get_ident_id(name),
+            Value::new_float(val),
+        );
+    }
+    globals.set_constant(
+        FLOAT_CLASS,
+        IdentId::get_ident_id("DIG"),
+        Value::new_integer(f64::DIGITS as i64),
+    );
 }
 
 extern "C" fn tof(
@@ -20,7 +51,143 @@ extern "C" fn tof(
     Some(self_val)
 }
 
+/// ### Float#to_i
+/// - to_i -> Integer
+/// - truncate -> Integer
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Float/i/to_i.html]
 extern "C" fn toi(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    float_to_integer(globals, self_val.as_float().trunc())
+}
+
+/// ### Float#round
+/// - round(ndigits = 0) -> Integer | Float
+/// - [TODO] round(ndigits = 0, half: :up) -> Integer | Float
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Float/i/round.html]
+extern "C" fn round(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let ndigits = ndigits(globals, arg, len)?;
+    let number = self_val.as_float();
+    if number == 0.0 {
+        return Some(zero(number, ndigits));
+    }
+    if ndigits > 0 {
+        let binexp = frexp_exp(number);
+        if float_round_overflow(ndigits, binexp) {
+            return Some(self_val);
+        }
+        if number > 0.0 && float_round_underflow(ndigits, binexp) {
+            return Some(Value::new_float(0.0));
+        }
+        if ndigits > 14 {
+            // pow(10, ndigits) may not be accurate.
+            let s = format!("{:.*}", ndigits as usize, number);
+            return Some(Value::new_float(s.parse().unwrap()));
+        }
+        let s = 10f64.powi(ndigits as i32);
+        Some(Value::new_float(round_half_up(number, s) / s))
+    } else if ndigits == 0 {
+        float_to_integer(globals, round_half_up(number, 1.0))
+    } else {
+        let i = float_to_integer(globals, number.trunc())?;
+        Some(int_round_to(i, ndigits, |x, y| {
+            let (q, r) = x.abs().div_rem(y);
+            let q = if &r + &r >= *y { q + 1 } else { q };
+            if x < &BigInt::zero() {
+                -(q * y)
+            } else {
+                q * y
+            }
+        }))
+    }
+}
+
+/// ### Float#floor
+/// - floor(ndigits = 0) -> Integer | Float
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Float/i/floor.html]
+extern "C" fn floor(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let ndigits = ndigits(globals, arg, len)?;
+    float_floor(globals, self_val, ndigits)
+}
+
+/// ### Float#ceil
+/// - ceil(ndigits = 0) -> Integer | Float
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Float/i/ceil.html]
+extern "C" fn ceil(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let ndigits = ndigits(globals, arg, len)?;
+    float_ceil(globals, self_val, ndigits)
+}
+
+/// ### Float#truncate
+/// - truncate(ndigits = 0) -> Integer | Float
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Float/i/truncate.html]
+extern "C" fn truncate(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let ndigits = ndigits(globals, arg, len)?;
+    if self_val.as_float() > 0.0 {
+        float_floor(globals, self_val, ndigits)
+    } else {
+        float_ceil(globals, self_val, ndigits)
+    }
+}
+
+/// ### Float#nan?
+/// - nan? -> bool
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Float/i/nan=3f.html]
+extern "C" fn nan_(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(Value::bool(self_val.as_float().is_nan()))
+}
+
+/// ### Float#infinite?
+/// - infinite? -> 1 | -1 | nil
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Float/i/infinite=3f.html]
+extern "C" fn infinite_(
     _vm: &mut Executor,
     _globals: &mut Globals,
     self_val: Value,
@@ -28,10 +195,227 @@ extern "C" fn toi(
     _len: usize,
     _: Option<Value>,
 ) -> Option<Value> {
-    match self_val.unpack() {
-        RV::Float(f) => Some(Value::new_integer(f.trunc() as i64)),
+    let f = self_val.as_float();
+    let v = if f == f64::INFINITY {
+        Value::new_integer(1)
+    } else if f == f64::NEG_INFINITY {
+        Value::new_integer(-1)
+    } else {
+        Value::nil()
+    };
+    Some(v)
+}
+
+/// ### Float#finite?
+/// - finite? -> bool
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Float/i/finite=3f.html]
+extern "C" fn finite_(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(Value::bool(self_val.as_float().is_finite()))
+}
+
+/// ### Float#abs
+/// - abs -> Float
+/// - magnitude -> Float
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Float/i/abs.html]
+extern "C" fn abs(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(Value::new_float(self_val.as_float().abs()))
+}
+
+/// ### Float#divmod
+/// - divmod(other) -> [Integer, Float]
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Float/i/divmod.html]
+extern "C" fn divmod(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let rhs = arg[0];
+    if !rhs.is_numeric() {
+        return match vm.coerce(globals, self_val, rhs)? {
+            Some((lhs, rhs)) => {
+                vm.invoke_method(globals, IdentId::get_ident_id("divmod"), lhs, &[rhs])
+            }
+            None => {
+                globals.err_cant_be_coerced(rhs, self_val);
+                None
+            }
+        };
+    }
+    let rhs = rhs.expect_float(globals)?;
+    float_divmod(globals, self_val.as_float(), rhs)
+}
+
+fn ndigits(globals: &mut Globals, arg: Arg, len: usize) -> Option<i64> {
+    match len {
+        0 => Some(0),
+        1 => arg[0].expect_integer(globals),
+        _ => {
+            globals.err_wrong_number_of_arguments_range(len, 0..=1);
+            None
+        }
+    }
+}
+
+/// `0` or `0.0` for *number* == 0.0.
+fn zero(number: f64, ndigits: i64) -> Value {
+    if ndigits > 0 {
+        Value::new_float(number)
+    } else {
+        Value::new_integer(0)
+    }
+}
+
+fn float_floor(globals: &mut Globals, self_val: Value, ndigits: i64) -> Option<Value> {
+    let number = self_val.as_float();
+    if number == 0.0 {
+        return Some(zero(number, ndigits));
+    }
+    if ndigits > 0 {
+        let binexp = frexp_exp(number);
+        if float_round_overflow(ndigits, binexp) {
+            return Some(self_val);
+        }
+        if number > 0.0 && float_round_underflow(ndigits, binexp) {
+            return Some(Value::new_float(0.0));
+        }
+        let f = 10f64.powi(ndigits as i32);
+        let mul = (number * f).floor();
+        let res = (mul + 1.0) / f;
+        let res = if res > number { mul / f } else { res };
+        Some(Value::new_float(res))
+    } else {
+        let i = float_to_integer(globals, number.floor())?;
+        if ndigits == 0 {
+            return Some(i);
+        }
+        Some(int_round_to(i, ndigits, |x, y| x.div_floor(y) * y))
+    }
+}
+
+fn float_ceil(globals: &mut Globals, self_val: Value, ndigits: i64) -> Option<Value> {
+    let number = self_val.as_float();
+    if number == 0.0 {
+        return Some(zero(number, ndigits));
+    }
+    if ndigits > 0 {
+        let binexp = frexp_exp(number);
+        if float_round_overflow(ndigits, binexp) {
+            return Some(self_val);
+        }
+        if number < 0.0 && float_round_underflow(ndigits, binexp) {
+            return Some(Value::new_float(0.0));
+        }
+        let f = 10f64.powi(ndigits as i32);
+        let mul = (number * f).ceil();
+        let res = (mul - 1.0) / f;
+        let res = if res < number { mul / f } else { res };
+        Some(Value::new_float(res))
+    } else {
+        let i = float_to_integer(globals, number.ceil())?;
+        if ndigits == 0 {
+            return Some(i);
+        }
+        Some(int_round_to(i, ndigits, |x, y| -((-x).div_floor(y) * y)))
+    }
+}
+
+///
+/// Round Integer *i* to the multiple of 10 ** -*ndigits* by *f*.
+///
+fn int_round_to(i: Value, ndigits: i64, f: impl Fn(&BigInt, &BigInt) -> BigInt) -> Value {
+    // |f64| < 10 ** 309
+    if ndigits < -310 {
+        return Value::new_integer(0);
+    }
+    let x = match i.unpack() {
+        RV::Integer(i) => BigInt::from(i),
+        RV::BigInt(b) => b.clone(),
         _ => unreachable!(),
+    };
+    let y = BigInt::from(10).pow(-ndigits as u32);
+    Value::new_bigint(f(&x, &y))
+}
+
+///
+/// Round half up *x* at the precision of 1 / *s*.
+///
+fn round_half_up(x: f64, s: f64) -> f64 {
+    let xs = x * s;
+    let mut f = xs.round();
+    if s == 1.0 {
+        return f;
+    }
+    if x > 0.0 {
+        if (f + 0.5) / s <= x {
+            f += 1.0;
+        }
+    } else if (f - 0.5) / s >= x {
+        f -= 1.0;
+    }
+    f
+}
+
+///
+/// Get the binary exponent *e* of *x* such that 2 ** (e - 1) <= |x| < 2 ** e.
+///
+fn frexp_exp(x: f64) -> i64 {
+    if x == 0.0 || !x.is_finite() {
+        return 0;
     }
+    let bits = x.to_bits();
+    let exp = ((bits >> 52) & 0x7ff) as i64;
+    if exp == 0 {
+        // subnormal
+        frexp_exp(x * 2f64.powi(54)) - 54
+    } else {
+        exp - 1022
+    }
+}
+
+///
+/// Whether `number.round(ndigits)` is always *number*.
+///
+fn float_round_overflow(ndigits: i64, binexp: i64) -> bool {
+    const FLOAT_DIG: i64 = f64::DIGITS as i64 + 2;
+    ndigits
+        >= FLOAT_DIG
+            - if binexp > 0 {
+                binexp / 4
+            } else {
+                binexp / 3 - 1
+            }
+}
+
+///
+/// Whether `number.round(ndigits)` is always 0.
+///
+fn float_round_underflow(ndigits: i64, binexp: i64) -> bool {
+    ndigits
+        < -(if binexp > 0 {
+            binexp / 3 + 1
+        } else {
+            binexp / 4
+        })
 }
 
 #[cfg(test)]
@@ -44,5 +428,38 @@ mod test {
         run_test("-2.18.to_i");
         run_test("4.7777.to_f");
         run_test("-725.11.to_f");
+        run_test("1e30.to_i");
+        run_test_error("Float::NAN.to_i");
+    }
+
+    #[test]
+    fn float_round() {
+        run_test("[2.5.round, -2.5.round, 3.5.round, 1.4999.round, 0.0.round, -0.0.round(1)]");
+        run_test(
+            "[1.23456.round(2), 1.235.round(2), 1.015.round(2), 2.675.round(2), 0.5e-10.round(3)]",
+        );
+        run_test("[12345.678.round(-2), -12345.678.round(-2), 15.0.round(-1), -25.0.round(-1), 1.5.round(-400)]");
+        run_test("[1.23456789012345678.round(16), 123.456.round(20), 1e300.round(2)]");
+        run_test("[1.5.floor, -1.5.floor, 1.55.floor(1), -1.55.floor(1), 2.1.floor(1), 12345.678.floor(-2)]");
+        run_test(
+            "[1.5.ceil, -1.5.ceil, 1.55.ceil(1), -1.55.ceil(1), 2.1.ceil(1), 12345.678.ceil(-2)]",
+        );
+        run_test("[1.5.truncate, -1.5.truncate, 1.55.truncate(1), -1.55.truncate(1), 12345.678.truncate(-2)]");
+        run_test_error("Float::INFINITY.round");
+        run_test_error("Float::NAN.floor");
+    }
+
+    #[test]
+    fn float_predicates() {
+        run_test("[Float::NAN.nan?, 1.0.nan?, Float::INFINITY.infinite?, (-Float::INFINITY).infinite?, 1.0.infinite?]");
+        run_test(
+            "[Float::INFINITY.finite?, Float::NAN.finite?, 1.0.finite?, -2.5.abs, 2.5.magnitude]",
+        );
+        run_test(
+            "[Float::EPSILON, Float::MAX, Float::MIN, Float::DIG, 1.0 + Float::EPSILON > 1.0]",
+        );
+        run_test("[7.5.divmod(2), -7.5.divmod(2), 7.5.divmod(-2.5), 1.0.divmod(0.3)]");
+        run_test("[1.5.coerce(2), 1.5.zero?, 0.0.zero?, 1.5 <=> 2]");
+        run_test_error("1.0.divmod(0)");
     }
 }
@@ -0,0 +1,93 @@
+use crate::*;
+
+//
+// GC class
+//
+
+pub(super) fn init(globals: &mut Globals, class_id: ClassId) {
+    globals.define_builtin_singleton_func(class_id, "start", start, 0);
+    globals.define_builtin_singleton_func(class_id, "count", count, 0);
+}
+
+/// ### GC.start
+/// - start -> nil
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/GC/s/start.html]
+extern "C" fn start(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    _self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    ALLOC.with(|alloc| alloc.borrow_mut().gc(&*globals));
+    Some(Value::nil())
+}
+
+/// ### GC.count
+/// - count -> Integer
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/GC/s/count.html]
+extern "C" fn count(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    _self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let count = ALLOC.with(|alloc| alloc.borrow().count());
+    Some(Value::new_integer(count as i64))
+}
+
+#[cfg(test)]
+mod test {
+    use super::tests::*;
+
+    #[test]
+    fn gc_start() {
+        run_test2(r#"GC.start"#);
+        run_test2(
+            r#"
+            x = nil
+            50000.times do
+              x = "abc" + "def"
+            end
+            GC.start
+            [x, GC.count > 0]
+            "#,
+        );
+    }
+
+    #[test]
+    fn gc_children() {
+        run_test2(
+            r#"
+            a = [["abc", 1.5], 100000000000000000000, "x".."z"]
+            50000.times do
+              x = "abc" + "def"
+            end
+            GC.start
+            a
+            "#,
+        );
+        run_test2(
+            r#"
+            class C
+              def initialize
+                @a = [1, "a"]
+                @r = ("a" + "b")..("c" + "d")
+              end
+              attr_reader :a, :r
+            end
+            c = C.new
+            GC.start
+            50000.times do
+              x = "abc" + "def"
+            end
+            [c.a, c.r]
+            "#,
+        );
+    }
+}
@@ -0,0 +1,543 @@
+use crate::*;
+
+//
+// Hash class
+//
+
+pub(super) fn init(globals: &mut Globals) {
+    globals.define_builtin_singleton_func(HASH_CLASS, "new", hash_new, -1);
+    globals.define_builtin_func(HASH_CLASS, "[]", index, 1);
+    globals.define_builtin_func(HASH_CLASS, "[]=", index_assign, 2);
+    globals.define_builtin_func(HASH_CLASS, "size", size, 0);
+    globals.define_builtin_func(HASH_CLASS, "length", size, 0);
+    globals.define_builtin_func(HASH_CLASS, "empty?", empty, 0);
+    globals.define_builtin_func(HASH_CLASS, "each", each, 0);
+    globals.define_builtin_func(HASH_CLASS, "each_pair", each, 0);
+    globals.define_builtin_func(HASH_CLASS, "keys", keys, 0);
+    globals.define_builtin_func(HASH_CLASS, "values", values, 0);
+    globals.define_builtin_func(HASH_CLASS, "fetch", fetch, -1);
+    globals.define_builtin_func(HASH_CLASS, "key?", include, 1);
+    globals.define_builtin_func(HASH_CLASS, "has_key?", include, 1);
+    globals.define_builtin_func(HASH_CLASS, "include?", include, 1);
+    globals.define_builtin_func(HASH_CLASS, "member?", include, 1);
+    globals.define_builtin_func(HASH_CLASS, "delete", delete, 1);
+    globals.define_builtin_func(HASH_CLASS, "merge", merge, -1);
+    globals.define_builtin_func(HASH_CLASS, "to_a", to_a, 0);
+    globals.define_builtin_func(HASH_CLASS, "default", default, 0);
+    globals.define_builtin_func(HASH_CLASS, "default=", set_default, 1);
+    globals.define_builtin_func(HASH_CLASS, "to_s", inspect, 0);
+    globals.define_builtin_func(HASH_CLASS, "inspect", inspect, 0);
+}
+
+/// ### Hash.new
+/// - new(ifnone = nil) -> Hash
+/// - new {|hash, key| ... } -> Hash
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Hash/s/new.html]
+extern "C" fn hash_new(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    let max = if block.is_some() { 0 } else { 1 };
+    if len > max {
+        globals.err_wrong_number_of_arguments_range(len, 0..=max);
+        return None;
+    }
+    let mut hash = Value::new_hash_with_class(HashInner::new(), self_val.as_class());
+    if let Some(block) = block {
+        vm.with_temp(hash, |vm| {
+            let proc = vm.generate_proc(globals, block, false);
+            hash.as_hash_mut().set_default_proc(proc);
+        });
+    } else if len == 1 {
+        hash.as_hash_mut().set_default(arg[0]);
+    }
+    Some(hash)
+}
+
+/// ### Hash#[]
+/// - self[key] -> object | nil
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Hash/i/=5b=5d.html]
+extern "C" fn index(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    _: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    HashInner::index(self_val, vm, globals, arg[0])
+}
+
+/// ### Hash#[]=
+/// - self[key] = value
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Hash/i/=5b=5d=3d.html]
+extern "C" fn index_assign(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    mut self_val: Value,
+    arg: Arg,
+    _: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    self_val.as_hash_mut().insert(vm, globals, arg[0], arg[1])?;
+    Some(arg[1])
+}
+
+/// ### Hash#size
+/// - length -> Integer
+/// - size -> Integer
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Hash/i/length.html]
+extern "C" fn size(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _: Arg,
+    _: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(Value::new_integer(self_val.as_hash().len() as i64))
+}
+
+/// ### Hash#empty?
+/// - empty? -> bool
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Hash/i/empty=3f.html]
+extern "C" fn empty(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _: Arg,
+    _: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(Value::bool(self_val.as_hash().is_empty()))
+}
+
+/// ### Hash#each
+/// - each {|key, value| ... } -> self
+/// - each_pair {|key, value| ... } -> self
+/// - each -> Enumerator
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Hash/i/each.html]
+extern "C" fn each(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _: Arg,
+    _: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    let block = match block {
+        Some(block) => block,
+        None => return Some(Value::new_enumerator(self_val, "each", vec![])),
+    };
+    let entries: Vec<_> = self_val.as_hash().iter().collect();
+    for (k, v) in entries {
+        let pair = Value::new_array_from_vec(vec![k, v]);
+        vm.invoke_block(globals, block, self_val, &[pair])?;
+    }
+    Some(self_val)
+}
+
+/// ### Hash#keys
+/// - keys -> [object]
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Hash/i/keys.html]
+extern "C" fn keys(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _: Arg,
+    _: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(Value::new_array_from_vec(self_val.as_hash().keys()))
+}
+
+/// ### Hash#values
+/// - values -> [object]
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Hash/i/values.html]
+extern "C" fn values(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _: Arg,
+    _: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(Value::new_array_from_vec(self_val.as_hash().values()))
+}
+
+/// ### Hash#fetch
+/// - fetch(key) -> object
+/// - fetch(key, default) -> object
+/// - fetch(key) {|key| ... } -> object
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Hash/i/fetch.html]
+extern "C" fn fetch(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    if !(1..=2).contains(&len) {
+        globals.err_wrong_number_of_arguments_range(len, 1..=2);
+        return None;
+    }
+    let key = arg[0];
+    if let Some(val) = self_val.as_hash().get(vm, globals, key)? {
+        return Some(val);
+    }
+    if let Some(block) = block {
+        vm.invoke_block(globals, block, self_val, &[key])
+    } else if len == 2 {
+        Some(arg[1])
+    } else {
+        globals.err_key_not_found(key);
+        None
+    }
+}
+
+/// ### Hash#key?
+/// - has_key?(key) -> bool
+/// - include?(key) -> bool
+/// - key?(key) -> bool
+/// - member?(key) -> bool
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Hash/i/has_key=3f.html]
+extern "C" fn include(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    _: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let b = self_val.as_hash().get(vm, globals, arg[0])?.is_some();
+    Some(Value::bool(b))
+}
+
+/// ### Hash#delete
+/// - delete(key) -> object | nil
+/// - delete(key) {|key| ... } -> object
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Hash/i/delete.html]
+extern "C" fn delete(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    mut self_val: Value,
+    arg: Arg,
+    _: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    let key = arg[0];
+    match self_val.as_hash_mut().remove(vm, globals, key)? {
+        Some(val) => Some(val),
+        None => match block {
+            Some(block) => vm.invoke_block(globals, block, self_val, &[key]),
+            None => Some(Value::nil()),
+        },
+    }
+}
+
+/// ### Hash#merge
+/// - merge(*others) -> Hash
+/// - merge(*others) {|key, self_val, other_val| ... } -> Hash
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Hash/i/merge.html]
+extern "C" fn merge(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    let mut hash = Value::new_hash(self_val.as_hash().clone());
+    vm.with_temp(hash, |vm| {
+        for i in 0..len {
+            let other = arg[i];
+            let pairs: Vec<_> = match other.is_hash() {
+                Some(other) => other.iter().flat_map(|(k, v)| [k, v]).collect(),
+                None => {
+                    globals.err_no_implict_conv(other, HASH_CLASS);
+                    return None;
+                }
+            };
+            // *other* may be modified while the entries are merged, so they are held in an Array.
+            let pairs = Value::new_array_from_vec(pairs);
+            vm.with_temp(pairs, |vm| {
+                for kv in pairs.as_array().to_vec().chunks(2) {
+                    let (k, mut v) = (kv[0], kv[1]);
+                    if let Some(block) = block {
+                        if let Some(old) = hash.as_hash().get(vm, globals, k)? {
+                            v = vm.invoke_block(globals, block, self_val, &[k, old, v])?;
+                        }
+                    }
+                    vm.with_temp(v, |vm| hash.as_hash_mut().insert(vm, globals, k, v))?;
+                }
+                Some(())
+            })?;
+        }
+        Some(hash)
+    })
+}
+
+/// ### Hash#to_a
+/// - to_a -> [[object, object]]
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Hash/i/to_a.html]
+extern "C" fn to_a(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _: Arg,
+    _: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let v = self_val
+        .as_hash()
+        .iter()
+        .map(|(k, v)| Value::new_array_from_vec(vec![k, v]))
+        .collect();
+    Some(Value::new_array_from_vec(v))
+}
+
+/// ### Hash#default
+/// - default -> object | nil
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Hash/i/default.html]
+extern "C" fn default(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _: Arg,
+    _: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(self_val.as_hash().default())
+}
+
+/// ### Hash#default=
+/// - default=(value)
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Hash/i/default=3d.html]
+extern "C" fn set_default(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    mut self_val: Value,
+    arg: Arg,
+    _: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    self_val.as_hash_mut().set_default(arg[0]);
+    Some(arg[0])
+}
+
+/// ### Hash#inspect
+/// - to_s -> String
+/// - inspect -> String
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Hash/i/inspect.html]
+extern "C" fn inspect(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _: Arg,
+    _: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(Value::new_string(globals.val_inspect(self_val)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::tests::*;
+
+    #[test]
+    fn hash_literal() {
+        run_test("{}");
+        run_test(r#"{1 => 2, :a => "x", "b" => 3.5, nil => [1, 2]}"#);
+        run_test(r#"a = 7; {a => a * 2, "k#{a}" => {a => 1}}"#);
+        run_test(r#"{1 => 2, 1 => 3}"#);
+    }
+
+    #[test]
+    fn hash_index() {
+        run_test(r#"h = {1 => 2, "a" => 3}; h[1] + h["a"]"#);
+        run_test(
+            r#"
+            h = {}
+            h[:a] = 1
+            h["b"] = 2
+            h[[1, 2]] = 3
+            h[1.5] = 4
+            h[100000000000000000000] = 5
+            h[:a] = 6
+            [h[:a], h["b"], h[[1, 2]], h[1.5], h[100000000000000000000], h[:c], h]
+        "#,
+        );
+        run_test(
+            r#"
+            h = {"key" => 1}
+            k = "ke" + "y"
+            h[k] = 2
+            [h["key"], h[k], h.size]
+        "#,
+        );
+        run_test(r#"h = {0.0 => 1}; [h[-0.0], h[0.0], {-0.0 => 2}[0.0], {[0.0] => 3}[[-0.0]]]"#);
+        run_test(r#"h = Hash.new(5); h[:a] = 1; [h[:a], h[:b], h.default]"#);
+        run_test(r#"h = {}; h.default = 7; [h[:a], h.default]"#);
+        run_test(
+            r#"
+            h = Hash.new { |hash, k| hash[k] = k.to_s * 2 }
+            a = [h[:a], h[:b], h.size, h.default]
+            c = Hash.new { |hash, k| [] }
+            c[1] << 5
+            res = []
+            30.times { |i| res << h[i % 3] << c[i] }
+            [a, h, c, res, "abcab".gsub(/[ab]/, h)]
+        "#,
+        );
+        run_test(r#"h = Hash.new { 1 }; h.default = 3; [h[:a], h.default]"#);
+        run_test_error(r#"Hash.new(1) { 2 }"#);
+    }
+
+    #[test]
+    fn hash_methods() {
+        run_test(r#"h = {a: 1, b: 2, c: 3}; [h.keys, h.values, h.size, h.length, h.to_a]"#);
+        run_test(r#"[{}.empty?, {a: 1}.empty?]"#);
+        run_test(
+            r#"
+            r = []
+            {a: 1, b: 2, c: 3}.each do |k, v|
+              r << k
+              r << v
+            end
+            r
+        "#,
+        );
+        run_test(
+            r#"
+            h = {a: 1}
+            [h.fetch(:a), h.fetch(:z, 9), h.fetch(:z) { |k| k }]
+        "#,
+        );
+        run_test(
+            r#"
+            h = {a: 1, b: 2, c: 3, d: 4}
+            [h.key?(:a), h.key?(:z), h.has_key?(:b), h.include?(:c), h.member?(:d)]
+        "#,
+        );
+        run_test(
+            r#"
+            h = {a: 1, b: 2, c: 3}
+            [h.delete(:a), h.delete(:z), h.delete(:y) { |k| k }, h, h.keys]
+        "#,
+        );
+        run_test(
+            r#"
+            h = {a: 1, b: 2}
+            [h.merge({c: 3}, {a: 100}), h]
+        "#,
+        );
+        run_test(
+            r#"
+            h = {a: 1, b: 2}
+            [h.merge({b: 10, c: 3}, {a: 100}) { |k, x, y| [k, x, y] }, h]
+        "#,
+        );
+        run_test(
+            r#"
+            h = Hash.new { |hash, k| k }
+            h[:a] = 1
+            m = h.merge({a: 2}) { |k, x, y| x + y }
+            [m, m[:z]]
+        "#,
+        );
+        run_test(
+            r#"
+            begin
+              {}.fetch(:a)
+            rescue KeyError => e
+              e.message
+            end
+        "#,
+        );
+    }
+
+    #[test]
+    fn hash_user_defined_key() {
+        run_test(
+            r#"
+            class Foo
+              attr_reader :x
+              def initialize(x)
+                @x = x
+              end
+              def hash
+                @x.hash
+              end
+              def eql?(other)
+                @x == other.x
+              end
+            end
+            h = {}
+            h[Foo.new(1)] = :one
+            h[Foo.new(2)] = :two
+            h[Foo.new(1)] = :uno
+            [h[Foo.new(1)], h[Foo.new(2)], h[Foo.new(3)], h.size]
+        "#,
+        );
+    }
+
+    /// Keys whose `hash` runs Ruby code may trigger GC while a Hash is under construction.
+    /// Run with `--features gc-stress` to collect garbage at every chance.
+    #[test]
+    fn hash_gc() {
+        run_test(
+            r#"
+            class Key
+              attr_reader :x
+              def initialize(x)
+                @x = x
+              end
+              def hash
+                a = []
+                for i in 0..30
+                  a << "s#{i}"
+                end
+                @x.hash
+              end
+              def eql?(other)
+                @x == other.x
+              end
+            end
+            def f(a:, **rest)
+              [a, rest]
+            end
+            res = []
+            for i in 0..10
+              k = Key.new(i)
+              h = {"a#{i}" => "x" * i, k => "b#{i}", "c" => ["d#{i}"]}
+              res << h["a#{i}"]
+              res << h[Key.new(i)]
+              res << h.merge({"e" => "f#{i}", Key.new(i) => "g"}, {"h" => i.to_s})[Key.new(i)]
+              res << f(a: "a", **{k => "v#{i}", "s" => "t"})[1][k]
+            end
+            res
+        "#,
+        );
+    }
+}
@@ -1,5 +1,6 @@
-use num::ToPrimitive;
+use num::{BigInt, Integer, Signed, ToPrimitive, Zero};
 
+use super::numeric::{float_divmod, float_step};
 use crate::*;
 
 //
@@ -9,12 +10,32 @@ use crate::*;
 pub(super) fn init(globals: &mut Globals) {
     globals.define_builtin_func(INTEGER_CLASS, "chr", chr, 0);
     globals.define_builtin_func(INTEGER_CLASS, "times", times, 0);
+    globals.define_builtin_func(INTEGER_CLASS, "upto", upto, 1);
+    globals.define_builtin_func(INTEGER_CLASS, "downto", downto, 1);
+    globals.define_builtin_func(INTEGER_CLASS, "step", step, -1);
+    globals.define_builtin_func(INTEGER_CLASS, "divmod", divmod, 1);
+    globals.define_builtin_func(INTEGER_CLASS, "fdiv", fdiv, 1);
+    globals.define_builtin_func(INTEGER_CLASS, "pow", pow, -1);
+    globals.define_builtin_func(INTEGER_CLASS, "abs", abs, 0);
+    globals.define_builtin_func(INTEGER_CLASS, "magnitude", abs, 0);
+    globals.define_builtin_func(INTEGER_CLASS, "gcd", gcd, 1);
+    globals.define_builtin_func(INTEGER_CLASS, "lcm", lcm, 1);
+    globals.define_builtin_func(INTEGER_CLASS, "digits", digits, -1);
+    globals.define_builtin_func(INTEGER_CLASS, "bit_length", bit_length, 0);
+    globals.define_builtin_func(INTEGER_CLASS, "to_s", tos, -1);
+    globals.define_builtin_func(INTEGER_CLASS, "inspect", tos, -1);
+    globals.define_builtin_func(INTEGER_CLASS, "to_i", toi, 0);
+    globals.define_builtin_func(INTEGER_CLASS, "even?", even_, 0);
+    globals.define_builtin_func(INTEGER_CLASS, "odd?", odd_, 0);
+    globals.define_builtin_func(INTEGER_CLASS, "succ", succ, 0);
+    globals.define_builtin_func(INTEGER_CLASS, "next", succ, 0);
+    globals.define_builtin_func(INTEGER_CLASS, "pred", pred, 0);
     globals.define_builtin_func_inlinable(INTEGER_CLASS, "to_f", tof, 0, InlineMethod::IntegerTof);
 }
 
 /// ### Integer#times
 /// - times {|n| ... } -> self
-/// - [TODO] times -> Enumerator
+/// - times -> Enumerator
 ///
 /// [https://docs.ruby-lang.org/ja/latest/method/Integer/i/times.html]
 extern "C" fn times(
@@ -34,7 +55,7 @@ extern "C" fn times(
             vm.invoke_block(globals, block, self_val, &[Value::new_integer(i)])?;
         }
     } else {
-        unimplemented!("needs block.")
+        return Some(Value::new_enumerator(self_val, "times", vec![]));
     };
 
     Some(self_val)
@@ -78,6 +99,505 @@ extern "C" fn tof(
     Some(Value::new_float(f))
 }
 
+/// ### Integer#upto
+/// - upto(max) {|n| ... } -> self
+/// - upto(max) -> Enumerator
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Integer/i/upto.html]
+extern "C" fn upto(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    _len: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    let block = match block {
+        Some(block) => block,
+        None => return Some(Value::new_enumerator(self_val, "upto", vec![arg[0]])),
+    };
+    step_loop(
+        vm,
+        globals,
+        self_val,
+        arg[0],
+        Value::new_integer(1),
+        block,
+        false,
+    )?;
+    Some(self_val)
+}
+
+/// ### Integer#downto
+/// - downto(min) {|n| ... } -> self
+/// - downto(min) -> Enumerator
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Integer/i/downto.html]
+extern "C" fn downto(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    _len: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    let block = match block {
+        Some(block) => block,
+        None => return Some(Value::new_enumerator(self_val, "downto", vec![arg[0]])),
+    };
+    step_loop(
+        vm,
+        globals,
+        self_val,
+        arg[0],
+        Value::new_integer(-1),
+        block,
+        false,
+    )?;
+    Some(self_val)
+}
+
+/// ### Numeric#step
+/// - step(limit, step = 1) {|n| ... } -> self
+/// - [TODO] step(by: step, to: limit) {|n| ... } -> self
+/// - [TODO] step(limit, step = 1) -> Enumerator
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Numeric/i/step.html]
+extern "C" fn step(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    if !(1..=2).contains(&len) {
+        globals.err_wrong_number_of_arguments_range(len, 1..=2);
+        return None;
+    }
+    let block = globals.expect_block(block)?;
+    let step = if len == 2 {
+        arg[1]
+    } else {
+        Value::new_integer(1)
+    };
+    step_loop(vm, globals, self_val, arg[0], step, block, true)?;
+    Some(self_val)
+}
+
+///
+/// Yield *start*, *start* + *step*, ... while not exceeding *limit*.
+///
+/// If *by_float* is true and *limit* or *step* is a Float, Floats are yielded.
+///
+fn step_loop(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    start: Value,
+    limit: Value,
+    step: Value,
+    block: Value,
+    by_float: bool,
+) -> Option<()> {
+    let receiver = start;
+    match (start.unpack(), limit.unpack(), step.unpack()) {
+        (RV::Integer(start), RV::Integer(limit), RV::Integer(step)) => {
+            if step == 0 {
+                globals.err_argument("step can't be 0");
+                return None;
+            }
+            let mut i = start;
+            while (step > 0 && i <= limit) || (step < 0 && i >= limit) {
+                vm.invoke_block(globals, block, receiver, &[Value::new_integer(i)])?;
+                i = match i.checked_add(step) {
+                    Some(i) => i,
+                    None => break,
+                };
+            }
+        }
+        (_, RV::Float(_), _) | (_, _, RV::Float(_)) if by_float => {
+            let beg = start.expect_float(globals)?;
+            let end = limit.expect_float(globals)?;
+            let unit = step.expect_float(globals)?;
+            float_step(vm, globals, receiver, beg, end, unit, false, block)?;
+        }
+        _ => {
+            let positive = vm
+                .binary_op(globals, IdentId::_GT, step, Value::new_integer(0))?
+                .as_bool();
+            let cmp = if positive { IdentId::_LE } else { IdentId::_GE };
+            let mut i = start;
+            while vm.binary_op(globals, cmp, i, limit)?.as_bool() {
+                vm.invoke_block(globals, block, receiver, &[i])?;
+                i = vm.binary_op(globals, IdentId::_ADD, i, step)?;
+            }
+        }
+    }
+    Some(())
+}
+
+/// ### Integer#divmod
+/// - divmod(other) -> [Numeric, Numeric]
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Integer/i/divmod.html]
+extern "C" fn divmod(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let rhs = arg[0];
+    let (div, rem) = match (self_val.unpack(), rhs.unpack()) {
+        (RV::Integer(_) | RV::BigInt(_), RV::Integer(_) | RV::BigInt(_)) => {
+            let lhs = to_bigint(self_val);
+            let rhs = to_bigint(rhs);
+            if rhs.is_zero() {
+                globals.err_divide_by_zero();
+                return None;
+            }
+            let (div, rem) = lhs.div_mod_floor(&rhs);
+            (Value::new_bigint(div), Value::new_bigint(rem))
+        }
+        (_, RV::Float(rhs)) => {
+            let lhs = self_val.expect_float(globals)?;
+            return float_divmod(globals, lhs, rhs);
+        }
+        _ => {
+            return match vm.coerce(globals, self_val, rhs)? {
+                Some((lhs, rhs)) => {
+                    vm.invoke_method(globals, IdentId::get_ident_id("divmod"), lhs, &[rhs])
+                }
+                None => {
+                    globals.err_cant_be_coerced(rhs, self_val);
+                    None
+                }
+            };
+        }
+    };
+    Some(Value::new_array_from_vec(vec![div, rem]))
+}
+
+/// ### Integer#fdiv
+/// - fdiv(other) -> Float
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Integer/i/fdiv.html]
+extern "C" fn fdiv(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let lhs = self_val.expect_float(globals)?;
+    let rhs = arg[0].expect_float(globals)?;
+    Some(Value::new_float(lhs / rhs))
+}
+
+/// ### Integer#pow
+/// - pow(other) -> Numeric
+/// - pow(other, modulo) -> Integer
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Integer/i/pow.html]
+extern "C" fn pow(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    if !(1..=2).contains(&len) {
+        globals.err_wrong_number_of_arguments_range(len, 1..=2);
+        return None;
+    }
+    if len == 1 {
+        return vm.binary_op(globals, IdentId::_POW, self_val, arg[0]);
+    }
+    let exp = expect_int_arg(globals, arg[0])?;
+    let modulo = expect_int_arg(globals, arg[1])?;
+    if exp.is_negative() {
+        globals.set_error(MonorubyErr::range(
+            "Integer#pow() 2nd argument not allowed to be negative when 3rd argument specified"
+                .to_string(),
+        ));
+        return None;
+    }
+    if modulo.is_zero() {
+        globals.err_divide_by_zero();
+        return None;
+    }
+    let m = modulo.abs();
+    let mut res = to_bigint(self_val).mod_floor(&m).modpow(&exp, &m);
+    // The result takes the sign of the modulus, as Integer#% does.
+    if modulo.is_negative() && !res.is_zero() {
+        res -= m;
+    }
+    Some(Value::new_bigint(res))
+}
+
+/// ### Integer#abs
+/// - abs -> Integer
+/// - magnitude -> Integer
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Integer/i/abs.html]
+extern "C" fn abs(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let v = match self_val.unpack() {
+        RV::Integer(i) => match i.checked_abs() {
+            Some(i) => Value::new_integer(i),
+            None => Value::new_bigint(BigInt::from(i).abs()),
+        },
+        RV::BigInt(b) => Value::new_bigint(b.abs()),
+        _ => unreachable!(),
+    };
+    Some(v)
+}
+
+/// ### Integer#gcd
+/// - gcd(n) -> Integer
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Integer/i/gcd.html]
+extern "C" fn gcd(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let rhs = expect_int_arg(globals, arg[0])?;
+    Some(Value::new_bigint(to_bigint(self_val).gcd(&rhs)))
+}
+
+/// ### Integer#lcm
+/// - lcm(n) -> Integer
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Integer/i/lcm.html]
+extern "C" fn lcm(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let rhs = expect_int_arg(globals, arg[0])?;
+    Some(Value::new_bigint(to_bigint(self_val).lcm(&rhs)))
+}
+
+/// ### Integer#digits
+/// - digits -> [Integer]
+/// - digits(base) -> [Integer]
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Integer/i/digits.html]
+extern "C" fn digits(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    if len > 1 {
+        globals.err_wrong_number_of_arguments_range(len, 0..=1);
+        return None;
+    }
+    let base = if len == 1 {
+        let base = expect_int_arg(globals, arg[0])?;
+        if base.is_negative() {
+            globals.err_argument("negative radix");
+            return None;
+        }
+        if base < BigInt::from(2) {
+            globals.err_argument(&format!("invalid radix {}", base));
+            return None;
+        }
+        base
+    } else {
+        BigInt::from(10)
+    };
+    let mut n = to_bigint(self_val);
+    if n.is_negative() {
+        globals.set_error(MonorubyErr::range("out of domain".to_string()));
+        return None;
+    }
+    let mut res = vec![];
+    loop {
+        let (div, rem) = n.div_mod_floor(&base);
+        res.push(Value::new_bigint(rem));
+        if div.is_zero() {
+            break;
+        }
+        n = div;
+    }
+    Some(Value::new_array_from_vec(res))
+}
+
+/// ### Integer#bit_length
+/// - bit_length -> Integer
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Integer/i/bit_length.html]
+extern "C" fn bit_length(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let len = match self_val.unpack() {
+        RV::Integer(i) => {
+            let i = if i < 0 { !i } else { i };
+            64 - i.leading_zeros() as u64
+        }
+        RV::BigInt(b) => {
+            if b.is_negative() {
+                (-b - 1u32).bits()
+            } else {
+                b.bits()
+            }
+        }
+        _ => unreachable!(),
+    };
+    Some(Value::new_integer(len as i64))
+}
+
+/// ### Integer#to_s
+/// - to_s(base = 10) -> String
+/// - inspect(base = 10) -> String
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Integer/i/to_s.html]
+extern "C" fn tos(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    if len > 1 {
+        globals.err_wrong_number_of_arguments_range(len, 0..=1);
+        return None;
+    }
+    let base = if len == 1 {
+        arg[0].expect_integer(globals)?
+    } else {
+        10
+    };
+    if !(2..=36).contains(&base) {
+        globals.err_argument(&format!("invalid radix {}", base));
+        return None;
+    }
+    let s = match self_val.unpack() {
+        RV::Integer(i) if base == 10 => i.to_string(),
+        _ => to_bigint(self_val).to_str_radix(base as u32),
+    };
+    Some(Value::new_string(s))
+}
+
+/// ### Integer#to_i
+/// - to_i -> self
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Integer/i/to_i.html]
+extern "C" fn toi(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(self_val)
+}
+
+/// ### Integer#even?
+/// - even? -> bool
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Integer/i/even=3f.html]
+extern "C" fn even_(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(Value::bool(to_bigint(self_val).is_even()))
+}
+
+/// ### Integer#odd?
+/// - odd? -> bool
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Integer/i/odd=3f.html]
+extern "C" fn odd_(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(Value::bool(to_bigint(self_val).is_odd()))
+}
+
+/// ### Integer#succ
+/// - succ -> Integer
+/// - next -> Integer
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Integer/i/next.html]
+extern "C" fn succ(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(Value::new_bigint(to_bigint(self_val) + 1))
+}
+
+/// ### Integer#pred
+/// - pred -> Integer
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Integer/i/pred.html]
+extern "C" fn pred(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(Value::new_bigint(to_bigint(self_val) - 1))
+}
+
+fn to_bigint(val: Value) -> BigInt {
+    match val.unpack() {
+        RV::Integer(i) => BigInt::from(i),
+        RV::BigInt(b) => b.clone(),
+        _ => unreachable!(),
+    }
+}
+
+fn expect_int_arg(globals: &mut Globals, val: Value) -> Option<BigInt> {
+    match val.unpack() {
+        RV::Integer(_) | RV::BigInt(_) => Some(to_bigint(val)),
+        _ => {
+            globals.err_no_implict_conv(val, INTEGER_CLASS);
+            None
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::tests::*;
@@ -104,6 +624,47 @@ mod test {
         );
     }
 
+    #[test]
+    fn integer_iterators() {
+        run_test(r#"a = []; 3.upto(6) { |i| a << i }; a"#);
+        run_test(r#"a = []; 6.downto(3) { |i| a << i }; a"#);
+        run_test(r#"[1.succ, 1.next, 0.pred, (2**64).pred, (2**64 - 1).succ]"#);
+        run_test(r#"a = []; 1.upto(3.5) { |i| a << i }; a"#);
+        run_test(r#"a = []; 1.step(10, 3) { |i| a << i }; a"#);
+        run_test(r#"a = []; 10.step(1, -4) { |i| a << i }; a"#);
+        run_test(r#"a = []; 1.step(2, 0.25) { |i| a << i }; a"#);
+        run_test(r#"a = []; 1.0.step(0.0, -0.1) { |i| a << i }; a"#);
+        run_test(r#"a = []; 5.upto(3) { |i| a << i }; a"#);
+        run_test_error(r#"1.step(3, 0) { }"#);
+        run_test_error(r#"1.upto(3)"#);
+    }
+
+    #[test]
+    fn integer_arith() {
+        run_test(r#"[7.divmod(2), -7.divmod(2), 7.divmod(-2), 7.divmod(2.5), -7.divmod(2.5)]"#);
+        run_test(r#"[(2**70).divmod(3), 10.divmod(2**65)]"#);
+        run_test(r#"[1.fdiv(3), 10.fdiv(2.5), (-5).abs, 5.abs, (-(2**70)).abs, -3.magnitude]"#);
+        run_test(r#"[12.gcd(18), 12.lcm(18), -12.gcd(18), 0.gcd(5), (2**70).gcd(2**40)]"#);
+        run_test(r#"[0.digits, 1234.digits, 1234.digits(100), (2**70).digits(1000)]"#);
+        run_test(
+            r#"[0.bit_length, 1.bit_length, 255.bit_length, 256.bit_length, -1.bit_length, -256.bit_length, -257.bit_length, (2**70).bit_length, (-(2**70)).bit_length]"#,
+        );
+        run_test(
+            r#"[255.to_s, 255.to_s(2), 255.to_s(16), -255.to_s(36), (2**70).to_s(16), 10.inspect]"#,
+        );
+        run_test(r#"[0.even?, 1.even?, -3.odd?, (2**70).even?, (2**70+1).odd?, 0.zero?, 3.zero?]"#);
+        run_test(
+            r#"[2.pow(10), 2.pow(10, 1000), 3.pow(200, 1000), (-3).pow(3, 7), 3.pow(3, -7), (2**70).pow(3, 10**9), 5.pow(0, 1)]"#,
+        );
+        run_test(r#"[2.pow(-2).to_s, 2.pow(0.5)]"#);
+        run_test_error(r#"1.divmod(0)"#);
+        run_test_error(r#"2.pow(-1, 3)"#);
+        run_test_error(r#"2.pow(3, 0)"#);
+        run_test_error(r#"-1.digits"#);
+        run_test_error(r#"1.to_s(37)"#);
+        run_test_error(r#"1.gcd(1.5)"#);
+    }
+
     #[test]
     fn to_f() {
         run_test("253.to_f");
@@ -0,0 +1,456 @@
+use num::{BigInt, FromPrimitive, ToPrimitive, Zero};
+use std::cmp::Ordering;
+
+use crate::*;
+
+//
+// Numeric class
+//
+
+pub(super) fn init(globals: &mut Globals) {
+    globals.define_builtin_func(NUMERIC_CLASS, "coerce", coerce, 1);
+    globals.define_builtin_func(NUMERIC_CLASS, "integer?", integer_, 0);
+    globals.define_builtin_func(NUMERIC_CLASS, "zero?", zero_, 0);
+    globals.define_builtin_func(NUMERIC_CLASS, "nonzero?", nonzero_, 0);
+    globals.define_builtin_func(NUMERIC_CLASS, "positive?", positive_, 0);
+    globals.define_builtin_func(NUMERIC_CLASS, "negative?", negative_, 0);
+    globals.define_builtin_func(NUMERIC_CLASS, "+@", uplus, 0);
+    // Operators are defined on Integer, Float and Rational, because they are executed
+    // natively and fall back to `coerce` for other values.
+    for class_id in [INTEGER_CLASS, FLOAT_CLASS, RATIONAL_CLASS] {
+        globals.define_builtin_func(class_id, "+", add, 1);
+        globals.define_builtin_func(class_id, "-", sub, 1);
+        globals.define_builtin_func(class_id, "*", mul, 1);
+        globals.define_builtin_func(class_id, "/", div, 1);
+        globals.define_builtin_func(class_id, "%", rem, 1);
+        globals.define_builtin_func(class_id, "modulo", rem, 1);
+        globals.define_builtin_func(class_id, "**", pow, 1);
+        globals.define_builtin_func(class_id, "<", lt, 1);
+        globals.define_builtin_func(class_id, "<=", le, 1);
+        globals.define_builtin_func(class_id, ">", gt, 1);
+        globals.define_builtin_func(class_id, ">=", ge, 1);
+    }
+    for class_id in [INTEGER_CLASS, FLOAT_CLASS] {
+        globals.define_builtin_func(class_id, "==", eq, 1);
+        globals.define_builtin_func(class_id, "===", eq, 1);
+        globals.define_builtin_func(class_id, "<=>", cmp, 1);
+        globals.define_builtin_func(class_id, "-@", neg, 0);
+    }
+    for (name, op) in [("&", bitand as BuiltinFn), ("|", bitor), ("^", bitxor)] {
+        globals.define_builtin_func(INTEGER_CLASS, name, op, 1);
+    }
+    globals.define_builtin_func(INTEGER_CLASS, "<<", shl, 1);
+    globals.define_builtin_func(INTEGER_CLASS, ">>", shr, 1);
+}
+
+macro_rules! binop {
+    ($(($name:ident, $op:expr)),+) => {
+        $(
+            extern "C" fn $name(
+                vm: &mut Executor,
+                globals: &mut Globals,
+                self_val: Value,
+                arg: Arg,
+                _len: usize,
+                _: Option<Value>,
+            ) -> Option<Value> {
+                vm.binary_op(globals, $op, self_val, arg[0])
+            }
+        )+
+    };
+}
+
+binop!(
+    (add, IdentId::_ADD),
+    (sub, IdentId::_SUB),
+    (mul, IdentId::_MUL),
+    (div, IdentId::_DIV),
+    (rem, IdentId::_REM),
+    (pow, IdentId::_POW),
+    (lt, IdentId::_LT),
+    (le, IdentId::_LE),
+    (gt, IdentId::_GT),
+    (ge, IdentId::_GE),
+    (bitand, IdentId::_BAND),
+    (bitor, IdentId::_BOR),
+    (bitxor, IdentId::_BXOR),
+    (shl, IdentId::_SHL),
+    (shr, IdentId::_SHR)
+);
+
+/// ### Numeric#-@
+/// - -self -> Numeric
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Numeric/i/-=40.html]
+extern "C" fn neg(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    super::super::op::neg_value(vm, globals, self_val)
+}
+
+/// ### Numeric#+@
+/// - +self -> self
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Numeric/i/+=40.html]
+extern "C" fn uplus(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(self_val)
+}
+
+/// ### Integer#==
+/// - self == other -> bool
+///
+/// `other == self` is called when *other* is not a numeric.
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Integer/i/=3d=3d.html]
+extern "C" fn eq(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let rhs = arg[0];
+    let b = if rhs.is_numeric() {
+        vm.eq_values(globals, self_val, rhs)?
+    } else {
+        vm.invoke_method(globals, IdentId::_EQ, rhs, &[self_val])?
+            .as_bool()
+    };
+    Some(Value::bool(b))
+}
+
+/// ### Integer#<=>
+/// - self <=> other -> -1 | 0 | 1 | nil
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Integer/i/=3c=3d=3e.html]
+extern "C" fn cmp(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let rhs = arg[0];
+    if !rhs.is_numeric() {
+        return match vm.coerce(globals, self_val, rhs)? {
+            Some((lhs, rhs)) => vm.invoke_method(globals, IdentId::_CMP, lhs, &[rhs]),
+            None => Some(Value::nil()),
+        };
+    }
+    let ord = match (self_val.unpack(), rhs.unpack()) {
+        (RV::Integer(lhs), RV::Integer(rhs)) => lhs.partial_cmp(&rhs),
+        (RV::Integer(lhs), RV::BigInt(rhs)) => BigInt::from(lhs).partial_cmp(rhs),
+        (RV::BigInt(lhs), RV::Integer(rhs)) => lhs.partial_cmp(&BigInt::from(rhs)),
+        (RV::BigInt(lhs), RV::BigInt(rhs)) => lhs.partial_cmp(rhs),
+        _ => {
+            let lhs = self_val.expect_float(globals)?;
+            let rhs = rhs.expect_float(globals)?;
+            lhs.partial_cmp(&rhs)
+        }
+    };
+    Some(match ord {
+        Some(ord) => Value::new_integer(ord as i64),
+        None => Value::nil(),
+    })
+}
+
+/// ### Numeric#coerce
+/// - coerce(other) -> [Numeric, Numeric]
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Numeric/i/coerce.html]
+extern "C" fn coerce(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let other = arg[0];
+    let (lhs, rhs) = if self_val.class_id() == other.class_id() {
+        (other, self_val)
+    } else {
+        let lhs = Value::new_float(other.expect_float(globals)?);
+        let rhs = Value::new_float(self_val.expect_float(globals)?);
+        (lhs, rhs)
+    };
+    Some(Value::new_array_from_vec(vec![lhs, rhs]))
+}
+
+/// ### Numeric#integer?
+/// - integer? -> bool
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Numeric/i/integer=3f.html]
+extern "C" fn integer_(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let b = matches!(self_val.unpack(), RV::Integer(_) | RV::BigInt(_));
+    Some(Value::bool(b))
+}
+
+/// Get the sign of *val*. NaN is regarded as zero.
+fn sign(val: Value) -> Ordering {
+    match val.unpack() {
+        RV::Integer(i) => i.cmp(&0),
+        RV::BigInt(b) => b.cmp(&BigInt::zero()),
+        RV::Float(f) => f.partial_cmp(&0.0).unwrap_or(Ordering::Equal),
+        _ => val.as_rational().numer().cmp(&BigInt::zero()),
+    }
+}
+
+/// ### Numeric#zero?
+/// - zero? -> bool
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Numeric/i/zero=3f.html]
+extern "C" fn zero_(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let b = match self_val.unpack() {
+        RV::Float(f) => f == 0.0,
+        _ => sign(self_val) == Ordering::Equal,
+    };
+    Some(Value::bool(b))
+}
+
+/// ### Numeric#nonzero?
+/// - nonzero? -> self | nil
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Numeric/i/nonzero=3f.html]
+extern "C" fn nonzero_(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let zero = match self_val.unpack() {
+        RV::Float(f) => f == 0.0,
+        _ => sign(self_val) == Ordering::Equal,
+    };
+    Some(if zero { Value::nil() } else { self_val })
+}
+
+/// ### Numeric#positive?
+/// - positive? -> bool
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Numeric/i/positive=3f.html]
+extern "C" fn positive_(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(Value::bool(sign(self_val) == Ordering::Greater))
+}
+
+/// ### Numeric#negative?
+/// - negative? -> bool
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Numeric/i/negative=3f.html]
+extern "C" fn negative_(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(Value::bool(sign(self_val) == Ordering::Less))
+}
+
+///
+/// Yield *beg*, *beg* + *unit*, ... while not exceeding *end*, avoiding the accumulation of
+/// rounding errors.
+///
+/// *end* itself is not yielded if *excl* is true.
+///
+#[allow(clippy::too_many_arguments)]
+pub(super) fn float_step(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    receiver: Value,
+    beg: f64,
+    end: f64,
+    unit: f64,
+    excl: bool,
+    block: Value,
+) -> Option<()> {
+    if unit == 0.0 {
+        globals.err_argument("step can't be 0");
+        return None;
+    }
+    let n = if unit.is_infinite() {
+        let b = if unit > 0.0 { beg <= end } else { beg >= end };
+        b as i64 as f64
+    } else {
+        let n = (end - beg) / unit;
+        let err =
+            ((beg.abs() + end.abs() + (end - beg).abs()) / unit.abs() * f64::EPSILON).min(0.5);
+        if excl {
+            if n <= 0.0 {
+                0.0
+            } else {
+                let mut n = if n < 1.0 { 0.0 } else { (n - err).floor() };
+                let d = (n + 1.0) * unit + beg;
+                if (beg < end && d < end) || (beg > end && d > end) {
+                    n += 1.0;
+                }
+                n + 1.0
+            }
+        } else if n < 0.0 {
+            0.0
+        } else {
+            (n + err).floor() + 1.0
+        }
+    };
+    let mut i = 0.0;
+    while i < n {
+        let mut d = i * unit + beg;
+        if (unit >= 0.0 && end < d) || (unit < 0.0 && d < end) {
+            d = end;
+        }
+        vm.invoke_block(globals, block, receiver, &[Value::new_float(d)])?;
+        i += 1.0;
+    }
+    Some(())
+}
+
+///
+/// `divmod` for floats.
+///
+/// The quotient is converted to Integer.
+///
+pub(super) fn float_divmod(globals: &mut Globals, x: f64, y: f64) -> Option<Value> {
+    if y == 0.0 {
+        globals.err_divide_by_zero();
+        return None;
+    }
+    let mut m = x % y;
+    let mut div = if x.is_infinite() && !y.is_infinite() {
+        x
+    } else {
+        (x - m) / y
+    };
+    if y * m < 0.0 {
+        m += y;
+        div -= 1.0;
+    }
+    let div = float_to_integer(globals, div.round())?;
+    Some(Value::new_array_from_vec(vec![div, Value::new_float(m)]))
+}
+
+///
+/// Convert f64 to Integer.
+///
+/// Raise FloatDomainError for NaN and Infinity.
+///
+pub(super) fn float_to_integer(globals: &mut Globals, f: f64) -> Option<Value> {
+    if f.is_nan() {
+        globals.set_error(MonorubyErr::range("NaN".to_string()));
+        return None;
+    }
+    if f.is_infinite() {
+        let msg = if f > 0.0 { "Infinity" } else { "-Infinity" };
+        globals.set_error(MonorubyErr::range(msg.to_string()));
+        return None;
+    }
+    let v = match f.to_i64() {
+        Some(i) => Value::new_integer(i),
+        None => Value::new_bigint(BigInt::from_f64(f).unwrap()),
+    };
+    Some(v)
+}
+
+#[cfg(test)]
+mod test {
+    use super::tests::*;
+
+    #[test]
+    fn numeric_operators() {
+        run_test(
+            r#"[1.send(:+, 2), 1.send(:-, 2.5), 3.send(:*, 4), 7.send(:/, 2), 7.send(:%, 3)]"#,
+        );
+        run_test(r#"[2.send(:**, 10), 1.5.send(:+, 2), 1.5.send(:<, 2), 3.send(:<=>, 2.5)]"#);
+        run_test(r#"[5 & 3, 5 | 3, 5 ^ 3, 1 << 10, 1024 >> 3, -5, -(1.5), +3]"#);
+        run_test(r#"[1 == 1.0, 1.eql?(1), 1 <=> "a", 1.0 <=> 0.0 / 0.0, 1 === 1]"#);
+        run_test(r#"[1, 2, 3].inject(:+)"#);
+        run_test(r#"[1, 2, 3].map(&:-@)"#);
+        run_test(
+            r#"[Integer.ancestors.include?(Numeric), 1.is_a?(Comparable), 1.5.integer?, 1.integer?]"#,
+        );
+        run_test(r#"[0.zero?, 0.0.zero?, 3.nonzero?, 0.nonzero?, -1.positive?, -1.negative?]"#);
+    }
+
+    #[test]
+    fn numeric_coerce() {
+        run_test(r#"[1.coerce(2), 1.coerce(2.5), 1.5.coerce(2), 1.5.coerce(2.5)]"#);
+        run_test_with_prelude(
+            r#"
+        [1 + Meter.new(3), 2 * Meter.new(4), 10 - Meter.new(1), 1 < Meter.new(5), 1.5 + Meter.new(2)]
+        "#,
+            r#"
+        class Meter
+          attr_reader :v
+          def initialize(v)
+            @v = v
+          end
+          def coerce(other)
+            [Meter.new(other), self]
+          end
+          def +(other)
+            @v + other.v
+          end
+          def -(other)
+            @v - other.v
+          end
+          def *(other)
+            @v * other.v
+          end
+          def <(other)
+            @v < other.v
+          end
+        end
+        "#,
+        );
+        run_test_error(r#"1 + nil"#);
+        run_test_error(r#"1.5 * "a""#);
+        run_test_error(r#"1 < "a""#);
+        run_test_error(
+            r#"
+        class C
+          def coerce(other)
+            1
+          end
+        end
+        1 + C.new
+        "#,
+        );
+    }
+}
@@ -13,13 +13,32 @@ pub(super) fn init(globals: &mut Globals) {
     globals.define_builtin_func(OBJECT_CLASS, "p", p, -1);
     globals.define_builtin_func(OBJECT_CLASS, "__assert", assert, 2);
     globals.define_builtin_func(OBJECT_CLASS, "__dump", dump, 0);
-    globals.define_builtin_func(OBJECT_CLASS, "respond_to?", respond_to, 1);
+    globals.define_builtin_func(OBJECT_CLASS, "respond_to?", respond_to, -1);
+    globals.define_builtin_func(OBJECT_CLASS, "respond_to_missing?", respond_to_missing, 2);
+    let func_id = globals.define_builtin_func(OBJECT_CLASS, "method_missing", method_missing, -1);
+    globals.add_method_with_visibility(
+        OBJECT_CLASS,
+        IdentId::_METHOD_MISSING,
+        func_id,
+        Visibility::Private,
+    );
     globals.define_builtin_func(OBJECT_CLASS, "inspect", inspect, 0);
+    globals.define_builtin_func(OBJECT_CLASS, "hash", hash, 0);
+    globals.define_builtin_func(OBJECT_CLASS, "eql?", eql, 1);
+    globals.define_builtin_func(OBJECT_CLASS, "==", eq, 1);
+    globals.define_builtin_func(OBJECT_CLASS, "===", teq, 1);
     globals.define_builtin_func(OBJECT_CLASS, "class", class, 0);
     globals.define_builtin_func(OBJECT_CLASS, "rand", rand, -1);
     globals.define_builtin_func(OBJECT_CLASS, "singleton_class", singleton_class, 0);
-    globals.define_builtin_func(OBJECT_CLASS, "Integer", kernel_integer, 1);
+    globals.define_builtin_func(OBJECT_CLASS, "extend", extend, -1);
+    globals.define_builtin_func(OBJECT_CLASS, "is_a?", is_a, 1);
+    globals.define_builtin_func(OBJECT_CLASS, "kind_of?", is_a, 1);
+    globals.define_builtin_func(OBJECT_CLASS, "Integer", kernel_integer, -1);
     globals.define_builtin_func(OBJECT_CLASS, "require", require, 1);
+    globals.define_builtin_func(OBJECT_CLASS, "raise", raise, -1);
+    globals.define_builtin_func(OBJECT_CLASS, "loop", loop_, 0);
+    globals.define_builtin_func(OBJECT_CLASS, "to_enum", to_enum, -1);
+    globals.define_builtin_func(OBJECT_CLASS, "enum_for", to_enum, -1);
     globals.define_builtin_func(
         OBJECT_CLASS,
         "instance_variable_defined?",
@@ -181,19 +200,142 @@ extern "C" fn dump(
 ///
 /// [https://docs.ruby-lang.org/ja/latest/method/Object/i/respond_to=3f.html]
 extern "C" fn respond_to(
-    _vm: &mut Executor,
+    vm: &mut Executor,
     globals: &mut Globals,
     self_val: Value,
     arg: Arg,
-    _len: usize,
+    len: usize,
     _: Option<Value>,
 ) -> Option<Value> {
+    if !(1..=2).contains(&len) {
+        globals.err_wrong_number_of_arguments_range(len, 1..=2);
+        return None;
+    }
     let name = match arg[0].unpack() {
         RV::Symbol(id) => id,
         RV::String(b) => IdentId::get_ident_id_from_string(String::from_utf8_lossy(b).into_owned()),
         _ => unimplemented!(),
     };
-    Some(Value::bool(globals.find_method(self_val, name).is_some()))
+    let include_all = len == 2 && arg[1].as_bool();
+    let res = match globals.find_method_entry_for_class(self_val.class_id(), name) {
+        Some(entry) => include_all || entry.visibility == Visibility::Public,
+        None => false,
+    };
+    if res {
+        return Some(Value::bool(true));
+    }
+    let args = [Value::new_symbol(name), Value::bool(include_all)];
+    let res = vm.invoke_method(globals, IdentId::_RESPOND_TO_MISSING, self_val, &args)?;
+    Some(Value::bool(res.as_bool()))
+}
+
+/// ### Object#respond_to_missing?
+/// - respond_to_missing?(name, include_private) -> bool
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Object/i/respond_to_missing=3f.html]
+extern "C" fn respond_to_missing(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    _self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(Value::bool(false))
+}
+
+/// ### BasicObject#method_missing
+/// - method_missing(name, *args) -> object
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/BasicObject/i/method_missing.html]
+extern "C" fn method_missing(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    if len == 0 {
+        globals.err_argument("no method name given");
+        return None;
+    }
+    let name = arg[0].expect_symbol_or_string(globals)?;
+    globals.err_method_not_found(name, self_val);
+    None
+}
+
+/// ### Object#hash
+/// - hash -> Integer
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Object/i/hash.html]
+extern "C" fn hash(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let hash = HashInner::native_hash(self_val).unwrap_or(self_val.get());
+    Some(Value::new_integer((hash >> 2) as i64))
+}
+
+/// ### Object#eql?
+/// - eql?(other) -> bool
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Object/i/eql=3f.html]
+extern "C" fn eql(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let b = HashInner::native_eql(self_val, arg[0]).unwrap_or(self_val == arg[0]);
+    Some(Value::bool(b))
+}
+
+/// ### Object#==
+/// - self == other -> bool
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Object/i/=3d=3d.html]
+extern "C" fn eq(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let b = HashInner::native_eql(self_val, arg[0]).unwrap_or(self_val == arg[0]);
+    Some(Value::bool(b))
+}
+
+/// ### Object#===
+/// - self === other -> bool
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Object/i/=3d=3d=3d.html]
+extern "C" fn teq(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let (lhs, rhs) = (self_val, arg[0]);
+    let b = match (lhs.unpack(), rhs.unpack()) {
+        (RV::Integer(lhs), RV::Float(rhs)) => lhs as f64 == rhs,
+        (RV::Float(lhs), RV::Integer(rhs)) => lhs == rhs as f64,
+        (RV::BigInt(lhs), RV::Float(rhs)) => lhs.to_f64() == Some(rhs),
+        (RV::Float(lhs), RV::BigInt(rhs)) => Some(lhs) == rhs.to_f64(),
+        _ => vm
+            .invoke_method(globals, IdentId::_EQ, lhs, &[rhs])?
+            .as_bool(),
+    };
+    Some(Value::bool(b))
 }
 
 /// ### Object#inspect
@@ -273,7 +415,48 @@ extern "C" fn singleton_class(
     _len: usize,
     _: Option<Value>,
 ) -> Option<Value> {
-    Some(self_val.get_singleton(globals))
+    self_val.get_singleton(globals)
+}
+
+/// ### Object#extend
+/// - extend(*modules) -> self
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Object/i/extend.html]
+extern "C" fn extend(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let singleton = globals.get_singleton_class(self_val)?;
+    super::class::append_modules(globals, singleton, arg, len, false)?;
+    Some(self_val)
+}
+
+/// ### Object#is_a?
+/// - is_a?(mod) -> bool
+/// - kind_of?(mod) -> bool
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Object/i/is_a=3f.html]
+extern "C" fn is_a(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let target = match arg[0].is_class_or_module() {
+        Some(class_id) => class_id,
+        None => {
+            globals.set_error(MonorubyErr::typeerr("class or module required".to_string()));
+            return None;
+        }
+    };
+    let class_id = self_val.class_id();
+    Some(Value::bool(globals.is_subclass_of(class_id, target)))
 }
 
 /// ### Object#instance_variable_defined?
@@ -336,15 +519,43 @@ extern "C" fn instance_variable_get(
 /// - Integer(arg, base = 0, exception: true) -> Integer | nil
 ///
 /// [https://docs.ruby-lang.org/ja/latest/method/Kernel/m/Integer.html]
+///
+/// !! `base` is not supported.
 extern "C" fn kernel_integer(
     _vm: &mut Executor,
     globals: &mut Globals,
     _: Value,
     arg: Arg,
-    _len: usize,
+    len: usize,
     _: Option<Value>,
 ) -> Option<Value> {
-    let arg0 = arg[0];
+    let (len, kw) = arg.keywords(len);
+    if len != 1 {
+        globals.err_wrong_number_of_arguments_range(len, 1..=1);
+        return None;
+    }
+    let mut exception = true;
+    if let Some(kw) = kw {
+        for (k, v) in kw.as_hash().iter() {
+            if k == Value::new_symbol(IdentId::get_ident_id("exception")) {
+                exception = v.as_bool();
+            } else {
+                let k = globals.val_inspect(k);
+                globals.err_argument(&format!("unknown keyword: {k}"));
+                return None;
+            }
+        }
+    }
+    match to_integer(globals, arg[0]) {
+        None if !exception => {
+            globals.take_error();
+            Some(Value::nil())
+        }
+        res => res,
+    }
+}
+
+fn to_integer(globals: &mut Globals, arg0: Value) -> Option<Value> {
     match arg0.unpack() {
         RV::Integer(num) => return Some(Value::new_integer(num)),
         RV::BigInt(num) => return Some(Value::new_bigint(num.clone())),
@@ -385,6 +596,128 @@ extern "C" fn require(
     executor.eval_script(globals, file_body, &path)
 }
 
+/// ### Kernel.#raise
+/// - raise -> ()
+/// - raise(message) -> ()
+/// - raise(error_type, message = nil) -> ()
+/// - raise(error_object) -> ()
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Kernel/m/raise.html]
+extern "C" fn raise(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    _: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    if len > 2 {
+        globals.err_wrong_number_of_arguments_range(len, 0..=2);
+        return None;
+    }
+    if len == 0 {
+        // re-raise `$!` if it is set.
+        let err = globals.get_gvar(IdentId::get_ident_id("$!"));
+        if err.is_exception().is_some() {
+            globals.raise_exception(err);
+        } else {
+            globals.err_runtime("unhandled exception".to_string());
+        }
+        return None;
+    }
+    let arg0 = arg[0];
+    if let Some(class_id) = arg0.is_class() {
+        if globals.is_subclass_of(class_id, EXCEPTION_CLASS) {
+            let args = if len == 2 { vec![arg[1]] } else { vec![] };
+            let err = vm.invoke_method(globals, IdentId::NEW, arg0, &args)?;
+            if err.is_exception().is_none() {
+                globals.set_error(MonorubyErr::typeerr(
+                    "exception object expected".to_string(),
+                ));
+                return None;
+            }
+            globals.raise_exception(err);
+            return None;
+        }
+    } else if arg0.is_exception().is_some() {
+        let err = if len == 2 {
+            let msg = globals.val_tos(arg[1]);
+            Value::new_exception(arg0.class_id(), MonorubyErr::runtimeerr(msg))
+        } else {
+            arg0
+        };
+        globals.raise_exception(err);
+        return None;
+    } else if let RV::String(s) = arg0.unpack() {
+        if len == 1 {
+            globals.err_runtime(String::from_utf8_lossy(s).into_owned());
+            return None;
+        }
+    }
+    globals.set_error(MonorubyErr::typeerr(
+        "exception class/object expected".to_string(),
+    ));
+    None
+}
+
+/// ### Kernel.#loop
+/// - loop { ... } -> object | nil
+/// - loop -> Enumerator
+///
+/// The loop is terminated by StopIteration.
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Kernel/m/loop.html]
+extern "C" fn loop_(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _: Arg,
+    _: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    let block = match block {
+        Some(block) => block,
+        None => return Some(Value::new_enumerator(self_val, "loop", vec![])),
+    };
+    loop {
+        if vm.invoke_block(globals, block, self_val, &[]).is_none() {
+            if globals.error_is_kind_of(STOP_ITERATION_CLASS) {
+                globals.take_error();
+                return Some(Value::nil());
+            }
+            return None;
+        }
+    }
+}
+
+/// ### Object#to_enum
+/// - to_enum(method = :each, *args) -> Enumerator
+/// - enum_for(method = :each, *args) -> Enumerator
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Object/i/enum_for.html]
+extern "C" fn to_enum(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    if len == 0 {
+        return Some(Value::new_enumerator(self_val, "each", vec![]));
+    }
+    let method = match arg[0].unpack() {
+        RV::Symbol(id) => IdentId::get_name(id),
+        RV::String(s) => String::from_utf8_lossy(s).into_owned(),
+        _ => {
+            globals.err_no_implict_conv(arg[0], SYMBOL_CLASS);
+            return None;
+        }
+    };
+    let args = (1..len).map(|i| arg[i]).collect();
+    Some(Value::new_enumerator(self_val, &method, args))
+}
+
 #[cfg(test)]
 mod test {
     use super::tests::*;
@@ -428,4 +761,94 @@ mod test {
         run_test2(r#"Integer(-2435766756886769978978435)"#);
         run_test2(r#"Integer(2435.4556787)"#);
     }
+
+    #[test]
+    fn kernel_loop() {
+        run_test("a = []; i = 0; loop { i += 1; raise StopIteration if i > 5; a << i }; a");
+        run_test("e = [1, 2, 3].each; a = []; loop { a << e.next * 2 }; a");
+        run_test_error("loop { raise 'err' }");
+        run_test("e = 3.to_enum(:upto, 6); [e.next, e.next, e.to_a]");
+        run_test("[1, 2, 3].enum_for.map { |x| x * 3 }");
+    }
+
+    #[test]
+    fn kernel_raise() {
+        run_test_error(r#"raise"#);
+        run_test_error(r#"raise "woo""#);
+        run_test_error(r#"raise RuntimeError"#);
+        run_test_error(r#"raise StandardError, "foo""#);
+        run_test_error(r#"raise 3"#);
+    }
+
+    #[test]
+    fn method_missing() {
+        run_test_with_prelude(
+            r#"
+        b = Builder.new
+        b.name("foo")
+        b.size(3, 4)
+        b.tags { :block }
+        [b.log, b.respond_to?(:set_x), b.respond_to?(:x), b.respond_to?(:name), b.known]
+        "#,
+            r#"
+        class Builder
+          attr_reader :log
+          def initialize; @log = []; end
+          def known; :known; end
+          def method_missing(name, *args, &block)
+            if name.to_s.start_with?("set_") || name == :x
+              super(name)
+            else
+              @log << [name, args, block ? block.call : nil]
+              self
+            end
+          end
+          def respond_to_missing?(name, include_private)
+            name.to_s.start_with?("set_")
+          end
+        end
+        "#,
+        );
+        run_test_with_prelude(
+            r#"
+        p = Proxy.new([3, 1, 2])
+        res = [p.sort, p.size, p.map { |x| x * 2 }, p.secret, p.calls]
+        begin
+          p.set_x(1)
+        rescue NoMethodError => e
+          res << e.class
+        end
+        res
+        "#,
+            r#"
+        class Proxy
+          attr_reader :calls
+          def initialize(target)
+            @target = target
+            @calls = 0
+          end
+          def method_missing(name, *args, &block)
+            @calls += 1
+            case name
+            when :sort then @target.sort
+            when :size then @target.size
+            when :map then @target.map(&block)
+            when :secret then :missing
+            else super(name)
+            end
+          end
+          private
+          def secret; :secret; end
+        end
+        "#,
+        );
+    }
+
+    #[test]
+    fn method_missing_error() {
+        run_test_error("Object.new.foo");
+        run_test_error("class A; def method_missing(name); super; end; end; A.new.foo");
+        run_test_error("class A; def method_missing(name); 1; end; end; A.new.foo(1)");
+        run_test_error("Object.new.method_missing(:foo)");
+    }
 }
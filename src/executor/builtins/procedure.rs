@@ -0,0 +1,353 @@
+use crate::*;
+
+//
+// Proc class
+//
+
+pub(super) fn init(globals: &mut Globals) {
+    globals.define_builtin_singleton_func(PROC_CLASS, "new", proc_new, 0);
+    globals.define_builtin_func(PROC_CLASS, "call", call, -1);
+    globals.define_builtin_func(PROC_CLASS, "[]", call, -1);
+    globals.define_builtin_func(PROC_CLASS, "===", call, -1);
+    globals.define_builtin_func(PROC_CLASS, "yield", call, -1);
+    globals.define_builtin_func(PROC_CLASS, "arity", arity, 0);
+    globals.define_builtin_func(PROC_CLASS, "lambda?", lambda_, 0);
+    globals.define_builtin_func(PROC_CLASS, "to_proc", to_proc, 0);
+    globals.define_builtin_func(OBJECT_CLASS, "proc", proc, 0);
+    globals.define_builtin_func(OBJECT_CLASS, "lambda", lambda, 0);
+}
+
+fn generate_proc(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    block: Option<Value>,
+    is_lambda: bool,
+) -> Option<Value> {
+    match block {
+        Some(block) => Some(vm.generate_proc(globals, block, is_lambda)),
+        None => {
+            globals.err_argument("tried to create Proc object without a block");
+            None
+        }
+    }
+}
+
+/// ### Proc.new
+/// - new { ... } -> Proc
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Proc/s/new.html]
+extern "C" fn proc_new(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    _: Value,
+    _: Arg,
+    _: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    generate_proc(vm, globals, block, false)
+}
+
+/// ### Kernel.#proc
+/// - proc { ... } -> Proc
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Kernel/m/proc.html]
+extern "C" fn proc(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    _: Value,
+    _: Arg,
+    _: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    generate_proc(vm, globals, block, false)
+}
+
+/// ### Kernel.#lambda
+/// - lambda { ... } -> Proc
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Kernel/m/lambda.html]
+extern "C" fn lambda(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    _: Value,
+    _: Arg,
+    _: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    generate_proc(vm, globals, block, true)
+}
+
+/// ### Proc#call
+/// - self[*arg] -> ()
+/// - call(*arg) -> ()
+/// - self === *arg -> ()
+/// - yield(*arg) -> ()
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Proc/i/=3d=3d=3d.html]
+extern "C" fn call(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let proc = self_val.as_proc();
+    if let (true, Some(info)) = (
+        proc.is_lambda(),
+        globals.func[proc.func_id()].is_ruby_func(),
+    ) {
+        // lambdas check the number of arguments strictly.
+        let hidden_rest = info.keyword.as_ref().map_or(false, |kw| kw.hidden_rest);
+        let (req_num, pos_num) = (info.req_num(), info.pos_num());
+        let has_rest = info.arg_num() != pos_num && !hidden_rest;
+        let given = if info.keyword.is_some() {
+            arg.keywords(len).0
+        } else {
+            len
+        };
+        if given < req_num || (!has_rest && given > pos_num) {
+            globals.err_wrong_number_of_arguments_range(given, req_num..=pos_num);
+            return None;
+        }
+    }
+    vm.invoke_block(globals, self_val, self_val, &arg.to_vec(len))
+}
+
+/// ### Proc#arity
+/// - arity -> Integer
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Proc/i/arity.html]
+extern "C" fn arity(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _: Arg,
+    _: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let proc = self_val.as_proc();
+    let arity = match globals.func[proc.func_id()].is_ruby_func() {
+        Some(info) => info.proc_arity(proc.is_lambda()),
+        // a Proc from Symbol#to_proc takes a receiver and optional arguments.
+        None => -2,
+    };
+    Some(Value::new_integer(arity))
+}
+
+/// ### Proc#lambda?
+/// - lambda? -> bool
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Proc/i/lambda=3f.html]
+extern "C" fn lambda_(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _: Arg,
+    _: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(Value::bool(self_val.as_proc().is_lambda()))
+}
+
+/// ### Proc#to_proc
+/// - to_proc -> self
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Proc/i/to_proc.html]
+extern "C" fn to_proc(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _: Arg,
+    _: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(self_val)
+}
+
+#[cfg(test)]
+mod test {
+    use super::tests::*;
+
+    #[test]
+    fn proc_closure() {
+        run_test_with_prelude(
+            r##"
+        c = counter
+        c.call
+        c.call
+        c.call
+        "##,
+            r##"
+        def counter
+          x = 0
+          proc { x += 1 }
+        end
+        "##,
+        );
+        run_test_with_prelude(
+            r##"
+        a = make(10)
+        a[0].call
+        a[0][]
+        a[0].yield
+        [a[1].call, a[1] === 0]
+        "##,
+            r##"
+        def make(x)
+          inc = proc { x += 1 }
+          get = lambda { x }
+          [inc, get]
+        end
+        "##,
+        );
+        run_test_with_prelude(
+            r##"
+        a = gen
+        [a[0].call(1), a[1].call(1), a[2].call(1)]
+        "##,
+            r##"
+        def gen
+          a = []
+          3.times do |i|
+            j = i * 10
+            a << proc { |x| x + i + j }
+          end
+          a
+        end
+        "##,
+        );
+        run_test(
+            r##"
+        x = 5
+        pr = Proc.new { |a, b| x += a * b }
+        pr.call(2, 3)
+        pr.call([4, 5])
+        x
+        "##,
+        );
+    }
+
+    #[test]
+    fn proc_heap_frame() {
+        run_test_with_prelude(
+            r##"
+        a = []
+        for i in 0..50
+          p1 = outer(i)
+          p2 = p1.call(i * 2)
+          s = "garbage" * 10
+          a << p2.call(1) if i % 10 == 0
+        end
+        a << inner_first.call
+        a
+        "##,
+            r##"
+        def outer(x)
+          proc { |y| proc { |z| x + y + z } }
+        end
+        def inner_first
+          x = 1
+          pr = nil
+          [2].each { |y| pr = proc { x + y } }
+          x = 10
+          pr
+        end
+        "##,
+        );
+    }
+
+    #[test]
+    fn proc_block_arg() {
+        run_test_with_prelude(
+            r##"
+        pr = m { |x| x * 7 }
+        [pr.call(2), n(&pr), w { |x| x + 1 }, m, n(&nil)]
+        "##,
+            r##"
+        def m(&b)
+          b
+        end
+        def n(&b)
+          if b
+            yield 3
+          else
+            0
+          end
+        end
+        def w(&b)
+          n(&b)
+        end
+        "##,
+        );
+    }
+
+    #[test]
+    fn proc_arity() {
+        run_test(
+            r##"
+        [proc {}.arity, proc { |x| }.arity, proc { |x, y| }.arity, proc { |*a| }.arity,
+         proc { |x, *a| }.arity, lambda { |x, y = 1| }.arity, proc { |x, y = 1| }.arity,
+         ->(){}.arity, ->(x){}.arity]
+        "##,
+        );
+        run_test("[proc {}.lambda?, lambda {}.lambda?, ->(){}.lambda?]");
+        run_test("->(x, y){ x * y }.call(4, 5)");
+        run_test("->(x){ x }.to_proc.lambda?");
+        run_test_error("->(x, y){ x * y }.call(4)");
+        run_test_error("proc");
+        run_test_error("lambda");
+    }
+
+    #[test]
+    fn proc_return() {
+        run_test_with_prelude(
+            r##"
+        [f, g, h, k]
+        "##,
+            r##"
+        def f
+          5.times { |i| return i * 10 if i == 2 }
+          0
+        end
+        def g
+          pr = proc { return 7 }
+          pr.call
+          8
+        end
+        def h
+          l = lambda { return 5 }
+          l.call + 1
+        end
+        def k
+          l = lambda { 3.times { |i| return i + 100 } }
+          l.call + 1
+        end
+        "##,
+        );
+        run_test_with_prelude(
+            r##"
+        a = []
+        [e(a), a]
+        "##,
+            r##"
+        def e(a)
+          begin
+            3.times { |i| return i + 1 }
+          ensure
+            a << 1
+          end
+          a << 2
+        end
+        "##,
+        );
+        run_test_error(
+            r##"
+        def mk
+          proc { return 1 }
+        end
+        mk.call
+        "##,
+        );
+    }
+}
@@ -1,3 +1,8 @@
+use num::{BigInt, Signed, ToPrimitive, Zero};
+use std::cmp::Ordering;
+
+use super::numeric::float_step;
+
 use crate::*;
 
 //
@@ -9,6 +14,21 @@ pub(super) fn init(globals: &mut Globals) {
     globals.define_builtin_func(RANGE_CLASS, "begin", begin, 0);
     globals.define_builtin_func(RANGE_CLASS, "end", end, 0);
     globals.define_builtin_func(RANGE_CLASS, "exclude_end?", exclude_end, 0);
+    globals.define_builtin_func(RANGE_CLASS, "===", include, 1);
+    globals.define_builtin_func(RANGE_CLASS, "include?", include, 1);
+    globals.define_builtin_func(RANGE_CLASS, "member?", include, 1);
+    globals.define_builtin_func(RANGE_CLASS, "cover?", include, 1);
+    globals.define_builtin_func(RANGE_CLASS, "first", first, -1);
+    globals.define_builtin_func(RANGE_CLASS, "size", size, 0);
+    globals.define_builtin_func_inlinable(RANGE_CLASS, "each", each, 0, InlineMethod::RangeEach);
+    globals.define_builtin_func(RANGE_CLASS, "reverse_each", reverse_each, 0);
+    globals.define_builtin_func(RANGE_CLASS, "step", step, 1);
+    globals.define_builtin_func(RANGE_CLASS, "%", step, 1);
+    globals.define_builtin_func(RANGE_CLASS, "to_a", to_a, 0);
+    globals.define_builtin_func(RANGE_CLASS, "entries", to_a, 0);
+    globals.define_builtin_func(RANGE_CLASS, "map", map, 0);
+    globals.define_builtin_func(RANGE_CLASS, "collect", map, 0);
+    globals.define_builtin_func(RANGE_CLASS, "sum", sum, -1);
 }
 
 /// ### Range.new
@@ -27,7 +47,8 @@ extern "C" fn range_new(
         globals.err_wrong_number_of_arguments_range(len, 2..=3);
         return None;
     }
-    globals.generate_range(arg[0], arg[1], false)
+    let exclude_end = len == 3 && arg[2].as_bool();
+    globals.generate_range(arg[0], arg[1], exclude_end)
 }
 
 /// ### Range#begin
@@ -75,6 +96,526 @@ extern "C" fn exclude_end(
     Some(Value::bool(self_val.as_range().exclude_end()))
 }
 
+/// ### Range#===
+/// - self === obj -> bool
+/// - include?(obj) -> bool
+/// - member?(obj) -> bool
+/// - cover?(obj) -> bool
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Range/i/=3d=3d=3d.html]
+extern "C" fn include(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    _: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let b = cover(vm, globals, self_val, arg[0])?;
+    Some(Value::bool(b))
+}
+
+/// ### Range#first
+/// - first -> object
+/// - first(n) -> Array
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Range/i/first.html]
+extern "C" fn first(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    if len > 1 {
+        globals.err_wrong_number_of_arguments_range(len, 0..=1);
+        return None;
+    }
+    let start = self_val.as_range().start;
+    if start.is_nil() {
+        globals.set_error(MonorubyErr::range(
+            "cannot get the first element of beginless range".to_string(),
+        ));
+        return None;
+    }
+    if len == 0 {
+        return Some(start);
+    }
+    let n = match arg[0].try_fixnum() {
+        Some(n) if n < 0 => {
+            globals.err_argument("negative array size (or size too big)");
+            return None;
+        }
+        Some(n) => n as usize,
+        None => {
+            globals.err_no_implict_conv(arg[0], INTEGER_CLASS);
+            return None;
+        }
+    };
+    let mut res = Value::new_array_from_vec(vec![]);
+    if n != 0 {
+        vm.with_temp(res, |vm| {
+            range_each(vm, globals, self_val, |_, _, v| {
+                res.as_array_mut().push(v);
+                Some(res.as_array().len() < n)
+            })
+        })?;
+    }
+    Some(res)
+}
+
+/// ### Range#size
+/// - size -> Integer | Float | nil
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Range/i/size.html]
+extern "C" fn size(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _: Arg,
+    _: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let range = self_val.as_range();
+    let start = match range.start.unpack() {
+        RV::Integer(i) => BigInt::from(i),
+        RV::BigInt(b) => b.clone(),
+        _ => return Some(Value::nil()),
+    };
+    let size = match range.end.unpack() {
+        RV::Integer(_) | RV::BigInt(_) => {
+            let end = to_bigint(range.end);
+            let n = end - start + if range.exclude_end() { 0 } else { 1 };
+            Value::new_bigint(if n.is_negative() { BigInt::zero() } else { n })
+        }
+        RV::Float(f) if f.is_infinite() => {
+            return Some(if f > 0.0 {
+                Value::new_float(f64::INFINITY)
+            } else {
+                Value::new_integer(0)
+            });
+        }
+        RV::Float(f) => {
+            let d = f - start.to_f64().unwrap();
+            if d < 0.0 {
+                Value::new_integer(0)
+            } else {
+                let n = d.floor();
+                let n = if range.exclude_end() && n == d {
+                    n
+                } else {
+                    n + 1.0
+                };
+                Value::new_bigint(BigInt::from(n as i64))
+            }
+        }
+        RV::Nil => Value::new_float(f64::INFINITY),
+        _ => Value::nil(),
+    };
+    Some(size)
+}
+
+/// ### Range#each
+/// - each {|item| ... } -> self
+/// - each -> Enumerator
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Range/i/each.html]
+extern "C" fn each(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _: Arg,
+    _: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    let block = match block {
+        Some(block) => block,
+        None => return Some(Value::new_enumerator(self_val, "each", vec![])),
+    };
+    range_each(vm, globals, self_val, |vm, globals, v| {
+        vm.invoke_block(globals, block, self_val, &[v])?;
+        Some(true)
+    })?;
+    Some(self_val)
+}
+
+/// ### Range#reverse_each
+/// - reverse_each {|item| ... } -> self
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Range/i/reverse_each.html]
+extern "C" fn reverse_each(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _: Arg,
+    _: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    let block = globals.expect_block(block)?;
+    let range = self_val.as_range();
+    if let (RV::Integer(start), RV::Integer(end)) = (range.start.unpack(), range.end.unpack()) {
+        let mut i = if range.exclude_end() {
+            match end.checked_sub(1) {
+                Some(i) => i,
+                None => return Some(self_val),
+            }
+        } else {
+            end
+        };
+        while i >= start {
+            vm.invoke_block(globals, block, self_val, &[Value::new_integer(i)])?;
+            i = match i.checked_sub(1) {
+                Some(i) => i,
+                None => break,
+            };
+        }
+        return Some(self_val);
+    }
+    if range.end.is_nil() {
+        let msg = format!(
+            "can't iterate from {}",
+            range.end.get_real_class_name(globals)
+        );
+        globals.set_error(MonorubyErr::typeerr(msg));
+        return None;
+    }
+    let mut elems = Value::new_array_from_vec(vec![]);
+    vm.with_temp(elems, |vm| {
+        range_each(vm, globals, self_val, |_, _, v| {
+            elems.as_array_mut().push(v);
+            Some(true)
+        })?;
+        for v in elems.as_array().to_vec().into_iter().rev() {
+            vm.invoke_block(globals, block, self_val, &[v])?;
+        }
+        Some(self_val)
+    })
+}
+
+/// ### Range#step
+/// - step(s) {|item| ... } -> self
+/// - self % s {|item| ... } -> self
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Range/i/step.html]
+extern "C" fn step(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    _: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    let block = globals.expect_block(block)?;
+    let range = self_val.as_range();
+    let (start, end, excl) = (range.start, range.end, range.exclude_end());
+    let step = arg[0];
+    let is_real = |v: Value| v.is_numeric() || v.is_rational().is_some();
+    if !is_real(step) {
+        globals.err_no_implict_conv(step, INTEGER_CLASS);
+        return None;
+    }
+    if vm
+        .binary_op(globals, IdentId::_EQ, step, Value::new_integer(0))?
+        .as_bool()
+    {
+        globals.err_argument("step can't be 0");
+        return None;
+    }
+    if vm
+        .binary_op(globals, IdentId::_LT, step, Value::new_integer(0))?
+        .as_bool()
+    {
+        globals.err_argument("step can't be negative");
+        return None;
+    }
+    if is_real(start) && (end.is_nil() || is_real(end)) {
+        if let (RV::Float(_), _, _) | (_, RV::Float(_), _) | (_, _, RV::Float(_)) =
+            (start.unpack(), end.unpack(), step.unpack())
+        {
+            let beg = start.expect_float(globals)?;
+            let end = if end.is_nil() {
+                f64::INFINITY
+            } else {
+                end.expect_float(globals)?
+            };
+            let unit = step.expect_float(globals)?;
+            float_step(vm, globals, self_val, beg, end, unit, excl, block)?;
+            return Some(self_val);
+        }
+        let cmp = if excl { IdentId::_LT } else { IdentId::_LE };
+        let mut i = start;
+        while end.is_nil() || vm.binary_op(globals, cmp, i, end)?.as_bool() {
+            vm.invoke_block(globals, block, self_val, &[i])?;
+            i = vm.binary_op(globals, IdentId::_ADD, i, step)?;
+        }
+        return Some(self_val);
+    }
+    let step = match step.try_fixnum() {
+        Some(step) => step,
+        None => {
+            globals.err_no_implict_conv(step, INTEGER_CLASS);
+            return None;
+        }
+    };
+    let mut count = 0;
+    range_each(vm, globals, self_val, |vm, globals, v| {
+        if count % step == 0 {
+            vm.invoke_block(globals, block, self_val, &[v])?;
+        }
+        count += 1;
+        Some(true)
+    })?;
+    Some(self_val)
+}
+
+/// ### Range#to_a
+/// - to_a -> Array
+/// - entries -> Array
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Range/i/to_a.html]
+extern "C" fn to_a(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _: Arg,
+    _: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    if self_val.as_range().end.is_nil() {
+        globals.set_error(MonorubyErr::range(
+            "cannot convert endless range to an array".to_string(),
+        ));
+        return None;
+    }
+    let mut res = Value::new_array_from_vec(vec![]);
+    vm.with_temp(res, |vm| {
+        range_each(vm, globals, self_val, |_, _, v| {
+            res.as_array_mut().push(v);
+            Some(true)
+        })
+    })?;
+    Some(res)
+}
+
+/// ### Range#map
+/// - map {|item| ... } -> Array
+/// - collect {|item| ... } -> Array
+/// - map -> Enumerator
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Enumerable/i/collect.html]
+extern "C" fn map(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _: Arg,
+    _: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    let block = match block {
+        Some(block) => block,
+        None => return Some(Value::new_enumerator(self_val, "map", vec![])),
+    };
+    let mut res = Value::new_array_from_vec(vec![]);
+    vm.with_temp(res, |vm| {
+        range_each(vm, globals, self_val, |vm, globals, v| {
+            let v = vm.invoke_block(globals, block, self_val, &[v])?;
+            res.as_array_mut().push(v);
+            Some(true)
+        })
+    })?;
+    Some(res)
+}
+
+/// ### Range#sum
+/// - sum(init = 0) -> object
+/// - sum(init = 0) {|item| ... } -> object
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Range/i/sum.html]
+extern "C" fn sum(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    if len > 1 {
+        globals.err_wrong_number_of_arguments_range(len, 0..=1);
+        return None;
+    }
+    let init = if len == 0 {
+        Value::new_integer(0)
+    } else {
+        arg[0]
+    };
+    let range = self_val.as_range();
+    if block.is_none() {
+        if let (
+            RV::Integer(_) | RV::BigInt(_),
+            RV::Integer(_) | RV::BigInt(_),
+            RV::Integer(_) | RV::BigInt(_),
+        ) = (range.start.unpack(), range.end.unpack(), init.unpack())
+        {
+            // (first + last) * n / 2
+            let first = to_bigint(range.start);
+            let mut last = to_bigint(range.end);
+            if range.exclude_end() {
+                last -= 1;
+            }
+            let sum = if last < first {
+                BigInt::zero()
+            } else {
+                let n = &last - &first + 1;
+                (first + last) * n / 2
+            };
+            return Some(Value::new_bigint(sum + to_bigint(init)));
+        }
+    }
+    let mut sum = init;
+    range_each(vm, globals, self_val, |vm, globals, v| {
+        let v = match block {
+            Some(block) => {
+                vm.with_temp(sum, |vm| vm.invoke_block(globals, block, self_val, &[v]))?
+            }
+            None => v,
+        };
+        sum = vm.binary_op(globals, IdentId::_ADD, sum, v)?;
+        Some(true)
+    })?;
+    Some(sum)
+}
+
+///
+/// Call *f* with each element of *range* in order, until *f* returns false.
+///
+fn range_each(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    range: Value,
+    mut f: impl FnMut(&mut Executor, &mut Globals, Value) -> Option<bool>,
+) -> Option<()> {
+    let (start, end, excl) = {
+        let range = range.as_range();
+        (range.start, range.end, range.exclude_end())
+    };
+    match (start.unpack(), end.unpack()) {
+        (RV::Integer(start), RV::Integer(end)) => {
+            let mut i = start;
+            while i < end || (!excl && i == end) {
+                if !f(vm, globals, Value::new_integer(i))? || i == end {
+                    break;
+                }
+                i += 1;
+            }
+        }
+        (
+            RV::Integer(_) | RV::BigInt(_),
+            RV::Integer(_) | RV::BigInt(_) | RV::Float(_) | RV::Nil,
+        ) => {
+            let cmp = if excl { IdentId::_GE } else { IdentId::_GT };
+            let mut i = start;
+            while end.is_nil() || !vm.binary_op(globals, cmp, i, end)?.as_bool() {
+                if !f(vm, globals, i)? {
+                    break;
+                }
+                i = vm.binary_op(globals, IdentId::_ADD, i, Value::new_integer(1))?;
+            }
+        }
+        _ => {
+            let succ = IdentId::get_ident_id("succ");
+            if start.is_nil() || globals.find_method(start, succ).is_none() {
+                let msg = format!("can't iterate from {}", start.get_real_class_name(globals));
+                globals.set_error(MonorubyErr::typeerr(msg));
+                return None;
+            }
+            let mut v = start;
+            loop {
+                let last = if end.is_nil() {
+                    false
+                } else {
+                    match compare(vm, globals, v, end)? {
+                        Some(Ordering::Less) => false,
+                        Some(Ordering::Equal) if !excl => true,
+                        _ => break,
+                    }
+                };
+                if !f(vm, globals, v)? || last {
+                    break;
+                }
+                v = vm.invoke_method(globals, succ, v, &[])?;
+            }
+        }
+    }
+    Some(())
+}
+
+///
+/// Check whether *val* is between the beginning and the end of *range*.
+///
+fn cover(vm: &mut Executor, globals: &mut Globals, range: Value, val: Value) -> Option<bool> {
+    let (start, end, excl) = {
+        let range = range.as_range();
+        (range.start, range.end, range.exclude_end())
+    };
+    if !start.is_nil() {
+        match compare(vm, globals, start, val)? {
+            Some(Ordering::Less | Ordering::Equal) => {}
+            _ => return Some(false),
+        }
+    }
+    if !end.is_nil() {
+        match compare(vm, globals, val, end)? {
+            Some(Ordering::Less) => {}
+            Some(Ordering::Equal) if !excl => {}
+            _ => return Some(false),
+        }
+    }
+    Some(true)
+}
+
+///
+/// Compare *lhs* and *rhs*.
+///
+/// `<=>` is called for values which can not be compared natively.
+/// Returns Some(None) if they are not comparable.
+///
+fn compare(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    lhs: Value,
+    rhs: Value,
+) -> Option<Option<Ordering>> {
+    let ord = match (lhs.unpack(), rhs.unpack()) {
+        (RV::Integer(lhs), RV::Integer(rhs)) => lhs.partial_cmp(&rhs),
+        (RV::Integer(lhs), RV::BigInt(rhs)) => BigInt::from(lhs).partial_cmp(rhs),
+        (RV::Integer(lhs), RV::Float(rhs)) => (lhs as f64).partial_cmp(&rhs),
+        (RV::BigInt(lhs), RV::Integer(rhs)) => lhs.partial_cmp(&BigInt::from(rhs)),
+        (RV::BigInt(lhs), RV::BigInt(rhs)) => lhs.partial_cmp(rhs),
+        (RV::BigInt(lhs), RV::Float(rhs)) => lhs.to_f64().unwrap().partial_cmp(&rhs),
+        (RV::Float(lhs), RV::Integer(rhs)) => lhs.partial_cmp(&(rhs as f64)),
+        (RV::Float(lhs), RV::BigInt(rhs)) => lhs.partial_cmp(&rhs.to_f64().unwrap()),
+        (RV::Float(lhs), RV::Float(rhs)) => lhs.partial_cmp(&rhs),
+        (RV::String(lhs), RV::String(rhs)) => lhs.partial_cmp(rhs),
+        _ => {
+            if globals.find_method(lhs, IdentId::_CMP).is_none() {
+                return Some(None);
+            }
+            let res = vm.invoke_method(globals, IdentId::_CMP, lhs, &[rhs])?;
+            res.try_fixnum().map(|i| i.cmp(&0))
+        }
+    };
+    Some(ord)
+}
+
+fn to_bigint(val: Value) -> BigInt {
+    match val.unpack() {
+        RV::Integer(i) => BigInt::from(i),
+        RV::BigInt(b) => b.clone(),
+        _ => unreachable!(),
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::tests::*;
@@ -89,5 +630,90 @@ mod test {
         );
         run_test("(1..5).exclude_end?");
         run_test("(1...5).exclude_end?");
+        run_test("Range.new(1, 5, true).exclude_end?");
+    }
+
+    #[test]
+    fn range_each() {
+        run_test("a = []; (1..5).each { |i| a << i }; a");
+        run_test("a = []; (1...5).each { |i| a << i }; a");
+        run_test("a = []; (5..1).each { |i| a << i }; a");
+        run_test("a = []; (1..5).reverse_each { |i| a << i }; a");
+        run_test("a = []; (1...5).reverse_each { |i| a << i }; a");
+        run_test("a = []; (1..10).step(3) { |i| a << i }; a");
+        run_test("a = []; (1...10).step(3) { |i| a << i }; a");
+        run_test("a = []; (1.0..2.0).step(0.5) { |i| a << i }; a");
+        run_test("a = []; (1.0...2.0).step(0.5) { |i| a << i }; a");
+        run_test("a = []; (1..2).step(0.5) { |i| a << i }; a");
+        run_test("[(1..5).to_a, (1...5).entries, (3..1).to_a]");
+        run_test("[(1..5).map { |i| i * 2 }, (1...5).collect { |i| i * i }]");
+        run_test("[(1..100).sum, (1...100).sum, (1..10).sum(5), (5..1).sum]");
+        run_test("[(1..10).sum { |i| i * 2 }, (1..3).sum(0.5)]");
+        run_test("[(1..5).first, (1..5).first(3), (1..5).first(10), (1..5).first(0)]");
+        run_test_error("(1..5).step(0) {}");
+        run_test_error("(1..5).step(-1) {}");
+        run_test_error("(1..5).first(-1)");
+    }
+
+    #[test]
+    fn range_jit() {
+        run_test(
+            r##"
+          res = []
+          30.times do |x|
+            sum = 0
+            (x..x + 10).each { |i| sum += i }
+            (x...x + 10).each { |i| sum += i }
+            res << sum
+          end
+          res
+        "##,
+        );
+        run_test(
+            r##"
+          res = []
+          30.times do
+            (1..3).each { |i| res << i }
+            res << (10...1).each { |i| res << i }
+          end
+          res
+        "##,
+        );
+        run_test(
+            r##"
+          def f(x)
+            f = x * 1.5
+            begin
+              (1..x).each { |i| raise "err" if i == 7 }
+              f += 1.0
+            rescue => e
+              f += 0.5
+            end
+            f
+          end
+          res = []
+          i = 0
+          while i < 40
+            res << f(i % 10)
+            i += 1
+          end
+          res
+        "##,
+        );
+    }
+
+    #[test]
+    fn range_include() {
+        run_test("[(1..5).include?(5), (1...5).include?(5), (1..5).member?(0)]");
+        run_test("[(1..5) === 3, (1..5) === 3.5, (1.0..2.0).cover?(2)]");
+        run_test(r#"[("a".."z").cover?("c"), ("a".."z").include?("zz")]"#);
+        run_test("[(1..5) === nil, (1..5) === :a]");
+    }
+
+    #[test]
+    fn range_size() {
+        run_test("[(1..5).size, (1...5).size, (5..1).size, (1..5.5).size]");
+        run_test("[(1..5.0).size, (1...5.0).size, (\"a\"..\"z\").size]");
+        run_test("[(1..2**64).size]");
     }
 }
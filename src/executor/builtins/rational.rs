@@ -0,0 +1,389 @@
+use num::{BigRational, FromPrimitive, Signed, ToPrimitive, Zero};
+
+use crate::*;
+
+//
+// Rational class
+//
+
+pub(super) fn init(globals: &mut Globals) {
+    globals.define_builtin_func(OBJECT_CLASS, "Rational", kernel_rational, -1);
+    globals.define_builtin_func(INTEGER_CLASS, "to_r", to_r, 0);
+    globals.define_builtin_func(FLOAT_CLASS, "to_r", to_r, 0);
+    globals.define_builtin_func(RATIONAL_CLASS, "to_r", to_r, 0);
+    globals.define_builtin_func(RATIONAL_CLASS, "numerator", numerator, 0);
+    globals.define_builtin_func(RATIONAL_CLASS, "denominator", denominator, 0);
+    globals.define_builtin_func(RATIONAL_CLASS, "==", eq, 1);
+    globals.define_builtin_func(RATIONAL_CLASS, "===", eq, 1);
+    globals.define_builtin_func(RATIONAL_CLASS, "<=>", cmp, 1);
+    globals.define_builtin_func(RATIONAL_CLASS, "-@", neg, 0);
+    globals.define_builtin_func(RATIONAL_CLASS, "coerce", coerce, 1);
+    globals.define_builtin_func(RATIONAL_CLASS, "abs", abs, 0);
+    globals.define_builtin_func(RATIONAL_CLASS, "magnitude", abs, 0);
+    globals.define_builtin_func(RATIONAL_CLASS, "to_f", tof, 0);
+    globals.define_builtin_func(RATIONAL_CLASS, "to_i", truncate, 0);
+    globals.define_builtin_func(RATIONAL_CLASS, "truncate", truncate, 0);
+    globals.define_builtin_func(RATIONAL_CLASS, "floor", floor, 0);
+    globals.define_builtin_func(RATIONAL_CLASS, "ceil", ceil, 0);
+    globals.define_builtin_func(RATIONAL_CLASS, "round", round, 0);
+}
+
+///
+/// Convert *val* to BigRational for Kernel#Rational.
+///
+fn expect_rational(globals: &mut Globals, val: Value) -> Option<BigRational> {
+    if let Some(r) = val.try_rational() {
+        return Some(r);
+    }
+    if let RV::Float(f) = val.unpack() {
+        if let Some(r) = BigRational::from_f64(f) {
+            return Some(r);
+        }
+        globals.set_error(MonorubyErr::range(globals.val_inspect(val)));
+        return None;
+    }
+    let msg = match val.unpack() {
+        RV::Nil | RV::Bool(_) => globals.val_inspect(val),
+        _ => val.get_real_class_name(globals),
+    };
+    globals.set_error(MonorubyErr::typeerr(format!(
+        "can't convert {} into Rational",
+        msg
+    )));
+    None
+}
+
+/// ### Kernel.#Rational
+/// - Rational(x, y = 1) -> Rational
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Kernel/m/Rational.html]
+extern "C" fn kernel_rational(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    _self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    if !(1..=2).contains(&len) {
+        globals.err_wrong_number_of_arguments_range(len, 1..=2);
+        return None;
+    }
+    let num = expect_rational(globals, arg[0])?;
+    let r = if len == 2 {
+        let den = expect_rational(globals, arg[1])?;
+        if den.is_zero() {
+            globals.err_divide_by_zero();
+            return None;
+        }
+        num / den
+    } else {
+        num
+    };
+    Some(Value::new_rational(r))
+}
+
+/// ### Integer#to_r
+/// - to_r -> Rational
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Integer/i/to_r.html]
+extern "C" fn to_r(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    if self_val.is_rational().is_some() {
+        return Some(self_val);
+    }
+    let r = expect_rational(globals, self_val)?;
+    Some(Value::new_rational(r))
+}
+
+/// ### Rational#numerator
+/// - numerator -> Integer
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Rational/i/numerator.html]
+extern "C" fn numerator(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(Value::new_bigint(self_val.as_rational().numer().clone()))
+}
+
+/// ### Rational#denominator
+/// - denominator -> Integer
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Rational/i/denominator.html]
+extern "C" fn denominator(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(Value::new_bigint(self_val.as_rational().denom().clone()))
+}
+
+/// ### Rational#==
+/// - self == other -> bool
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Rational/i/=3d=3d.html]
+extern "C" fn eq(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let lhs = self_val.as_rational();
+    let rhs = arg[0];
+    let b = if let Some(rhs) = rhs.try_rational() {
+        *lhs == rhs
+    } else if let RV::Float(rhs) = rhs.unpack() {
+        lhs.to_f64().unwrap() == rhs
+    } else {
+        vm.invoke_method(globals, IdentId::_EQ, rhs, &[self_val])?
+            .as_bool()
+    };
+    Some(Value::bool(b))
+}
+
+/// ### Rational#<=>
+/// - self <=> other -> -1 | 0 | 1 | nil
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Rational/i/=3c=3d=3e.html]
+extern "C" fn cmp(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let lhs = self_val.as_rational();
+    let rhs = arg[0];
+    let ord = if let Some(rhs) = rhs.try_rational() {
+        lhs.partial_cmp(&rhs)
+    } else if let RV::Float(rhs) = rhs.unpack() {
+        lhs.to_f64().unwrap().partial_cmp(&rhs)
+    } else {
+        return match vm.coerce(globals, self_val, rhs)? {
+            Some((lhs, rhs)) => vm.invoke_method(globals, IdentId::_CMP, lhs, &[rhs]),
+            None => Some(Value::nil()),
+        };
+    };
+    Some(match ord {
+        Some(ord) => Value::new_integer(ord as i64),
+        None => Value::nil(),
+    })
+}
+
+/// ### Rational#-@
+/// - -self -> Rational
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Numeric/i/-=40.html]
+extern "C" fn neg(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(Value::new_rational(-self_val.as_rational()))
+}
+
+/// ### Rational#coerce
+/// - coerce(other) -> [Numeric, Numeric]
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Rational/i/coerce.html]
+extern "C" fn coerce(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let other = arg[0];
+    let pair = if let Some(r) = other.try_rational() {
+        vec![Value::new_rational(r), self_val]
+    } else if let RV::Float(_) = other.unpack() {
+        let f = self_val.as_rational().to_f64().unwrap();
+        vec![other, Value::new_float(f)]
+    } else {
+        globals.err_cant_be_coerced(other, self_val);
+        return None;
+    };
+    Some(Value::new_array_from_vec(pair))
+}
+
+/// ### Rational#abs
+/// - abs -> Rational
+/// - magnitude -> Rational
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Rational/i/abs.html]
+extern "C" fn abs(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(Value::new_rational(self_val.as_rational().abs()))
+}
+
+/// ### Rational#to_f
+/// - to_f -> Float
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Rational/i/to_f.html]
+extern "C" fn tof(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(Value::new_float(self_val.as_rational().to_f64().unwrap()))
+}
+
+/// ### Rational#truncate
+/// - truncate -> Integer
+/// - to_i -> Integer
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Rational/i/truncate.html]
+extern "C" fn truncate(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(Value::new_bigint(
+        self_val.as_rational().trunc().to_integer(),
+    ))
+}
+
+/// ### Rational#floor
+/// - floor -> Integer
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Rational/i/floor.html]
+extern "C" fn floor(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(Value::new_bigint(
+        self_val.as_rational().floor().to_integer(),
+    ))
+}
+
+/// ### Rational#ceil
+/// - ceil -> Integer
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Rational/i/ceil.html]
+extern "C" fn ceil(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(Value::new_bigint(
+        self_val.as_rational().ceil().to_integer(),
+    ))
+}
+
+/// ### Rational#round
+/// - round -> Integer
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Rational/i/round.html]
+extern "C" fn round(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(Value::new_bigint(
+        self_val.as_rational().round().to_integer(),
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::tests::*;
+
+    #[test]
+    fn rational_pow() {
+        run_test(r#"(2 ** -1).inspect"#);
+        run_test(r#"[(2 ** -2).to_s, ((-3) ** -3).to_s, (1 ** -5).to_s]"#);
+        run_test(r#"((2 ** 70) ** -1).to_s"#);
+        run_test(r#"a = 0; 30.times { |i| a = 2 ** -i }; a.to_s"#);
+        run_test(
+            r#"[2 ** Rational(1, 2), Rational(4, 9) ** 0.5, (Rational(2, 3) ** 3).to_s, (Rational(2, 3) ** -2).to_s]"#,
+        );
+        run_test(r#"(4 ** Rational(3)).inspect"#);
+        run_test_error(r#"0 ** -1"#);
+        run_test_error(r#"Rational(0) ** -2"#);
+    }
+
+    #[test]
+    fn rational_arith() {
+        run_test(r#"(Rational(1, 2) + Rational(1, 3)).inspect"#);
+        run_test(
+            r#"[Rational(1, 2) - 1, 1 - Rational(1, 2), Rational(3, 4) * 2, 3 / Rational(3, 4)].map(&:to_s)"#,
+        );
+        run_test(r#"[Rational(1, 2) + 0.25, 0.25 + Rational(1, 2), Rational(1, 2) * 1.5]"#);
+        run_test(r#"[Rational(7, 2) % 1, Rational(-7, 2) % 2, 5 % Rational(3, 2)].map(&:inspect)"#);
+        run_test(
+            r#"[-Rational(1, 2), Rational(-3, 4).abs, Rational(6, 4), Rational(5), Rational(0.75), 3.to_r].map(&:inspect)"#,
+        );
+        run_test(
+            r#"[Rational(7, 2).numerator, Rational(7, 2).denominator, Rational(-6, 4).numerator, Rational(-6, 4).denominator]"#,
+        );
+        run_test(
+            r#"[Rational(1, 3).to_f, Rational(7, 2).to_i, Rational(-7, 2).truncate, Rational(-7, 2).floor, Rational(-7, 2).ceil, Rational(5, 2).round, Rational(-5, 2).round]"#,
+        );
+        run_test(
+            r#"[Rational(1, 2).to_s, Rational(1, 2).class, Rational(1, 2).is_a?(Numeric), Rational(1, 2).zero?, Rational(0).zero?]"#,
+        );
+        run_test_error(r#"Rational(1, 0)"#);
+        run_test_error(r#"Rational(1, 2) / 0"#);
+        run_test_error(r#"Rational(1, 2) + "a""#);
+        run_test_error(r#"Rational(nil)"#);
+    }
+
+    #[test]
+    fn rational_cmp() {
+        run_test(
+            r#"[Rational(1, 2) == Rational(2, 4), Rational(1, 2) == 0.5, Rational(2) == 2, 2 == Rational(4, 2), 0.5 == Rational(1, 2)]"#,
+        );
+        run_test(
+            r#"[Rational(1, 2) < 1, 1 > Rational(1, 2), Rational(1, 2) <= 0.5, 0.75 >= Rational(3, 4), Rational(1, 3) > Rational(1, 2)]"#,
+        );
+        run_test(
+            r#"[Rational(1, 2) <=> 1, 1 <=> Rational(1, 2), Rational(1, 2) <=> 0.5, Rational(1, 2) <=> "a"]"#,
+        );
+        run_test(r#"[Rational(1, 2), 0.3, 1, Rational(-1, 3)].sort.map(&:inspect)"#);
+        run_test(r#"h = { Rational(1, 2) => 1 }; h[Rational(2, 4)]"#);
+        run_test_error(r#"Rational(1, 2) < "a""#);
+    }
+}
@@ -0,0 +1,925 @@
+use crate::*;
+
+//
+// Regexp class
+//
+
+pub(super) fn init(globals: &mut Globals) {
+    globals.define_builtin_singleton_func(REGEXP_CLASS, "new", regexp_new, -1);
+    globals.define_builtin_singleton_func(REGEXP_CLASS, "compile", regexp_new, -1);
+    globals.define_builtin_singleton_func(REGEXP_CLASS, "escape", escape, 1);
+    globals.define_builtin_singleton_func(REGEXP_CLASS, "quote", escape, 1);
+    globals.define_builtin_singleton_func(REGEXP_CLASS, "last_match", last_match, -1);
+    globals.define_builtin_func(REGEXP_CLASS, "=~", match_op, 1);
+    globals.define_builtin_func(REGEXP_CLASS, "match", regexp_match, -1);
+    globals.define_builtin_func(REGEXP_CLASS, "match?", regexp_match_p, -1);
+    globals.define_builtin_func(REGEXP_CLASS, "===", teq, 1);
+    globals.define_builtin_func(REGEXP_CLASS, "==", eq, 1);
+    globals.define_builtin_func(REGEXP_CLASS, "eql?", eq, 1);
+    globals.define_builtin_func(REGEXP_CLASS, "source", source, 0);
+    globals.define_builtin_func(REGEXP_CLASS, "to_s", tos, 0);
+    globals.define_builtin_func(REGEXP_CLASS, "options", options, 0);
+    globals.define_builtin_func(REGEXP_CLASS, "casefold?", casefold, 0);
+    globals.define_builtin_func(REGEXP_CLASS, "names", names, 0);
+    for (name, flag) in [
+        ("IGNORECASE", RegexpInner::IGNORECASE),
+        ("EXTENDED", RegexpInner::EXTENDED),
+        ("MULTILINE", RegexpInner::MULTILINE),
+    ] {
+        globals.set_constant(
+            REGEXP_CLASS,
+            IdentId::get_ident_id(name),
+            Value::new_integer(flag as i64),
+        );
+    }
+
+    globals.define_builtin_func(MATCHDATA_CLASS, "[]", md_index, 1);
+    globals.define_builtin_func(MATCHDATA_CLASS, "captures", captures, 0);
+    globals.define_builtin_func(MATCHDATA_CLASS, "named_captures", named_captures, 0);
+    globals.define_builtin_func(MATCHDATA_CLASS, "names", md_names, 0);
+    globals.define_builtin_func(MATCHDATA_CLASS, "to_a", to_a, 0);
+    globals.define_builtin_func(MATCHDATA_CLASS, "to_s", md_tos, 0);
+    globals.define_builtin_func(MATCHDATA_CLASS, "pre_match", pre_match, 0);
+    globals.define_builtin_func(MATCHDATA_CLASS, "post_match", post_match, 0);
+    globals.define_builtin_func(MATCHDATA_CLASS, "begin", begin, 1);
+    globals.define_builtin_func(MATCHDATA_CLASS, "end", end, 1);
+    globals.define_builtin_func(MATCHDATA_CLASS, "size", size, 0);
+    globals.define_builtin_func(MATCHDATA_CLASS, "length", size, 0);
+    globals.define_builtin_func(MATCHDATA_CLASS, "string", string, 0);
+    globals.define_builtin_func(MATCHDATA_CLASS, "regexp", regexp, 0);
+}
+
+///
+/// Escape regexp meta characters in *s*.
+///
+pub(super) fn escape_str(s: &str) -> String {
+    let mut res = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '.' | '*' | '?' | '+' | '^' | '$' | '|' | '(' | ')' | '[' | ']' | '{' | '}' | '\\'
+            | '-' | '#' => {
+                res.push('\\');
+                res.push(c);
+            }
+            ' ' => res.push_str("\\ "),
+            '\t' => res.push_str("\\t"),
+            '\n' => res.push_str("\\n"),
+            '\r' => res.push_str("\\r"),
+            '\x0c' => res.push_str("\\f"),
+            '\x0b' => res.push_str("\\v"),
+            c => res.push(c),
+        }
+    }
+    res
+}
+
+fn compile(globals: &mut Globals, source: String, options: u32) -> Option<Value> {
+    match RegexpInner::new(source, options) {
+        Ok(regexp) => Some(Value::new_regexp(regexp)),
+        Err(msg) => {
+            globals.err_regex(msg);
+            None
+        }
+    }
+}
+
+///
+/// Convert *val* to a Regexp.
+///
+/// A String is compiled as a pattern, or as a literal string if *quote* is true.
+///
+pub(super) fn to_regexp(globals: &mut Globals, val: Value, quote: bool) -> Option<Value> {
+    if val.is_regexp().is_some() {
+        return Some(val);
+    }
+    match val.is_string() {
+        Some(s) => {
+            let s = String::from_utf8_lossy(s);
+            let source = if quote {
+                escape_str(&s)
+            } else {
+                s.into_owned()
+            };
+            compile(globals, source, 0)
+        }
+        None => {
+            globals.err_wrong_argument_type(val, REGEXP_CLASS);
+            None
+        }
+    }
+}
+
+///
+/// Get a frozen String which MatchData refers to.
+///
+pub(super) fn frozen_string(val: Value) -> Value {
+    if val.is_frozen() {
+        return val;
+    }
+    let mut s = Value::new_string_from_inner(val.as_string_inner().clone());
+    s.set_frozen();
+    s
+}
+
+///
+/// Search *regexp* in the String *string* from the byte index *start*, and set `$~` of the caller.
+///
+/// Returns MatchData, or nil if not matched.
+///
+pub(super) fn search(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    regexp: Value,
+    string: Value,
+    start: usize,
+) -> Value {
+    let res = match regexp.as_regexp().search(string.as_bytes(), start) {
+        Some(captures) => {
+            let target = frozen_string(string);
+            vm.with_temp(target, |_| {
+                Value::new_match_data(MatchDataInner::new(regexp, target, captures))
+            })
+        }
+        None => Value::nil(),
+    };
+    vm.set_last_match(globals, res);
+    res
+}
+
+///
+/// Convert the character index *pos* (negative counts from the end) into the byte index of *s*.
+///
+pub(super) fn byte_pos(s: &StringInner, pos: i64) -> Option<usize> {
+    let pos = if pos < 0 {
+        pos + s.char_length() as i64
+    } else {
+        pos
+    };
+    if pos < 0 {
+        return None;
+    }
+    s.byte_index(pos as usize)
+}
+
+///
+/// Get the String from the argument for matching.
+///
+fn expect_target(globals: &mut Globals, val: Value) -> Option<Option<Value>> {
+    if val.is_nil() {
+        return Some(None);
+    }
+    if let Some(sym) = val.is_symbol() {
+        return Some(Some(Value::new_string(IdentId::get_name(sym))));
+    }
+    if val.is_string().is_some() {
+        return Some(Some(val));
+    }
+    globals.err_no_implict_conv(val, STRING_CLASS);
+    None
+}
+
+///
+/// Match *regexp* with *target* from the character index *pos*, and set `$~`.
+///
+pub(super) fn match_at(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    regexp: Value,
+    target: Value,
+    pos: i64,
+) -> Option<Value> {
+    let target = match expect_target(globals, target)? {
+        Some(target) => target,
+        None => {
+            vm.set_last_match(globals, Value::nil());
+            return Some(Value::nil());
+        }
+    };
+    let start = match byte_pos(target.as_string_inner(), pos) {
+        Some(start) => start,
+        None => {
+            vm.set_last_match(globals, Value::nil());
+            return Some(Value::nil());
+        }
+    };
+    Some(vm.with_temp(target, |vm| search(vm, globals, regexp, target, start)))
+}
+
+///
+/// Get the character index of the match start of MatchData *md*.
+///
+pub(super) fn match_start(md: Value) -> Value {
+    match md.is_match_data() {
+        Some(md) => {
+            let (start, _) = md.pos(0).unwrap();
+            Value::new_integer(md.string().as_string_inner().char_index(start) as i64)
+        }
+        None => Value::nil(),
+    }
+}
+
+fn parse_pos(globals: &mut Globals, arg: Arg, len: usize) -> Option<i64> {
+    if !(1..=2).contains(&len) {
+        globals.err_wrong_number_of_arguments_range(len, 1..=2);
+        return None;
+    }
+    if len == 2 {
+        arg[1].expect_integer(globals)
+    } else {
+        Some(0)
+    }
+}
+
+/// ### Regexp.new
+/// - new(string, option = nil) -> Regexp
+/// - compile(string, option = nil) -> Regexp
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Regexp/s/compile.html]
+extern "C" fn regexp_new(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    _: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    if !(1..=2).contains(&len) {
+        globals.err_wrong_number_of_arguments_range(len, 1..=2);
+        return None;
+    }
+    if let Some(regexp) = arg[0].is_regexp() {
+        return Some(Value::new_regexp(regexp.clone()));
+    }
+    let source = arg[0].expect_string(globals)?;
+    let options = if len == 2 {
+        match arg[1].try_fixnum() {
+            Some(i) => i as u32,
+            None if arg[1].as_bool() => RegexpInner::IGNORECASE,
+            None => 0,
+        }
+    } else {
+        0
+    };
+    compile(globals, source, options)
+}
+
+/// ### Regexp.escape
+/// - escape(string) -> String
+/// - quote(string) -> String
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Regexp/s/escape.html]
+extern "C" fn escape(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    _: Value,
+    arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let s = match arg[0].is_symbol() {
+        Some(sym) => IdentId::get_name(sym),
+        None => arg[0].expect_string(globals)?,
+    };
+    Some(Value::new_string(escape_str(&s)))
+}
+
+/// ### Regexp.last_match
+/// - last_match -> MatchData | nil
+/// - last_match(nth) -> String | nil
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Regexp/s/last_match.html]
+extern "C" fn last_match(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    _: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    if len > 1 {
+        globals.err_wrong_number_of_arguments_range(len, 0..=1);
+        return None;
+    }
+    let md = vm.caller_last_match(globals);
+    if len == 0 || md.is_nil() {
+        return Some(md);
+    }
+    group(globals, md, arg[0])
+}
+
+/// ### Regexp#=~
+/// - self =~ string -> Integer | nil
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Regexp/i/=3d=7e.html]
+extern "C" fn match_op(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let md = match_at(vm, globals, self_val, arg[0], 0)?;
+    Some(match_start(md))
+}
+
+/// ### Regexp#match
+/// - match(str, pos = 0) -> MatchData | nil
+/// - match(str, pos = 0) {|m| ... } -> object | nil
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Regexp/i/match.html]
+extern "C" fn regexp_match(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    let pos = parse_pos(globals, arg, len)?;
+    let md = match_at(vm, globals, self_val, arg[0], pos)?;
+    match block {
+        Some(block) if !md.is_nil() => vm.invoke_block(globals, block, self_val, &[md]),
+        _ => Some(md),
+    }
+}
+
+/// ### Regexp#match?
+/// - match?(str, pos = 0) -> bool
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Regexp/i/match=3f.html]
+extern "C" fn regexp_match_p(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let pos = parse_pos(globals, arg, len)?;
+    let target = match expect_target(globals, arg[0])? {
+        Some(target) => target,
+        None => return Some(Value::bool(false)),
+    };
+    let s = target.as_string_inner();
+    let b = match byte_pos(s, pos) {
+        Some(start) => self_val.as_regexp().search(s, start).is_some(),
+        None => false,
+    };
+    Some(Value::bool(b))
+}
+
+/// ### Regexp#===
+/// - self === string -> bool
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Regexp/i/=3d=3d=3d.html]
+extern "C" fn teq(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let target = match arg[0].unpack() {
+        RV::String(_) => arg[0],
+        RV::Symbol(sym) => Value::new_string(IdentId::get_name(sym)),
+        _ => return Some(Value::bool(false)),
+    };
+    let md = vm.with_temp(target, |vm| search(vm, globals, self_val, target, 0));
+    Some(Value::bool(!md.is_nil()))
+}
+
+/// ### Regexp#==
+/// - self == other -> bool
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Regexp/i/=3d=3d.html]
+extern "C" fn eq(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let b = match arg[0].is_regexp() {
+        Some(other) => self_val.as_regexp() == other,
+        None => false,
+    };
+    Some(Value::bool(b))
+}
+
+/// ### Regexp#source
+/// - source -> String
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Regexp/i/source.html]
+extern "C" fn source(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(Value::new_string_from_str(self_val.as_regexp().source()))
+}
+
+/// ### Regexp#to_s
+/// - to_s -> String
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Regexp/i/to_s.html]
+extern "C" fn tos(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(Value::new_string(self_val.as_regexp().tos()))
+}
+
+/// ### Regexp#options
+/// - options -> Integer
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Regexp/i/options.html]
+extern "C" fn options(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(Value::new_integer(self_val.as_regexp().options() as i64))
+}
+
+/// ### Regexp#casefold?
+/// - casefold? -> bool
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Regexp/i/casefold=3f.html]
+extern "C" fn casefold(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let b = self_val.as_regexp().options() & RegexpInner::IGNORECASE != 0;
+    Some(Value::bool(b))
+}
+
+fn names_of(regexp: &RegexpInner) -> Value {
+    let v = regexp
+        .regex()
+        .names()
+        .iter()
+        .map(|(name, _)| Value::new_string_from_str(name))
+        .collect();
+    Value::new_array_from_vec(v)
+}
+
+/// ### Regexp#names
+/// - names -> [String]
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Regexp/i/names.html]
+extern "C" fn names(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(names_of(self_val.as_regexp()))
+}
+
+//
+// MatchData class
+//
+
+pub(super) fn group_value(md: &MatchDataInner, index: usize) -> Value {
+    match md.group(index) {
+        Some(bytes) => Value::new_string_from_slice(bytes),
+        None => Value::nil(),
+    }
+}
+
+///
+/// Get the group index of MatchData *md* for *idx*. (an Integer or a name)
+///
+/// Returns None with an error for an undefined name, and Some(None) for an index out of range.
+///
+fn group_index(globals: &mut Globals, md: &MatchDataInner, idx: Value) -> Option<Option<usize>> {
+    let name = match idx.unpack() {
+        RV::Integer(i) => {
+            let i = if i < 0 { i + md.len() as i64 } else { i };
+            return Some(if 0 <= i && i < md.len() as i64 {
+                Some(i as usize)
+            } else {
+                None
+            });
+        }
+        RV::Symbol(sym) => IdentId::get_name(sym),
+        RV::String(s) => String::from_utf8_lossy(s).into_owned(),
+        _ => {
+            globals.err_no_implict_conv(idx, INTEGER_CLASS);
+            return None;
+        }
+    };
+    match md.name_to_index(&name) {
+        Some(i) => Some(Some(i)),
+        None => {
+            globals.err_index(format!("undefined group name reference: {}", name));
+            None
+        }
+    }
+}
+
+fn group(globals: &mut Globals, md: Value, idx: Value) -> Option<Value> {
+    let md = md.as_match_data();
+    Some(match group_index(globals, md, idx)? {
+        Some(i) => group_value(md, i),
+        None => Value::nil(),
+    })
+}
+
+///
+/// Make a new Array of the groups from *start* of MatchData *md*.
+///
+pub(super) fn collect_groups(vm: &mut Executor, md: Value, start: usize) -> Value {
+    let mut res = Value::new_array_from_vec(vec![]);
+    vm.with_temp(res, |_| {
+        let md = md.as_match_data();
+        for i in start..md.len() {
+            let v = group_value(md, i);
+            res.as_array_mut().push(v);
+        }
+    });
+    res
+}
+
+/// ### MatchData#[]
+/// - self[n] -> String | nil
+/// - self[name] -> String | nil
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/MatchData/i/=5b=5d.html]
+extern "C" fn md_index(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    group(globals, self_val, arg[0])
+}
+
+/// ### MatchData#captures
+/// - captures -> [String]
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/MatchData/i/captures.html]
+extern "C" fn captures(
+    vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(collect_groups(vm, self_val, 1))
+}
+
+/// ### MatchData#to_a
+/// - to_a -> [String]
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/MatchData/i/to_a.html]
+extern "C" fn to_a(
+    vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(collect_groups(vm, self_val, 0))
+}
+
+/// ### MatchData#named_captures
+/// - named_captures -> Hash
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/MatchData/i/named_captures.html]
+extern "C" fn named_captures(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let mut hash = Value::new_hash(HashInner::new());
+    vm.with_temp(hash, |vm| {
+        let md = self_val.as_match_data();
+        for (name, _) in md.regexp().as_regexp().regex().names() {
+            let key = Value::new_string_from_str(name);
+            vm.with_temp(key, |vm| {
+                let val = group_value(md, md.name_to_index(name).unwrap());
+                vm.with_temp(val, |vm| hash.as_hash_mut().insert(vm, globals, key, val))
+            })?;
+        }
+        Some(hash)
+    })
+}
+
+/// ### MatchData#names
+/// - names -> [String]
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/MatchData/i/names.html]
+extern "C" fn md_names(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(names_of(self_val.as_match_data().regexp().as_regexp()))
+}
+
+/// ### MatchData#to_s
+/// - to_s -> String
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/MatchData/i/to_s.html]
+extern "C" fn md_tos(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(group_value(self_val.as_match_data(), 0))
+}
+
+/// ### MatchData#pre_match
+/// - pre_match -> String
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/MatchData/i/pre_match.html]
+extern "C" fn pre_match(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(Value::new_string_from_slice(
+        self_val.as_match_data().pre_match(),
+    ))
+}
+
+/// ### MatchData#post_match
+/// - post_match -> String
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/MatchData/i/post_match.html]
+extern "C" fn post_match(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(Value::new_string_from_slice(
+        self_val.as_match_data().post_match(),
+    ))
+}
+
+///
+/// Get the character offset of the start (or end, if *is_end* is true) of the group *idx*.
+///
+fn offset(globals: &mut Globals, md: Value, idx: Value, is_end: bool) -> Option<Value> {
+    let md = md.as_match_data();
+    let i = match group_index(globals, md, idx)? {
+        Some(i) => i,
+        None => {
+            globals.err_index(format!("index {} out of matches", globals.val_tos(idx)));
+            return None;
+        }
+    };
+    Some(match md.pos(i) {
+        Some((start, end)) => {
+            let pos = if is_end { end } else { start };
+            Value::new_integer(md.string().as_string_inner().char_index(pos) as i64)
+        }
+        None => Value::nil(),
+    })
+}
+
+/// ### MatchData#begin
+/// - begin(n) -> Integer | nil
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/MatchData/i/begin.html]
+extern "C" fn begin(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    offset(globals, self_val, arg[0], false)
+}
+
+/// ### MatchData#end
+/// - end(n) -> Integer | nil
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/MatchData/i/end.html]
+extern "C" fn end(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    offset(globals, self_val, arg[0], true)
+}
+
+/// ### MatchData#size
+/// - size -> Integer
+/// - length -> Integer
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/MatchData/i/length.html]
+extern "C" fn size(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(Value::new_integer(self_val.as_match_data().len() as i64))
+}
+
+/// ### MatchData#string
+/// - string -> String
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/MatchData/i/string.html]
+extern "C" fn string(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(self_val.as_match_data().string())
+}
+
+/// ### MatchData#regexp
+/// - regexp -> Regexp
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/MatchData/i/regexp.html]
+extern "C" fn regexp(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(self_val.as_match_data().regexp())
+}
+
+#[cfg(test)]
+mod test {
+    use super::tests::*;
+
+    #[test]
+    fn regexp_literal() {
+        run_test(r#"/ab+c/"#);
+        run_test(r#"/ab+c/i"#);
+        run_test(r#"/a\/b/m"#);
+        run_test(r#"x = "b+"; /a#{x}c/"#);
+        run_test(r#"/ab+c/.source"#);
+        run_test(r#"/ab+c/i.to_s"#);
+        run_test(r#"/ab+c/x.options"#);
+        run_test(r#"Regexp.new("a.c", Regexp::IGNORECASE)"#);
+        run_test(r#"Regexp.new("a.c") == /a.c/"#);
+        run_test(r#"Regexp.escape("a.b*c (d)")"#);
+        run_test_error(r#"Regexp.new("(ab")"#);
+        run_test_error(r#"Regexp.new("*a")"#);
+        run_test_error(r#"Regexp.new("[ab")"#);
+    }
+
+    #[test]
+    fn regexp_match() {
+        run_test(r#""hello world" =~ /o w/"#);
+        run_test(r#"/o w/ =~ "hello world""#);
+        run_test(r#"/xyz/ =~ "hello world""#);
+        run_test(r#"/(\d+)-(\d+)/.match("tel: 03-1234")"#);
+        run_test(r#"/(\d+)-(\d+)/.match("tel: 03-1234").captures"#);
+        run_test(r#"/(\d+)-(\d+)/.match("tel: 03-1234", 8)"#);
+        run_test(r#"/(\d+)-(\d+)/.match?("tel: 03-1234")"#);
+        run_test(r#""tel: 03-1234".match?(/\d-\d/)"#);
+        run_test(r#""tel: 03-1234".match("(\\d)-")[1]"#);
+        run_test(r#"/(?<year>\d+)-(?<month>\d+)/.match("at 2022-10!").named_captures"#);
+        run_test(
+            r#"m = /(?<year>\d+)-(?<month>\d+)/.match("at 2022-10!"); [m[:year], m["month"], m[0], m[-1]]"#,
+        );
+        run_test(
+            r#"m = /(\d+)(x)?/.match("at 2022-10!"); [m.pre_match, m.post_match, m.to_a, m.begin(0), m.end(1), m.size]"#,
+        );
+        run_test(r#"/(\d+)(x)?/.match("at 2022-10!")"#);
+        run_test(r#"case "hello" when /^h/ then 1 else 2 end"#);
+        run_test(r#"case "hello" when /^x/ then 1 else 2 end"#);
+    }
+
+    #[test]
+    fn regexp_syntax() {
+        run_test(r#"/a.c/ =~ "a\nc""#);
+        run_test(r#"/a.c/m =~ "a\nc""#);
+        run_test(r#"/^b/ =~ "a\nb""#);
+        run_test(r#"/\Ab/ =~ "a\nb""#);
+        run_test(r#"/a$/ =~ "a\nb""#);
+        run_test(r#"/b\z/ =~ "a\nb""#);
+        run_test(r#"/\bcat\b/ =~ "concat cat""#);
+        run_test(r#"/HeLLo/i =~ "say hello""#);
+        run_test(r#"/[a-c]+/.match("xxabcbaxx")[0]"#);
+        run_test(r#"/[^a-c\s]+/.match("ab cd")[0]"#);
+        run_test(r#"/[[:upper:]][[:lower:]]+/.match("hello World")[0]"#);
+        run_test(r#"/a{2,3}/.match("aaaa")[0]"#);
+        run_test(r#"/a{2,3}?/.match("aaaa")[0]"#);
+        run_test(r#"/<.+>/.match("<a><b>")[0]"#);
+        run_test(r#"/<.+?>/.match("<a><b>")[0]"#);
+        run_test(r#"/(a|ab)(c|bcd)(d*)/.match("abcd").to_a"#);
+        run_test(r#"/(\w)\1/.match("hello")[0]"#);
+        run_test(r#"/(?<c>\w)\k<c>/.match("bookkeeper").begin(0)"#);
+        run_test(r#"/foo(?=bar)/.match("foobaz foobar").begin(0)"#);
+        run_test(r#"/foo(?!bar)/.match("foobar foobaz").begin(0)"#);
+        run_test(r#"/(?<=\$)\d+/.match("cost: $42")[0]"#);
+        run_test(r#"/(?<!\$)\b\d+/.match("$42 17")[0]"#);
+        run_test(r#"/(?>a+)b/ =~ "aaab""#);
+        run_test(r#"/(?>a+)a/ =~ "aaaa""#);
+        run_test(r#"/a(?i)bc/ =~ "xaBC""#);
+        run_test(r#"/a(?i:b)c/ =~ "aBC""#);
+        run_test(r#"/(a*)*b/ =~ "aaab""#);
+        run_test(r#"/あ(.)う/.match("いあいうえ")[1]"#);
+        run_test(r#"/あ+/.match("ああい")[0]"#);
+        run_test(
+            r#"
+        /
+          (\d+)  # digits
+          \s*    # spaces
+          (\w+)  # word
+        /x.match("12  apples").captures"#,
+        );
+    }
+
+    #[test]
+    fn special_vars() {
+        run_test(
+            r#"
+        "2022-10-17" =~ /(\d+)-(\d+)-(\d+)/
+        [$~[0], $&, $1, $2, $3, $4]
+        "#,
+        );
+        run_test(
+            r#"
+        "abc" =~ /x/
+        [$~, $1]
+        "#,
+        );
+        run_test(
+            r#"
+        def f(s)
+          s =~ /(\w+)@(\w+)/
+          [$1, $2]
+        end
+        $1
+        [f("foo@bar"), f("x@y"), $1]
+        "#,
+        );
+        run_test(
+            r#"
+        res = []
+        ["a1", "b2", "c"].each do |s|
+          if s =~ /([a-z])(\d)/
+            res << $2 + $1
+          else
+            res << $~
+          end
+        end
+        res
+        "#,
+        );
+        run_test(
+            r#"
+        "key = value" =~ /(\w+) = (\w+)/
+        Regexp.last_match(2)
+        "#,
+        );
+    }
+}
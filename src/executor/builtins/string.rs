@@ -1,5 +1,6 @@
 use num::BigInt;
 
+use super::regexp::{byte_pos, frozen_string, match_at, match_start, to_regexp};
 use crate::*;
 
 //
@@ -8,7 +9,48 @@ use crate::*;
 
 pub(super) fn init(globals: &mut Globals) {
     globals.define_builtin_func(STRING_CLASS, "+", add, 1);
+    globals.define_builtin_func(STRING_CLASS, "*", mul, 1);
     globals.define_builtin_func(STRING_CLASS, "%", rem, 1);
+    globals.define_builtin_func(STRING_CLASS, "==", eq, 1);
+    globals.define_builtin_func(STRING_CLASS, "<=>", cmp, 1);
+    globals.define_builtin_func(STRING_CLASS, "<<", shl, 1);
+    globals.define_builtin_func(STRING_CLASS, "concat", concat, -1);
+    globals.define_builtin_func(STRING_CLASS, "[]", index, -1);
+    globals.define_builtin_func(STRING_CLASS, "slice", index, -1);
+    globals.define_builtin_func(STRING_CLASS, "length", length, 0);
+    globals.define_builtin_func(STRING_CLASS, "size", length, 0);
+    globals.define_builtin_func(STRING_CLASS, "bytesize", bytesize, 0);
+    globals.define_builtin_func(STRING_CLASS, "upcase", upcase, 0);
+    globals.define_builtin_func(STRING_CLASS, "downcase", downcase, 0);
+    globals.define_builtin_func(STRING_CLASS, "capitalize", capitalize, 0);
+    globals.define_builtin_func(STRING_CLASS, "strip", strip, 0);
+    globals.define_builtin_func(STRING_CLASS, "lstrip", lstrip, 0);
+    globals.define_builtin_func(STRING_CLASS, "rstrip", rstrip, 0);
+    globals.define_builtin_func(STRING_CLASS, "split", split, -1);
+    globals.define_builtin_func(STRING_CLASS, "chars", chars, 0);
+    globals.define_builtin_func(STRING_CLASS, "bytes", bytes, 0);
+    globals.define_builtin_func(STRING_CLASS, "each_char", each_char, 0);
+    globals.define_builtin_func(STRING_CLASS, "start_with?", start_with, -1);
+    globals.define_builtin_func(STRING_CLASS, "end_with?", end_with, -1);
+    globals.define_builtin_func(STRING_CLASS, "include?", include, 1);
+    globals.define_builtin_func(STRING_CLASS, "index", index_of, -1);
+    globals.define_builtin_func(STRING_CLASS, "sub", sub, -1);
+    globals.define_builtin_func(STRING_CLASS, "gsub", gsub, -1);
+    globals.define_builtin_func(STRING_CLASS, "=~", match_op, 1);
+    globals.define_builtin_func(STRING_CLASS, "match", string_match, -1);
+    globals.define_builtin_func(STRING_CLASS, "match?", string_match_p, -1);
+    globals.define_builtin_func(STRING_CLASS, "scan", scan, 1);
+    globals.define_builtin_func(STRING_CLASS, "to_i", to_i, 0);
+    globals.define_builtin_func(STRING_CLASS, "to_f", to_f, 0);
+    globals.define_builtin_func(STRING_CLASS, "to_sym", to_sym, 0);
+    globals.define_builtin_func(STRING_CLASS, "intern", to_sym, 0);
+    globals.define_builtin_func(STRING_CLASS, "reverse", reverse, 0);
+    globals.define_builtin_func(STRING_CLASS, "ljust", ljust, -1);
+    globals.define_builtin_func(STRING_CLASS, "rjust", rjust, -1);
+    globals.define_builtin_func(STRING_CLASS, "center", center, -1);
+    globals.define_builtin_func(STRING_CLASS, "freeze", freeze, 0);
+    globals.define_builtin_func(STRING_CLASS, "frozen?", frozen, 0);
+    globals.define_builtin_func(STRING_CLASS, "dup", dup, 0);
 }
 
 /// ### String#+
@@ -16,16 +58,1380 @@ pub(super) fn init(globals: &mut Globals) {
 ///
 /// [https://docs.ruby-lang.org/ja/latest/method/String/i/=2b.html]
 extern "C" fn add(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let rhs = arg[0].expect_string_inner(globals)?;
+    let lhs = self_val.as_string_inner();
+    let mut res = StringInner::with_capacity(lhs.len() + rhs.len());
+    res.extend_from_slice(lhs);
+    res.extend_from_slice(rhs);
+    Some(Value::new_string_from_inner(res))
+}
+
+/// ### String#*
+/// - self * times -> String
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/String/i/=2a.html]
+extern "C" fn mul(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let times = arg[0].expect_integer(globals)?;
+    if times < 0 {
+        globals.err_argument("negative argument");
+        return None;
+    }
+    let lhs = self_val.as_string_inner();
+    let capacity = match lhs.len().checked_mul(times as usize) {
+        Some(capacity) => capacity,
+        None => {
+            globals.err_argument("argument too big");
+            return None;
+        }
+    };
+    let mut res = StringInner::with_capacity(capacity);
+    for _ in 0..times {
+        res.extend_from_slice(lhs);
+    }
+    Some(Value::new_string_from_inner(res))
+}
+
+/// ### String#==
+/// - self == other -> bool
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/String/i/=3d=3d.html]
+extern "C" fn eq(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let b = match arg[0].is_string() {
+        Some(rhs) => **self_val.as_string_inner() == **rhs,
+        None => false,
+    };
+    Some(Value::bool(b))
+}
+
+/// ### String#<=>
+/// - self <=> other -> -1 | 0 | 1 | nil
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/String/i/=3c=3d=3e.html]
+extern "C" fn cmp(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let res = match arg[0].is_string() {
+        Some(rhs) => Value::new_integer((**self_val.as_string_inner()).cmp(&**rhs) as i64),
+        None => Value::nil(),
+    };
+    Some(res)
+}
+
+fn check_frozen(globals: &mut Globals, val: Value) -> Option<()> {
+    if val.is_frozen() {
+        globals.err_cant_modify_frozen(val);
+        return None;
+    }
+    Some(())
+}
+
+///
+/// Append *other* to *self_val*.
+///
+/// An Integer *other* is regarded as a codepoint.
+///
+fn append(globals: &mut Globals, mut self_val: Value, other: Value) -> Option<()> {
+    check_frozen(globals, self_val)?;
+    if let Some(i) = other.try_fixnum() {
+        match u32::try_from(i).ok().and_then(char::from_u32) {
+            Some(ch) => self_val.as_string_inner_mut().push_char(ch),
+            None => {
+                globals.err_char_out_of_range(other);
+                return None;
+            }
+        }
+    } else {
+        // *other* may be the receiver itself.
+        let other = other.expect_string_inner(globals)?.clone();
+        self_val.as_string_inner_mut().extend_from_slice(&other);
+    }
+    Some(())
+}
+
+/// ### String#<<
+/// - self << other -> self
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/String/i/=3c=3c.html]
+extern "C" fn shl(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    append(globals, self_val, arg[0])?;
+    Some(self_val)
+}
+
+/// ### String#concat
+/// - concat(*arguments) -> self
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/String/i/concat.html]
+extern "C" fn concat(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    check_frozen(globals, self_val)?;
+    // arguments are evaluated before the receiver is modified.
+    let args: Vec<_> = (0..len)
+        .map(|i| match arg[i].is_string() {
+            Some(s) => Value::new_string_from_inner(s.clone()),
+            None => arg[i],
+        })
+        .collect();
+    for other in args {
+        append(globals, self_val, other)?;
+    }
+    Some(self_val)
+}
+
+/// ### String#[]
+/// - self[nth] -> String | nil
+/// - self[nth, len] -> String | nil
+/// - self[substr] -> String | nil
+/// - self[range] -> String | nil
+/// - slice(nth) -> String | nil
+/// - slice(nth, len) -> String | nil
+/// - slice(substr) -> String | nil
+/// - slice(range) -> String | nil
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/String/i/=5b=5d.html]
+extern "C" fn index(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let s = self_val.as_string_inner();
+    let res = match len {
+        1 => {
+            let idx = &arg[0];
+            if let Some(i) = idx.try_fixnum() {
+                match s.substr(i, 1) {
+                    Some(ch) if !ch.is_empty() => Some(ch),
+                    _ => None,
+                }
+            } else if let Some(range) = idx.is_range() {
+                let char_len = s.char_length() as i64;
+                let start = range.start.expect_integer(globals)?;
+                let end = if range.end.is_nil() {
+                    char_len
+                } else {
+                    let end = range.end.expect_integer(globals)?;
+                    let end = if end < 0 { end + char_len } else { end };
+                    if range.exclude_end() {
+                        end
+                    } else {
+                        end + 1
+                    }
+                };
+                let start_pos = if start < 0 { start + char_len } else { start };
+                s.substr(start, std::cmp::max(end - start_pos, 0))
+            } else if let Some(pat) = idx.is_string() {
+                s.find(pat, 0).map(|_| &**pat)
+            } else {
+                globals.err_no_implict_conv(*idx, INTEGER_CLASS);
+                return None;
+            }
+        }
+        2 => {
+            let start = arg[0].expect_integer(globals)?;
+            let len = arg[1].expect_integer(globals)?;
+            s.substr(start, len)
+        }
+        _ => {
+            globals.err_wrong_number_of_arguments_range(len, 1..=2);
+            return None;
+        }
+    };
+    Some(match res {
+        Some(slice) => Value::new_string_from_slice(slice),
+        None => Value::nil(),
+    })
+}
+
+/// ### String#length
+/// - length -> Integer
+/// - size -> Integer
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/String/i/length.html]
+extern "C" fn length(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let len = self_val.as_string_inner().char_length();
+    Some(Value::new_integer(len as i64))
+}
+
+/// ### String#bytesize
+/// - bytesize -> Integer
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/String/i/bytesize.html]
+extern "C" fn bytesize(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let len = self_val.as_string_inner().len();
+    Some(Value::new_integer(len as i64))
+}
+
+///
+/// Convert each character of *s* with *f*, which takes the character index and the character.
+///
+/// Bytes which are not a part of a valid UTF-8 sequence are copied as they are.
+///
+fn convert_chars(s: &StringInner, mut f: impl FnMut(usize, char, &mut StringInner)) -> StringInner {
+    let mut res = StringInner::with_capacity(s.len());
+    for (i, ch) in s.chars().enumerate() {
+        match std::str::from_utf8(ch) {
+            Ok(ch) => f(i, ch.chars().next().unwrap(), &mut res),
+            Err(_) => res.extend_from_slice(ch),
+        }
+    }
+    res
+}
+
+/// ### String#upcase
+/// - upcase -> String
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/String/i/upcase.html]
+extern "C" fn upcase(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let res = convert_chars(self_val.as_string_inner(), |_, ch, res| {
+        ch.to_uppercase().for_each(|ch| res.push_char(ch))
+    });
+    Some(Value::new_string_from_inner(res))
+}
+
+/// ### String#downcase
+/// - downcase -> String
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/String/i/downcase.html]
+extern "C" fn downcase(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let res = convert_chars(self_val.as_string_inner(), |_, ch, res| {
+        ch.to_lowercase().for_each(|ch| res.push_char(ch))
+    });
+    Some(Value::new_string_from_inner(res))
+}
+
+/// ### String#capitalize
+/// - capitalize -> String
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/String/i/capitalize.html]
+extern "C" fn capitalize(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let res = convert_chars(self_val.as_string_inner(), |i, ch, res| {
+        if i == 0 {
+            ch.to_uppercase().for_each(|ch| res.push_char(ch))
+        } else {
+            ch.to_lowercase().for_each(|ch| res.push_char(ch))
+        }
+    });
+    Some(Value::new_string_from_inner(res))
+}
+
+fn is_space(b: u8) -> bool {
+    matches!(b, b' ' | b'\t' | b'\n' | b'\x0b' | b'\x0c' | b'\r')
+}
+
+fn lstrip_bytes(s: &[u8]) -> &[u8] {
+    let start = s.iter().position(|b| !is_space(*b)).unwrap_or(s.len());
+    &s[start..]
+}
+
+fn rstrip_bytes(s: &[u8]) -> &[u8] {
+    let end = s
+        .iter()
+        .rposition(|b| !is_space(*b) && *b != 0)
+        .map_or(0, |pos| pos + 1);
+    &s[..end]
+}
+
+/// ### String#strip
+/// - strip -> String
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/String/i/strip.html]
+extern "C" fn strip(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let s = rstrip_bytes(lstrip_bytes(self_val.as_string_inner()));
+    Some(Value::new_string_from_slice(s))
+}
+
+/// ### String#lstrip
+/// - lstrip -> String
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/String/i/lstrip.html]
+extern "C" fn lstrip(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let s = lstrip_bytes(self_val.as_string_inner());
+    Some(Value::new_string_from_slice(s))
+}
+
+/// ### String#rstrip
+/// - rstrip -> String
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/String/i/rstrip.html]
+extern "C" fn rstrip(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let s = rstrip_bytes(self_val.as_string_inner());
+    Some(Value::new_string_from_slice(s))
+}
+
+///
+/// Make a new Array of Strings from *slices*.
+///
+fn collect_strings<'a>(vm: &mut Executor, slices: impl Iterator<Item = &'a [u8]>) -> Value {
+    let mut res = Value::new_array_from_vec(vec![]);
+    vm.with_temp(res, |_| {
+        for slice in slices {
+            let s = Value::new_string_from_slice(slice);
+            res.as_array_mut().push(s);
+        }
+    });
+    res
+}
+
+///
+/// Split *s* by *sep*.
+///
+/// If *sep* is None, *s* is split by runs of whitespaces, and leading whitespaces are ignored.
+///
+fn split_bytes<'a>(s: &'a StringInner, sep: Option<&[u8]>, limit: i64) -> Vec<&'a [u8]> {
+    if s.is_empty() {
+        return vec![];
+    }
+    if limit == 1 {
+        return vec![s];
+    }
+    let mut res = vec![];
+    match sep {
+        None => {
+            let mut rest: &[u8] = s;
+            loop {
+                let field = lstrip_bytes(rest);
+                if field.is_empty() {
+                    if limit < 0 && !res.is_empty() && !rest.is_empty() {
+                        res.push(field);
+                    }
+                    break;
+                }
+                if limit > 0 && res.len() as i64 == limit - 1 {
+                    res.push(field);
+                    break;
+                }
+                let end = field
+                    .iter()
+                    .position(|b| is_space(*b))
+                    .unwrap_or(field.len());
+                res.push(&field[..end]);
+                rest = &field[end..];
+            }
+        }
+        Some(sep) if sep.is_empty() => {
+            let mut pos = 0;
+            for ch in s.chars() {
+                if limit > 0 && res.len() as i64 == limit - 1 {
+                    res.push(&s[pos..]);
+                    break;
+                }
+                res.push(ch);
+                pos += ch.len();
+            }
+        }
+        Some(sep) => {
+            let mut start = 0;
+            loop {
+                if limit > 0 && res.len() as i64 == limit - 1 {
+                    res.push(&s[start..]);
+                    break;
+                }
+                match s.find(sep, start) {
+                    Some(pos) => {
+                        res.push(&s[start..pos]);
+                        start = pos + sep.len();
+                    }
+                    None => {
+                        res.push(&s[start..]);
+                        break;
+                    }
+                }
+            }
+        }
+    }
+    if limit == 0 {
+        while res.last().map_or(false, |field| field.is_empty()) {
+            res.pop();
+        }
+    }
+    res
+}
+
+///
+/// Split *s* by the Regexp *re*.
+///
+/// Captured groups in *re* are also included in the result.
+///
+fn split_regexp<'a>(re: &RegexpInner, s: &'a StringInner, limit: i64) -> Vec<&'a [u8]> {
+    if s.is_empty() {
+        return vec![];
+    }
+    if limit == 1 {
+        return vec![s];
+    }
+    let mut res = vec![];
+    // the start of the current field.
+    let mut beg = 0;
+    // the position to start searching.
+    let mut start = 0;
+    let mut last_null = false;
+    let mut count = 1;
+    while let Some(captures) = re.search(s, start) {
+        let (match_start, match_end) = captures[0].unwrap();
+        if start == match_start && match_start == match_end {
+            if last_null {
+                let ch_len = s.char_at(beg).map_or(1, |ch| ch.len());
+                res.push(&s[beg..beg + ch_len]);
+                beg = start;
+            } else {
+                // an empty match at the field start: retry at the next character.
+                start += s.char_at(start).map_or(1, |ch| ch.len());
+                last_null = true;
+                continue;
+            }
+        } else {
+            res.push(&s[beg..match_start]);
+            beg = match_end;
+            start = match_end;
+        }
+        last_null = false;
+        for (start, end) in captures.iter().skip(1).flatten() {
+            res.push(&s[*start..*end]);
+        }
+        if limit > 0 {
+            count += 1;
+            if limit <= count {
+                break;
+            }
+        }
+    }
+    if limit != 0 || s.len() > beg {
+        res.push(&s[beg..]);
+    }
+    if limit == 0 {
+        while res.last().map_or(false, |field| field.is_empty()) {
+            res.pop();
+        }
+    }
+    res
+}
+
+/// ### String#split
+/// - split(sep = nil, limit = 0) -> [String]
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/String/i/split.html]
+///
+/// TODO: Support block.
+extern "C" fn split(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    if len > 2 {
+        globals.err_wrong_number_of_arguments_range(len, 0..=2);
+        return None;
+    }
+    let limit = if len == 2 {
+        arg[1].expect_integer(globals)?
+    } else {
+        0
+    };
+    if len != 0 && arg[0].is_regexp().is_some() {
+        let fields = split_regexp(arg[0].as_regexp(), self_val.as_string_inner(), limit);
+        return Some(collect_strings(vm, fields.into_iter()));
+    }
+    let sep = if len == 0 || arg[0].is_nil() {
+        None
+    } else {
+        match arg[0].expect_string_inner(globals)? {
+            sep if **sep == *b" " => None,
+            sep => Some(&**sep),
+        }
+    };
+    let fields = split_bytes(self_val.as_string_inner(), sep, limit);
+    Some(collect_strings(vm, fields.into_iter()))
+}
+
+/// ### String#chars
+/// - chars -> [String]
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/String/i/chars.html]
+extern "C" fn chars(
+    vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(collect_strings(vm, self_val.as_string_inner().chars()))
+}
+
+/// ### String#bytes
+/// - bytes -> [Integer]
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/String/i/bytes.html]
+extern "C" fn bytes(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let v = self_val
+        .as_string_inner()
+        .iter()
+        .map(|b| Value::new_integer(*b as i64))
+        .collect();
+    Some(Value::new_array_from_vec(v))
+}
+
+/// ### String#each_char
+/// - each_char {|cstr| block } -> self
+/// - each_char -> Enumerator
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/String/i/each_char.html]
+extern "C" fn each_char(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    let block = match block {
+        Some(block) => block,
+        None => return Some(Value::new_enumerator(self_val, "each_char", vec![])),
+    };
+    let mut pos = 0;
+    // the receiver may be modified in the block, so we re-fetch the character at each iteration.
+    while let Some(ch) = self_val.as_string_inner().char_at(pos) {
+        pos += ch.len();
+        let ch = Value::new_string_from_slice(ch);
+        vm.invoke_block(globals, block, self_val, &[ch])?;
+    }
+    Some(self_val)
+}
+
+/// ### String#start_with?
+/// - start_with?(*prefixes) -> bool
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/String/i/start_with=3f.html]
+extern "C" fn start_with(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let s = self_val.as_string_inner();
+    for i in 0..len {
+        if s.starts_with(arg[i].expect_string_inner(globals)?) {
+            return Some(Value::bool(true));
+        }
+    }
+    Some(Value::bool(false))
+}
+
+/// ### String#end_with?
+/// - end_with?(*strs) -> bool
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/String/i/end_with=3f.html]
+extern "C" fn end_with(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let s = self_val.as_string_inner();
+    for i in 0..len {
+        if s.ends_with(arg[i].expect_string_inner(globals)?) {
+            return Some(Value::bool(true));
+        }
+    }
+    Some(Value::bool(false))
+}
+
+/// ### String#include?
+/// - include?(substr) -> bool
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/String/i/include=3f.html]
+extern "C" fn include(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let pat = arg[0].expect_string_inner(globals)?;
+    let b = self_val.as_string_inner().find(pat, 0).is_some();
+    Some(Value::bool(b))
+}
+
+/// ### String#index
+/// - index(pattern, pos = 0) -> Integer | nil
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/String/i/index.html]
+extern "C" fn index_of(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    if !(1..=2).contains(&len) {
+        globals.err_wrong_number_of_arguments_range(len, 1..=2);
+        return None;
+    }
+    let pos = if len == 2 {
+        arg[1].expect_integer(globals)?
+    } else {
+        0
+    };
+    if arg[0].is_regexp().is_some() {
+        let md = match_at(vm, globals, arg[0], self_val, pos)?;
+        return Some(match_start(md));
+    }
+    let pat = arg[0].expect_string_inner(globals)?;
+    let s = self_val.as_string_inner();
+    let char_len = s.char_length() as i64;
+    let pos = if pos < 0 { pos + char_len } else { pos };
+    if pos < 0 || pos > char_len {
+        return Some(Value::nil());
+    }
+    let byte_pos = s.byte_index(pos as usize).unwrap();
+    Some(match s.find(pat, byte_pos) {
+        Some(i) => Value::new_integer(s.char_index(i) as i64),
+        None => Value::nil(),
+    })
+}
+
+///
+/// Expand the replacement string *rep* for the match *captures* of *re* in *s* into *res*.
+///
+/// `\0` and `\&` are replaced with the matched string, `\1`..`\9` and `\k<name>` with the groups,
+/// `` \` `` and `\'` with the pre- and post-match, and `\\` with a backslash.
+///
+fn expand_replacement(
+    res: &mut StringInner,
+    rep: &[u8],
+    s: &[u8],
+    captures: &Captures,
+    re: &RegexpInner,
+) {
+    let mut push_group = |res: &mut StringInner, i: usize| {
+        if let Some(Some((start, end))) = captures.get(i) {
+            res.extend_from_slice(&s[*start..*end]);
+        }
+    };
+    let (match_start, match_end) = captures[0].unwrap();
+    let mut i = 0;
+    while i < rep.len() {
+        let b = rep[i];
+        i += 1;
+        if b != b'\\' {
+            res.push(b);
+            continue;
+        }
+        match rep.get(i) {
+            Some(b'0') | Some(b'&') => push_group(res, 0),
+            Some(b @ b'1'..=b'9') => push_group(res, (b - b'0') as usize),
+            Some(b'`') => res.extend_from_slice(&s[..match_start]),
+            Some(b'\'') => res.extend_from_slice(&s[match_end..]),
+            Some(b'\\') => res.push(b'\\'),
+            Some(b'k') if rep.get(i + 1) == Some(&b'<') => {
+                let name_end = match rep[i + 2..].iter().position(|b| *b == b'>') {
+                    Some(len) => i + 2 + len,
+                    None => {
+                        res.push(b'\\');
+                        continue;
+                    }
+                };
+                let name = &rep[i + 2..name_end];
+                if let Some((_, indices)) = re
+                    .regex()
+                    .names()
+                    .iter()
+                    .find(|(n, _)| n.as_bytes() == name)
+                {
+                    // the last matched group is used for a duplicated name.
+                    if let Some(idx) = indices
+                        .iter()
+                        .rev()
+                        .find(|idx| matches!(captures.get(**idx), Some(Some(_))))
+                    {
+                        push_group(res, *idx);
+                    }
+                }
+                i = name_end + 1;
+                continue;
+            }
+            Some(b) => {
+                res.push(b'\\');
+                res.push(*b);
+            }
+            None => res.push(b'\\'),
+        }
+        i += 1;
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Replacement {
+    Str(Value),
+    Hash(Value),
+    Block(Value),
+}
+
+///
+/// Replace the first (or all, if *global* is true) occurence of the pattern in *self_val*.
+///
+fn replace(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    block: Option<Value>,
+    global: bool,
+) -> Option<Value> {
+    let (pat, rep) = match (len, block) {
+        (1, Some(block)) => (arg[0], Replacement::Block(block)),
+        (2, _) if arg[1].is_hash().is_some() => (arg[0], Replacement::Hash(arg[1])),
+        (2, _) => {
+            arg[1].expect_string_inner(globals)?;
+            (arg[0], Replacement::Str(arg[1]))
+        }
+        _ => {
+            globals.err_wrong_number_of_arguments_range(len, 1..=2);
+            return None;
+        }
+    };
+    let regexp = to_regexp(globals, pat, true)?;
+    vm.with_temp(regexp, |vm| {
+        // the block may modify the receiver, so we work on a frozen copy of it.
+        let target = frozen_string(self_val);
+        vm.with_temp(target, |vm| {
+            replace_match(vm, globals, self_val, regexp, target, rep, global)
+        })
+    })
+}
+
+fn replace_match(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    regexp: Value,
+    target: Value,
+    rep: Replacement,
+    global: bool,
+) -> Option<Value> {
+    let re = regexp.as_regexp();
+    let s = target.as_string_inner();
+    let mut res = StringInner::with_capacity(s.len());
+    let mut pos = 0;
+    let mut last = 0;
+    let mut last_match = None;
+    while let Some(captures) = re.search(s, pos) {
+        let (start, end) = captures[0].unwrap();
+        res.extend_from_slice(&s[last..start]);
+        match rep {
+            Replacement::Str(rep) => {
+                expand_replacement(&mut res, rep.as_bytes(), s, &captures, re);
+            }
+            Replacement::Hash(hash) => {
+                let key = Value::new_string_from_slice(&s[start..end]);
+                let v = vm.with_temp(key, |vm| HashInner::index(hash, vm, globals, key))?;
+                res.extend_from_slice(v.to_s(globals).as_bytes());
+            }
+            Replacement::Block(block) => {
+                let md =
+                    Value::new_match_data(MatchDataInner::new(regexp, target, captures.clone()));
+                let v = vm.with_temp(md, |vm| {
+                    vm.set_last_match(globals, md);
+                    let matched = Value::new_string_from_slice(&s[start..end]);
+                    vm.invoke_block(globals, block, self_val, &[matched])
+                })?;
+                res.extend_from_slice(v.to_s(globals).as_bytes());
+            }
+        }
+        last = end;
+        last_match = Some(captures);
+        if !global {
+            break;
+        }
+        pos = if start == end {
+            // an empty match: retry at the next character.
+            match s.char_at(end) {
+                Some(ch) => end + ch.len(),
+                None => break,
+            }
+        } else {
+            end
+        };
+    }
+    res.extend_from_slice(&s[last..]);
+    let md = match last_match {
+        Some(captures) => Value::new_match_data(MatchDataInner::new(regexp, target, captures)),
+        None => Value::nil(),
+    };
+    vm.set_last_match(globals, md);
+    Some(Value::new_string_from_inner(res))
+}
+
+/// ### String#sub
+/// - sub(pattern, replace) -> String
+/// - sub(pattern) {|matched| .... } -> String
+///
+/// - sub(pattern, hash) -> String
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/String/i/sub.html]
+extern "C" fn sub(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    replace(vm, globals, self_val, arg, len, block, false)
+}
+
+/// ### String#gsub
+/// - gsub(pattern, replace) -> String
+/// - gsub(pattern) {|matched| .... } -> String
+///
+/// - gsub(pattern, hash) -> String
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/String/i/gsub.html]
+extern "C" fn gsub(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    replace(vm, globals, self_val, arg, len, block, true)
+}
+
+/// ### String#=~
+/// - self =~ other -> Integer | nil
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/String/i/=3d=7e.html]
+extern "C" fn match_op(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    if arg[0].is_regexp().is_none() {
+        if arg[0].is_string().is_some() {
+            globals.set_error(MonorubyErr::typeerr(
+                "type mismatch: String given".to_string(),
+            ));
+            return None;
+        }
+        return Some(Value::nil());
+    }
+    let md = match_at(vm, globals, arg[0], self_val, 0)?;
+    Some(match_start(md))
+}
+
+fn parse_match_args(globals: &mut Globals, arg: Arg, len: usize) -> Option<(Value, i64)> {
+    if !(1..=2).contains(&len) {
+        globals.err_wrong_number_of_arguments_range(len, 1..=2);
+        return None;
+    }
+    let pos = if len == 2 {
+        arg[1].expect_integer(globals)?
+    } else {
+        0
+    };
+    Some((to_regexp(globals, arg[0], false)?, pos))
+}
+
+/// ### String#match
+/// - match(regexp, pos = 0) -> MatchData | nil
+/// - match(regexp, pos = 0) {|m| ... } -> object | nil
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/String/i/match.html]
+extern "C" fn string_match(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    let (regexp, pos) = parse_match_args(globals, arg, len)?;
+    let md = vm.with_temp(regexp, |vm| match_at(vm, globals, regexp, self_val, pos))?;
+    match block {
+        Some(block) if !md.is_nil() => vm.invoke_block(globals, block, self_val, &[md]),
+        _ => Some(md),
+    }
+}
+
+/// ### String#match?
+/// - match?(regexp, pos = 0) -> bool
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/String/i/match=3f.html]
+extern "C" fn string_match_p(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let (regexp, pos) = parse_match_args(globals, arg, len)?;
+    let s = self_val.as_string_inner();
+    let b = match byte_pos(s, pos) {
+        Some(start) => regexp.as_regexp().search(s, start).is_some(),
+        None => false,
+    };
+    Some(Value::bool(b))
+}
+
+/// ### String#scan
+/// - scan(pattern) -> [String] | [[String]]
+/// - scan(pattern) {|s| ... } -> self
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/String/i/scan.html]
+extern "C" fn scan(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    _len: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    let regexp = to_regexp(globals, arg[0], true)?;
+    vm.with_temp(regexp, |vm| {
+        let target = frozen_string(self_val);
+        vm.with_temp(target, |vm| {
+            scan_match(vm, globals, self_val, regexp, target, block)
+        })
+    })
+}
+
+fn scan_match(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    regexp: Value,
+    target: Value,
+    block: Option<Value>,
+) -> Option<Value> {
+    let re = regexp.as_regexp();
+    let s = target.as_string_inner();
+    let mut res = Value::new_array_from_vec(vec![]);
+    vm.with_temp(res, |vm| {
+        let mut pos = 0;
+        let mut last_match = None;
+        while let Some(captures) = re.search(s, pos) {
+            let (start, end) = captures[0].unwrap();
+            pos = if start == end {
+                // an empty match: retry at the next character.
+                end + s.char_at(end).map_or(1, |ch| ch.len())
+            } else {
+                end
+            };
+            let md = Value::new_match_data(MatchDataInner::new(regexp, target, captures.clone()));
+            last_match = Some(captures);
+            vm.with_temp(md, |vm| {
+                let item = if md.as_match_data().len() == 1 {
+                    super::regexp::group_value(md.as_match_data(), 0)
+                } else {
+                    super::regexp::collect_groups(vm, md, 1)
+                };
+                match block {
+                    Some(block) => {
+                        vm.set_last_match(globals, md);
+                        vm.invoke_block(globals, block, self_val, &[item])?;
+                    }
+                    None => res.as_array_mut().push(item),
+                }
+                Some(())
+            })?;
+        }
+        let md = match last_match {
+            Some(captures) => Value::new_match_data(MatchDataInner::new(regexp, target, captures)),
+            None => Value::nil(),
+        };
+        vm.set_last_match(globals, md);
+        Some(if block.is_some() { self_val } else { res })
+    })
+}
+
+///
+/// Take the leading digits of *s* which may be separated by single underscores.
+///
+fn take_digits(s: &[u8], buf: &mut String) -> usize {
+    let mut i = 0;
+    while i < s.len() {
+        match s[i] {
+            b @ b'0'..=b'9' => buf.push(b as char),
+            b'_' if i != 0 && s.get(i + 1).map_or(false, |b| b.is_ascii_digit()) => {}
+            _ => break,
+        }
+        i += 1;
+    }
+    i
+}
+
+///
+/// Take the leading sign of *s*.
+///
+fn take_sign(s: &[u8], buf: &mut String) -> usize {
+    match s.first() {
+        Some(b'-') => {
+            buf.push('-');
+            1
+        }
+        Some(b'+') => 1,
+        _ => 0,
+    }
+}
+
+/// ### String#to_i
+/// - to_i(base = 10) -> Integer
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/String/i/to_i.html]
+///
+/// TODO: Support base.
+extern "C" fn to_i(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let s = lstrip_bytes(self_val.as_string_inner());
+    let mut buf = String::new();
+    let sign_len = take_sign(s, &mut buf);
+    if take_digits(&s[sign_len..], &mut buf) == 0 {
+        return Some(Value::new_integer(0));
+    }
+    let res = match buf.parse::<i64>() {
+        Ok(i) => Value::new_integer(i),
+        Err(_) => Value::new_bigint(buf.parse::<BigInt>().unwrap()),
+    };
+    Some(res)
+}
+
+/// ### String#to_f
+/// - to_f -> Float
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/String/i/to_f.html]
+extern "C" fn to_f(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let s = lstrip_bytes(self_val.as_string_inner());
+    let mut buf = String::new();
+    let mut i = take_sign(s, &mut buf);
+    let int_len = take_digits(&s[i..], &mut buf);
+    i += int_len;
+    let frac = s.get(i) == Some(&b'.') && s.get(i + 1).map_or(false, |b| b.is_ascii_digit());
+    if int_len == 0 && !frac {
+        return Some(Value::new_float(0.0));
+    }
+    if frac {
+        buf.push_str(if int_len == 0 { "0." } else { "." });
+        i += 1;
+        i += take_digits(&s[i..], &mut buf);
+    }
+    if let Some(b'e') | Some(b'E') = s.get(i) {
+        let mut exp = String::from("e");
+        let sign_len = take_sign(&s[i + 1..], &mut exp);
+        if take_digits(&s[i + 1 + sign_len..], &mut exp) != 0 {
+            buf += &exp;
+        }
+    }
+    Some(Value::new_float(buf.parse::<f64>().unwrap()))
+}
+
+/// ### String#to_sym
+/// - to_sym -> Symbol
+/// - intern -> Symbol
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/String/i/intern.html]
+extern "C" fn to_sym(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let id = IdentId::get_ident_id_from_string(self_val.as_string());
+    Some(Value::new_symbol(id))
+}
+
+/// ### String#reverse
+/// - reverse -> String
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/String/i/reverse.html]
+extern "C" fn reverse(
     _vm: &mut Executor,
     _globals: &mut Globals,
     self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let s = self_val.as_string_inner();
+    let chars: Vec<_> = s.chars().collect();
+    let mut res = StringInner::with_capacity(s.len());
+    for ch in chars.into_iter().rev() {
+        res.extend_from_slice(ch);
+    }
+    Some(Value::new_string_from_inner(res))
+}
+
+///
+/// Pad *self_val* to *width* characters with *padstr*.
+///
+/// *split* takes the number of padding characters and returns the numbers for the left and right side.
+///
+fn justify(
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    split: fn(usize) -> (usize, usize),
+) -> Option<Value> {
+    if !(1..=2).contains(&len) {
+        globals.err_wrong_number_of_arguments_range(len, 1..=2);
+        return None;
+    }
+    let width = arg[0].expect_integer(globals)?;
+    let default_pad;
+    let padstr = if len == 2 {
+        arg[1].expect_string_inner(globals)?
+    } else {
+        default_pad = StringInner::from_slice(b" ");
+        &default_pad
+    };
+    if padstr.is_empty() {
+        globals.err_argument("zero width padding");
+        return None;
+    }
+    let s = self_val.as_string_inner();
+    let char_len = s.char_length();
+    if width <= char_len as i64 {
+        return Some(Value::new_string_from_slice(s));
+    }
+    let (left, right) = split(width as usize - char_len);
+    let mut res = StringInner::with_capacity(s.len() + (left + right) * padstr.len());
+    padstr
+        .chars()
+        .cycle()
+        .take(left)
+        .for_each(|ch| res.extend_from_slice(ch));
+    res.extend_from_slice(s);
+    padstr
+        .chars()
+        .cycle()
+        .take(right)
+        .for_each(|ch| res.extend_from_slice(ch));
+    Some(Value::new_string_from_inner(res))
+}
+
+/// ### String#ljust
+/// - ljust(width, padding = ' ') -> String
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/String/i/ljust.html]
+extern "C" fn ljust(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    justify(globals, self_val, arg, len, |pad| (0, pad))
+}
+
+/// ### String#rjust
+/// - rjust(width, padding = ' ') -> String
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/String/i/rjust.html]
+extern "C" fn rjust(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    justify(globals, self_val, arg, len, |pad| (pad, 0))
+}
+
+/// ### String#center
+/// - center(width, padding = ' ') -> String
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/String/i/center.html]
+extern "C" fn center(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
     arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    justify(globals, self_val, arg, len, |pad| (pad / 2, pad - pad / 2))
+}
+
+/// ### Object#freeze
+/// - freeze -> self
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Object/i/freeze.html]
+extern "C" fn freeze(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    mut self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    self_val.set_frozen();
+    Some(self_val)
+}
+
+/// ### Object#frozen?
+/// - frozen? -> bool
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Object/i/frozen=3f.html]
+extern "C" fn frozen(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(Value::bool(self_val.is_frozen()))
+}
+
+/// ### Object#dup
+/// - dup -> object
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Object/i/dup.html]
+extern "C" fn dup(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
     _len: usize,
     _: Option<Value>,
 ) -> Option<Value> {
-    let mut b = self_val.as_bytes().to_vec();
-    b.extend_from_slice(arg[0].as_bytes());
-    Some(Value::new_string_from_slice(&b))
+    let s = self_val.as_string_inner().clone();
+    Some(Value::new_string_from_inner(s))
 }
 
 fn expect_char(globals: &mut Globals, chars: &mut std::str::Chars) -> Option<char> {
@@ -307,6 +1713,201 @@ mod test {
         run_test(r##"a = "We will"; a + " " + "rock you." "##);
     }
 
+    #[test]
+    fn string_mul() {
+        run_test(r##""abc" * 3"##);
+        run_test(r##""abc" * 0"##);
+        run_test_error(r##""abc" * -1"##);
+    }
+
+    #[test]
+    fn string_cmp() {
+        run_test(r##"["abc" == "abc", "abc" == "abd", "abc" == 1]"##);
+        run_test(r##"["abc" <=> "abd", "abc" <=> "abc", "b" <=> "abc", "a" <=> 1]"##);
+    }
+
+    #[test]
+    fn string_concat() {
+        run_test(r##"a = "abc"; a << "def"; a << 12354; a"##);
+        run_test(r##"a = "a"; a.concat("b", a, "c"); a"##);
+        run_test(r##"a = "a"; a << a; a"##);
+        run_test_error(r##"a = "abc".freeze; a << "d""##);
+    }
+
+    #[test]
+    fn string_freeze() {
+        run_test(
+            r##"a = "abc"; b = a.freeze; [a.frozen?, b.frozen?, a.dup.frozen?, "x".frozen?]"##,
+        );
+        run_test(r##"a = "abc".freeze; b = a.dup; b << "d"; [a, b]"##);
+    }
+
+    #[test]
+    fn string_length() {
+        run_test(r##""本日は晴天なり".length"##);
+        run_test(r##""本日は晴天なり".size"##);
+        run_test(r##""本日は晴天なり".bytesize"##);
+        run_test(r##""".length"##);
+    }
+
+    #[test]
+    fn string_index() {
+        run_test(r##""本日は晴天なり"[2]"##);
+        run_test(r##""abcde"[-2]"##);
+        run_test(r##""abcde"[5]"##);
+        run_test(r##""abcde"[-6]"##);
+        run_test(r##""abcde"[1, 3]"##);
+        run_test(r##""abcde"[5, 3]"##);
+        run_test(r##""abcde"[6, 3]"##);
+        run_test(r##""本日は晴天なり"[1..3]"##);
+        run_test(r##""abcde"[1...-1]"##);
+        run_test(r##""abcde"[3..10]"##);
+        run_test(r##""abcde".slice(-3, 2)"##);
+        run_test(r##""abcde"["cd"]"##);
+        run_test(r##""abcde"["x"]"##);
+    }
+
+    #[test]
+    fn string_case() {
+        run_test(r##""Hello World".upcase"##);
+        run_test(r##""Hello World".downcase"##);
+        run_test(r##""hELLO wORLD".capitalize"##);
+        run_test(r##""äöü".upcase"##);
+    }
+
+    #[test]
+    fn string_strip() {
+        run_test(r##""  \t abc def \n ".strip"##);
+        run_test(r##""  \t abc def \n ".lstrip"##);
+        run_test(r##""  \t abc def \n ".rstrip"##);
+        run_test(r##""   ".strip"##);
+    }
+
+    #[test]
+    fn string_split() {
+        run_test(r##"" a  b c ".split"##);
+        run_test(r##"" a  b c ".split(" ")"##);
+        run_test(r##"" a  b c ".split(" ", 2)"##);
+        run_test(r##""a b ".split(" ", -1)"##);
+        run_test(r##""a,b,,c,,".split(",")"##);
+        run_test(r##""a,b,,c,,".split(",", -1)"##);
+        run_test(r##""a,b,,c,,".split(",", 2)"##);
+        run_test(r##"",a,b".split(",")"##);
+        run_test(r##""a::b::c".split("::")"##);
+        run_test(r##""日本語".split("")"##);
+        run_test(r##""abc".split("", 2)"##);
+        run_test(r##""".split(",")"##);
+    }
+
+    #[test]
+    fn string_chars() {
+        run_test(r##""日本語".chars"##);
+        run_test(r##""日本".bytes"##);
+        run_test(r##"a = []; "日本語".each_char { |c| a << c }; a"##);
+        run_test(r##"a = []; s = "abc"; t = s.each_char { |c| a << c * 2 }; [a, t]"##);
+    }
+
+    #[test]
+    fn string_search() {
+        run_test(
+            r##"["hello".start_with?("he"), "hello".start_with?("lo"), "hello".start_with?("x", "hel")]"##,
+        );
+        run_test(
+            r##"["hello".end_with?("lo"), "hello".end_with?("he"), "hello".end_with?("x", "llo")]"##,
+        );
+        run_test(r##"["hello".include?("ll"), "hello".include?("lll"), "hello".include?("")]"##);
+        run_test(r##""日本語の本".index("本")"##);
+        run_test(r##""日本語の本".index("本", 2)"##);
+        run_test(r##""日本語の本".index("本", -1)"##);
+        run_test(r##""日本語の本".index("x")"##);
+        run_test(r##""abc".index("", 3)"##);
+        run_test(r##""abc".index("", 4)"##);
+    }
+
+    #[test]
+    fn string_sub() {
+        run_test(r##""hello world".sub("o", "0")"##);
+        run_test(r##""hello world".gsub("o", "0")"##);
+        run_test(r##""hello world".gsub("o", "<\\0>")"##);
+        run_test(r##""hello world".gsub("x", "0")"##);
+        run_test(r##""abc".gsub("", "-")"##);
+        run_test(r##""hello world".sub("o") { |m| m.upcase }"##);
+        run_test(r##"i = 0; "a-b-c".gsub("-") { i += 1; i }"##);
+        run_test(r##""a.b.c".gsub(".", "-")"##);
+    }
+
+    #[test]
+    fn string_sub_regexp() {
+        run_test(r##""hello world".sub(/o\b/, "0")"##);
+        run_test(r##""hello world".gsub(/[aeiou]/, "*")"##);
+        run_test(r##""2022-10-17".sub(/(\d+)-(\d+)-(\d+)/, "\\3/\\2/\\1")"##);
+        run_test(r##""2022-10-17".sub(/(?<y>\d+)-(?<m>\d+)/, "\\k<m>.\\k<y>")"##);
+        run_test(r##""abc".sub(/b/, "[\\`|\\&|\\']")"##);
+        run_test(r##""abc".gsub(/x*/, "-")"##);
+        run_test(r##""abc".gsub(/b*/, "-")"##);
+        run_test(r##""cat hat".gsub(/[ch]at/, "cat" => "dog", "hat" => "cap")"##);
+        run_test(r##""a1b22c333".gsub(/\d+/) { |m| m.size.to_s }"##);
+        run_test(r##""john smith".gsub(/(\w)(\w*)/) { $1.upcase + $2 }"##);
+        run_test(r##""abc".gsub(/b/, "x"); $~[0]"##);
+        run_test(r##""abc".gsub(/z/, "x"); $~"##);
+    }
+
+    #[test]
+    fn string_scan() {
+        run_test(r##""foo bar baz".scan(/ba./)"##);
+        run_test(r##""foo bar baz".scan("ba")"##);
+        run_test(r##""a1 b2 c3".scan(/(\w)(\d)/)"##);
+        run_test(r##""abc".scan(//)"##);
+        run_test(r##"res = []; "a1 b2".scan(/\w(\d)/) { |x| res << x << $~[0] }; res"##);
+    }
+
+    #[test]
+    fn string_match() {
+        run_test(r##""hello" =~ /ll/"##);
+        run_test(r##""hello" =~ /x/"##);
+        run_test(r##""hello".match(/l+/)"##);
+        run_test(r##""hello".match("l", 3).begin(0)"##);
+        run_test(r##""hello".match(/(l)(o)/) { |m| m[2] }"##);
+        run_test(r##""hello".match?(/L/i)"##);
+        run_test(r##""hello".index(/l+/)"##);
+        run_test(r##""hello".index(/l/, 3)"##);
+        run_test(r##""a1b2c3".split(/\d/)"##);
+        run_test(r##""a1b2c3".split(/(\d)/)"##);
+        run_test(r##""a, b,c ,d".split(/\s*,\s*/)"##);
+        run_test(r##""abc".split(//)"##);
+        run_test(r##""a1b2c3".split(/\d/, 2)"##);
+        run_test(r##""a1b2c3".split(/\d/, -1)"##);
+        run_test_error(r##""hello" =~ "ll""##);
+    }
+
+    #[test]
+    fn string_conversion() {
+        run_test(
+            r##"["123".to_i, "  -42abc".to_i, "+7".to_i, "1_000".to_i, "1__0".to_i, "abc".to_i, "".to_i]"##,
+        );
+        run_test(r##""123456789012345678901234567890".to_i"##);
+        run_test(
+            r##"["1.5".to_f, " -2.5e3x".to_f, "3".to_f, ".5".to_f, "1e".to_f, "abc".to_f, "1_0.2_5".to_f]"##,
+        );
+        run_test(r##"["abc".to_sym, "foo bar".intern]"##);
+    }
+
+    #[test]
+    fn string_reverse() {
+        run_test(r##""日本語abc".reverse"##);
+        run_test(r##""".reverse"##);
+    }
+
+    #[test]
+    fn string_justify() {
+        run_test(r##""abc".ljust(6)"##);
+        run_test(r##""abc".rjust(6, "12")"##);
+        run_test(r##""abc".center(8)"##);
+        run_test(r##""日本".center(7, "ab")"##);
+        run_test(r##""abcdef".center(3)"##);
+        run_test_error(r##""abc".ljust(6, "")"##);
+    }
+
     #[test]
     fn string_format() {
         run_test2(r###""-%d-" % 12"###);
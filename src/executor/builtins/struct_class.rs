@@ -0,0 +1,465 @@
+use crate::*;
+
+//
+// Struct class
+//
+
+pub(super) fn init(globals: &mut Globals) {
+    globals.define_builtin_singleton_func(STRUCT_CLASS, "new", struct_new, -1);
+    globals.define_builtin_singleton_func(STRUCT_CLASS, "members", class_members, 0);
+    globals.define_builtin_func(STRUCT_CLASS, "initialize", initialize, -1);
+    globals.define_builtin_func(STRUCT_CLASS, "==", eq, 1);
+    globals.define_builtin_func(STRUCT_CLASS, "[]", index, 1);
+    globals.define_builtin_func(STRUCT_CLASS, "[]=", index_assign, 2);
+    globals.define_builtin_func(STRUCT_CLASS, "to_a", to_a, 0);
+    globals.define_builtin_func(STRUCT_CLASS, "values", to_a, 0);
+    globals.define_builtin_func(STRUCT_CLASS, "deconstruct", to_a, 0);
+    globals.define_builtin_func(STRUCT_CLASS, "to_h", to_h, 0);
+    globals.define_builtin_func(STRUCT_CLASS, "members", members, 0);
+    globals.define_builtin_func(STRUCT_CLASS, "each", each, 0);
+    globals.define_builtin_func(STRUCT_CLASS, "inspect", inspect, 0);
+    globals.define_builtin_func(STRUCT_CLASS, "to_s", inspect, 0);
+}
+
+///
+/// Get the members of the Struct object *val*.
+///
+fn get_members(globals: &Globals, val: Value) -> Vec<IdentId> {
+    let class_id = globals.get_real_class_id(val);
+    globals.struct_members(class_id).unwrap_or_default()
+}
+
+///
+/// Get the values of the members of the Struct object *val*.
+///
+fn get_values(globals: &Globals, val: Value) -> Vec<Value> {
+    get_members(globals, val)
+        .into_iter()
+        .map(|m| {
+            globals
+                .get_ivar(val, IdentId::add_ivar_prefix(m))
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// ### Struct.new
+/// - new(*args, keyword_init: nil) -> Class
+/// - new(*args, keyword_init: nil) {|subclass| block } -> Class
+///
+/// For the classes generated by `Struct.new`, this creates a new instance of the class.
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Struct/s/new.html]
+extern "C" fn struct_new(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    if self_val.as_class() != STRUCT_CLASS {
+        return super::class::new(vm, globals, self_val, arg, len, block);
+    }
+    let (len, kw) = arg.keywords(len);
+    let mut keyword_init = Value::nil();
+    if let Some(kw) = kw {
+        for (k, v) in kw.as_hash().iter() {
+            if k == Value::new_symbol(IdentId::get_ident_id("keyword_init")) {
+                keyword_init = v;
+            } else {
+                let k = globals.val_inspect(k);
+                globals.err_argument(&format!("unknown keyword: {k}"));
+                return None;
+            }
+        }
+    }
+    let mut members = vec![];
+    for i in 0..len {
+        let name = arg[i].expect_symbol_or_string(globals)?;
+        if members.contains(&name) {
+            globals.err_argument(&format!("duplicate member: {}", IdentId::get_name(name)));
+            return None;
+        }
+        members.push(name);
+    }
+    let class_val = globals.new_unnamed_class(STRUCT_CLASS);
+    let class_id = class_val.as_class();
+    for name in &members {
+        globals.define_attr_reader(class_id, *name);
+        globals.define_attr_writer(class_id, *name);
+    }
+    let members = Value::new_array_from_vec(members.into_iter().map(Value::new_symbol).collect());
+    globals.set_ivar(class_val, IdentId::_MEMBERS, members)?;
+    globals.set_ivar(class_val, IdentId::_KEYWORD_INIT, keyword_init)?;
+    if let Some(block) = block {
+        // methods defined in the block belong to the new class.
+        vm.push_class_context(class_id);
+        let res = vm.invoke_block(globals, block, class_val, &[class_val]);
+        vm.pop_class_context();
+        res?;
+    }
+    Some(class_val)
+}
+
+/// ### Struct.members
+/// - members -> [Symbol]
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Struct/s/members.html]
+extern "C" fn class_members(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let members = globals
+        .struct_members(self_val.as_class())
+        .unwrap_or_default();
+    Some(Value::new_array_from_vec(
+        members.into_iter().map(Value::new_symbol).collect(),
+    ))
+}
+
+/// ### Struct#initialize
+/// - initialize(*args) -> nil
+/// - initialize(**kwargs) -> nil
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Struct/s/new.html]
+extern "C" fn initialize(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let class_id = globals.get_real_class_id(self_val);
+    let members = globals.struct_members(class_id).unwrap_or_default();
+    let keyword_init = globals
+        .get_struct_ivar(class_id, IdentId::_KEYWORD_INIT)
+        .unwrap_or_default();
+    for m in &members {
+        globals.set_ivar(self_val, IdentId::add_ivar_prefix(*m), Value::nil())?;
+    }
+    let (pos_len, kw) = arg.keywords(len);
+    if keyword_init.as_bool() || (keyword_init.is_nil() && pos_len == 0 && kw.is_some()) {
+        if pos_len != 0 {
+            globals.err_wrong_number_of_arguments_range(pos_len, 0..=0);
+            return None;
+        }
+        if let Some(kw) = kw {
+            let mut unknown = vec![];
+            for (k, v) in kw.as_hash().iter() {
+                match k.is_symbol() {
+                    Some(name) if members.contains(&name) => {
+                        globals.set_ivar(self_val, IdentId::add_ivar_prefix(name), v)?;
+                    }
+                    _ => unknown.push(globals.val_tos(k)),
+                }
+            }
+            if !unknown.is_empty() {
+                globals.err_argument(&format!("unknown keywords: {}", unknown.join(", ")));
+                return None;
+            }
+        }
+    } else {
+        // a keyword Hash is treated as a positional argument.
+        if len > members.len() {
+            globals.err_argument("struct size differs");
+            return None;
+        }
+        for (i, m) in members.iter().take(len).enumerate() {
+            globals.set_ivar(self_val, IdentId::add_ivar_prefix(*m), arg[i])?;
+        }
+    }
+    Some(Value::nil())
+}
+
+/// ### Struct#==
+/// - self == other -> bool
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Struct/i/=3d=3d.html]
+extern "C" fn eq(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let other = arg[0];
+    if self_val == other {
+        return Some(Value::bool(true));
+    }
+    if globals.get_real_class_id(self_val) != globals.get_real_class_id(other) {
+        return Some(Value::bool(false));
+    }
+    let lhs = get_values(globals, self_val);
+    let rhs = get_values(globals, other);
+    for (l, r) in lhs.into_iter().zip(rhs) {
+        if !vm.eq_values(globals, l, r)? {
+            return Some(Value::bool(false));
+        }
+    }
+    Some(Value::bool(true))
+}
+
+///
+/// Get the member of the Struct object *self_val* specified by *idx*.
+///
+/// *idx* is a Symbol, a String or an Integer.
+///
+fn member_of(globals: &mut Globals, self_val: Value, idx: Value) -> Option<IdentId> {
+    let members = get_members(globals, self_val);
+    let len = members.len() as i64;
+    if let Some(i) = idx.try_fixnum() {
+        let pos = if i < 0 { i + len } else { i };
+        if pos < 0 {
+            globals.err_index(format!("offset {i} too small for struct(size:{len})"));
+            return None;
+        }
+        if pos >= len {
+            globals.err_index(format!("offset {i} too large for struct(size:{len})"));
+            return None;
+        }
+        return Some(members[pos as usize]);
+    }
+    let name = match idx.unpack() {
+        RV::Symbol(_) | RV::String(_) => idx.expect_symbol_or_string(globals)?,
+        _ => {
+            globals.err_no_implict_conv(idx, INTEGER_CLASS);
+            return None;
+        }
+    };
+    if !members.contains(&name) {
+        let msg = format!("no member '{}' in struct", IdentId::get_name(name));
+        globals.set_error(MonorubyErr::nameerr(msg));
+        return None;
+    }
+    Some(name)
+}
+
+/// ### Struct#[]
+/// - self[member] -> object
+/// - self[index] -> object
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Struct/i/=5b=5d.html]
+extern "C" fn index(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let member = member_of(globals, self_val, arg[0])?;
+    Some(
+        globals
+            .get_ivar(self_val, IdentId::add_ivar_prefix(member))
+            .unwrap_or_default(),
+    )
+}
+
+/// ### Struct#[]=
+/// - self[member] = value
+/// - self[index] = value
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Struct/i/=5b=5d=3d.html]
+extern "C" fn index_assign(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let member = member_of(globals, self_val, arg[0])?;
+    globals.set_ivar(self_val, IdentId::add_ivar_prefix(member), arg[1])?;
+    Some(arg[1])
+}
+
+/// ### Struct#to_a
+/// - to_a -> [object]
+/// - values -> [object]
+/// - deconstruct -> [object]
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Struct/i/to_a.html]
+extern "C" fn to_a(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(Value::new_array_from_vec(get_values(globals, self_val)))
+}
+
+/// ### Struct#to_h
+/// - to_h -> Hash
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Struct/i/to_h.html]
+extern "C" fn to_h(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let members = get_members(globals, self_val);
+    let values = get_values(globals, self_val);
+    let mut res = Value::new_hash(HashInner::new());
+    for (m, v) in members.into_iter().zip(values) {
+        res.as_hash_mut()
+            .insert(vm, globals, Value::new_symbol(m), v)?;
+    }
+    Some(res)
+}
+
+/// ### Struct#members
+/// - members -> [Symbol]
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Struct/i/members.html]
+extern "C" fn members(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let members = get_members(globals, self_val);
+    Some(Value::new_array_from_vec(
+        members.into_iter().map(Value::new_symbol).collect(),
+    ))
+}
+
+/// ### Struct#each
+/// - each {|value| ... } -> self
+/// - each -> Enumerator
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Struct/i/each.html]
+extern "C" fn each(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    block: Option<Value>,
+) -> Option<Value> {
+    let block = match block {
+        Some(block) => block,
+        None => return Some(Value::new_enumerator(self_val, "each", vec![])),
+    };
+    for m in get_members(globals, self_val) {
+        // the members may be modified in the block.
+        let v = globals
+            .get_ivar(self_val, IdentId::add_ivar_prefix(m))
+            .unwrap_or_default();
+        vm.invoke_block(globals, block, self_val, &[v])?;
+    }
+    Some(self_val)
+}
+
+/// ### Struct#inspect
+/// - inspect -> String
+/// - to_s -> String
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Struct/i/inspect.html]
+extern "C" fn inspect(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(Value::new_string(globals.val_inspect(self_val)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::tests::*;
+
+    #[test]
+    fn struct_basic() {
+        run_test(
+            r#"
+            Point = Struct.new(:x, :y)
+            pt = Point.new(1, 2)
+            [pt.x, pt.y, pt.to_a, pt == Point.new(1, 2), pt == Point.new(2, 1), Point.members, pt.members]
+            "#,
+        );
+        run_test(
+            r#"
+            Point = Struct.new(:x, :y)
+            pt = Point.new(1)
+            pt.x += 10
+            pt.y = "a"
+            pt[:x] += 1
+            pt[1] *= 2
+            [pt, pt[0], pt[-1], pt["y"], pt.to_h, pt.inspect, pt.to_s, Point.new(1).y]
+            "#,
+        );
+        run_test(
+            r#"
+            Point = Struct.new(:x, :y)
+            a = []
+            Point.new(3, 4).each { |v| a << v * 2 }
+            [a, Point.new(3, 4).sum, Point.new(3, 4).map { |v| v + 1 }, Struct.new(:a).new(1).inspect]
+            "#,
+        );
+    }
+
+    #[test]
+    fn struct_keyword_init() {
+        run_test(
+            r#"
+            Opt = Struct.new(:name, :size, keyword_init: true)
+            o = Opt.new(size: 3, name: "x")
+            [o.name, o.size, o.to_h, Opt.new(name: "y").size]
+            "#,
+        );
+        run_test_error("Opt = Struct.new(:a, keyword_init: true); Opt.new(1)");
+        run_test_error("Opt = Struct.new(:a, keyword_init: true); Opt.new(b: 1)");
+    }
+
+    #[test]
+    fn struct_block() {
+        run_test(
+            r#"
+            Vec2 = Struct.new(:x, :y) do
+              def len2
+                x * x + y * y
+              end
+              def +(other)
+                Vec2.new(x + other.x, y + other.y)
+              end
+            end
+            v = Vec2.new(1, 2) + Vec2.new(2, 2)
+            [v, v.len2]
+            "#,
+        );
+        run_test(
+            r#"
+            class Pt3 < Struct.new(:x, :y, :z)
+              def sum
+                x + y + z
+              end
+            end
+            [Pt3.new(1, 2, 3).sum, Pt3.new(1, 2, 3).to_a, Pt3.members]
+            "#,
+        );
+    }
+
+    #[test]
+    fn struct_error() {
+        run_test_error("Point = Struct.new(:x, :y); Point.new(1, 2, 3)");
+        run_test_error("Point = Struct.new(:x, :y); Point.new(1, 2)[:z]");
+        run_test_error("Point = Struct.new(:x, :y); Point.new(1, 2)[2]");
+        run_test_error("Struct.new(:x, :x)");
+    }
+}
@@ -0,0 +1,198 @@
+use crate::*;
+
+//
+// Symbol class
+//
+
+pub(super) fn init(globals: &mut Globals) {
+    globals.define_builtin_func(SYMBOL_CLASS, "to_s", tos, 0);
+    globals.define_builtin_func(SYMBOL_CLASS, "id2name", tos, 0);
+    globals.define_builtin_func(SYMBOL_CLASS, "name", tos, 0);
+    globals.define_builtin_func(SYMBOL_CLASS, "to_sym", to_sym, 0);
+    globals.define_builtin_func(SYMBOL_CLASS, "length", length, 0);
+    globals.define_builtin_func(SYMBOL_CLASS, "size", length, 0);
+    globals.define_builtin_func(SYMBOL_CLASS, "<=>", cmp, 1);
+    globals.define_builtin_func(SYMBOL_CLASS, "inspect", inspect, 0);
+    globals.define_builtin_func(SYMBOL_CLASS, "to_proc", to_proc, 0);
+}
+
+///
+/// The body of the block for `&:method`.
+///
+/// The name of this function is *method*, which is called on the first argument
+/// with the rest of the arguments.
+///
+pub(crate) extern "C" fn call_symbol(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    _self_val: Value,
+    arg: Arg,
+    len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    if len == 0 {
+        globals.err_argument("no receiver given");
+        return None;
+    }
+    let func_id = vm.current_func_id();
+    let method = IdentId::get_ident_id(globals.func[func_id].name().unwrap());
+    let args = arg.to_vec(len);
+    vm.invoke_method(globals, method, args[0], &args[1..])
+}
+
+/// ### Symbol#to_s
+/// - to_s -> String
+/// - id2name -> String
+/// - name -> String
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Symbol/i/id2name.html]
+extern "C" fn tos(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let sym = self_val.as_symbol();
+    Some(Value::new_string(IdentId::get_name(sym)))
+}
+
+/// ### Symbol#to_sym
+/// - to_sym -> self
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Symbol/i/to_sym.html]
+extern "C" fn to_sym(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(self_val)
+}
+
+/// ### Symbol#length
+/// - length -> Integer
+/// - size -> Integer
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Symbol/i/length.html]
+extern "C" fn length(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let len = IdentId::get_name(self_val.as_symbol()).chars().count();
+    Some(Value::new_integer(len as i64))
+}
+
+/// ### Symbol#<=>
+/// - self <=> other -> -1 | 0 | 1 | nil
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Symbol/i/=3c=3d=3e.html]
+extern "C" fn cmp(
+    _vm: &mut Executor,
+    _globals: &mut Globals,
+    self_val: Value,
+    arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let other = match arg[0].is_symbol() {
+        Some(other) => other,
+        None => return Some(Value::nil()),
+    };
+    let lhs = IdentId::get_name(self_val.as_symbol());
+    let rhs = IdentId::get_name(other);
+    Some(Value::new_integer(lhs.cmp(&rhs) as i64))
+}
+
+/// ### Symbol#inspect
+/// - inspect -> String
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Symbol/i/inspect.html]
+extern "C" fn inspect(
+    _vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    Some(Value::new_string(globals.val_inspect(self_val)))
+}
+
+/// ### Symbol#to_proc
+/// - to_proc -> Proc
+///
+/// [https://docs.ruby-lang.org/ja/latest/method/Symbol/i/to_proc.html]
+extern "C" fn to_proc(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    self_val: Value,
+    _arg: Arg,
+    _len: usize,
+    _: Option<Value>,
+) -> Option<Value> {
+    let func_id = globals.func.symbol_proc(self_val.as_symbol());
+    let block_handler = ((func_id.0 as i64) << 16) + 1;
+    Some(vm.generate_proc(globals, Value::new_integer(block_handler), true))
+}
+
+#[cfg(test)]
+mod test {
+    use super::tests::*;
+
+    #[test]
+    fn symbol() {
+        run_test(r#":foo.to_s"#);
+        run_test(r#":foo.id2name"#);
+        run_test(r#":foo.to_sym"#);
+        run_test(r#"[:foo.length, :"日本語".size]"#);
+        run_test(r#"[:a <=> :b, :b <=> :a, :a <=> :a, :a <=> "a"]"#);
+        run_test(
+            r#"[:foo, :"foo bar", :+, :[]=, :foo?, :foo=, :@a, :@@b, :$c, :A].map { |s| s.inspect }"#,
+        );
+        run_test(r#"[:b, :c, :a].sort"#);
+    }
+
+    #[test]
+    fn symbol_to_proc() {
+        run_test(r#"%w(a b c).map(&:upcase)"#);
+        run_test(r#"[1, 2, 3].map(&:to_s)"#);
+        run_test(r#"[[1, 2], [3, 4]].map(&:first)"#);
+        run_test(r#"[1, 2, 3].inject(&:+)"#);
+        run_test(r#"s = :upcase; %w(a b).map(&s)"#);
+        run_test(r#"p = :upcase.to_proc; [p.call("a"), p.lambda?, p.arity]"#);
+        run_test(
+            r#"
+        def f(&b)
+          b.call("x")
+        end
+        f(&:upcase)
+        "#,
+        );
+        run_test(
+            r#"
+        def g
+          yield 3, 4
+        end
+        g(&:+)
+        "#,
+        );
+        run_test(
+            r#"
+        res = []
+        for i in 0..30
+          res << [i, i + 1].map(&:to_s)
+        end
+        res
+        "#,
+        );
+        run_test_error(r#"[1].map(&:foo)"#);
+    }
+}
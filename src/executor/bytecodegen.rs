@@ -1,7 +1,9 @@
 use super::*;
 use num::BigInt;
 use paste::paste;
-use ruruby_parse::{ArgList, BinOp, BlockInfo, CmpKind, Loc, Node, NodeKind, UnOp};
+use ruruby_parse::{
+    ArgList, BinOp, BlockInfo, CaseBranch, CmpKind, Loc, Node, NodeKind, RescueEntry, UnOp,
+};
 
 mod binary;
 mod encode;
@@ -92,6 +94,43 @@ fn is_smi(node: &Node) -> Option<i16> {
     None
 }
 
+///
+/// Get the id of the special variable *name* for `LoadSvar`.
+///
+/// `$~` is 0, and `$n` is n + 1 (`$&` is the same as `$0` of MatchData).
+///
+fn special_var_id(name: &str) -> Option<u32> {
+    match name {
+        "$~" => Some(0),
+        "$&" => Some(1),
+        _ => match name.strip_prefix('$')?.parse::<u32>() {
+            Ok(n) if (1..=9).contains(&n) => Some(n + 1),
+            _ => None,
+        },
+    }
+}
+
+///
+/// Remove the option character of the regexp literal from *nodes*, and return the option flags.
+///
+/// The parser appends one of 'i', 'm', 'x', 'o' or '-' (no option) to the last string node.
+///
+fn regexp_options(nodes: &mut [Node]) -> u32 {
+    let option = match nodes.last_mut() {
+        Some(Node {
+            kind: NodeKind::String(s),
+            ..
+        }) => s.pop(),
+        _ => None,
+    };
+    match option {
+        Some('i') => RegexpInner::IGNORECASE,
+        Some('x') => RegexpInner::EXTENDED,
+        Some('m') => RegexpInner::MULTILINE,
+        _ => 0,
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 enum LvalueKind {
     Const(IdentId),
@@ -127,7 +166,13 @@ pub(crate) struct IrContext {
     /// destination labels.
     labels: Vec<Option<InstId>>,
     /// loop information.
-    loops: Vec<(LoopKind, usize, Option<BcReg>)>, // (kind, label for exit, return register)
+    loops: Vec<(LoopKind, usize, Option<BcReg>, usize)>, // (kind, label for exit, return register, ensure depth)
+    /// ensure clauses which are active at the current position.
+    ensure: Vec<Node>,
+    /// rescue clause information.
+    retry: Vec<(usize, usize)>, // (label for begin start, ensure depth)
+    /// exception table.
+    exception_table: Vec<(usize, usize, usize, BcReg, bool)>, // (start label, end label, label for handler, error register, is ensure)
 }
 
 impl IrContext {
@@ -135,6 +180,9 @@ impl IrContext {
         let mut ir = IrContext::new();
         let ast = std::mem::take(&mut info.ast).unwrap();
         ir.gen_dummy_init(info.is_block);
+        if info.keyword.is_some() {
+            ir.push(BcIr::InitKeyword, Loc::default());
+        }
         for ExpandInfo { src, dst, len } in &info.expand {
             ir.gen_expand_array(*src, *dst, *len);
         }
@@ -159,6 +207,9 @@ impl IrContext {
             ir: vec![],
             labels: vec![],
             loops: vec![],
+            ensure: vec![],
+            retry: vec![],
+            exception_table: vec![],
         }
     }
 
@@ -282,6 +333,35 @@ impl IrContext {
         self.emit_array(ret, src, len, loc);
         Ok(())
     }
+
+    fn gen_hash(
+        &mut self,
+        ctx: &mut FnStore,
+        info: &mut ISeqInfo,
+        ret: Option<BcReg>,
+        nodes: Vec<(Node, Node)>,
+        loc: Loc,
+    ) -> Result<()> {
+        let len = nodes.len();
+        let args = info.next_reg().into();
+        for (k, v) in nodes {
+            self.push_expr(ctx, info, k)?;
+            self.push_expr(ctx, info, v)?;
+        }
+        info.popn(len * 2);
+        let ret = BcReg::get_reg(info, ret);
+        self.push(
+            BcIr::Hash {
+                ret,
+                args,
+                len: len as u16,
+                kw: false,
+            },
+            loc,
+        );
+        Ok(())
+    }
+
     fn gen_range(
         &mut self,
         ctx: &mut FnStore,
@@ -404,6 +484,11 @@ impl IrContext {
         self.push(BcIr::LoadGvar { ret, name }, loc);
     }
 
+    fn gen_load_svar(&mut self, info: &mut ISeqInfo, dst: Option<BcReg>, id: u32, loc: Loc) {
+        let ret = BcReg::get_reg(info, dst);
+        self.push(BcIr::LoadSvar { ret, id }, loc);
+    }
+
     fn gen_index(
         &mut self,
         ctx: &mut FnStore,
@@ -663,6 +748,7 @@ impl IrContext {
                 self.gen_literal(info, None, val);
             }
             NodeKind::Array(nodes, false) => self.gen_array(ctx, info, None, nodes, loc)?,
+            NodeKind::Hash(nodes, ..) => self.gen_hash(ctx, info, None, nodes, loc)?,
             NodeKind::Range { is_const: true, .. } => {
                 let val = Value::from_ast2(&expr);
                 self.gen_literal(info, None, val);
@@ -757,9 +843,46 @@ impl IrContext {
                 let name = IdentId::get_ident_id_from_string(name);
                 self.gen_load_ivar(info, None, name, loc);
             }
-            NodeKind::GlobalVar(name) => {
-                let name = IdentId::get_ident_id_from_string(name);
-                self.gen_load_gvar(info, None, name, loc);
+            NodeKind::GlobalVar(name) => match special_var_id(&name) {
+                Some(id) => self.gen_load_svar(info, None, id, loc),
+                None => {
+                    let name = IdentId::get_ident_id_from_string(name);
+                    self.gen_load_gvar(info, None, name, loc);
+                }
+            },
+            NodeKind::RegExp(mut nodes, true) => {
+                let options = regexp_options(&mut nodes);
+                let source = nodes
+                    .into_iter()
+                    .map(|node| match node.kind {
+                        NodeKind::String(s) => s,
+                        _ => unreachable!(),
+                    })
+                    .collect();
+                let regexp = match RegexpInner::new(source, options) {
+                    Ok(regexp) => regexp,
+                    Err(msg) => {
+                        return Err(MonorubyErr::invalid_regexp(
+                            msg,
+                            loc,
+                            info.sourceinfo.clone(),
+                        ))
+                    }
+                };
+                self.gen_literal(info, None, Value::new_regexp(regexp));
+            }
+            NodeKind::RegExp(mut nodes, false) => {
+                let options = regexp_options(&mut nodes);
+                let ret = if use_mode.use_val() {
+                    Some(info.push().into())
+                } else {
+                    None
+                };
+                self.gen_dynamic_regexp(ctx, info, ret, nodes, options, loc)?;
+                if use_mode.is_ret() {
+                    self.gen_ret(info, None);
+                }
+                return Ok(());
             }
             NodeKind::MethodCall {
                 box receiver,
@@ -804,6 +927,31 @@ impl IrContext {
                     loc,
                 );
             }
+            NodeKind::Lambda(block) => {
+                // `->(){}` is evaluated as `lambda {}`.
+                let ret = if use_mode.use_val() {
+                    Some(info.push().into())
+                } else {
+                    None
+                };
+                let arglist = ArgList {
+                    block: Some(Box::new(Node {
+                        kind: NodeKind::Lambda(block),
+                        loc,
+                    })),
+                    ..ArgList::default()
+                };
+                return self.gen_method_call(
+                    ctx,
+                    info,
+                    "lambda".to_string(),
+                    None,
+                    arglist,
+                    ret,
+                    use_mode.is_ret(),
+                    loc,
+                );
+            }
             NodeKind::Yield(arglist) => {
                 let ret = if use_mode.use_val() {
                     Some(info.push().into())
@@ -812,6 +960,14 @@ impl IrContext {
                 };
                 return self.gen_yield(ctx, info, arglist, ret, use_mode.is_ret(), loc);
             }
+            NodeKind::Super(arglist) => {
+                let ret = if use_mode.use_val() {
+                    Some(info.push().into())
+                } else {
+                    None
+                };
+                return self.gen_super(ctx, info, arglist, ret, use_mode.is_ret(), loc);
+            }
             NodeKind::Ident(method) => {
                 let arglist = ArgList::default();
                 let ret = if use_mode.use_val() {
@@ -854,6 +1010,14 @@ impl IrContext {
                 self.apply_label(succ_pos);
                 return Ok(());
             }
+            NodeKind::Case {
+                cond,
+                when_,
+                box else_,
+            } => {
+                self.gen_case(ctx, info, cond, when_, else_, use_mode, loc)?;
+                return Ok(());
+            }
             NodeKind::While {
                 box cond,
                 box body,
@@ -889,7 +1053,7 @@ impl IrContext {
                 return Ok(());
             }
             NodeKind::Break(box val) => {
-                let (_kind, break_pos, ret_reg) = match self.loops.last() {
+                let (_kind, break_pos, ret_reg, ensure_depth) = match self.loops.last() {
                     Some(data) => data.clone(),
                     None => {
                         return Err(MonorubyErr::escape_from_eval(loc, info.sourceinfo.clone()))
@@ -899,14 +1063,40 @@ impl IrContext {
                     let temp = self.gen_temp_expr(ctx, info, val)?;
                     self.gen_mov(reg, temp)
                 }
+                self.gen_ensure_clauses(ctx, info, ensure_depth)?;
                 self.push(BcIr::Br(break_pos), loc);
                 return Ok(());
             }
+            NodeKind::Retry => {
+                let (start, ensure_depth) = match self.retry.last() {
+                    Some(data) => *data,
+                    None => return Err(MonorubyErr::invalid_retry(loc, info.sourceinfo.clone())),
+                };
+                self.gen_ensure_clauses(ctx, info, ensure_depth)?;
+                self.gen_nil_dollar_bang(info, loc);
+                self.push(BcIr::Br(start), loc);
+                return Ok(());
+            }
             NodeKind::Return(box expr) => {
-                if let Some(local) = info.is_refer_local(&expr) {
-                    self.gen_ret(info, Some(local.into()));
+                if info.is_block {
+                    // `return` in a block returns from the method in which the block was defined.
+                    let temp = info.temp;
+                    let ret = self.push_expr(ctx, info, expr)?;
+                    self.push(BcIr::MethodRet(ret), loc);
+                    info.temp = temp;
+                } else if self.ensure.is_empty() && info.temp == 0 {
+                    if let Some(local) = info.is_refer_local(&expr) {
+                        self.gen_ret(info, Some(local.into()));
+                    } else {
+                        self.gen_expr(ctx, info, expr, UseMode::Ret)?;
+                    }
                 } else {
-                    self.gen_expr(ctx, info, expr, UseMode::Ret)?;
+                    // we are in begin clauses.
+                    let temp = info.temp;
+                    let ret = self.push_expr(ctx, info, expr)?;
+                    self.gen_ensure_clauses(ctx, info, 0)?;
+                    self.push(BcIr::Ret(ret), loc);
+                    info.temp = temp;
                 }
                 assert_ne!(use_mode, UseMode::Use);
                 return Ok(());
@@ -920,16 +1110,15 @@ impl IrContext {
                 else_,
                 ensure,
             } => {
-                assert!(rescue.is_empty());
-
-                if let Some(box else_) = else_ {
-                    self.gen_expr(ctx, info, body, UseMode::NotUse)?;
-                    self.gen_expr(ctx, info, else_, use_mode)?;
+                if rescue.is_empty() && ensure.is_none() {
+                    if let Some(box else_) = else_ {
+                        self.gen_expr(ctx, info, body, UseMode::NotUse)?;
+                        self.gen_expr(ctx, info, else_, use_mode)?;
+                    } else {
+                        self.gen_expr(ctx, info, body, use_mode)?;
+                    }
                 } else {
-                    self.gen_expr(ctx, info, body, use_mode)?;
-                }
-                if let Some(box ensure) = ensure {
-                    self.gen_expr(ctx, info, ensure, UseMode::NotUse)?;
+                    self.gen_begin(ctx, info, body, rescue, else_, ensure, use_mode, loc)?;
                 }
                 return Ok(());
             }
@@ -943,27 +1132,56 @@ impl IrContext {
                 }
                 return Ok(());
             }
+            NodeKind::SingletonMethodDef(box obj, name, block) => {
+                self.gen_singleton_method_def(ctx, info, obj, name.clone(), block, loc)?;
+                if use_mode.use_val() {
+                    self.gen_symbol(info, None, IdentId::get_ident_id_from_string(name));
+                }
+                if use_mode.is_ret() {
+                    self.gen_ret(info, None);
+                }
+                return Ok(());
+            }
+            NodeKind::SingletonClassDef {
+                box singleton,
+                info: block_info,
+            } => {
+                let ret = if use_mode.use_val() {
+                    Some(info.push().into())
+                } else {
+                    None
+                };
+                self.gen_singleton_class_def(ctx, info, singleton, *block_info.body, ret, loc)?;
+                if use_mode.is_ret() {
+                    self.gen_ret(info, None);
+                }
+                return Ok(());
+            }
             NodeKind::ClassDef {
                 base,
                 name,
                 superclass,
                 info: block_info,
-                is_module: _,
+                is_module,
             } => {
-                if let Some(base) = base {
-                    return Err(MonorubyErr::unsupported_feature(
-                        &format!("base in class def. {:?}", base.kind),
-                        loc,
-                        info.sourceinfo.clone(),
-                    ));
-                };
                 let ret = if use_mode.use_val() {
                     Some(info.push().into())
                 } else {
                     None
                 };
+                let base = base.map(|c| *c);
                 let superclass = superclass.map(|c| *c);
-                self.gen_class_def(ctx, info, name, superclass, *block_info.body, ret, loc)?;
+                self.gen_class_def(
+                    ctx,
+                    info,
+                    name,
+                    base,
+                    superclass,
+                    *block_info.body,
+                    ret,
+                    is_module,
+                    loc,
+                )?;
                 if use_mode.is_ret() {
                     self.gen_ret(info, None);
                 }
@@ -1026,6 +1244,7 @@ impl IrContext {
                 self.gen_literal(info, Some(dst), val);
             }
             NodeKind::Array(nodes, false) => self.gen_array(ctx, info, Some(dst), nodes, loc)?,
+            NodeKind::Hash(nodes, ..) => self.gen_hash(ctx, info, Some(dst), nodes, loc)?,
             NodeKind::Range { is_const: true, .. } => {
                 let val = Value::from_ast2(&rhs);
                 self.gen_literal(info, Some(dst), val);
@@ -1109,7 +1328,10 @@ impl IrContext {
                 let ret = Some(dst);
                 self.gen_method_call(ctx, info, method, None, arglist, ret, false, loc)?;
             }
-            NodeKind::Return(_) => unreachable!(),
+            NodeKind::Return(_) => {
+                // the control never reaches *dst*.
+                self.gen_expr(ctx, info, rhs, UseMode::NotUse)?;
+            }
             NodeKind::CompStmt(nodes) => {
                 self.gen_comp_stmts(ctx, info, nodes, Some(dst), UseMode::NotUse)?;
             }
@@ -1118,18 +1340,22 @@ impl IrContext {
                 name,
                 superclass,
                 info: block_info,
-                is_module: _,
+                is_module,
             } => {
-                if let Some(base) = base {
-                    return Err(MonorubyErr::unsupported_feature(
-                        &format!("base in class def. {:?}", base.kind),
-                        loc,
-                        info.sourceinfo.clone(),
-                    ));
-                };
                 let ret = Some(dst);
+                let base = base.map(|c| *c);
                 let superclass = superclass.map(|c| *c);
-                self.gen_class_def(ctx, info, name, superclass, *block_info.body, ret, loc)?;
+                self.gen_class_def(
+                    ctx,
+                    info,
+                    name,
+                    base,
+                    superclass,
+                    *block_info.body,
+                    ret,
+                    is_module,
+                    loc,
+                )?;
             }
             _ => {
                 let ret = self.push_expr(ctx, info, rhs)?;
@@ -1156,36 +1382,90 @@ impl IrContext {
         Ok(())
     }
 
+    fn gen_singleton_method_def(
+        &mut self,
+        ctx: &mut FnStore,
+        info: &mut ISeqInfo,
+        obj: Node,
+        name: String,
+        block: BlockInfo,
+        loc: Loc,
+    ) -> Result<()> {
+        let func_id =
+            ctx.functions
+                .add_method(Some(name.clone()), block, info.sourceinfo.clone())?;
+        let name = IdentId::get_ident_id_from_string(name);
+        let obj = self.push_expr(ctx, info, obj)?;
+        info.pop();
+        self.push(BcIr::SingletonMethodDef { obj, name, func_id }, loc);
+        Ok(())
+    }
+
     fn gen_class_def(
         &mut self,
         ctx: &mut FnStore,
         info: &mut ISeqInfo,
         name: String,
+        base: Option<Node>,
         superclass: Option<Node>,
         body: Node,
         ret: Option<BcReg>,
+        is_module: bool,
         loc: Loc,
     ) -> Result<()> {
         let func_id = ctx
             .functions
             .add_classdef(Some(name.clone()), body, info.sourceinfo.clone());
         let name = IdentId::get_ident_id_from_string(name);
+        // *base* (e.g. A in `class A::B`) is evaluated before *superclass*.
+        let mut temp_num = 0;
+        let base = match base {
+            Some(base) => {
+                temp_num += 1;
+                Some(self.push_expr(ctx, info, base)?)
+            }
+            None => None,
+        };
         let superclass = match superclass {
-            Some(superclass) => Some(self.gen_temp_expr(ctx, info, superclass)?),
+            Some(superclass) => {
+                temp_num += 1;
+                Some(self.push_expr(ctx, info, superclass)?)
+            }
             None => None,
         };
+        info.temp -= temp_num;
         self.push(
             BcIr::ClassDef {
                 ret,
+                base,
                 superclass,
                 name,
                 func_id,
+                is_module,
             },
             loc,
         );
         Ok(())
     }
 
+    fn gen_singleton_class_def(
+        &mut self,
+        ctx: &mut FnStore,
+        info: &mut ISeqInfo,
+        singleton: Node,
+        body: Node,
+        ret: Option<BcReg>,
+        loc: Loc,
+    ) -> Result<()> {
+        let func_id = ctx
+            .functions
+            .add_classdef(None, body, info.sourceinfo.clone());
+        let base = self.push_expr(ctx, info, singleton)?;
+        info.pop();
+        self.push(BcIr::SingletonClassDef { ret, base, func_id }, loc);
+        Ok(())
+    }
+
     fn gen_args(
         &mut self,
         ctx: &mut FnStore,
@@ -1199,6 +1479,76 @@ impl IrContext {
         Ok(arg)
     }
 
+    ///
+    /// Generate keyword arguments (`k: v` and `**h`) as a trailing keyword Hash.
+    ///
+    /// Return true if the keyword Hash was pushed.
+    ///
+    fn gen_kw_args(
+        &mut self,
+        ctx: &mut FnStore,
+        info: &mut ISeqInfo,
+        kw_args: Vec<(String, Node)>,
+        hash_splat: Vec<Node>,
+        loc: Loc,
+    ) -> Result<bool> {
+        if kw_args.is_empty() && hash_splat.is_empty() {
+            return Ok(false);
+        }
+        let len = kw_args.len() + hash_splat.len();
+        let args = info.next_reg().into();
+        for (name, val) in kw_args {
+            self.gen_symbol(info, None, IdentId::get_ident_id_from_string(name));
+            self.push_expr(ctx, info, val)?;
+        }
+        // a Hash in the key position is expanded into the keyword Hash.
+        for splat in hash_splat {
+            self.push_expr(ctx, info, splat)?;
+            self.gen_nil(info, None);
+        }
+        info.popn(len * 2);
+        let ret = info.push().into();
+        self.push(
+            BcIr::Hash {
+                ret,
+                args,
+                len: len as u16,
+                kw: true,
+            },
+            loc,
+        );
+        Ok(true)
+    }
+
+    ///
+    /// Generate `::Regexp.new(<interpolated string>, options)`.
+    ///
+    fn gen_dynamic_regexp(
+        &mut self,
+        ctx: &mut FnStore,
+        info: &mut ISeqInfo,
+        ret: Option<BcReg>,
+        nodes: Vec<Node>,
+        options: u32,
+        loc: Loc,
+    ) -> Result<()> {
+        let recv = info.push().into();
+        self.gen_load_const(info, Some(recv), true, "Regexp".to_string(), vec![], loc);
+        let arg = info.next_reg();
+        let len = nodes.len();
+        for expr in nodes {
+            self.push_expr(ctx, info, expr)?;
+        }
+        info.temp -= len as u16;
+        let source = info.push().into();
+        self.push(BcIr::ConcatStr(Some(source), arg, len), loc);
+        self.gen_integer(info, None, options as i64);
+        info.temp -= 2;
+        info.pop();
+        self.gen_call(recv, IdentId::NEW, ret, arg.into(), 2, false, loc);
+        Ok(())
+    }
+
     fn gen_call(
         &mut self,
         recv: BcReg,
@@ -1285,36 +1635,20 @@ impl IrContext {
             None => RecvKind::SelfValue,
         };
 
-        assert!(arglist.kw_args.is_empty());
-        assert!(arglist.hash_splat.is_empty());
         assert!(!arglist.delegate);
         let mut has_block = false;
         let old_temp = info.temp;
         let arg = info.next_reg();
         if let Some(box block) = arglist.block {
             has_block = true;
-
-            match block.kind {
-                NodeKind::Lambda(block) => {
-                    let outer_locals = info.get_locals();
-                    let func_id = ctx.functions.add_block(
-                        (info.id, outer_locals),
-                        block,
-                        info.sourceinfo.clone(),
-                    )?;
-                    let block_handler = ((func_id.0 as i64) << 16) + 1;
-                    self.gen_literal(info, None, Value::new_integer(block_handler));
-                }
-                NodeKind::LocalVar(proc_local) if Some(&proc_local) == info.block_param_name() => {
-                    let proc_temp = info.push().into();
-                    self.push(BcIr::BlockArgProxy(proc_temp), loc);
-                }
-                _ => unimplemented!(),
-            }
+            self.gen_block_arg(ctx, info, block, loc)?;
         }
         let args = arglist.args;
-        let len = args.len();
+        let mut len = args.len();
         self.gen_args(ctx, info, args)?;
+        if self.gen_kw_args(ctx, info, arglist.kw_args, arglist.hash_splat, loc)? {
+            len += 1;
+        }
         info.temp = old_temp;
 
         let recv = match recv_kind {
@@ -1338,15 +1672,16 @@ impl IrContext {
         is_ret: bool,
         loc: Loc,
     ) -> Result<()> {
-        assert!(arglist.kw_args.is_empty());
-        assert!(arglist.hash_splat.is_empty());
         assert!(!arglist.delegate);
         assert!(arglist.block.is_none());
         let old_temp = info.temp;
         let arg = info.next_reg();
         let args = arglist.args;
-        let len = args.len();
+        let mut len = args.len();
         self.gen_args(ctx, info, args)?;
+        if self.gen_kw_args(ctx, info, arglist.kw_args, arglist.hash_splat, loc)? {
+            len += 1;
+        }
         info.temp = old_temp;
 
         self.push(
@@ -1364,6 +1699,166 @@ impl IrContext {
         Ok(())
     }
 
+    ///
+    /// Push a block handler for the block argument *block* of a method call.
+    ///
+    fn gen_block_arg(
+        &mut self,
+        ctx: &mut FnStore,
+        info: &mut ISeqInfo,
+        block: Node,
+        loc: Loc,
+    ) -> Result<()> {
+        match block.kind {
+            NodeKind::Lambda(block) => {
+                let outer_locals = info.get_locals();
+                let func_id = ctx.functions.add_block(
+                    (info.id, outer_locals),
+                    block,
+                    info.sourceinfo.clone(),
+                )?;
+                let block_handler = ((func_id.0 as i64) << 16) + 1;
+                self.gen_literal(info, None, Value::new_integer(block_handler));
+            }
+            NodeKind::Symbol(method) => {
+                // `&:method` does not need a Proc object.
+                let method = IdentId::get_ident_id_from_string(method);
+                let func_id = ctx.symbol_proc(method);
+                let block_handler = ((func_id.0 as i64) << 16) + 1;
+                self.gen_literal(info, None, Value::new_integer(block_handler));
+            }
+            NodeKind::LocalVar(proc_local) if Some(&proc_local) == info.block_param_name() => {
+                let proc_temp = info.push().into();
+                self.push(BcIr::BlockArgProxy(proc_temp), loc);
+            }
+            _ => {
+                // `&expr`
+                let proc_temp = self.push_expr(ctx, info, block)?;
+                self.push(BcIr::BlockArg(proc_temp, proc_temp), loc);
+            }
+        }
+        Ok(())
+    }
+
+    ///
+    /// Generate `super` call.
+    ///
+    /// `super` without an argument list (zsuper) passes the current arguments of the method.
+    /// If no block is given, the block of the method is passed.
+    ///
+    /// If the method has a rest parameter, the rest array is passed as the last positional argument,
+    /// and expanded when the call is executed.
+    ///
+    fn gen_super(
+        &mut self,
+        ctx: &mut FnStore,
+        info: &mut ISeqInfo,
+        arglist: Option<ArgList>,
+        ret: Option<BcReg>,
+        is_ret: bool,
+        loc: Loc,
+    ) -> Result<()> {
+        let (func_id, depth) = info.method_context(ctx);
+        let (arglist, splat) = match arglist {
+            Some(arglist) => (arglist, false),
+            None => Self::zsuper_args(ctx, func_id, depth, loc),
+        };
+        assert!(!arglist.delegate);
+        let old_temp = info.temp;
+        let arg = info.next_reg();
+        let has_block = match arglist.block {
+            Some(box block) => {
+                self.gen_block_arg(ctx, info, block, loc)?;
+                true
+            }
+            None if depth == 0 => {
+                let proc_temp = info.push().into();
+                self.push(BcIr::BlockArgProxy(proc_temp), loc);
+                true
+            }
+            None => false,
+        };
+        let args = arglist.args;
+        let mut len = args.len();
+        self.gen_args(ctx, info, args)?;
+        if self.gen_kw_args(ctx, info, arglist.kw_args, arglist.hash_splat, loc)? {
+            len += 1;
+        }
+        info.temp = old_temp;
+
+        self.push(
+            BcIr::Super {
+                ret,
+                func_id,
+                has_block,
+                splat,
+            },
+            loc,
+        );
+        self.push(BcIr::MethodArgs(BcReg::Self_, arg.into(), len), loc);
+        self.push(BcIr::InlineCache, loc);
+        if is_ret {
+            self.gen_ret(info, None);
+        }
+        Ok(())
+    }
+
+    ///
+    /// Build the argument list of zsuper from the parameters of the method *func_id*.
+    ///
+    /// *depth* is the depth of the block nesting from the method.
+    /// Destructuring parameters are rebuilt as Arrays from their local variables.
+    /// Return true with the argument list if the rest array must be expanded.
+    ///
+    fn zsuper_args(ctx: &FnStore, func_id: FuncId, depth: usize, loc: Loc) -> (ArgList, bool) {
+        let info = ctx[func_id].as_ruby_func();
+        let params = info.args();
+        let local = |name: &String| Node {
+            kind: if depth == 0 {
+                NodeKind::LocalVar(name.clone())
+            } else {
+                NodeKind::DynamicLocalVar(depth, name.clone())
+            },
+            loc,
+        };
+        let mut args: Vec<_> = params.names[0..params.pos_num]
+            .iter()
+            .enumerate()
+            .map(|(i, name)| match name {
+                Some(name) => local(name),
+                None => {
+                    let ExpandInfo { dst, len, .. } =
+                        info.expand.iter().find(|expand| expand.src == i).unwrap();
+                    let elems = params.names[*dst..*dst + *len]
+                        .iter()
+                        .map(|name| local(name.as_ref().unwrap()))
+                        .collect();
+                    Node {
+                        kind: NodeKind::Array(elems, false),
+                        loc,
+                    }
+                }
+            })
+            .collect();
+        let splat = params.arg_num != params.pos_num && !params.hidden_rest;
+        if splat {
+            args.push(local(params.names[params.pos_num].as_ref().unwrap()));
+        }
+        let kw_args = params
+            .keyword
+            .iter()
+            .map(|(name, _)| (name.clone(), local(name)))
+            .collect();
+        let hash_splat = params.kw_rest.iter().map(local).collect();
+        let arglist = ArgList {
+            args,
+            kw_args,
+            hash_splat,
+            ..ArgList::default()
+        };
+        (arglist, splat)
+    }
+
     fn gen_method_assign(&mut self, method: IdentId, receiver: BcReg, val: BcReg, loc: Loc) {
         self.gen_call(receiver, method, None, val, 1, false, loc);
     }
@@ -1496,6 +1991,7 @@ impl IrContext {
                 true => Some(info.next_reg().into()),
                 false => None,
             },
+            self.ensure.len(),
         ));
         let loc = iter.loc;
         if let NodeKind::Range {
@@ -1590,6 +2086,7 @@ impl IrContext {
                 true => Some(info.next_reg().into()),
                 false => None,
             },
+            self.ensure.len(),
         ));
         let loc = body.loc;
         self.apply_label(cond_pos);
@@ -1628,6 +2125,7 @@ impl IrContext {
                 true => Some(info.next_reg().into()),
                 false => None,
             },
+            self.ensure.len(),
         ));
         let loc = body.loc;
         self.apply_label(loop_pos);
@@ -1644,4 +2142,307 @@ impl IrContext {
 
         Ok(())
     }
+
+    ///
+    /// Generate bytecode Ir for begin-rescue-else-ensure.
+    ///
+    /// ~~~text
+    /// start:
+    ///     <body>
+    /// end_body:
+    ///     <else>
+    ///     br ensure_normal
+    /// rescue_entry:  (handler for [start, end_body))
+    ///     <match %err with each rescue clause, or raise %err again>
+    ///     <rescue clauses>  (`retry` in the clauses: br start)
+    ///     br ensure_normal
+    /// ensure_normal:
+    ///     <ensure>
+    ///     br exit
+    /// ensure_err:  (handler for [start, ensure_normal))
+    ///     <ensure>
+    ///     raise %err
+    /// exit:
+    /// ~~~
+    fn gen_begin(
+        &mut self,
+        ctx: &mut FnStore,
+        info: &mut ISeqInfo,
+        body: Node,
+        rescue: Vec<RescueEntry>,
+        else_: Option<Box<Node>>,
+        ensure: Option<Box<Node>>,
+        use_mode: UseMode,
+        loc: Loc,
+    ) -> Result<()> {
+        let ret: Option<BcReg> = if use_mode.use_val() {
+            Some(info.push().into())
+        } else {
+            None
+        };
+        let start = self.new_label();
+        let end_body = self.new_label();
+        let rescue_entry = self.new_label();
+        let ensure_normal = self.new_label();
+        let ensure_err = self.new_label();
+        let exit = self.new_label();
+        if let Some(ensure) = &ensure {
+            self.ensure.push(*ensure.clone());
+        }
+
+        self.apply_label(start);
+        match ret {
+            Some(ret) if else_.is_none() => self.gen_store_expr(ctx, info, ret, body)?,
+            _ => self.gen_expr(ctx, info, body, UseMode::NotUse)?,
+        }
+        self.apply_label(end_body);
+        if let Some(box else_) = else_ {
+            self.gen_begin_clause(ctx, info, ret, else_)?;
+        }
+        self.gen_br(ensure_normal);
+
+        if !rescue.is_empty() {
+            self.apply_label(rescue_entry);
+            let err_reg: BcReg = info.push().into();
+            let mut clauses = vec![];
+            for RescueEntry { exception_list, .. } in &rescue {
+                let clause = self.new_label();
+                if exception_list.is_empty() {
+                    self.gen_load_const(
+                        info,
+                        None,
+                        false,
+                        "StandardError".to_string(),
+                        vec![],
+                        loc,
+                    );
+                    self.gen_teq_condbr(info, err_reg, clause, loc);
+                } else {
+                    for class in exception_list {
+                        let loc = class.loc;
+                        self.push_expr(ctx, info, class.clone())?;
+                        self.gen_teq_condbr(info, err_reg, clause, loc);
+                    }
+                }
+                clauses.push(clause);
+            }
+            // no rescue clause matched.
+            self.push(BcIr::Raise(err_reg), loc);
+            for (RescueEntry { assign, body, .. }, clause) in rescue.into_iter().zip(clauses) {
+                self.apply_label(clause);
+                if let Some(box assign) = assign {
+                    match info.is_assign_local(&assign) {
+                        Some(local) => self.gen_mov(local.into(), err_reg),
+                        None => {
+                            let temp = info.temp;
+                            let lhs = self.eval_lvalue(ctx, info, &assign)?;
+                            self.gen_assign(err_reg, lhs, assign.loc);
+                            info.temp = temp;
+                        }
+                    }
+                }
+                self.retry.push((start, self.ensure.len()));
+                self.gen_begin_clause(ctx, info, ret, *body)?;
+                self.retry.pop().unwrap();
+                // the exception has been handled.
+                self.gen_nil_dollar_bang(info, loc);
+                self.gen_br(ensure_normal);
+            }
+            info.pop();
+            self.exception_table
+                .push((start, end_body, rescue_entry, err_reg, false));
+        }
+
+        self.apply_label(ensure_normal);
+        if let Some(box ensure) = ensure {
+            self.ensure.pop().unwrap();
+            self.gen_expr(ctx, info, ensure.clone(), UseMode::NotUse)?;
+            self.gen_br(exit);
+            self.apply_label(ensure_err);
+            let err_reg: BcReg = info.push().into();
+            self.gen_expr(ctx, info, ensure, UseMode::NotUse)?;
+            self.push(BcIr::Raise(err_reg), loc);
+            info.pop();
+            self.exception_table
+                .push((start, ensure_normal, ensure_err, err_reg, true));
+        }
+        self.apply_label(exit);
+        if use_mode.is_ret() {
+            self.gen_ret(info, None);
+        }
+        Ok(())
+    }
+
+    /// Clear `$!` when the exception has been handled.
+    fn gen_nil_dollar_bang(&mut self, info: &mut ISeqInfo, loc: Loc) {
+        let nil = info.push().into();
+        self.gen_nil(info, Some(nil));
+        self.push(
+            BcIr::StoreGvar {
+                val: nil,
+                name: IdentId::get_ident_id("$!"),
+            },
+            loc,
+        );
+        info.pop();
+    }
+
+    /// Generate bytecode Ir for a clause of begin, and store the result to *ret* if needed.
+    fn gen_begin_clause(
+        &mut self,
+        ctx: &mut FnStore,
+        info: &mut ISeqInfo,
+        ret: Option<BcReg>,
+        clause: Node,
+    ) -> Result<()> {
+        match ret {
+            Some(ret) => self.gen_store_expr(ctx, info, ret, clause),
+            None => self.gen_expr(ctx, info, clause, UseMode::NotUse),
+        }
+    }
+
+    ///
+    /// Generate bytecode Ir for case expression.
+    ///
+    /// If all of the *when* clauses are Integer or Symbol literals and they are dense enough,
+    /// OptCase is emitted before the chain of `===` to jump to the matched clause directly.
+    ///
+    fn gen_case(
+        &mut self,
+        ctx: &mut FnStore,
+        info: &mut ISeqInfo,
+        cond: Option<Box<Node>>,
+        when_: Vec<CaseBranch>,
+        else_: Node,
+        use_mode: UseMode,
+        loc: Loc,
+    ) -> Result<()> {
+        let else_pos = self.new_label();
+        let exit_pos = self.new_label();
+        let mut clauses = vec![];
+        if let Some(box cond) = cond {
+            let cond = self.push_expr(ctx, info, cond)?;
+            let labels: Vec<_> = when_.iter().map(|_| self.new_label()).collect();
+            if let Some((symbol, min, table)) = Self::opt_case_table(&when_) {
+                let table = table
+                    .into_iter()
+                    .map(|i| match i {
+                        Some(i) => labels[i],
+                        None => else_pos,
+                    })
+                    .collect();
+                self.push(
+                    BcIr::OptCase {
+                        cond,
+                        symbol,
+                        min,
+                        table,
+                    },
+                    loc,
+                );
+            }
+            for (CaseBranch { when, body }, clause) in when_.into_iter().zip(labels) {
+                for expr in when {
+                    let loc = expr.loc;
+                    self.push_expr(ctx, info, expr)?;
+                    self.gen_teq_condbr(info, cond, clause, loc);
+                }
+                clauses.push((clause, *body));
+            }
+            info.pop();
+        } else {
+            for CaseBranch { when, body } in when_ {
+                let clause = self.new_label();
+                for expr in when {
+                    self.gen_opt_condbr(ctx, info, true, expr, clause)?;
+                }
+                clauses.push((clause, *body));
+            }
+        }
+        self.apply_label(else_pos);
+        self.gen_expr(ctx, info, else_, use_mode)?;
+        for (clause, body) in clauses {
+            match use_mode {
+                UseMode::Ret => {}
+                UseMode::NotUse => {
+                    self.gen_br(exit_pos);
+                }
+                UseMode::Use => {
+                    self.gen_br(exit_pos);
+                    info.pop();
+                }
+            }
+            self.apply_label(clause);
+            self.gen_expr(ctx, info, body, use_mode)?;
+        }
+        self.apply_label(exit_pos);
+        Ok(())
+    }
+
+    ///
+    /// Build a jump table for case expression.
+    ///
+    /// Return Some((is_symbol, the smallest key, indices of *when* clauses for each key))
+    /// if all of the *when* clauses are Integer literals or all are Symbol literals,
+    /// and the range of keys is dense enough.
+    ///
+    fn opt_case_table(when_: &[CaseBranch]) -> Option<(bool, i64, Vec<Option<usize>>)> {
+        let mut symbol = None;
+        let mut keys = vec![];
+        for (i, CaseBranch { when, .. }) in when_.iter().enumerate() {
+            for expr in when {
+                let (is_symbol, key) = match &expr.kind {
+                    NodeKind::Integer(n) => (false, i32::try_from(*n).ok()? as i64),
+                    NodeKind::Symbol(sym) => (true, IdentId::get_ident_id(sym).get() as i64),
+                    _ => return None,
+                };
+                if *symbol.get_or_insert(is_symbol) != is_symbol {
+                    return None;
+                }
+                keys.push((key, i));
+            }
+        }
+        let min = keys.iter().map(|(key, _)| *key).min()?;
+        let max = keys.iter().map(|(key, _)| *key).max()?;
+        let len = (max - min + 1) as usize;
+        if len > 256 || len > keys.len() * 4 {
+            return None;
+        }
+        let mut table = vec![None; len];
+        for (key, i) in keys {
+            // the first matching clause wins.
+            table[(key - min) as usize].get_or_insert(i);
+        }
+        Some((symbol?, min, table))
+    }
+
+    ///
+    /// Generate bytecode Ir which calls `===` of the value on the stack top with *arg*,
+    /// and branches to *dest* if the result is truthy.
+    ///
+    fn gen_teq_condbr(&mut self, info: &mut ISeqInfo, arg: BcReg, dest: usize, loc: Loc) {
+        let lhs: BcReg = info.pop().into();
+        self.gen_call(lhs, IdentId::_TEQ, Some(lhs), arg, 1, false, loc);
+        self.gen_condbr(lhs, dest, false);
+    }
+
+    ///
+    /// Generate bytecode Ir for ensure clauses which are deeper than *depth*, innermost first.
+    ///
+    /// This is used for escaping from begin clauses by break or return.
+    ///
+    fn gen_ensure_clauses(
+        &mut self,
+        ctx: &mut FnStore,
+        info: &mut ISeqInfo,
+        depth: usize,
+    ) -> Result<()> {
+        let saved = self.ensure.clone();
+        while self.ensure.len() > depth {
+            let ensure = self.ensure.pop().unwrap();
+            self.gen_expr(ctx, info, ensure, UseMode::NotUse)?;
+        }
+        self.ensure = saved;
+        Ok(())
+    }
 }
@@ -27,6 +27,7 @@ impl IrContext {
             BinOp::LAnd => self.gen_land(ctx, info, dst, lhs, rhs),
             BinOp::LOr => self.gen_lor(ctx, info, dst, lhs, rhs),
             BinOp::Cmp(kind) => self.gen_cmp(ctx, info, dst, kind, lhs, rhs, false, loc),
+            BinOp::Match => self.gen_match(ctx, info, dst, lhs, rhs, loc),
             _ => Err(MonorubyErr::unsupported_operator(
                 op,
                 loc,
@@ -34,6 +35,20 @@ impl IrContext {
             )),
         }
     }
+
+    fn gen_match(
+        &mut self,
+        ctx: &mut FnStore,
+        info: &mut ISeqInfo,
+        dst: Option<BcReg>,
+        lhs: Node,
+        rhs: Node,
+        loc: Loc,
+    ) -> Result<BcReg> {
+        let (dst, lhs, rhs) = self.gen_binary(ctx, info, dst, lhs, rhs)?;
+        self.gen_call(lhs, IdentId::_MATCH, Some(dst), rhs, 1, false, loc);
+        Ok(dst)
+    }
 }
 
 macro_rules! gen_ops {
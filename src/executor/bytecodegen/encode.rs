@@ -59,6 +59,20 @@ impl IrContext {
                     );
                     Bc::from(op)
                 }
+                BcIr::OptCase {
+                    cond,
+                    symbol,
+                    min,
+                    table,
+                } => {
+                    let op1 = info.get_index(cond);
+                    let branch_table = table
+                        .iter()
+                        .map(|dst| self.labels[*dst].unwrap().0 as i32 - idx as i32 - 1)
+                        .collect();
+                    let op2 = info.add_optcase(store, *symbol, *min, branch_table);
+                    Bc::from(enc_wl(28, op1.0, op2.0))
+                }
                 BcIr::LoopStart => Bc::from(enc_l(14, 0)),
                 BcIr::LoopEnd => Bc::from(enc_l(15, 0)),
                 BcIr::LoadIvar(reg, name) => {
@@ -80,6 +94,28 @@ impl IrContext {
                         -1i32 as u32,
                     )
                 }
+                BcIr::Super {
+                    ret,
+                    func_id,
+                    has_block,
+                    splat,
+                } => {
+                    let op1 = match ret {
+                        None => SlotId::new(0),
+                        Some(ret) => info.get_index(ret),
+                    };
+                    let opcode = match (*splat, *has_block) {
+                        (false, false) => 30,
+                        (false, true) => 31,
+                        (true, false) => 35,
+                        (true, true) => 36,
+                    };
+                    Bc::from_with_class_and_version(
+                        enc_wl(opcode, op1.0, func_id.0),
+                        ClassId::new(0),
+                        -1i32 as u32,
+                    )
+                }
                 BcIr::CheckLocal(local, dst) => {
                     let op1 = info.get_index(local);
                     let dst = self.labels[*dst].unwrap().0 as i32;
@@ -90,10 +126,19 @@ impl IrContext {
                     let op1 = info.get_index(dst);
                     Bc::from(enc_wl(21, op1.0, 0))
                 }
+                BcIr::BlockArg(dst, src) => {
+                    let op1 = info.get_index(dst);
+                    let op2 = info.get_index(src);
+                    Bc::from(enc_wl(23, op1.0, op2.0 as u32))
+                }
                 BcIr::LoadGvar { ret, name } => {
                     let op1 = info.get_index(ret);
                     Bc::from(enc_wl(25, op1.0, name.get()))
                 }
+                BcIr::LoadSvar { ret, id } => {
+                    let op1 = info.get_index(ret);
+                    Bc::from(enc_wl(34, op1.0, id))
+                }
                 BcIr::StoreGvar { val, name } => {
                     let op1 = info.get_index(val);
                     Bc::from(enc_wl(26, op1.0, name.get()))
@@ -103,6 +148,11 @@ impl IrContext {
                     let op2 = info.get_index(src);
                     Bc::from(enc_www(131, op1.0, op2.0, *len))
                 }
+                BcIr::Hash { ret, args, len, kw } => {
+                    let op1 = info.get_index(ret);
+                    let op2 = info.get_index(args);
+                    Bc::from(enc_www(if *kw { 174 } else { 173 }, op1.0, op2.0, *len))
+                }
                 BcIr::Index(ret, base, idx) => {
                     let op1 = info.get_index(ret);
                     let op2 = info.get_index(base);
@@ -193,6 +243,15 @@ impl IrContext {
                     let op1 = info.get_index(reg);
                     Bc::from(enc_w(148, op1.0))
                 }
+                BcIr::MethodRet(reg) => {
+                    let op1 = info.get_index(reg);
+                    Bc::from(enc_w(24, op1.0))
+                }
+                BcIr::Raise(reg) => {
+                    let op1 = info.get_index(reg);
+                    Bc::from(enc_w(27, op1.0))
+                }
+                BcIr::InitKeyword => Bc::from(enc_l(29, info.id.0)),
                 BcIr::Mov(dst, src) => {
                     let op1 = info.get_index(dst);
                     let op2 = info.get_index(src);
@@ -247,11 +306,17 @@ impl IrContext {
                 BcIr::MethodDef(name, func_id) => {
                     Bc::from_with_func_name_id(enc_l(2, 0), *name, *func_id)
                 }
+                BcIr::SingletonMethodDef { obj, name, func_id } => {
+                    let op1 = info.get_index(obj);
+                    Bc::from_with_func_name_id(enc_wl(33, op1.0, 0), *name, *func_id)
+                }
                 BcIr::ClassDef {
                     ret,
+                    base,
                     superclass,
                     name,
                     func_id,
+                    is_module,
                 } => {
                     let op1 = match ret {
                         None => SlotId::new(0),
@@ -261,7 +326,28 @@ impl IrContext {
                         None => SlotId::new(0),
                         Some(ret) => info.get_index(ret),
                     };
-                    Bc::from_with_func_name_id(enc_wl(18, op1.0, op2.0 as u32), *name, *func_id)
+                    let op3 = match base {
+                        None => SlotId::new(0),
+                        Some(base) => info.get_index(base),
+                    };
+                    let opcode = if *is_module { 22 } else { 18 };
+                    Bc::from_with_func_name_id(
+                        enc_www(opcode, op1.0, op2.0, op3.0),
+                        *name,
+                        *func_id,
+                    )
+                }
+                BcIr::SingletonClassDef { ret, base, func_id } => {
+                    let op1 = match ret {
+                        None => SlotId::new(0),
+                        Some(ret) => info.get_index(ret),
+                    };
+                    let op3 = info.get_index(base);
+                    Bc::from_with_func_name_id(
+                        enc_www(32, op1.0, 0, op3.0),
+                        IdentId::default(),
+                        *func_id,
+                    )
                 }
                 BcIr::ConcatStr(ret, arg, len) => {
                     let op1 = ret.map_or(SlotId::self_(), |ret| info.get_index(&ret));
@@ -279,6 +365,21 @@ impl IrContext {
         }
         info.set_bytecode(ops);
         info.sourcemap = locs;
+        info.exception_map = self
+            .exception_table
+            .iter()
+            .map(|(start, end, dest, err_reg, ensure)| {
+                let start = self.labels[*start].unwrap().0 as usize;
+                let end = self.labels[*end].unwrap().0 as usize;
+                let dest = self.labels[*dest].unwrap().0 as usize;
+                ExceptionMapEntry {
+                    range: start..end,
+                    dest,
+                    err_reg: info.get_index(err_reg),
+                    ensure: *ensure,
+                }
+            })
+            .collect();
     }
 }
 
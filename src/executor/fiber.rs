@@ -0,0 +1,381 @@
+use super::*;
+
+/// The size of the machine stack of a Fiber.
+const FIBER_STACK_SIZE: usize = 1024 * 1024;
+
+///
+/// The machine stack of a Fiber.
+///
+/// The stack is mapped with a guard page below it, so that a stack overflow causes
+/// a segmentation fault instead of corrupting the heap.
+///
+struct FiberStack {
+    /// the lowest address of the mapping, where the guard page is placed.
+    ptr: *mut libc::c_void,
+    page_size: usize,
+}
+
+impl Drop for FiberStack {
+    fn drop(&mut self) {
+        unsafe { libc::munmap(self.ptr, self.page_size + FIBER_STACK_SIZE) };
+    }
+}
+
+impl FiberStack {
+    fn new() -> Self {
+        let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) } as usize;
+        let ptr = unsafe {
+            libc::mmap(
+                std::ptr::null_mut(),
+                page_size + FIBER_STACK_SIZE,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_PRIVATE | libc::MAP_ANONYMOUS | libc::MAP_STACK,
+                -1,
+                0,
+            )
+        };
+        if ptr == libc::MAP_FAILED {
+            panic!("failed to allocate the stack of a Fiber.");
+        }
+        if unsafe { libc::mprotect(ptr, page_size, libc::PROT_NONE) } != 0 {
+            panic!("failed to set the guard page of the stack of a Fiber.");
+        }
+        Self { ptr, page_size }
+    }
+
+    /// the lowest address of the usable stack.
+    fn bottom(&self) -> usize {
+        self.ptr as usize + self.page_size
+    }
+
+    /// the highest address of the stack (exclusive).
+    fn top(&self) -> usize {
+        self.bottom() + FIBER_STACK_SIZE
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FiberState {
+    Created,
+    Suspended,
+    Running,
+    Terminated,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum FiberBody {
+    /// the block given to `Fiber.new`.
+    Proc(Value),
+    /// the external iteration of the Enumerator.
+    Enumerator(Value),
+}
+
+///
+/// The part of the Executor state which belongs to each machine stack.
+///
+#[derive(Default)]
+struct FiberContext {
+    cfp: CFP,
+    temp_stack: Vec<Value>,
+    collectors: Vec<Collector>,
+    indexers: Vec<Indexer>,
+}
+
+impl GC<RValue> for FiberContext {
+    fn mark(&self, alloc: &mut Allocator<RValue>) {
+        let mut cfp = self.cfp;
+        while !cfp.is_null() {
+            cfp.mark(alloc);
+            cfp = cfp.next();
+        }
+        self.temp_stack.iter().for_each(|v| v.mark(alloc));
+        self.collectors.iter().for_each(|c| c.ary.mark(alloc));
+        self.indexers.iter().for_each(|i| i.block.mark(alloc));
+    }
+}
+
+///
+/// Fiber object.
+///
+/// A Fiber runs on its own machine stack. `Executor::fiber_resume` and `Executor::fiber_yield`
+/// switch the machine stack and the context of the Executor (CFP chain and the values held by
+/// native functions) between the Fiber and the resumer.
+///
+/// The frames of the Fiber form a separate CFP chain terminated by null, so the Executor
+/// only sees the frames of the running stack. The frames of the other side are marked via
+/// *context*.
+///
+pub struct FiberInner {
+    state: FiberState,
+    body: FiberBody,
+    /// allocated when the Fiber is resumed for the first time.
+    stack: Option<FiberStack>,
+    /// saved stack pointer of the suspended side.
+    rsp: usize,
+    /// saved context of the suspended side, which is the Fiber itself while it is suspended,
+    /// and the resumer while it is running.
+    context: FiberContext,
+    /// the arguments of the first `resume`, which are passed to the block.
+    args: Vec<Value>,
+    /// the value passed by `resume` and `Fiber.yield`, or the result of the body.
+    transfer: Value,
+    /// true if the body was terminated by an error.
+    error: bool,
+    /// true if frames on the stack may have been captured by Procs.
+    has_escaped: bool,
+    /// the frames captured by Procs, shared with the Executor.
+    escaped: Rc<RefCell<HashMap<usize, EscapedFrame>>>,
+}
+
+impl std::fmt::Debug for FiberInner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Fiber({:?})", self.state)
+    }
+}
+
+impl GC<RValue> for FiberInner {
+    fn mark(&self, alloc: &mut Allocator<RValue>) {
+        match self.body {
+            FiberBody::Proc(v) | FiberBody::Enumerator(v) => v.mark(alloc),
+        }
+        self.context.mark(alloc);
+        self.args.iter().for_each(|v| v.mark(alloc));
+        self.transfer.mark(alloc);
+    }
+}
+
+impl Drop for FiberInner {
+    fn drop(&mut self) {
+        // frames captured by Procs are moved to the heap only when they exit, so the captured
+        // frames on the stack of a Fiber suspended forever are moved here. (inner frames first)
+        if !self.has_escaped || self.state == FiberState::Terminated {
+            return;
+        }
+        if let Some(stack) = &self.stack {
+            let mut escaped = self.escaped.borrow_mut();
+            let mut frames: Vec<_> = escaped
+                .keys()
+                .filter(|bp| (stack.bottom()..stack.top()).contains(*bp))
+                .cloned()
+                .collect();
+            frames.sort_unstable();
+            for bp in frames {
+                exit_escaped_frame(&mut escaped, bp);
+            }
+        }
+    }
+}
+
+impl FiberInner {
+    pub(crate) fn new(body: FiberBody) -> Self {
+        Self {
+            state: FiberState::Created,
+            body,
+            stack: None,
+            rsp: 0,
+            context: FiberContext::default(),
+            args: vec![],
+            transfer: Value::nil(),
+            error: false,
+            has_escaped: false,
+            escaped: Rc::default(),
+        }
+    }
+
+    pub(crate) fn state(&self) -> FiberState {
+        self.state
+    }
+
+    pub(crate) fn body(&self) -> FiberBody {
+        self.body
+    }
+
+    ///
+    /// Allocate the machine stack, and prepare it so that the first switch to the Fiber
+    /// starts `fiber_main`.
+    ///
+    fn init_stack(&mut self, vm: &mut Executor, globals: &mut Globals) {
+        let stack = FiberStack::new();
+        let top = stack.top() & !0xf;
+        //
+        //       +-------------+
+        //  0x00 |             | <- top
+        //       +-------------+
+        // -0x08 |             |
+        //       +-------------+
+        // -0x10 |             |
+        //       +-------------+
+        // -0x18 | fiber_start |
+        //       +-------------+
+        // -0x20 | rbp (null)  |
+        //       +-------------+
+        // -0x28 | rbx: vm     |
+        //       +-------------+
+        // -0x30 | r12: globals|
+        //       +-------------+
+        // -0x38 | r13: fiber  |
+        //       +-------------+
+        // -0x40 |     r14     |
+        //       +-------------+
+        // -0x48 |     r15     | <- rsp
+        //       +-------------+
+        //
+        // rsp is aligned to 16 bytes when fiber_start is entered.
+        let rsp = top - 0x48;
+        let regs = [
+            0,
+            0,
+            self as *mut Self as u64,
+            globals as *mut Globals as u64,
+            vm as *mut Executor as u64,
+            0,
+            globals.codegen.fiber_start as u64,
+        ];
+        for (i, reg) in regs.into_iter().enumerate() {
+            unsafe { *((rsp + i * 8) as *mut u64) = reg };
+        }
+        self.rsp = rsp;
+        self.stack = Some(stack);
+        self.escaped = vm.escaped.clone();
+    }
+}
+
+///
+/// Pack the values passed by `resume` or `Fiber.yield`.
+///
+fn pack_values(args: &[Value]) -> Value {
+    match args.len() {
+        0 => Value::nil(),
+        1 => args[0],
+        _ => Value::new_array_from_vec(args.to_vec()),
+    }
+}
+
+///
+/// The entry point of Fibers, which runs on the stack of the Fiber.
+///
+/// This never returns, and switches back to the resumer after the body finished.
+///
+pub(super) extern "C" fn fiber_main(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    fiber: *mut FiberInner,
+) -> ! {
+    let fiber = unsafe { &mut *fiber };
+    let res = match fiber.body {
+        FiberBody::Proc(proc) => {
+            let args = fiber.args.clone();
+            vm.invoke_block(globals, proc, Value::nil(), &args)
+        }
+        FiberBody::Enumerator(e) => {
+            if e.as_enumerator().is_generator {
+                let proc = e.as_enumerator().obj;
+                let yielder = Value::new_object(YIELDER_CLASS);
+                vm.with_temp(yielder, |vm| {
+                    vm.invoke_block(globals, proc, Value::nil(), &[yielder])
+                })
+            } else {
+                // the outer frame of the block is the frame of `each` itself.
+                let func_id = globals.func.fiber_yielder();
+                let block = Value::new_integer((func_id.0 as i64) << 16);
+                vm.invoke_method_with_block(globals, IdentId::EACH, e, &[], Some(block))
+            }
+        }
+    };
+    match res {
+        Some(val) => fiber.transfer = val,
+        None => {
+            fiber.transfer = Value::nil();
+            fiber.error = true;
+        }
+    }
+    fiber.args = vec![];
+    fiber.state = FiberState::Terminated;
+    vm.switch_fiber(globals, fiber);
+    unreachable!("a terminated fiber was resumed.")
+}
+
+impl Executor {
+    ///
+    /// Resume *fiber* with *args*.
+    ///
+    /// Returns the value passed to `Fiber.yield`, or the result of the body if the Fiber
+    /// finished.
+    ///
+    pub(crate) fn fiber_resume(
+        &mut self,
+        globals: &mut Globals,
+        mut fiber: Value,
+        args: &[Value],
+    ) -> Option<Value> {
+        let inner = unsafe { &mut *(fiber.as_fiber_mut() as *mut FiberInner) };
+        match inner.state {
+            FiberState::Created => {
+                inner.init_stack(self, globals);
+                inner.args = args.to_vec();
+            }
+            FiberState::Suspended => inner.transfer = pack_values(args),
+            FiberState::Running => {
+                if self.fibers.last() == Some(&fiber) {
+                    globals.err_fiber("attempt to resume the current fiber");
+                } else {
+                    globals.err_fiber("attempt to resume a resuming fiber");
+                }
+                return None;
+            }
+            FiberState::Terminated => {
+                globals.err_fiber("attempt to resume a terminated fiber");
+                return None;
+            }
+        }
+        inner.state = FiberState::Running;
+        self.fibers.push(fiber);
+        self.switch_fiber(globals, inner);
+        self.fibers.pop();
+        if inner.error {
+            inner.error = false;
+            return None;
+        }
+        Some(inner.transfer)
+    }
+
+    ///
+    /// Suspend the current Fiber and pass *val* to the resumer.
+    ///
+    /// Returns the value passed to the next `resume`.
+    ///
+    pub(crate) fn fiber_yield(&mut self, globals: &mut Globals, val: Value) -> Option<Value> {
+        let mut fiber = match self.fibers.last() {
+            Some(fiber) => *fiber,
+            None => {
+                globals.err_fiber("can't yield from root fiber");
+                return None;
+            }
+        };
+        let inner = unsafe { &mut *(fiber.as_fiber_mut() as *mut FiberInner) };
+        inner.transfer = val;
+        inner.state = FiberState::Suspended;
+        self.switch_fiber(globals, inner);
+        Some(inner.transfer)
+    }
+
+    ///
+    /// Mark the running Fibers as having frames captured by Procs.
+    ///
+    fn set_fibers_escaped(&mut self) {
+        for mut fiber in self.fibers.iter().cloned() {
+            fiber.as_fiber_mut().has_escaped = true;
+        }
+    }
+
+    ///
+    /// Switch the machine stack and the context between the current side and *fiber*.
+    ///
+    fn switch_fiber(&mut self, globals: &Globals, fiber: &mut FiberInner) {
+        std::mem::swap(&mut self.cfp, &mut fiber.context.cfp);
+        std::mem::swap(&mut self.temp_stack, &mut fiber.context.temp_stack);
+        std::mem::swap(&mut self.collectors, &mut fiber.context.collectors);
+        std::mem::swap(&mut self.indexers, &mut fiber.context.indexers);
+        (globals.codegen.fiber_switch)(&mut fiber.rsp);
+    }
+}
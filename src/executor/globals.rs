@@ -24,6 +24,7 @@ pub enum InlineMethod {
     MathSqrt,
     MathCos,
     MathSin,
+    RangeEach,
 }
 
 ///
@@ -48,6 +49,10 @@ pub struct Globals {
     stdout: BufWriter<Stdout>,
     /// library directries.
     pub lib_directories: Vec<String>,
+    /// *main* object.
+    main_object: Value,
+    /// the executor which is running now. (root set of GC)
+    executor: *const Executor,
     #[cfg(feature = "log-jit")]
     /// stats for deoptimization
     pub deopt_stats: HashMap<(FuncId, usize), usize>,
@@ -66,6 +71,8 @@ impl Globals {
             no_jit,
             stdout: BufWriter::new(stdout()),
             lib_directories: vec![],
+            main_object,
+            executor: std::ptr::null(),
             #[cfg(feature = "log-jit")]
             deopt_stats: HashMap::default(),
         };
@@ -105,10 +112,30 @@ impl Globals {
         }
     }
 
+    ///
+    /// Set *executor* and `self` as the root set of GC.
+    ///
+    /// If *executor* is null, GC never runs at allocation.
+    /// Returns the previous executor.
+    ///
+    pub(crate) fn set_gc_root(&mut self, executor: *const Executor) -> *const Executor {
+        let root = if executor.is_null() {
+            None
+        } else {
+            Some(&*self as &dyn GCRoot<RValue> as *const _)
+        };
+        ALLOC.with(|alloc| alloc.borrow_mut().set_root(root));
+        std::mem::replace(&mut self.executor, executor)
+    }
+
     pub(crate) fn class_version_inc(&mut self) {
         unsafe { *self.codegen.class_version_addr += 1 }
     }
 
+    pub(crate) fn const_version_inc(&mut self) {
+        unsafe { *self.codegen.const_version_addr += 1 }
+    }
+
     pub fn exec_startup(&mut self) {
         // load library path
         let load_path = include_str!(concat!(env!("OUT_DIR"), "/libpath.rb"));
@@ -265,18 +292,41 @@ impl Globals {
 
     pub(crate) fn get_block_data(&mut self, block_handler: Value, interp: &Executor) -> BlockData {
         if let Some(bh) = block_handler.try_fixnum() {
-            let func_id = FuncId(u32::try_from((bh as u64) >> 16).unwrap());
-            let mut cfp = interp.cfp;
-            for _ in 0..bh as i16 as u16 {
-                cfp = cfp.next();
-            }
+            let (func_id, outer_cfp) = interp.decode_block_handler(bh);
             let func_data = self.compile_on_demand(func_id) as _;
             return BlockData {
-                outer_cfp: cfp,
+                outer_cfp,
                 func_data,
             };
         }
-        unreachable!()
+        let mut block_handler = block_handler;
+        let proc = block_handler.as_proc_mut();
+        let data = self.compile_on_demand(proc.func_id());
+        BlockData {
+            outer_cfp: proc.env().outer_cfp(),
+            func_data: proc.func_data(data),
+        }
+    }
+}
+
+impl GC<RValue> for Globals {
+    fn mark(&self, alloc: &mut Allocator<RValue>) {
+        self.main_object.mark(alloc);
+        self.global_vars.values().for_each(|v| v.mark(alloc));
+        self.class.mark(alloc);
+        self.func.mark(alloc);
+        if let Some(err) = &self.error {
+            err.mark(alloc);
+        }
+        if let Some(executor) = unsafe { self.executor.as_ref() } {
+            executor.mark(alloc);
+        }
+    }
+}
+
+impl GCRoot<RValue> for Globals {
+    fn startup_flag(&self) -> bool {
+        true
     }
 }
 
@@ -302,9 +352,30 @@ impl Globals {
         }
     }
 
+    fn hash_tos(&self, v: &HashInner) -> String {
+        match v.len() {
+            0 => "{}".to_string(),
+            _ => {
+                let mut first = true;
+                let mut s = "{".to_string();
+                for (k, v) in v.iter() {
+                    if !first {
+                        s += ", ";
+                    }
+                    s += &format!("{}=>{}", self.val_inspect(k), self.val_inspect(v));
+                    first = false;
+                }
+                s += "}";
+                s
+            }
+        }
+    }
+
     fn object_tos(&self, val: Value) -> String {
         if let Some(name) = self.get_ivar(val, IdentId::_NAME) {
             self.val_tos(name)
+        } else if let Some(members) = self.struct_members(self.get_real_class_id(val)) {
+            self.struct_inspect(val, &members)
         } else {
             format!(
                 "#<{}:0x{:016x}>",
@@ -317,6 +388,8 @@ impl Globals {
     fn object_inspect(&self, val: Value) -> String {
         if let Some(name) = self.get_ivar(val, IdentId::_NAME) {
             self.val_tos(name)
+        } else if let Some(members) = self.struct_members(self.get_real_class_id(val)) {
+            self.struct_inspect(val, &members)
         } else {
             let mut s = String::new();
             for (id, v) in self.get_ivars(val).into_iter() {
@@ -330,6 +403,98 @@ impl Globals {
         }
     }
 
+    fn struct_inspect(&self, val: Value, members: &[IdentId]) -> String {
+        let class_id = self.get_real_class_id(val);
+        let mut s = "#<struct ".to_string();
+        if !class_id.is_anonymous(self) {
+            s += &format!("{} ", class_id.get_name(self));
+        }
+        let fields: Vec<_> = members
+            .iter()
+            .map(|m| {
+                let v = self
+                    .get_ivar(val, IdentId::add_ivar_prefix(*m))
+                    .unwrap_or_default();
+                format!("{}={}", IdentId::get_name(*m), self.val_inspect(v))
+            })
+            .collect();
+        s += &fields.join(", ");
+        s += ">";
+        s
+    }
+
+    ///
+    /// Get the hidden instance variable *name* of the class generated by `Struct.new`.
+    ///
+    /// The variable is searched in *class_id* and its super classes, and None is returned
+    /// if *class_id* is not a Struct class.
+    ///
+    pub(crate) fn get_struct_ivar(&self, class_id: ClassId, name: IdentId) -> Option<Value> {
+        let mut class_id = Some(class_id);
+        while let Some(id) = class_id {
+            if let Some(v) = self.get_ivar(id.get_obj(self), name) {
+                return Some(v);
+            }
+            class_id = id.super_class(self);
+        }
+        None
+    }
+
+    ///
+    /// Get the members of the Struct class *class_id*.
+    ///
+    pub(crate) fn struct_members(&self, class_id: ClassId) -> Option<Vec<IdentId>> {
+        let members = self.get_struct_ivar(class_id, IdentId::_MEMBERS)?;
+        Some(members.as_array().iter().map(|m| m.as_symbol()).collect())
+    }
+
+    fn proc_tos(&self, val: Value) -> String {
+        format!(
+            "#<Proc:0x{:016x}{}>",
+            val.rvalue().id(),
+            if val.as_proc().is_lambda() {
+                " (lambda)"
+            } else {
+                ""
+            }
+        )
+    }
+
+    fn fiber_tos(&self, val: Value) -> String {
+        let state = match val.is_fiber().unwrap().state() {
+            FiberState::Created => "created",
+            FiberState::Suspended => "suspended",
+            FiberState::Running => "resumed",
+            FiberState::Terminated => "terminated",
+        };
+        format!("#<Fiber:0x{:016x} ({state})>", val.rvalue().id())
+    }
+
+    fn enumerator_inspect(&self, val: Value) -> String {
+        let e = val.as_enumerator();
+        let class_name = val.get_real_class_name(self);
+        if e.is_generator {
+            return format!(
+                "#<{class_name}: #<Enumerator::Generator:0x{:016x}>:each>",
+                e.obj.rvalue().id()
+            );
+        }
+        if self.get_real_class_id(val) == LAZY_CLASS {
+            return format!("#<{class_name}: {}>", self.val_inspect(e.obj));
+        }
+        let args = if e.args.is_empty() {
+            String::new()
+        } else {
+            let args: Vec<_> = e.args.iter().map(|v| self.val_inspect(*v)).collect();
+            format!("({})", args.join(", "))
+        };
+        format!(
+            "#<{class_name}: {}:{}{args}>",
+            self.val_inspect(e.obj),
+            IdentId::get_name(e.method)
+        )
+    }
+
     fn range_inspect(&self, val: Value) -> String {
         let range = val.as_range();
         format!(
@@ -340,6 +505,105 @@ impl Globals {
         )
     }
 
+    ///
+    /// Inspect the Symbol *name*. A name which can not be written as a Symbol literal is quoted.
+    ///
+    fn symbol_inspect(name: &str) -> String {
+        const OPERATORS: &[&str] = &[
+            "+", "-", "*", "/", "%", "**", "==", "===", "!=", "=~", "!~", "!", "<", ">", "<=",
+            ">=", "<=>", "<<", ">>", "&", "|", "^", "~", "+@", "-@", "[]", "[]=", "`",
+        ];
+        fn is_ident(s: &str) -> bool {
+            let mut chars = s.chars();
+            match chars.next() {
+                Some(c) if c.is_ascii_digit() => false,
+                Some(_) => s
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || c == '_' || !c.is_ascii()),
+                None => false,
+            }
+        }
+        let plain = OPERATORS.contains(&name)
+            || match name.as_bytes().first() {
+                Some(b'@') => is_ident(name.strip_prefix("@@").unwrap_or(&name[1..])),
+                Some(b'$') => is_ident(&name[1..]),
+                _ => {
+                    let ident = name
+                        .strip_suffix(|c| c == '?' || c == '!' || c == '=')
+                        .unwrap_or(name);
+                    is_ident(ident)
+                }
+            };
+        if plain {
+            format!(":{}", name)
+        } else {
+            format!(":\"{}\"", escape_string::escape(name))
+        }
+    }
+
+    fn bytes_inspect(bytes: &[u8]) -> String {
+        match String::from_utf8(bytes.to_vec()) {
+            Ok(s) => format!("\"{}\"", escape_string::escape(&s)),
+            Err(_) => format!("{:?}", bytes),
+        }
+    }
+
+    fn match_data_inspect(&self, val: Value) -> String {
+        let md = val.as_match_data();
+        let names = md.regexp().as_regexp().regex().names();
+        let mut s = format!(
+            "#<MatchData {}",
+            Self::bytes_inspect(md.group(0).unwrap_or_default())
+        );
+        for i in 1..md.len() {
+            let name = match names.iter().find(|(_, indices)| indices.contains(&i)) {
+                Some((name, _)) => name.to_string(),
+                None => i.to_string(),
+            };
+            let group = match md.group(i) {
+                Some(bytes) => Self::bytes_inspect(bytes),
+                None => "nil".to_string(),
+            };
+            s += &format!(" {}:{}", name, group);
+        }
+        s + ">"
+    }
+
+    fn rational_tos(r: &num::BigRational) -> String {
+        format!("{}/{}", r.numer(), r.denom())
+    }
+
+    fn complex_tos(&self, complex: &ComplexInner, inspect: bool) -> String {
+        use num::{BigInt, Signed};
+        let tos = |v| {
+            if inspect {
+                self.val_inspect(v)
+            } else {
+                self.val_tos(v)
+            }
+        };
+        let (negative, im) = match complex.im.unpack() {
+            RV::Integer(i) => (i < 0, Value::new_bigint(BigInt::from(i).abs())),
+            RV::BigInt(b) => (b.is_negative(), Value::new_bigint(b.abs())),
+            RV::Float(f) => (f.is_sign_negative(), Value::new_float(f.abs())),
+            _ => {
+                let r = complex.im.as_rational();
+                (r.is_negative(), Value::new_rational(r.abs()))
+            }
+        };
+        let mut s = format!(
+            "{}{}{}",
+            tos(complex.re),
+            if negative { '-' } else { '+' },
+            tos(im)
+        );
+        if !s.ends_with(|c: char| c.is_ascii_digit()) {
+            s.push('*');
+        }
+        s.push('i');
+        s
+    }
+
     pub(crate) fn val_tos(&self, val: Value) -> String {
         match val.unpack() {
             RV::None => "Undef".to_string(),
@@ -354,10 +618,22 @@ impl Globals {
                 Err(_) => format!("{:?}", s),
             },
             RV::Object(rvalue) => match rvalue.kind() {
-                ObjKind::CLASS => rvalue.as_class().get_name(self),
+                ObjKind::CLASS | ObjKind::MODULE => rvalue.as_class().get_name(self),
                 ObjKind::TIME => rvalue.as_time().to_string(),
                 ObjKind::ARRAY => self.array_tos(rvalue.as_array()),
+                ObjKind::HASH => self.hash_tos(rvalue.as_hash()),
                 ObjKind::OBJECT => self.object_tos(val),
+                ObjKind::EXCEPTION => rvalue.as_exception().get_error_message(self),
+                ObjKind::PROC => self.proc_tos(val),
+                ObjKind::REGEXP => rvalue.as_regexp().tos(),
+                ObjKind::RATIONAL => Self::rational_tos(rvalue.as_rational()),
+                ObjKind::COMPLEX => self.complex_tos(rvalue.as_complex(), false),
+                ObjKind::MATCHDATA => {
+                    String::from_utf8_lossy(val.as_match_data().group(0).unwrap_or_default())
+                        .into_owned()
+                }
+                ObjKind::FIBER => self.fiber_tos(val),
+                ObjKind::ENUMERATOR => self.enumerator_inspect(val),
                 _ => format!("{:016x}", val.get()),
             },
         }
@@ -378,24 +654,43 @@ impl Globals {
             RV::Integer(n) => format!("{}", n),
             RV::BigInt(n) => format!("{}", n),
             RV::Float(f) => dtoa::Buffer::new().format(f).to_string(),
-            RV::Symbol(id) => format!(":{}", IdentId::get_name(id)),
+            RV::Symbol(id) => Self::symbol_inspect(&IdentId::get_name(id)),
             RV::String(s) => match String::from_utf8(s.to_vec()) {
                 Ok(s) => format!("\"{}\"", escape_string::escape(&s)),
                 Err(_) => format!("{:?}", s),
             },
             RV::Object(rvalue) => match rvalue.kind() {
-                ObjKind::CLASS => rvalue.as_class().get_name(self),
+                ObjKind::CLASS | ObjKind::MODULE => rvalue.as_class().get_name(self),
                 ObjKind::TIME => rvalue.as_time().to_string(),
                 ObjKind::ARRAY => self.array_tos(rvalue.as_array()),
+                ObjKind::HASH => self.hash_tos(rvalue.as_hash()),
                 ObjKind::OBJECT => self.object_inspect(val),
                 ObjKind::RANGE => self.range_inspect(val),
+                ObjKind::EXCEPTION => format!(
+                    "#<{}: {}>",
+                    val.get_real_class_name(self),
+                    rvalue.as_exception().get_error_message(self)
+                ),
+                ObjKind::PROC => self.proc_tos(val),
+                ObjKind::REGEXP => rvalue.as_regexp().inspect(),
+                ObjKind::MATCHDATA => self.match_data_inspect(val),
+                ObjKind::RATIONAL => format!("({})", Self::rational_tos(rvalue.as_rational())),
+                ObjKind::COMPLEX => format!("({})", self.complex_tos(rvalue.as_complex(), true)),
+                ObjKind::FIBER => self.fiber_tos(val),
+                ObjKind::ENUMERATOR => self.enumerator_inspect(val),
                 _ => unreachable!("{:016x}", val.get()),
             },
         }
     }
 
     pub fn generate_range(&mut self, start: Value, end: Value, exclude_end: bool) -> Option<Value> {
-        if start.get_real_class_id(self) != end.get_real_class_id(self) {
+        let is_real = |v: Value| v.is_numeric() || v.is_rational().is_some();
+        // Beginless or endless ranges, and ranges of mixed real numbers are allowed.
+        if !start.is_nil()
+            && !end.is_nil()
+            && !(is_real(start) && is_real(end))
+            && start.get_real_class_id(self) != end.get_real_class_id(self)
+        {
             self.err_bad_range(start, end);
             return None;
         }
@@ -403,17 +698,7 @@ impl Globals {
     }
 
     pub(crate) fn find_method(&mut self, obj: Value, name: IdentId) -> Option<FuncId> {
-        let mut class_id = obj.class_id();
-        if let Some(func_id) = self.get_method(class_id, name) {
-            return Some(func_id);
-        }
-        while let Some(super_class) = class_id.super_class(self) {
-            class_id = super_class;
-            if let Some(func_id) = self.get_method(class_id, name) {
-                return Some(func_id);
-            }
-        }
-        None
+        self.find_method_for_class(obj.class_id(), name)
     }
 
     pub(crate) fn find_method_checked(
@@ -433,6 +718,85 @@ impl Globals {
         Some(func_id)
     }
 
+    ///
+    /// Find the method *func_name* for a method call with the receiver *obj*, checking its visibility.
+    ///
+    /// *is_func_call* is true if the receiver is *self* (e.g. `foo` or `self.foo`), where private
+    /// methods can be called. Protected methods can also be called if *caller*, *self* of the
+    /// calling method, is a kind of the owner of the method.
+    ///
+    pub(crate) fn find_method_for_call(
+        &mut self,
+        obj: Value,
+        func_name: IdentId,
+        args_len: usize,
+        caller: Value,
+        is_func_call: bool,
+    ) -> Option<FuncId> {
+        let entry = match self.find_method_entry_for_class(obj.class_id(), func_name) {
+            Some(entry) => entry,
+            None => {
+                self.err_method_not_found(func_name, obj);
+                return None;
+            }
+        };
+        let accessible = match entry.visibility {
+            Visibility::Public => true,
+            Visibility::Private => is_func_call,
+            Visibility::Protected => {
+                is_func_call || self.is_subclass_of(caller.class_id(), entry.owner)
+            }
+        };
+        if !accessible {
+            self.err_method_not_accessible(func_name, obj, entry.visibility);
+            return None;
+        }
+        self.check_arg(entry.func_id, args_len)?;
+        Some(entry.func_id)
+    }
+
+    ///
+    /// Find the method which `super` in the method *func_id* calls for the receiver *obj*.
+    ///
+    pub(crate) fn find_super_checked(
+        &mut self,
+        obj: Value,
+        func_id: FuncId,
+        args_len: usize,
+    ) -> Option<FuncId> {
+        let (owner, name) = match self.super_owner(func_id) {
+            Some(res) => res,
+            None => {
+                self.err_runtime("super called outside of method".to_string());
+                return None;
+            }
+        };
+        let func_id = match self.find_super_method_for_class(obj.class_id(), owner, name) {
+            Some(id) => id,
+            None => {
+                self.err_no_super_method(name, obj);
+                return None;
+            }
+        };
+        self.check_arg(func_id, args_len)?;
+        Some(func_id)
+    }
+
+    ///
+    /// Get the owner class and the name of the method *func_id* for `super`.
+    ///
+    pub(crate) fn super_owner(&self, func_id: FuncId) -> Option<(ClassId, IdentId)> {
+        let func = &self.func[func_id];
+        match &func.kind {
+            FuncKind::ISeq(info) => {
+                let owner = info.owner?;
+                let name = IdentId::get_ident_id(func.name()?);
+                Some((owner, name))
+            }
+            _ => None,
+        }
+    }
+
     pub(crate) fn check_arg(&mut self, func_id: FuncId, args_len: usize) -> Option<()> {
         let arity = self.func[func_id].arity();
         if arity != -1 && (arity as usize) != args_len {
@@ -535,7 +899,8 @@ impl Globals {
 
     pub fn compile_script(&mut self, code: String, path: impl Into<PathBuf>) -> Result<FuncId> {
         match Parser::parse_program(code, path.into()) {
-            Ok(res) => self.func.compile_script(res.node, res.source_info),
+            // literal values are not rooted until the bytecode is generated.
+            Ok(res) => without_gc(|| self.func.compile_script(res.node, res.source_info)),
             Err(err) => Err(MonorubyErr::parse(err)),
         }
     }
@@ -549,7 +914,7 @@ impl Globals {
         match Parser::parse_program_binding(code, path.into(), context, None) {
             Ok(res) => {
                 let collector = res.lvar_collector;
-                let fid = self.func.compile_script(res.node, res.source_info)?;
+                let fid = without_gc(|| self.func.compile_script(res.node, res.source_info))?;
                 Ok((fid, collector))
             }
             Err(err) => Err(MonorubyErr::parse(err)),
@@ -16,6 +16,39 @@ pub const SYMBOL_CLASS: ClassId = ClassId::new(9);
 pub const TIME_CLASS: ClassId = ClassId::new(10);
 pub const ARRAY_CLASS: ClassId = ClassId::new(11);
 pub const RANGE_CLASS: ClassId = ClassId::new(12);
+pub const EXCEPTION_CLASS: ClassId = ClassId::new(13);
+pub const STANDARD_ERROR_CLASS: ClassId = ClassId::new(14);
+pub const RUNTIME_ERROR_CLASS: ClassId = ClassId::new(15);
+pub const NAME_ERROR_CLASS: ClassId = ClassId::new(16);
+pub const NO_METHOD_ERROR_CLASS: ClassId = ClassId::new(17);
+pub const ZERO_DIVISION_ERROR_CLASS: ClassId = ClassId::new(18);
+pub const TYPE_ERROR_CLASS: ClassId = ClassId::new(19);
+pub const INDEX_ERROR_CLASS: ClassId = ClassId::new(20);
+pub const FROZEN_ERROR_CLASS: ClassId = ClassId::new(21);
+pub const SCRIPT_ERROR_CLASS: ClassId = ClassId::new(22);
+pub const LOAD_ERROR_CLASS: ClassId = ClassId::new(23);
+pub const ARGUMENT_ERROR_CLASS: ClassId = ClassId::new(24);
+pub const RANGE_ERROR_CLASS: ClassId = ClassId::new(25);
+pub const HASH_CLASS: ClassId = ClassId::new(26);
+pub const KEY_ERROR_CLASS: ClassId = ClassId::new(27);
+pub const MODULE_CLASS: ClassId = ClassId::new(28);
+pub const COMPARABLE_MODULE: ClassId = ClassId::new(29);
+pub const ENUMERABLE_MODULE: ClassId = ClassId::new(30);
+pub const PROC_CLASS: ClassId = ClassId::new(31);
+pub const LOCAL_JUMP_ERROR_CLASS: ClassId = ClassId::new(32);
+pub const REGEXP_CLASS: ClassId = ClassId::new(33);
+pub const MATCHDATA_CLASS: ClassId = ClassId::new(34);
+pub const REGEXP_ERROR_CLASS: ClassId = ClassId::new(35);
+pub const NUMERIC_CLASS: ClassId = ClassId::new(36);
+pub const RATIONAL_CLASS: ClassId = ClassId::new(37);
+pub const COMPLEX_CLASS: ClassId = ClassId::new(38);
+pub const FIBER_CLASS: ClassId = ClassId::new(39);
+pub const FIBER_ERROR_CLASS: ClassId = ClassId::new(40);
+pub const ENUMERATOR_CLASS: ClassId = ClassId::new(41);
+pub const YIELDER_CLASS: ClassId = ClassId::new(42);
+pub const LAZY_CLASS: ClassId = ClassId::new(43);
+pub const STOP_ITERATION_CLASS: ClassId = ClassId::new(44);
+pub const STRUCT_CLASS: ClassId = ClassId::new(45);
 
 #[derive(Clone, Copy, PartialEq, Eq, Default)]
 #[repr(transparent)]
@@ -54,6 +87,39 @@ impl std::fmt::Debug for ClassId {
             9 => write!(f, "SYMBOL"),
             10 => write!(f, "TIME"),
             11 => write!(f, "RANGE"),
+            13 => write!(f, "EXCEPTION"),
+            14 => write!(f, "STANDARD_ERROR"),
+            15 => write!(f, "RUNTIME_ERROR"),
+            16 => write!(f, "NAME_ERROR"),
+            17 => write!(f, "NO_METHOD_ERROR"),
+            18 => write!(f, "ZERO_DIVISION_ERROR"),
+            19 => write!(f, "TYPE_ERROR"),
+            20 => write!(f, "INDEX_ERROR"),
+            21 => write!(f, "FROZEN_ERROR"),
+            22 => write!(f, "SCRIPT_ERROR"),
+            23 => write!(f, "LOAD_ERROR"),
+            24 => write!(f, "ARGUMENT_ERROR"),
+            25 => write!(f, "RANGE_ERROR"),
+            26 => write!(f, "HASH"),
+            27 => write!(f, "KEY_ERROR"),
+            28 => write!(f, "MODULE"),
+            29 => write!(f, "COMPARABLE"),
+            30 => write!(f, "ENUMERABLE"),
+            31 => write!(f, "PROC"),
+            32 => write!(f, "LOCAL_JUMP_ERROR"),
+            33 => write!(f, "REGEXP"),
+            34 => write!(f, "MATCHDATA"),
+            35 => write!(f, "REGEXP_ERROR"),
+            36 => write!(f, "NUMERIC"),
+            37 => write!(f, "RATIONAL"),
+            38 => write!(f, "COMPLEX"),
+            39 => write!(f, "FIBER"),
+            40 => write!(f, "FIBER_ERROR"),
+            41 => write!(f, "ENUMERATOR"),
+            42 => write!(f, "YIELDER"),
+            43 => write!(f, "LAZY"),
+            44 => write!(f, "STOP_ITERATION"),
+            45 => write!(f, "STRUCT"),
             n => write!(f, "ClassId({})", n),
         }
     }
@@ -83,13 +149,21 @@ impl ClassId {
         }
         let val = self.get_obj(globals);
         match globals.class[self].name {
-            Some(id) => IdentId::get_name(id),
+            Some(id) => match globals.class[self].parent {
+                Some(parent) => format!("{}::{}", parent.get_name(globals), IdentId::get_name(id)),
+                None => IdentId::get_name(id),
+            },
             None => match globals.class[self].is_singleton {
                 None => format!("#<Class:{:016x}>", val.get()),
                 Some(base) => format!("#<Class:{}>", globals.val_tos(base)),
             },
         }
     }
+
+    /// Check whether *ClassId* is a class or module which has not been bound to a constant.
+    pub(crate) fn is_anonymous(self, globals: &Globals) -> bool {
+        globals.class[self].name.is_none()
+    }
 }
 
 impl Globals {
@@ -113,6 +187,26 @@ impl Globals {
         self.define_builtin_class(name, class_id, Some(OBJECT_CLASS), OBJECT_CLASS)
     }
 
+    pub(crate) fn define_module(&mut self, name: &str, parent: ClassId) -> Value {
+        let name_id = IdentId::get_ident_id(name);
+        self.define_module_by_ident_id(name_id, parent)
+    }
+
+    pub(in crate::executor) fn define_builtin_module_under_obj(
+        &mut self,
+        name: &str,
+        class_id: ClassId,
+    ) -> Value {
+        let name_id = IdentId::get_ident_id(name);
+        self.class.def_builtin_module(class_id);
+        self.generate_class_obj(name_id, class_id, OBJECT_CLASS)
+    }
+
+    pub(crate) fn define_module_by_ident_id(&mut self, name_id: IdentId, parent: ClassId) -> Value {
+        let class_id = self.class.add_module();
+        self.generate_class_obj(name_id, class_id, parent)
+    }
+
     pub(crate) fn define_class(
         &mut self,
         name: &str,
@@ -161,14 +255,34 @@ impl Globals {
         class_id: ClassId,
         parent: ClassId,
     ) -> Value {
-        let class_obj = Value::new_empty_class(class_id);
+        let class_obj = if self.class[class_id].is_module {
+            Value::new_empty_module(class_id)
+        } else {
+            Value::new_empty_class(class_id)
+        };
         self.class[class_id].object = Some(class_obj);
         self.class[class_id].name = Some(name_id);
+        if parent != OBJECT_CLASS {
+            self.class[class_id].parent = Some(parent);
+        }
         self.get_singleton_id(class_id);
         self.set_constant(parent, name_id, class_obj);
         class_obj
     }
 
+    ///
+    /// Create a new class without a name.
+    ///
+    /// The class is named when it is assigned to a constant for the first time.
+    ///
+    pub(crate) fn new_unnamed_class(&mut self, super_class: impl Into<Option<ClassId>>) -> Value {
+        let class_id = self.class.add_class(super_class.into());
+        let class_obj = Value::new_empty_class(class_id);
+        self.class[class_id].object = Some(class_obj);
+        self.get_singleton_id(class_id);
+        class_obj
+    }
+
     fn new_singleton_class(
         &mut self,
         super_class: impl Into<Option<ClassId>>,
@@ -184,6 +298,26 @@ impl Globals {
         self.class.get_real_class_id(val)
     }
 
+    ///
+    /// Check whether the class of *class_id* is *target* or a subclass of *target*.
+    ///
+    /// If *target* is a module, check whether *target* is in the ancestors of *class_id*.
+    ///
+    pub(crate) fn is_subclass_of(&self, mut class_id: ClassId, target: ClassId) -> bool {
+        if self.class[target].is_module {
+            return self.ancestors(class_id).contains(&target);
+        }
+        loop {
+            if class_id == target {
+                return true;
+            }
+            match class_id.super_class(self) {
+                Some(super_class) => class_id = super_class,
+                None => return false,
+            }
+        }
+    }
+
     pub(crate) fn get_singleton_id(&mut self, original_id: ClassId) -> ClassId {
         let mut original = self.get_class_obj(original_id);
         let original_class_id = original.class_id();
@@ -192,7 +326,8 @@ impl Globals {
         }
         let super_singleton_id = match original_id.super_class(self) {
             Some(id) => self.get_singleton_id(id),
-            None => CLASS_CLASS,
+            // Class for Object, and Module for modules.
+            None => original_class_id,
         };
 
         let (mut singleton, singleton_id) =
@@ -207,57 +342,316 @@ impl Globals {
         singleton_id
     }
 
+    ///
+    /// Get the singleton class of *obj*.
+    ///
+    /// If *obj* has no singleton class yet, a new one is created.
+    /// Return None and set TypeError if *obj* can not have a singleton class.
+    ///
+    pub(crate) fn get_singleton_class(&mut self, mut obj: Value) -> Option<ClassId> {
+        if let Some(class_id) = obj.is_class_or_module() {
+            return Some(self.get_singleton_id(class_id));
+        }
+        let original_class_id = obj.class_id();
+        if self.class[original_class_id].is_singleton() {
+            return Some(original_class_id);
+        }
+        match obj.try_rvalue() {
+            Some(rv) if rv.kind() == ObjKind::OBJECT => {}
+            _ => {
+                self.set_error(MonorubyErr::typeerr("can't define singleton".to_string()));
+                return None;
+            }
+        }
+        let (mut singleton, singleton_id) = self.new_singleton_class(Some(original_class_id), obj);
+        // instance variables of *obj* are looked up through the singleton class.
+        self.class[singleton_id].ivar_names = self.class[original_class_id].ivar_names.clone();
+        obj.change_class(singleton_id);
+        singleton.change_class(original_class_id);
+        Some(singleton_id)
+    }
+
+    ///
+    /// Get the ancestors of the class of *class_id* in the method resolution order.
+    ///
+    /// Modules prepended to a class precede the class, and modules included in a class follow it.
+    /// Modules added later take precedence over earlier ones.
+    ///
+    pub(crate) fn ancestors(&self, class_id: ClassId) -> Vec<ClassId> {
+        let mut v = vec![];
+        let mut class_id = Some(class_id);
+        while let Some(id) = class_id {
+            self.push_ancestors(&mut v, id);
+            class_id = id.super_class(self);
+        }
+        v
+    }
+
+    fn push_ancestors(&self, v: &mut Vec<ClassId>, class_id: ClassId) {
+        let info = &self.class[class_id];
+        for module in info.prepends.iter().rev() {
+            self.push_ancestors(v, *module);
+        }
+        v.push(class_id);
+        for module in info.includes.iter().rev() {
+            self.push_ancestors(v, *module);
+        }
+    }
+
+    ///
+    /// Search the method with *name* in the class of *class_id* and its prepended and included modules.
+    ///
+    fn find_method_in_class(&self, class_id: ClassId, name: IdentId) -> Option<MethodTableEntry> {
+        let info = &self.class[class_id];
+        for module in info.prepends.iter().rev() {
+            if let Some(entry) = self.find_method_in_class(*module, name) {
+                return Some(entry);
+            }
+        }
+        if let Some(entry) = info.methods.get(&name) {
+            return Some(*entry);
+        }
+        for module in info.includes.iter().rev() {
+            if let Some(entry) = self.find_method_in_class(*module, name) {
+                return Some(entry);
+            }
+        }
+        None
+    }
+
+    ///
+    /// Search the method with *name* along the ancestors of the class of *class_id*.
+    ///
+    pub(crate) fn find_method_for_class(&self, class_id: ClassId, name: IdentId) -> Option<FuncId> {
+        self.find_method_entry_for_class(class_id, name)
+            .map(|entry| entry.func_id)
+    }
+
+    ///
+    /// Search the method table entry with *name* along the ancestors of the class of *class_id*.
+    ///
+    pub(crate) fn find_method_entry_for_class(
+        &self,
+        mut class_id: ClassId,
+        name: IdentId,
+    ) -> Option<MethodTableEntry> {
+        loop {
+            if let Some(entry) = self.find_method_in_class(class_id, name) {
+                return Some(entry);
+            }
+            class_id = class_id.super_class(self)?;
+        }
+    }
+
+    ///
+    /// Search the method with *name* which `super` in a method defined in *owner* calls,
+    /// along the ancestors of the class of *class_id*.
+    ///
+    pub(crate) fn find_super_method_for_class(
+        &self,
+        class_id: ClassId,
+        owner: ClassId,
+        name: IdentId,
+    ) -> Option<FuncId> {
+        let mut ancestors = self.ancestors(class_id);
+        match ancestors.iter().position(|id| *id == owner) {
+            Some(pos) => {
+                ancestors.drain(..=pos);
+            }
+            None => {
+                ancestors = self.ancestors(owner);
+                ancestors.remove(0);
+            }
+        }
+        ancestors
+            .into_iter()
+            .find_map(|id| self.class[id].methods.get(&name).map(|entry| entry.func_id))
+    }
+
+    ///
+    /// Check whether *class_id* is a module.
+    ///
+    pub(crate) fn is_module(&self, class_id: ClassId) -> bool {
+        self.class[class_id].is_module
+    }
+
+    ///
+    /// Include the module *module* in the class of *class_id*.
+    ///
+    /// If *prepend* is true, *module* is prepended instead.
+    ///
+    pub(crate) fn include_module(
+        &mut self,
+        class_id: ClassId,
+        module: ClassId,
+        prepend: bool,
+    ) -> Option<()> {
+        if self.ancestors(module).contains(&class_id) {
+            self.err_argument(&format!(
+                "cyclic {} detected",
+                if prepend { "prepend" } else { "include" }
+            ));
+            return None;
+        }
+        if prepend {
+            let prepends = &mut self.class[class_id].prepends;
+            if !prepends.contains(&module) {
+                prepends.push(module);
+            }
+        } else if !self.ancestors(class_id).contains(&module) {
+            self.class[class_id].includes.push(module);
+        }
+        // method and constant resolution has been changed.
+        self.class_version_inc();
+        self.const_version_inc();
+        Some(())
+    }
+
     ///
     /// Add a new method *func* with *name* to the class of *class_id*.
     ///
     pub(crate) fn add_method(&mut self, class_id: ClassId, name: IdentId, func: FuncId) {
-        self.class[class_id].methods.insert(name, func);
+        self.add_method_with_visibility(class_id, name, func, Visibility::Public);
     }
 
     ///
-    /// Get a method with *name* in the class of *class_id*.
-    ///   
-    /// If not found, simply return None with no error.
+    /// Add a new method *func* with *name* and *visibility* to the class of *class_id*.
     ///
-    pub(super) fn get_method(&self, class_id: ClassId, name: IdentId) -> Option<FuncId> {
-        self.class[class_id].methods.get(&name).cloned()
+    /// `initialize` and `respond_to_missing?` are always private.
+    ///
+    pub(crate) fn add_method_with_visibility(
+        &mut self,
+        class_id: ClassId,
+        name: IdentId,
+        func_id: FuncId,
+        visibility: Visibility,
+    ) {
+        let visibility = if name == IdentId::INITIALIZE || name == IdentId::_RESPOND_TO_MISSING {
+            Visibility::Private
+        } else {
+            visibility
+        };
+        let entry = MethodTableEntry {
+            owner: class_id,
+            func_id,
+            visibility,
+        };
+        self.class[class_id].methods.insert(name, entry);
+    }
+
+    ///
+    /// Change the visibility of the method with *name* in the class of *class_id*.
+    ///
+    /// If the method is inherited, an entry with the new visibility is added to *class_id*.
+    /// Return None and set NameError if the method was not found.
+    ///
+    pub(crate) fn change_method_visibility(
+        &mut self,
+        class_id: ClassId,
+        name: IdentId,
+        visibility: Visibility,
+    ) -> Option<()> {
+        let func_id = match self.find_method_for_class(class_id, name) {
+            Some(func_id) => func_id,
+            None => {
+                self.err_undefined_method_for_class(name, class_id);
+                return None;
+            }
+        };
+        self.add_method_with_visibility(class_id, name, func_id, visibility);
+        // inline method caches must be invalidated.
+        self.class_version_inc();
+        Some(())
     }
 
     ///
-    /// Get method names in the class of *class_id*.
+    /// Get public and protected method names in the class of *class_id*.
     ///  
     pub(crate) fn get_method_names(&self, class_id: ClassId) -> Vec<IdentId> {
-        self.class[class_id].methods.keys().cloned().collect()
+        self.class[class_id]
+            .methods
+            .iter()
+            .filter(|(_, entry)| entry.visibility != Visibility::Private)
+            .map(|(name, _)| *name)
+            .collect()
     }
 }
 
+///
+/// Visibility of a method.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Visibility {
+    Public,
+    Private,
+    Protected,
+}
+
+///
+/// An entry of the method table of a class.
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct MethodTableEntry {
+    /// the class or module which has this entry.
+    pub(crate) owner: ClassId,
+    pub(crate) func_id: FuncId,
+    pub(crate) visibility: Visibility,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub(super) struct ClassInfo {
     /// the constant name which this class object is bound.
     name: Option<IdentId>,
+    /// the class or module in which this class is defined. (None for top-level)
+    parent: Option<ClassId>,
     /// corresponding class object.
     object: Option<Value>,
     /// super class.
     super_class_id: Option<ClassId>,
     /// is singleton class?
     is_singleton: Option<Value>,
+    /// is module?
+    is_module: bool,
+    /// included modules in the order of inclusion.
+    includes: Vec<ClassId>,
+    /// prepended modules in the order of prepending.
+    prepends: Vec<ClassId>,
     /// method table.
-    methods: HashMap<IdentId, FuncId>,
+    methods: HashMap<IdentId, MethodTableEntry>,
     /// constants table.
     constants: HashMap<IdentId, Value>,
+    /// constants made private by `private_constant`.
+    private_constants: Vec<IdentId>,
     /// instance variable table.
     ivar_names: HashMap<IdentId, IvarId>,
 }
 
+impl GC<RValue> for ClassInfo {
+    fn mark(&self, alloc: &mut Allocator<RValue>) {
+        if let Some(object) = &self.object {
+            object.mark(alloc);
+        }
+        if let Some(base) = &self.is_singleton {
+            base.mark(alloc);
+        }
+        self.constants.values().for_each(|v| v.mark(alloc));
+    }
+}
+
 impl ClassInfo {
     fn new(super_class_id: Option<ClassId>) -> Self {
         Self {
             name: None,
+            parent: None,
             object: None,
             super_class_id,
             is_singleton: None,
+            is_module: false,
+            includes: vec![],
+            prepends: vec![],
             methods: HashMap::default(),
             constants: HashMap::default(),
+            private_constants: vec![],
             ivar_names: HashMap::default(),
         }
     }
@@ -265,15 +659,27 @@ impl ClassInfo {
     fn new_singleton(super_class_id: Option<ClassId>, base: Value) -> Self {
         Self {
             name: None,
+            parent: None,
             object: None,
             super_class_id,
             is_singleton: Some(base),
+            is_module: false,
+            includes: vec![],
+            prepends: vec![],
             methods: HashMap::default(),
             constants: HashMap::default(),
+            private_constants: vec![],
             ivar_names: HashMap::default(),
         }
     }
 
+    fn new_module() -> Self {
+        Self {
+            is_module: true,
+            ..Self::new(None)
+        }
+    }
+
     fn is_singleton(&self) -> bool {
         self.is_singleton.is_some()
     }
@@ -298,10 +704,16 @@ impl std::ops::IndexMut<ClassId> for ClassStore {
     }
 }
 
+impl GC<RValue> for ClassStore {
+    fn mark(&self, alloc: &mut Allocator<RValue>) {
+        self.classes.iter().for_each(|info| info.mark(alloc));
+    }
+}
+
 impl ClassStore {
     pub(crate) fn new() -> Self {
         Self {
-            classes: vec![ClassInfo::new(None); 20],
+            classes: vec![ClassInfo::new(None); 46],
         }
     }
 
@@ -315,6 +727,16 @@ impl ClassStore {
         self[class] = ClassInfo::new(super_class);
     }
 
+    fn add_module(&mut self) -> ClassId {
+        let id = self.classes.len();
+        self.classes.push(ClassInfo::new_module());
+        ClassId(id as u32)
+    }
+
+    fn def_builtin_module(&mut self, class: ClassId) {
+        self[class] = ClassInfo::new_module();
+    }
+
     fn add_singleton_class(&mut self, super_class: Option<ClassId>, base: Value) -> ClassId {
         let id = self.classes.len();
         self.classes
@@ -7,6 +7,18 @@ impl Globals {
         name: IdentId,
         val: Value,
     ) -> Option<Value> {
+        // inline constant caches must be invalidated.
+        self.const_version_inc();
+        // an unnamed class or module is named by the first constant it is assigned to.
+        if let Some(id) = val.is_class_or_module() {
+            let info = &mut self.class[id];
+            if info.name.is_none() && info.is_singleton.is_none() {
+                info.name = Some(name);
+                if class_id != OBJECT_CLASS {
+                    info.parent = Some(class_id);
+                }
+            }
+        }
         self.class[class_id].constants.insert(name, val)
     }
 
@@ -19,6 +31,30 @@ impl Globals {
         self.class[class_id].constants.get(&name).cloned()
     }
 
+    ///
+    /// Make the constant with *name* in the class of *class_id* private.
+    ///
+    /// A private constant can not be referred with a qualifier (e.g. A::B).
+    /// Return None and set NameError if the constant is not defined.
+    ///
+    pub(crate) fn set_private_constant(&mut self, class_id: ClassId, name: IdentId) -> Option<()> {
+        if self.get_constant(class_id, name).is_none() {
+            self.set_error(MonorubyErr::nameerr(format!(
+                "constant {}::{} not defined",
+                class_id.get_name(self),
+                IdentId::get_name(name)
+            )));
+            return None;
+        }
+        // inline constant caches must be invalidated.
+        self.const_version_inc();
+        let private_constants = &mut self.class[class_id].private_constants;
+        if !private_constants.contains(&name) {
+            private_constants.push(name);
+        }
+        Some(())
+    }
+
     ///
     /// Get constant names in the class of *class_id*.
     ///
@@ -29,6 +65,10 @@ impl Globals {
     ///
     /// Get a value of a constant specified by ConstSiteId *id*.
     ///
+    /// A constant without a qualifier is searched in the lexical scopes in *class_context*
+    /// (innermost last), then in the ancestors of the innermost scope, and then in Object.
+    /// A qualified constant (e.g. A::B) is searched in the qualifier and its ancestors.
+    ///
     /// If not found, set uninitialized constant error and return None.
     ///
     pub(crate) fn find_constant(
@@ -38,49 +78,61 @@ impl Globals {
     ) -> Option<Value> {
         let ConstSiteInfo {
             toplevel,
-            mut prefix,
+            prefix,
             name,
             ..
         } = self.func[id].clone();
-        if toplevel {
-            let mut parent = OBJECT_CLASS;
-            for constant in prefix {
-                parent = self
-                    .get_constant_checked(parent, constant)?
-                    .expect_class(name, self)?;
-            }
-            self.get_constant_checked(parent, name)
-        } else if prefix.is_empty() {
-            match self.search_lexical_stack(name, class_context) {
-                Some(v) => Some(v),
-                _ => self.get_constant_checked(OBJECT_CLASS, name),
-            }
+        let mut prefix = prefix.into_iter();
+        let mut parent = if toplevel {
+            OBJECT_CLASS
         } else {
-            let parent = prefix.remove(0);
-            let mut parent = match self.search_lexical_stack(parent, class_context) {
-                Some(v) => v,
-                None => self.get_constant_checked(OBJECT_CLASS, parent)?,
+            match prefix.next() {
+                None => return self.search_lexical_checked(name, class_context),
+                Some(constant) => self
+                    .search_lexical_checked(constant, class_context)?
+                    .expect_class_or_module(constant, self)?,
             }
-            .expect_class(name, self)?;
-            for constant in prefix {
-                parent = self
-                    .get_constant_checked(parent, constant)?
-                    .expect_class(name, self)?;
-            }
-            self.get_constant_checked(parent, name)
+        };
+        for constant in prefix {
+            parent = self
+                .search_constant_checked(parent, constant)?
+                .expect_class_or_module(constant, self)?;
         }
+        self.search_constant_checked(parent, name)
+    }
+
+    ///
+    /// Search a constant with *name* in the class of *class_id* and its ancestors.
+    ///
+    /// Object and its ancestors are not searched unless *class_id* is Object itself.
+    ///
+    pub(crate) fn search_constant(&self, class_id: ClassId, name: IdentId) -> Option<Value> {
+        self.ancestors(class_id)
+            .into_iter()
+            .take_while(|id| class_id == OBJECT_CLASS || *id != OBJECT_CLASS)
+            .find_map(|id| self.get_constant(id, name))
     }
 }
 
 impl Globals {
     ///
-    /// Get constant with *name* and parent class *class_id*.
+    /// Search a constant with *name* qualified by the class of *class_id* in the class and its ancestors.
     ///
     /// If not found, set uninitialized constant error and return None.
+    /// If the constant is private, set NameError and return None.
     ///
-    fn get_constant_checked(&mut self, class_id: ClassId, name: IdentId) -> Option<Value> {
-        match self.get_constant(class_id, name) {
-            Some(v) => Some(v),
+    fn search_constant_checked(&mut self, class_id: ClassId, name: IdentId) -> Option<Value> {
+        let owner = self
+            .ancestors(class_id)
+            .into_iter()
+            .take_while(|id| class_id == OBJECT_CLASS || *id != OBJECT_CLASS)
+            .find(|id| self.class[*id].constants.contains_key(&name));
+        match owner {
+            Some(owner) if self.class[owner].private_constants.contains(&name) => {
+                self.err_private_constant(owner, name);
+                None
+            }
+            Some(owner) => self.get_constant(owner, name),
             None => {
                 self.err_uninitialized_constant(name);
                 None
@@ -88,10 +140,33 @@ impl Globals {
         }
     }
 
-    fn search_lexical_stack(&self, name: IdentId, class_context: &[ClassId]) -> Option<Value> {
-        class_context
+    ///
+    /// Search a constant with *name* in the lexical scopes, the ancestors of the innermost scope and Object.
+    ///
+    /// If not found, set uninitialized constant error and return None.
+    ///
+    fn search_lexical_checked(
+        &mut self,
+        name: IdentId,
+        class_context: &[ClassId],
+    ) -> Option<Value> {
+        let innermost = class_context.last().cloned().unwrap_or(OBJECT_CLASS);
+        let res = class_context
             .iter()
             .rev()
             .find_map(|class| self.get_constant(*class, name))
+            .or_else(|| {
+                self.ancestors(innermost)
+                    .into_iter()
+                    .find_map(|id| self.get_constant(id, name))
+            })
+            .or_else(|| self.get_constant(OBJECT_CLASS, name));
+        match res {
+            Some(v) => Some(v),
+            None => {
+                self.err_uninitialized_constant(name);
+                None
+            }
+        }
     }
 }
@@ -16,6 +16,7 @@ type MethodInvoker = extern "C" fn(
     Value,
     *const Value,
     usize,
+    Option<Value>,
 ) -> Option<Value>;
 
 type BlockInvoker = extern "C" fn(
@@ -40,6 +41,7 @@ pub struct Codegen {
     pub class_version: DestLabel,
     pub class_version_addr: *mut u32,
     pub const_version: DestLabel,
+    pub const_version_addr: *mut usize,
     pub entry_panic: DestLabel,
     pub vm_entry: DestLabel,
     pub vm_fetch: DestLabel,
@@ -59,6 +61,31 @@ pub struct Codegen {
     /// r13: pc (InitBlock/InitMethod)
     ///
     pub wrong_argument: DestLabel,
+    ///
+    /// The return address of frames captured by Procs.
+    ///
+    /// This moves the exiting frame to the heap, and jumps to the original return address.
+    ///
+    pub(crate) escape_trampoline: usize,
+    ///
+    /// Switch the machine stack.
+    ///
+    /// The callee-saved registers are saved on the current stack, and the stack pointer is
+    /// exchanged with the one stored in *rdi*. Then the registers are restored from the new stack.
+    ///
+    pub(crate) fiber_switch: extern "C" fn(*mut usize),
+    ///
+    /// The entry point of Fibers, which the first switch to a Fiber returns to.
+    ///
+    /// in
+    ///
+    /// rbx: &mut Executor
+    ///
+    /// r12: &mut Globals
+    ///
+    /// r13: *mut FiberInner
+    ///
+    pub(crate) fiber_start: usize,
     pub dispatch: Vec<CodePtr>,
     pub(crate) method_invoker: MethodInvoker,
     pub(crate) method_invoker2: MethodInvoker2,
@@ -72,19 +99,174 @@ pub struct Codegen {
 ///
 /// Get an absolute address of the given method.
 ///
-/// If no method was found, return None (==0u64).
+/// *caller* is *self* of the calling frame, and *is_func_call* is true if the receiver
+/// register is *self*. These are used for the visibility check.
+///
+/// If no method was found or the method is not accessible, return None (==0u64).
 ///
 extern "C" fn find_method(
     globals: &mut Globals,
     func_name: IdentId,
     args_len: usize,
     receiver: Value,
+    caller: Value,
+    is_func_call: bool,
 ) -> Option<&FuncData> {
-    let func_id = globals.find_method_checked(receiver, func_name, args_len)?;
+    let func_id =
+        globals.find_method_for_call(receiver, func_name, args_len, caller, is_func_call)?;
     let data = globals.compile_on_demand(func_id);
     Some(data)
 }
 
+///
+/// Get a key of the inline method cache for the method *func_name* of the receiver class *class_id*.
+///
+/// The key is the class of the receiver.
+/// If the method is protected, its visibility depends on the caller as well as the receiver,
+/// so 0 is returned and the callee is not cached.
+///
+extern "C" fn method_cache_key(
+    globals: &Globals,
+    class_id: ClassId,
+    func_name: IdentId,
+) -> ClassId {
+    match globals.find_method_entry_for_class(class_id, func_name) {
+        Some(entry) if entry.visibility == Visibility::Protected => ClassId::default(),
+        _ => class_id,
+    }
+}
+
+///
+/// Call `method_missing` of *receiver* for the method *func_name* which was not found.
+///
+/// *args* points to the first argument, and *len* is the number of arguments.
+///
+extern "C" fn method_missing(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    receiver: Value,
+    func_name: IdentId,
+    args: Arg,
+    len: usize,
+) -> Option<Value> {
+    vm.invoke_method_missing(globals, func_name, receiver, &args.to_vec(len), None)
+}
+
+///
+/// Call `method_missing` of *receiver* for the method *func_name* called with a block,
+/// which was not found.
+///
+/// *args* points to the block handler followed by *len* arguments.
+///
+extern "C" fn method_missing_with_block(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    receiver: Value,
+    func_name: IdentId,
+    args: Arg,
+    len: usize,
+) -> Option<Value> {
+    let block = Some(args[0]).filter(|block| !block.is_nil());
+    let args: Vec<_> = (1..=len).map(|i| args[i]).collect();
+    vm.invoke_method_missing(globals, func_name, receiver, &args, block)
+}
+
+///
+/// Get an absolute address of the method which `super` in the method *func_id* calls.
+///
+/// If no method was found, return None (==0u64).
+///
+extern "C" fn find_super(
+    globals: &mut Globals,
+    func_id: FuncId,
+    args_len: usize,
+    receiver: Value,
+) -> Option<&FuncData> {
+    let func_id = globals.find_super_checked(receiver, func_id, args_len)?;
+    let data = globals.compile_on_demand(func_id);
+    Some(data)
+}
+
+///
+/// Call the method which `super` in the method *func_id* calls, expanding the rest array.
+///
+/// *args* points to the first of *len* arguments. The rest array is the last positional argument,
+/// which may be followed by a keyword Hash.
+///
+extern "C" fn super_splat(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    receiver: Value,
+    func_id: FuncId,
+    args: Arg,
+    len: usize,
+) -> Option<Value> {
+    invoke_super_splat(vm, globals, receiver, func_id, args.to_vec(len), None)
+}
+
+///
+/// Call the method which `super` in the method *func_id* calls with a block, expanding the rest array.
+///
+/// *args* points to the block handler followed by *len* arguments.
+///
+extern "C" fn super_splat_with_block(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    receiver: Value,
+    func_id: FuncId,
+    args: Arg,
+    len: usize,
+) -> Option<Value> {
+    let block = args.block_handler(0).filter(|block| !block.is_nil());
+    let args: Vec<_> = (1..=len).map(|i| args[i]).collect();
+    invoke_super_splat(vm, globals, receiver, func_id, args, block)
+}
+
+fn invoke_super_splat(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    receiver: Value,
+    func_id: FuncId,
+    mut args: Vec<Value>,
+    block: Option<Value>,
+) -> Option<Value> {
+    let pos = match args.last() {
+        Some(last) if last.is_hash().map_or(false, |h| h.is_keyword()) => args.len() - 1,
+        _ => args.len(),
+    };
+    let rest = args[pos - 1];
+    let rest = match rest.is_array() {
+        Some(ary) => ary.to_vec(),
+        None => vec![rest],
+    };
+    args.splice(pos - 1..pos, rest);
+    let func_id = globals.find_super_checked(receiver, func_id, args.len())?;
+    let data = globals.compile_on_demand(func_id) as *const _;
+    (globals.codegen.method_invoker)(
+        vm,
+        globals,
+        data,
+        receiver,
+        args.as_ptr(),
+        args.len(),
+        block,
+    )
+}
+
+///
+/// Get a key of the inline method cache for `super` in the method *func_id*.
+///
+/// The key is the class in which the method was defined.
+/// If the callee also depends on the class of the receiver (e.g. the method was defined in a module),
+/// 0 is returned and the callee is not cached.
+///
+extern "C" fn super_cache_key(globals: &Globals, func_id: FuncId) -> ClassId {
+    match globals.super_owner(func_id) {
+        Some((owner, _)) if !globals.is_module(owner) => owner,
+        _ => ClassId::default(),
+    }
+}
+
 extern "C" fn get_func_data(globals: &mut Globals, func_id: FuncId) -> &FuncData {
     globals.compile_on_demand(func_id)
 }
@@ -97,6 +279,79 @@ extern "C" fn get_block_data(
     globals.get_block_data(block_handler, interp)
 }
 
+///
+/// Convert the block handler *block_handler* to a Proc object for a block parameter.
+///
+/// If no block was given, nil is returned.
+///
+extern "C" fn block_arg(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    block_handler: Option<Value>,
+) -> Value {
+    match block_handler {
+        Some(bh) => vm.generate_proc(globals, bh, false),
+        None => Value::nil(),
+    }
+}
+
+///
+/// Convert *val* to a block handler for `&val`.
+///
+/// A Symbol is converted to the block handler of the function for `&:method`
+/// without generating a Proc object.
+///
+extern "C" fn to_block(vm: &mut Executor, globals: &mut Globals, val: Value) -> Option<Value> {
+    if val.is_proc().is_some() {
+        return Some(val);
+    }
+    if let Some(method) = val.is_symbol() {
+        let func_id = globals.func.symbol_proc(method);
+        return Some(Value::new_integer(((func_id.0 as i64) << 16) + 1));
+    }
+    let proc = vm.invoke_method(globals, IdentId::get_ident_id("to_proc"), val, &[])?;
+    if proc.is_proc().is_none() {
+        globals.err_wrong_argument_type(val, PROC_CLASS);
+        return None;
+    }
+    Some(proc)
+}
+
+///
+/// Return from the method (or the lambda) in which the current block was defined.
+///
+/// An error which unwinds frames up to the target frame is set, and None is returned.
+/// Ensure clauses in the unwound frames are executed in `handle_error`.
+///
+extern "C" fn method_return(vm: &mut Executor, globals: &mut Globals, val: Value) -> Option<Value> {
+    let mut outer = vm.cfp.bp() as usize - OFFSET_OUTER as usize;
+    loop {
+        let meta = Meta::new(unsafe { *((outer - 8) as *const u64) });
+        if meta.is_lambda() {
+            break;
+        }
+        let next = unsafe { *(outer as *const usize) };
+        if next == 0 {
+            break;
+        }
+        outer = next;
+    }
+    let meta = Meta::new(unsafe { *((outer - 8) as *const u64) });
+    if meta.is_on_heap() {
+        globals.err_local_jump("unexpected return".to_string());
+    } else {
+        globals.set_error(MonorubyErr::method_return(
+            val,
+            outer + OFFSET_OUTER as usize,
+        ));
+    }
+    None
+}
+
+extern "C" fn exit_escaped_frame(vm: &mut Executor, bp: usize) -> usize {
+    vm.exit_escaped_frame(bp)
+}
+
 extern "C" fn gen_array(src: *const Value, len: usize) -> Value {
     let mut v = if len == 0 {
         vec![]
@@ -107,6 +362,179 @@ extern "C" fn gen_array(src: *const Value, len: usize) -> Value {
     Value::new_array_from_vec(v)
 }
 
+extern "C" fn gen_hash(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    src: *const Value,
+    len: usize,
+) -> Option<Value> {
+    let mut hash = Value::new_hash(HashInner::new());
+    vm.with_temp(hash, |vm| {
+        for i in 0..len {
+            let (k, v) = unsafe { (*src.sub(i * 2), *src.sub(i * 2 + 1)) };
+            hash.as_hash_mut().insert(vm, globals, k, v)?;
+        }
+        Some(hash)
+    })
+}
+
+///
+/// Generate a keyword Hash for keyword arguments.
+///
+/// *src* holds pairs of a Symbol key and a value for `k: v`, and pairs of a Hash and nil for `**h`.
+///
+extern "C" fn gen_kw_hash(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    src: *const Value,
+    len: usize,
+) -> Option<Value> {
+    let mut hash = Value::new_hash(HashInner::new());
+    vm.with_temp(hash, |vm| {
+        let inner = hash.as_hash_mut();
+        for i in 0..len {
+            let (k, v) = unsafe { (*src.sub(i * 2), *src.sub(i * 2 + 1)) };
+            if k.is_symbol().is_some() {
+                inner.insert(vm, globals, k, v)?;
+            } else if let Some(splat) = k.is_hash() {
+                for (k, v) in splat.iter().collect::<Vec<_>>() {
+                    inner.insert(vm, globals, k, v)?;
+                }
+            } else {
+                globals.err_no_implict_conv(k, HASH_CLASS);
+                return None;
+            }
+        }
+        inner.set_keyword();
+        Some(hash)
+    })
+}
+
+///
+/// Clear the keyword flag of the keyword Hash *hash* passed as the last argument, if the function
+/// of *meta* has no keyword parameters.
+///
+/// A keyword Hash bound to a positional or rest parameter is an ordinary Hash for the callee.
+///
+extern "C" fn bind_keyword_hash(globals: &Globals, meta: Meta, mut hash: Value) {
+    if hash.as_hash().is_keyword()
+        && globals.func[meta.func_id()]
+            .as_ruby_func()
+            .keyword
+            .is_none()
+    {
+        hash.as_hash_mut().clear_keyword();
+    }
+}
+
+///
+/// Clear the keyword flag of the keyword Hash passed to a native function as the last argument,
+/// so that it is an ordinary Hash when stored or returned by the native function.
+///
+extern "C" fn clear_keyword_arg(args: Arg, len: usize) {
+    if len == 0 {
+        return;
+    }
+    let mut hash = args[len - 1];
+    if hash.is_hash().map_or(false, |h| h.is_keyword()) {
+        hash.as_hash_mut().clear_keyword();
+    }
+}
+
+///
+/// Set keyword parameters of the function *func_id* from the keyword arguments.
+///
+/// The keyword Hash passed as the last argument is removed from the positional parameters.
+/// Unassigned keyword parameters with a default value are set to 0 so that their initializers are evaluated.
+///
+extern "C" fn init_keyword(
+    vm: &mut Executor,
+    globals: &mut Globals,
+    func_id: FuncId,
+    self_ptr: *mut Option<Value>,
+) -> Option<Value> {
+    let info = globals.func[func_id].as_ruby_func();
+    let (req_num, pos_num, is_block) = (info.req_num(), info.pos_num(), info.is_block);
+    let KeywordInfo {
+        params,
+        kw_rest,
+        rest,
+        hidden_rest,
+    } = info.keyword.clone().unwrap();
+    let slot = |reg: usize| unsafe { &mut *self_ptr.sub(reg) };
+    let is_kw =
+        |v: Option<Value>| v.and_then(|v| v.is_hash().map(|h| h.is_keyword())) == Some(true);
+
+    // take the keyword Hash out of the positional arguments.
+    let rest_ary = slot(rest.0 as usize).as_mut().unwrap().as_array_mut();
+    let mut kw = None;
+    if let Some(last) = rest_ary.last() {
+        if is_kw(Some(*last)) {
+            kw = rest_ary.pop();
+        }
+    } else if let Some(last) = (1..=pos_num).rev().find(|i| slot(*i).is_some()) {
+        if is_kw(*slot(last)) {
+            kw = slot(last).take();
+            if last <= req_num {
+                if !is_block {
+                    globals.err_wrong_number_of_arguments_range(req_num - 1, req_num..=pos_num);
+                    return None;
+                }
+                *slot(last) = Some(Value::nil());
+            }
+        }
+    }
+    if !is_block && hidden_rest && !rest_ary.is_empty() {
+        let given = (1..=pos_num).filter(|i| slot(*i).is_some()).count() + rest_ary.len();
+        globals.err_wrong_number_of_arguments_range(given, req_num..=pos_num);
+        return None;
+    }
+
+    // the keyword Hash was taken out of the frame, so it must be rooted while copying it.
+    let kw = kw.unwrap_or_default();
+    let mut remain = Value::new_hash(HashInner::new());
+    vm.with_temp(kw, |vm| {
+        vm.with_temp(remain, |vm| {
+            let inner = remain.as_hash_mut();
+            if let Some(kw) = kw.is_hash() {
+                for (k, v) in kw.iter().collect::<Vec<_>>() {
+                    inner.insert(vm, globals, k, v)?;
+                }
+            }
+            let mut missing = vec![];
+            for (name, reg, required) in params {
+                let val = inner.remove(vm, globals, Value::new_symbol(name))?;
+                if val.is_none() && required {
+                    missing.push(format!(":{}", IdentId::get_name(name)));
+                }
+                *slot(reg.0 as usize) = val;
+            }
+            if !missing.is_empty() {
+                let s = if missing.len() == 1 { "" } else { "s" };
+                globals.err_argument(&format!("missing keyword{s}: {}", missing.join(", ")));
+                return None;
+            }
+            Some(())
+        })
+    })?;
+    match kw_rest {
+        Some(reg) => *slot(reg.0 as usize) = Some(remain),
+        None if !remain.as_hash().is_empty() => {
+            let unknown: Vec<_> = remain
+                .as_hash()
+                .keys()
+                .into_iter()
+                .map(|k| globals.val_inspect(k))
+                .collect();
+            let s = if unknown.len() == 1 { "" } else { "s" };
+            globals.err_argument(&format!("unknown keyword{s}: {}", unknown.join(", ")));
+            return None;
+        }
+        None => {}
+    }
+    Some(Value::nil())
+}
+
 extern "C" fn gen_range(
     start: Value,
     end: Value,
@@ -138,6 +566,10 @@ extern "C" fn get_index(
                 return base.as_array().get_index(idx);
             }
         }
+        HASH_CLASS => {
+            class_slot.idx = index.class_id();
+            return HashInner::index(base, interp, globals, index);
+        }
         _ => {}
     }
     class_slot.idx = index.class_id();
@@ -157,6 +589,10 @@ extern "C" fn set_index(
                 return base.as_array_mut().set_index(globals, idx, src);
             }
         }
+        HASH_CLASS => {
+            base.as_hash_mut().insert(interp, globals, index, src)?;
+            return Some(src);
+        }
         _ => {}
     }
     interp.invoke_method(globals, IdentId::_INDEX_ASSIGN, base, &[index, src])
@@ -176,13 +612,34 @@ extern "C" fn set_instance_var(
     Some(val)
 }
 
+///
+/// Get the class or module in which a class or module is to be defined.
+///
+/// *base* is the qualifier of the class path (e.g. A in `class A::B`).
+///
+fn class_def_parent(
+    interp: &Executor,
+    globals: &mut Globals,
+    base: Option<Value>,
+) -> Option<ClassId> {
+    match base {
+        Some(base) => {
+            let name = globals.val_tos(base);
+            let name = IdentId::get_ident_id_from_string(name);
+            base.expect_class_or_module(name, globals)
+        }
+        None => Some(interp.get_class_context()),
+    }
+}
+
 extern "C" fn define_class(
     interp: &mut Executor,
     globals: &mut Globals,
     name: IdentId,
     superclass: Option<Value>,
+    base: Option<Value>,
 ) -> Option<Value> {
-    let parent = interp.get_class_context();
+    let parent = class_def_parent(interp, globals, base)?;
     let self_val = match globals.get_constant(parent, name) {
         Some(val) => {
             let class = val.expect_class(name, globals)?;
@@ -214,6 +671,40 @@ extern "C" fn define_class(
     Some(self_val)
 }
 
+extern "C" fn define_module(
+    interp: &mut Executor,
+    globals: &mut Globals,
+    name: IdentId,
+    _superclass: Option<Value>,
+    base: Option<Value>,
+) -> Option<Value> {
+    let parent = class_def_parent(interp, globals, base)?;
+    let self_val = match globals.get_constant(parent, name) {
+        Some(val) => {
+            val.expect_module(name, globals)?;
+            val
+        }
+        None => globals.define_module_by_ident_id(name, parent),
+    };
+    interp.push_class_context(self_val.as_class());
+    Some(self_val)
+}
+
+///
+/// Open the singleton class of *base* for `class << base`.
+///
+extern "C" fn define_singleton_class(
+    interp: &mut Executor,
+    globals: &mut Globals,
+    _name: IdentId,
+    _superclass: Option<Value>,
+    base: Option<Value>,
+) -> Option<Value> {
+    let singleton = globals.get_singleton_class(base.unwrap())?;
+    interp.push_class_context(singleton);
+    Some(globals.get_class_obj(singleton))
+}
+
 extern "C" fn pop_class_context(interp: &mut Executor, _globals: &mut Globals) {
     interp.pop_class_context();
 }
@@ -243,23 +734,89 @@ extern "C" fn err_wrong_number_of_arguments_range(
     globals.err_wrong_number_of_arguments_range(given, min..=max)
 }
 
-extern "C" fn get_error_location(
-    _interp: &mut Executor,
+#[repr(C)]
+struct ErrorReturn {
+    /// pc of the exception handler.
+    pc: BcPc,
+    /// the value to be returned from the current frame.
+    val: Option<Value>,
+}
+
+impl ErrorReturn {
+    fn propagate() -> Self {
+        Self {
+            pc: BcPc::default(),
+            val: None,
+        }
+    }
+
+    fn goto(pc: BcPc) -> Self {
+        Self { pc, val: None }
+    }
+
+    fn ret(val: Value) -> Self {
+        Self {
+            pc: BcPc::default(),
+            val: Some(val),
+        }
+    }
+}
+
+///
+/// Push the location of the current error, and look up an exception handler for *pc*.
+///
+/// If a handler was found, the exception object is stored in the register of the current
+/// frame designated by the handler, and the pc of the handler is returned.
+/// Otherwise, a null pc is returned and the error is propagated to the caller.
+///
+/// For `return` from a block, only ensure clauses are executed, and the value is returned
+/// when the frame of the method is reached.
+///
+extern "C" fn handle_error(
+    interp: &mut Executor,
     globals: &mut Globals,
     meta: Meta,
     pc: BcPc,
-) {
+) -> ErrorReturn {
+    let method_return = globals.method_return_target();
     let func_info = &globals.func[meta.func_id()];
     let bc_base = func_info.data.pc;
     let normal_info = match &func_info.kind {
         FuncKind::ISeq(info) => info,
-        FuncKind::Builtin { .. } => return,
-        FuncKind::AttrReader { .. } => return,
-        FuncKind::AttrWriter { .. } => return,
+        FuncKind::Builtin { .. } => return ErrorReturn::propagate(),
+        FuncKind::AttrReader { .. } => return ErrorReturn::propagate(),
+        FuncKind::AttrWriter { .. } => return ErrorReturn::propagate(),
     };
+    let index = pc - bc_base;
+    if let Some(target) = method_return {
+        return match normal_info.get_ensure_dest(index) {
+            Some((dest, err_reg)) => {
+                let err = globals.take_error_obj();
+                interp.cfp.set_register(err_reg.0 as usize, err);
+                ErrorReturn::goto(bc_base + dest as isize)
+            }
+            None if target == interp.cfp.bp() as usize => {
+                match globals.take_error().unwrap().kind {
+                    MonorubyErrKind::MethodReturn(val, _) => ErrorReturn::ret(val),
+                    _ => unreachable!(),
+                }
+            }
+            None => ErrorReturn::propagate(),
+        };
+    }
     let sourceinfo = normal_info.sourceinfo.clone();
-    let loc = normal_info.sourcemap[pc - bc_base];
+    let loc = normal_info.sourcemap[index];
+    let handler = normal_info.get_exception_dest(index);
     globals.push_error_location(loc, sourceinfo);
+    match handler {
+        Some((dest, err_reg)) => {
+            let err = globals.take_error_obj();
+            interp.cfp.set_register(err_reg.0 as usize, err);
+            globals.set_gvar(IdentId::get_ident_id("$!"), err);
+            ErrorReturn::goto(bc_base + dest as isize)
+        }
+        None => ErrorReturn::propagate(),
+    }
 }
 
 impl Codegen {
@@ -271,9 +828,14 @@ impl Codegen {
         let entry_find_method = jit.label();
         let jit_return = jit.label();
         let vm_return = jit.label();
+        let vm_fetch = jit.label();
+        let resume = jit.label();
         let div_by_zero = jit.label();
         let wrong_argument = jit.label();
         let heap_to_f64 = jit.label();
+        let escape_trampoline = jit.label();
+        let fiber_switch = jit.label();
+        let fiber_start = jit.label();
         //jit.select_page(1);
         monoasm!(&mut jit,
         entry_panic:
@@ -292,26 +854,37 @@ impl Codegen {
             movq rax, (find_method);
             jmp  rax;
         vm_return:
-            movq r15, rax;
             movq rdi, rbx;
             movq rsi, r12;
             movq rdx, [rbp - (OFFSET_META)];
             movq rcx, r13;
             subq rcx, 8;
-            movq rax, (get_error_location);
+            movq rax, (handle_error);
             call rax;
-            // restore return value
-            movq rax, r15;
+            testq rax, rax;
+            jne  resume;
+            // rdx: the value to be returned (or None)
+            movq rax, rdx;
         jit_return:
             leave;
             ret;
+        resume:
+            // rax: pc of the exception handler
+            movq r13, rax;
+            // restore stack pointer.
+            movzxw rax, [rbp - (OFFSET_REGNUM)];
+            shlq rax, 3;
+            addq rax, (OFFSET_SELF + 15);
+            andq rax, (-16);
+            movq rsp, rbp;
+            subq rsp, rax;
+            jmp  vm_fetch;
         div_by_zero:
+            // r13: the next pc
             movq rdi, r12;
             movq rax, (err_divide_by_zero);
             call rax;
-            xorq rax, rax;
-            leave;
-            ret;
+            jmp  vm_return;
         wrong_argument:
             movq rdi, r12;
             movl rsi, rdx;  // given
@@ -320,6 +893,56 @@ impl Codegen {
             movq rax, (err_wrong_number_of_arguments_range);
             call rax;
             jmp  vm_return;
+        escape_trampoline:
+            // rsp: the bottom of the exited frame + 16
+            // rax, rdx: return values
+            movq r8, rsp;
+            lea  rdi, [rsp - 16];
+            // skip the exited frame.
+            movzxw rcx, [rdi - (OFFSET_REGNUM)];
+            shlq rcx, 3;
+            addq rcx, (OFFSET_SELF + 16);
+            subq rsp, rcx;
+            andq rsp, (-16);
+            pushq r8;
+            pushq rax;
+            pushq rdx;
+            subq rsp, 8;
+            movq rsi, rdi;
+            movq rdi, rbx;
+            movq rax, (exit_escaped_frame);
+            call rax;
+            // rax <- the original return address
+            movq rcx, rax;
+            addq rsp, 8;
+            popq rdx;
+            popq rax;
+            popq r8;
+            movq rsp, r8;
+            jmp  rcx;
+        fiber_switch:
+            pushq rbp;
+            pushq rbx;
+            pushq r12;
+            pushq r13;
+            pushq r14;
+            pushq r15;
+            movq rax, [rdi];
+            movq [rdi], rsp;
+            movq rsp, rax;
+            popq r15;
+            popq r14;
+            popq r13;
+            popq r12;
+            popq rbx;
+            popq rbp;
+            ret;
+        fiber_start:
+            movq rdi, rbx;
+            movq rsi, r12;
+            movq rdx, r13;
+            movq rax, (fiber_main);
+            call rax;
         heap_to_f64:
             // we must save rdi for log_optimize.
             subq rsp, 128;
@@ -377,16 +1000,20 @@ impl Codegen {
             class_version,
             class_version_addr: std::ptr::null_mut(),
             const_version,
+            const_version_addr: std::ptr::null_mut(),
             entry_panic,
             entry_find_method,
             vm_entry: entry_panic,
-            vm_fetch: entry_panic,
+            vm_fetch,
             entry_point: unsafe { std::mem::transmute(entry_unimpl.as_ptr()) },
             vm_return,
             f64_to_val: entry_panic,
             heap_to_f64,
             div_by_zero,
             wrong_argument,
+            escape_trampoline: 0,
+            fiber_switch: unsafe { std::mem::transmute(entry_unimpl.as_ptr()) },
+            fiber_start: 0,
             dispatch,
             method_invoker: unsafe { std::mem::transmute(entry_unimpl.as_ptr()) },
             method_invoker2: unsafe { std::mem::transmute(entry_unimpl.as_ptr()) },
@@ -398,6 +1025,13 @@ impl Codegen {
         codegen.jit.finalize();
         codegen.class_version_addr =
             codegen.jit.get_label_address(class_version).as_ptr() as *mut u32;
+        codegen.const_version_addr =
+            codegen.jit.get_label_address(const_version).as_ptr() as *mut usize;
+        codegen.escape_trampoline =
+            codegen.jit.get_label_address(escape_trampoline).as_ptr() as usize;
+        codegen.fiber_switch =
+            unsafe { std::mem::transmute(codegen.jit.get_label_address(fiber_switch).as_ptr()) };
+        codegen.fiber_start = codegen.jit.get_label_address(fiber_start).as_ptr() as usize;
         codegen
     }
 
@@ -603,12 +1237,58 @@ impl Codegen {
             // fn(&mut Interp, &mut Globals, Value, *const Value, len:usize, block:Option<Value>)
             call rax;
 
+            pushq rax;
+            pushq rax;
+            lea  rdi, [rbp - (OFFSET_ARG0)];
+            movzxw rsi, [rbp - (OFFSET_REGNUM)];
+            movq rax, (clear_keyword_arg);
+            call rax;
+            popq rax;
+            popq rax;
             leave;
             ret;
         );
         label
     }
 
+    ///
+    /// Clear the keyword flag of the keyword Hash passed as the last argument, unless the callee
+    /// has keyword parameters.
+    ///
+    /// in
+    /// rdx: number of args passed from caller
+    /// destroy
+    /// rax, caller-save registers except rdi, rsi and rdx
+    ///
+    fn clear_keyword_flag(&mut self) {
+        let exit = self.jit.label();
+        monoasm!(self.jit,
+            testq rdx, rdx;
+            jz   exit;
+            movq rax, rdx;
+            negq rax;
+            movq rax, [rbp + rax * 8 - (OFFSET_SELF)];
+            testq rax, 0b111;
+            jnz  exit;
+            cmpl [rax + 4], (HASH_CLASS.0);
+            jne  exit;
+            pushq rdi;
+            pushq rsi;
+            pushq rdx;
+            pushq rdx;
+            movq rdi, r12;
+            movq rsi, [rbp - (OFFSET_META)];
+            movq rdx, rax;
+            movq rax, (bind_keyword_hash);
+            call rax;
+            popq rdx;
+            popq rdx;
+            popq rsi;
+            popq rdi;
+        exit:
+        );
+    }
+
     ///
     /// Generate attr_reader.
     ///
@@ -715,10 +1395,11 @@ impl Codegen {
         func_id: FuncId,
         self_value: Value,
         pc: BcPc,
-    ) {
+    ) -> CodePtr {
         let label = globals.jit_compile_ruby(func_id, self_value, Some(pc));
         let codeptr = globals.codegen.jit.get_label_address(label);
         pc.write2(codeptr.as_ptr() as u64);
+        codeptr
     }
 }
 
@@ -44,12 +44,13 @@ impl JitContext {
     ///
     fn new(
         func: &ISeqInfo,
+        fnstore: &FnStore,
         codegen: &mut Codegen,
         start_pos: usize,
         is_loop: bool,
         self_value: Value,
     ) -> Self {
-        let bb_info = func.get_bb_info();
+        let bb_info = func.get_bb_info(fnstore);
         let mut labels = HashMap::default();
         bb_info.into_iter().enumerate().for_each(|(idx, elem)| {
             if elem.is_some() {
@@ -58,7 +59,7 @@ impl JitContext {
         });
         Self {
             labels,
-            bb_info: func.get_bb_info(),
+            bb_info: func.get_bb_info(fnstore),
             bb_pos: start_pos,
             loop_count: 0,
             is_loop,
@@ -439,8 +440,6 @@ impl StackSlotInfo {
     }
 }
 
-type UsingXmm = Vec<Xmm>;
-
 #[derive(PartialEq)]
 enum BinOpMode {
     RR(SlotId, SlotId),
@@ -495,7 +494,14 @@ impl Codegen {
         let entry = self.jit.label();
         self.jit.bind_label(entry);
 
-        let mut cc = jitgen::JitContext::new(func, self, start_pos, position.is_some(), self_value);
+        let mut cc = jitgen::JitContext::new(
+            func,
+            fnstore,
+            self,
+            start_pos,
+            position.is_some(),
+            self_value,
+        );
         let bb_start_pos: Vec<_> = cc
             .bb_info
             .iter()
@@ -882,6 +888,10 @@ impl Codegen {
         cc: &mut JitContext,
         position: Option<BcPc>,
     ) -> bool {
+        // This basic block is only reachable from exception handlers, which are executed by the VM.
+        if !cc.branch_map.contains_key(&cc.bb_pos) {
+            return false;
+        }
         let mut skip = false;
         let is_loop = matches!(func.get_pc(cc.bb_pos).op1(), TraceIr::LoopStart(_));
         self.jit.bind_label(cc.labels[&cc.bb_pos]);
@@ -939,7 +949,7 @@ impl Codegen {
                         let imm = self.jit.const_f64(f);
                         monoasm!(self.jit,
                             movq xmm(freg.enc()), [rip + imm];
-                            movq rax, (Value::new_float(f).get());
+                            movq rax, (val.get());
                             movq [rbp - (conv(dst))], rax;
                         );
                     } else {
@@ -971,6 +981,23 @@ impl Codegen {
                     );
                     self.store_rax(ret);
                 }
+                TraceIr::Hash { ret, args, len, kw } => {
+                    self.write_back_range(&mut ctx, args, len * 2);
+                    ctx.dealloc_xmm(ret);
+                    let xmm_using = ctx.get_xmm_using();
+                    self.xmm_save(&xmm_using);
+                    monoasm!(self.jit,
+                        movq rdi, rbx;
+                        movq rsi, r12;
+                        lea  rdx, [rbp - (conv(args))];
+                        movq rcx, (len);
+                        movq rax, (if kw { gen_kw_hash as usize } else { gen_hash as usize });
+                        call rax;
+                    );
+                    self.xmm_restore(&xmm_using);
+                    self.handle_error(&ctx, pc);
+                    self.store_rax(ret);
+                }
                 TraceIr::Range {
                     ret,
                     start,
@@ -988,7 +1015,7 @@ impl Codegen {
                         call rax;
                     };
                     self.xmm_restore(&xmm_using);
-                    self.handle_error(pc);
+                    self.handle_error(&ctx, pc);
                     self.store_rax(ret);
                 }
                 TraceIr::Index(ret, base, idx) => {
@@ -1019,12 +1046,40 @@ impl Codegen {
                 }
                 TraceIr::BlockArgProxy(dst) => {
                     ctx.dealloc_xmm(dst);
-                    let panic = self.entry_panic;
+                    let exit = self.jit.label();
                     monoasm! { self.jit,
                         movq rax, [rbp - (OFFSET_BLOCK)];
+                        // a Proc object or no block is passed as it is.
                         testq rax, 0b1;
-                        jeq panic;
+                        jeq exit;
                         addq rax, 0b10;
+                    exit:
+                        movq [rbp - (conv(dst))], rax;
+                    };
+                }
+                TraceIr::BlockArg(dst, src) => {
+                    self.write_back_slot(&mut ctx, src);
+                    ctx.dealloc_xmm(dst);
+                    let exit = self.jit.label();
+                    monoasm! { self.jit,
+                        // nil is passed as no block.
+                        movq rdx, [rbp - (conv(src))];
+                        xorq rax, rax;
+                        cmpq rdx, (NIL_VALUE);
+                        jeq  exit;
+                    };
+                    let xmm_using = ctx.get_xmm_using();
+                    self.xmm_save(&xmm_using);
+                    monoasm! { self.jit,
+                        movq rdi, rbx;
+                        movq rsi, r12;
+                        movq rax, (to_block);
+                        call rax;
+                    };
+                    self.xmm_restore(&xmm_using);
+                    self.handle_error(&ctx, pc);
+                    monoasm! { self.jit,
+                    exit:
                         movq [rbp - (conv(dst))], rax;
                     };
                 }
@@ -1049,6 +1104,20 @@ impl Codegen {
                     };
                     self.xmm_restore(&xmm_using);
                 }
+                TraceIr::LoadSvar { ret, id } => {
+                    ctx.dealloc_xmm(ret);
+                    let xmm_using = ctx.get_xmm_using();
+                    self.xmm_save(&xmm_using);
+                    monoasm! { self.jit,
+                        movq rdi, rbx;
+                        movq rsi, r12;
+                        movl rdx, (id);
+                        movq rax, (get_special_var);
+                        call rax;
+                        movq [rbp - (conv(ret))], rax;
+                    };
+                    self.xmm_restore(&xmm_using);
+                }
                 TraceIr::StoreGvar { val, name } => {
                     self.write_back_slot(&mut ctx, val);
                     let xmm_using = ctx.get_xmm_using();
@@ -1124,7 +1193,7 @@ impl Codegen {
                         );
                         self.call_unop(neg_value as _);
                         self.xmm_restore(&xmm_using);
-                        self.handle_error(pc);
+                        self.handle_error(&ctx, pc);
                         self.store_rax(dst);
                     }
                 }
@@ -1148,8 +1217,10 @@ impl Codegen {
                     ..
                 } => {
                     let (flhs, frhs) = self.xmm_read_binary(&mut ctx, lhs, rhs, pc);
+                    // the stack slots must be written back before *ret* is overwritten.
+                    let div_by_zero = self.gen_float_div_by_zero(kind, &ctx, pc);
                     let fret = ctx.xmm_write(ret);
-                    self.gen_binop_float(kind, &ctx, fret, flhs, frhs);
+                    self.gen_binop_float(kind, &ctx, fret, flhs, frhs, div_by_zero);
                 }
                 TraceIr::BinOp {
                     kind,
@@ -1180,8 +1251,9 @@ impl Codegen {
                         self.gen_binop_integer(pc, kind, ret, BinOpMode::RI(lhs, rhs), &ctx);
                     } else if pc.is_float1() {
                         let flhs = self.xmm_read_assume_float(&mut ctx, lhs, pc);
+                        let div_by_zero = self.gen_float_div_by_zero(kind, &ctx, pc);
                         let fret = ctx.xmm_write(ret);
-                        self.gen_binop_float_ri(kind, &ctx, fret, flhs, rhs);
+                        self.gen_binop_float_ri(kind, &ctx, fret, flhs, rhs, div_by_zero);
                     } else {
                         if pc.classid1().0 == 0 {
                             self.recompile_and_deopt(&ctx, position, pc);
@@ -1208,8 +1280,9 @@ impl Codegen {
                         self.gen_binop_integer(pc, kind, ret, BinOpMode::IR(lhs, rhs), &ctx);
                     } else if pc.is_float2() {
                         let frhs = self.xmm_read_assume_float(&mut ctx, rhs, pc);
+                        let div_by_zero = self.gen_float_div_by_zero(kind, &ctx, pc);
                         let fret = ctx.xmm_write(ret);
-                        self.gen_binop_float_ir(kind, &ctx, fret, lhs, frhs);
+                        self.gen_binop_float_ir(kind, &ctx, fret, lhs, frhs, div_by_zero);
                     } else {
                         if pc.classid2().0 == 0 {
                             self.recompile_and_deopt(&ctx, position, pc);
@@ -1250,7 +1323,7 @@ impl Codegen {
                         self.write_back_slot(&mut ctx, rhs);
                         ctx.dealloc_xmm(ret);
                         self.gen_cmp_prep(lhs, rhs, generic);
-                        self.gen_cmp_kind(kind, generic, ret, &ctx);
+                        self.gen_cmp_kind(kind, generic, ret, &ctx, pc);
                     }
                 }
                 TraceIr::Cmp(_, _, _, _, true) => {}
@@ -1279,7 +1352,7 @@ impl Codegen {
                         self.write_back_slot(&mut ctx, lhs);
                         ctx.dealloc_xmm(ret);
                         self.gen_cmpri_prep(lhs, rhs, generic);
-                        self.gen_cmp_kind(kind, generic, ret, &ctx);
+                        self.gen_cmp_kind(kind, generic, ret, &ctx, pc);
                     }
                 }
                 TraceIr::Cmpri(_, _, _, _, true) => {}
@@ -1346,6 +1419,34 @@ impl Codegen {
                         unreachable!()
                     }
                 }
+                TraceIr::Super {
+                    ret,
+                    func_id,
+                    has_block,
+                    splat: true,
+                    ..
+                } => {
+                    if let TraceIr::MethodArgs(method_info) = (pc + 1).op1() {
+                        self.gen_super_splat(&mut ctx, method_info, ret, func_id, has_block, pc);
+                    } else {
+                        unreachable!()
+                    }
+                }
+                TraceIr::Super {
+                    ret,
+                    func_id,
+                    has_block,
+                    ..
+                } => {
+                    if let TraceIr::MethodArgs(method_info) = (pc + 1).op1() {
+                        if method_info.callee_codeptr.is_none() {
+                            self.recompile_and_deopt(&ctx, position, pc);
+                        }
+                        self.gen_super(fnstore, &mut ctx, method_info, ret, func_id, has_block, pc);
+                    } else {
+                        unreachable!()
+                    }
+                }
                 TraceIr::Yield { ret, args, len } => {
                     ctx.dealloc_xmm(ret);
                     self.write_back_range(&mut ctx, args, len);
@@ -1369,13 +1470,52 @@ impl Codegen {
                     );
                     self.xmm_restore(&xmm_using);
                 }
+                TraceIr::SingletonMethodDef { obj, name, func_id } => {
+                    self.write_back_slot(&mut ctx, obj);
+                    let class_version = self.class_version;
+                    let xmm_using = ctx.get_xmm_using();
+                    self.xmm_save(&xmm_using);
+                    monoasm!(self.jit,
+                        movq rdi, rbx; // &mut Interp
+                        movq rsi, r12; // &Globals
+                        movq rdx, (u32::from(name)); // IdentId
+                        movq rcx, (u32::from(func_id)); // FuncId
+                        movq r8, [rbp - (conv(obj))]; // obj: Value
+                        movq rax, (define_singleton_method);
+                        call rax;
+                    );
+                    self.xmm_restore(&xmm_using);
+                    self.handle_error(&ctx, pc);
+                    monoasm!(self.jit,
+                        addl [rip + class_version], 1;
+                    );
+                }
                 TraceIr::ClassDef {
                     ret,
+                    base,
                     superclass,
                     name,
                     func_id,
+                    is_module,
                 } => {
-                    self.jit_class_def(&ctx, ret, superclass, name, func_id);
+                    let func = if is_module {
+                        define_module as usize
+                    } else {
+                        define_class as usize
+                    };
+                    self.jit_class_def(&ctx, ret, base, superclass, name, func_id, func, pc);
+                }
+                TraceIr::SingletonClassDef { ret, base, func_id } => {
+                    self.jit_class_def(
+                        &ctx,
+                        ret,
+                        base,
+                        SlotId::new(0),
+                        IdentId::default(),
+                        func_id,
+                        define_singleton_class as usize,
+                        pc,
+                    );
                 }
                 TraceIr::Ret(lhs) => {
                     self.write_back_slot(&mut ctx, lhs);
@@ -1385,6 +1525,47 @@ impl Codegen {
                     self.epilogue();
                     return false;
                 }
+                TraceIr::MethodRet(lhs) => {
+                    self.write_back_slot(&mut ctx, lhs);
+                    monoasm!(self.jit,
+                        movq rdi, rbx;
+                        movq rsi, r12;
+                        movq rdx, [rbp - (conv(lhs))];
+                        movq rax, (method_return);
+                        call rax;
+                    );
+                    self.handle_error(&ctx, pc);
+                    self.epilogue();
+                    return false;
+                }
+                TraceIr::InitKeyword(func_id) => {
+                    let local_num = ctx.local_num as u16;
+                    self.write_back_range(&mut ctx, SlotId(1), local_num);
+                    ctx.dealloc_locals();
+                    let xmm_using = ctx.get_xmm_using();
+                    self.xmm_save(&xmm_using);
+                    monoasm!(self.jit,
+                        movq rdi, rbx;
+                        movq rsi, r12;
+                        movl rdx, (func_id.0);
+                        lea  rcx, [rbp - (OFFSET_SELF)];
+                        movq rax, (init_keyword);
+                        call rax;
+                    );
+                    self.xmm_restore(&xmm_using);
+                    self.handle_error(&ctx, pc);
+                }
+                TraceIr::Raise(src) => {
+                    self.write_back_slot(&mut ctx, src);
+                    monoasm!(self.jit,
+                        movq rdi, r12;
+                        movq rsi, [rbp - (conv(src))];
+                        movq rax, (raise_err);
+                        call rax;
+                    );
+                    self.handle_error(&ctx, pc);
+                    return false;
+                }
                 TraceIr::Br(disp) => {
                     let next_idx = cc.bb_pos + ofs + 1;
                     let dest_idx = (next_idx as i64 + disp as i64) as usize;
@@ -1395,6 +1576,63 @@ impl Codegen {
                     );
                     return false;
                 }
+                TraceIr::OptCase(cond, id) => {
+                    let OptCaseInfo {
+                        symbol,
+                        min,
+                        branch_table,
+                    } = &fnstore[id];
+                    let next_idx = cc.bb_pos + ofs + 1;
+                    self.write_back_slot(&mut ctx, cond);
+                    let mut dest_labels: HashMap<usize, DestLabel> = HashMap::default();
+                    for dest_idx in fnstore[id].dests(cc.bb_pos + ofs) {
+                        let branch_dest = self.jit.label();
+                        cc.new_branch(cc.bb_pos + ofs, dest_idx, ctx.clone(), branch_dest);
+                        dest_labels.insert(dest_idx, branch_dest);
+                    }
+                    let fallthrough = self.jit.label();
+                    let table = self.jit.label();
+                    monoasm!(self.jit,
+                        movq rax, [rbp - (conv(cond))];
+                    );
+                    if *symbol {
+                        monoasm!(self.jit,
+                            movq rdi, rax;
+                            andq rdi, 0xff;
+                            cmpq rdi, (TAG_SYMBOL);
+                            jne  fallthrough;
+                            shrq rax, 32;
+                        );
+                    } else {
+                        monoasm!(self.jit,
+                            testq rax, 0b1;
+                            jz   fallthrough;
+                            sarq rax, 1;
+                        );
+                    }
+                    // each entry of the table is `jmp rel32` (5 bytes).
+                    monoasm!(self.jit,
+                        subq rax, (*min);
+                        cmpq rax, (branch_table.len() as u64);
+                        jae  fallthrough;
+                        movq rdi, rax;
+                        shlq rax, 2;
+                        addq rax, rdi;
+                        lea  rdi, [rip + table];
+                        addq rax, rdi;
+                        jmp  rax;
+                    table:
+                    );
+                    for disp in branch_table.iter() {
+                        let dest = dest_labels[&((next_idx as i32 + disp) as usize)];
+                        monoasm!(self.jit,
+                            jmp  dest;
+                        );
+                    }
+                    monoasm!(self.jit,
+                    fallthrough:
+                    );
+                }
                 TraceIr::CondBr(cond_, disp, false, kind) => {
                     let dest_idx = ((cc.bb_pos + ofs + 1) as i32 + disp) as usize;
                     let branch_dest = self.jit.label();
@@ -1457,13 +1695,12 @@ impl Codegen {
                             _ => unreachable!(),
                         };
 
-                        let xmm_using = ctx.get_xmm_using();
                         monoasm! { self.jit,
                             cmpq rdi, rsi;
                         };
                         let branch_dest = self.jit.label();
                         cc.new_branch(cc.bb_pos + ofs, dest_idx, ctx.clone(), branch_dest);
-                        self.gen_cmp_int_opt(kind, branch_dest, generic, brkind, xmm_using);
+                        self.gen_cmp_int_opt(kind, branch_dest, generic, brkind, &ctx, pc);
                     }
                 }
                 TraceIr::CheckLocal(local, disp) => {
@@ -1527,13 +1764,89 @@ impl Codegen {
 }
 
 impl Codegen {
-    fn handle_error(&mut self, pc: BcPc) {
+    ///
+    /// Exit to `vm_return` if an error occured (rax == 0).
+    ///
+    /// The frame may be resumed at an exception handler by the VM, so all xmm registers linked to
+    /// stack slots are written back before exiting.
+    ///
+    fn handle_error(&mut self, ctx: &BBContext, pc: BcPc) {
         let jit_return = self.vm_return;
         monoasm!(self.jit,
             movq r13, ((pc + 1).get_u64());
-            testq rax, rax; // Option<Value>
-            jeq  jit_return;
         );
+        let wb = ctx.get_write_back();
+        if wb.is_empty() {
+            monoasm!(self.jit,
+                testq rax, rax; // Option<Value>
+                jeq  jit_return;
+            );
+        } else {
+            let error = self.jit.label();
+            monoasm!(self.jit,
+                testq rax, rax; // Option<Value>
+                jeq  error;
+            );
+            self.gen_error_exit(error, &[], wb);
+        }
+    }
+
+    ///
+    /// Generate an exit to `vm_return` for errors at *error*.
+    ///
+    /// xmm registers saved by `xmm_save(xmm_using)` are restored, and then written back to the stack
+    /// slots in *wb*. r13 must be set to the next pc before jumping to *error*.
+    ///
+    fn gen_error_exit(&mut self, error: DestLabel, xmm_using: &[Xmm], wb: WriteBack) {
+        let jit_return = self.vm_return;
+        let in_side_page = self.jit.get_page() != 0;
+        let cont = self.jit.label();
+        if in_side_page {
+            monoasm!(self.jit,
+                jmp  cont;
+            );
+        } else {
+            self.jit.select_page(1);
+        }
+        self.jit.bind_label(error);
+        self.xmm_restore(xmm_using);
+        self.gen_write_back(wb);
+        monoasm!(self.jit,
+            jmp  jit_return;
+        );
+        if in_side_page {
+            self.jit.bind_label(cont);
+        } else {
+            self.jit.select_page(0);
+        }
+    }
+
+    ///
+    /// Get *DestLabel* for raising ZeroDivisionError after writing back all linked xmms.
+    ///
+    /// Only the division needs this.
+    ///
+    fn gen_float_div_by_zero(
+        &mut self,
+        kind: BinOpK,
+        ctx: &BBContext,
+        pc: BcPc,
+    ) -> Option<DestLabel> {
+        if kind != BinOpK::Div {
+            return None;
+        }
+        assert_eq!(0, self.jit.get_page());
+        let div_by_zero = self.div_by_zero;
+        let entry = self.jit.label();
+        self.jit.select_page(1);
+        self.jit.bind_label(entry);
+        self.gen_write_back(ctx.get_write_back());
+        monoasm!(self.jit,
+            movq r13, ((pc + 1).get_u64());
+            jmp  div_by_zero;
+        );
+        self.jit.select_page(0);
+        Some(entry)
     }
 
     ///
@@ -1672,7 +1985,7 @@ impl Codegen {
         arg_num: usize,
         pos_num: usize,
         req_num: usize,
-        _block_pos: usize,
+        block_pos: usize,
         pc: BcPc,
         is_block: bool,
     ) {
@@ -1687,6 +2000,7 @@ impl Codegen {
         self.jit.select_page(0);
 
         // rdx: number of args passed from caller
+        self.clear_keyword_flag();
         let fill_req = self.jit.label();
         let fill_opt = self.jit.label();
         let set_rest_empty = self.jit.label();
@@ -1785,6 +2099,17 @@ impl Codegen {
                 );
             }
         }
+        // set block parameter.
+        if block_pos != 0 {
+            monoasm!(self.jit,
+                movq rdi, rbx;
+                movq rsi, r12;
+                movq rdx, [rbp - (OFFSET_BLOCK)];
+                movq rax, (block_arg);
+                call rax;
+                movq [rbp - (conv(SlotId::new(block_pos as u16)))], rax;
+            );
+        }
     }
 
     fn jit_expand_arg0(&mut self, req_num: usize) {
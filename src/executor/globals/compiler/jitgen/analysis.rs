@@ -20,7 +20,7 @@ impl LoopAnalysis {
         fnstore: &FnStore,
         bb_pos: usize,
     ) -> (Vec<(SlotId, bool)>, Vec<SlotId>) {
-        let mut ctx = LoopAnalysis::new(func);
+        let mut ctx = LoopAnalysis::new(func, fnstore);
         let regnum = func.total_reg_num();
         let bb_start_vec: Vec<usize> = ctx
             .bb_info
@@ -75,15 +75,21 @@ impl LoopAnalysis {
                 .collect::<Vec<_>>()
         );
 
-        (info.get_loop_used_as_float(), exit_info.get_unused())
+        let mut unused = exit_info.get_unused();
+        // Exception handlers are executed by the VM, and may read any local variable.
+        if !func.exception_map.is_empty() {
+            let local_num = func.local_num();
+            unused.retain(|slot| slot.0 as usize > local_num);
+        }
+        (info.get_loop_used_as_float(), unused)
     }
 }
 
 impl LoopAnalysis {
-    fn new(func: &ISeqInfo) -> Self {
+    fn new(func: &ISeqInfo, fnstore: &FnStore) -> Self {
         Self {
             branch_map: HashMap::default(),
-            bb_info: func.get_bb_info(),
+            bb_info: func.get_bb_info(fnstore),
             loop_level: 0,
             backedge_info: None,
             return_info: None,
@@ -167,6 +173,12 @@ impl LoopAnalysis {
                         reg_info.def_as(dst, false);
                     }
                 }
+                TraceIr::Hash { ret, args, len, .. } => {
+                    for r in args.0..args.0 + len * 2 {
+                        reg_info.use_non_float(SlotId(r));
+                    }
+                    reg_info.def_as(ret, false);
+                }
                 TraceIr::Range {
                     ret, start, end, ..
                 } => {
@@ -176,9 +188,16 @@ impl LoopAnalysis {
                 }
                 TraceIr::IndexAssign(..) => {}
                 TraceIr::MethodDef(..) => {}
+                TraceIr::SingletonMethodDef { obj, .. } => {
+                    reg_info.use_non_float(obj);
+                }
                 TraceIr::ClassDef { ret, .. } => {
                     reg_info.def_as(ret, false);
                 }
+                TraceIr::SingletonClassDef { ret, base, .. } => {
+                    reg_info.use_non_float(base);
+                    reg_info.def_as(ret, false);
+                }
                 TraceIr::LoadConst(dst, _const_id) => {
                     let is_float = if let Some(value) = pc.value() {
                         value.class_id() == FLOAT_CLASS
@@ -191,6 +210,10 @@ impl LoopAnalysis {
                 TraceIr::BlockArgProxy(dst) => {
                     reg_info.def_as(dst, false);
                 }
+                TraceIr::BlockArg(dst, src) => {
+                    reg_info.use_non_float(src);
+                    reg_info.def_as(dst, false);
+                }
                 TraceIr::LoadDynVar(dst, ..) => {
                     reg_info.def_as(dst, false);
                 }
@@ -203,7 +226,7 @@ impl LoopAnalysis {
                 TraceIr::StoreIvar(src, ..) => {
                     reg_info.use_non_float(src);
                 }
-                TraceIr::LoadGvar { ret, .. } => {
+                TraceIr::LoadGvar { ret, .. } | TraceIr::LoadSvar { ret, .. } => {
                     reg_info.def_as(ret, false);
                 }
                 TraceIr::StoreGvar { val, .. } => {
@@ -269,6 +292,7 @@ impl LoopAnalysis {
                 }
                 TraceIr::MethodCall { .. } => {}
                 TraceIr::MethodCallBlock { .. } => {}
+                TraceIr::Super { .. } => {}
                 TraceIr::MethodArgs(method_info) => {
                     let MethodInfo {
                         recv,
@@ -301,6 +325,9 @@ impl LoopAnalysis {
                                             reg_info.use_as(args, true, FLOAT_CLASS);
                                             reg_info.def_as(ret, true);
                                         }
+                                        InlineMethod::RangeEach => {
+                                            reg_info.call_method(recv, args, len, ret);
+                                        }
                                     }
                                 } else {
                                     reg_info.call_method(recv, args, len, ret);
@@ -309,7 +336,12 @@ impl LoopAnalysis {
                                 reg_info.call_method(recv, args, len, ret);
                             }
                         }
-                        TraceIr::MethodCallBlock { ret, .. } => {
+                        TraceIr::MethodCallBlock { ret, .. }
+                        | TraceIr::Super {
+                            ret,
+                            has_block: true,
+                            ..
+                        } => {
                             reg_info.use_non_float(recv);
                             for i in 0..len + 1 {
                                 reg_info.use_non_float(args + i);
@@ -320,14 +352,26 @@ impl LoopAnalysis {
                             }
                             reg_info.def_as(ret, false);
                         }
+                        TraceIr::Super { ret, .. } => {
+                            reg_info.call_method(recv, args, len, ret);
+                        }
                         _ => unreachable!(),
                     };
                     skip = true;
                 }
-                TraceIr::Ret(_ret) => {
+                TraceIr::Ret(_ret) | TraceIr::MethodRet(_ret) => {
                     self.add_return(&reg_info);
                     return None;
                 }
+                TraceIr::Raise(src) => {
+                    reg_info.use_non_float(src);
+                    return None;
+                }
+                TraceIr::InitKeyword(_) => {
+                    for i in 1..1 + func.local_num() as u16 {
+                        reg_info.def_as(SlotId(i), false);
+                    }
+                }
                 TraceIr::Br(disp) => {
                     let dest_idx = ((idx + 1) as i32 + disp) as usize;
                     if disp >= 0 {
@@ -346,6 +390,12 @@ impl LoopAnalysis {
                         self.add_backedge(&reg_info);
                     }
                 }
+                TraceIr::OptCase(cond, id) => {
+                    reg_info.use_non_float(cond);
+                    for dest_idx in fnstore[id].dests(idx) {
+                        self.add_branch(idx, reg_info.clone(), dest_idx);
+                    }
+                }
                 TraceIr::CheckLocal(_, disp) => {
                     let dest_idx = ((idx + 1) as i32 + disp) as usize;
                     if disp >= 0 {
@@ -4,19 +4,18 @@ use ruruby_parse::CmpKind;
 macro_rules! cmp_main {
     ($op:ident) => {
         paste! {
-            fn [<cmp_ $op>](&mut self, generic:DestLabel, xmm_using: Vec<Xmm>) {
+            fn [<cmp_ $op>](&mut self, generic:DestLabel, ctx: &BBContext, pc: BcPc) {
+                let xmm_using = ctx.get_xmm_using();
                 let exit = self.jit.label();
                 self.[<integer_cmp_ $op>]();
                 self.jit.bind_label(exit);
                 self.jit.select_page(1);
                 self.jit.bind_label(generic);
                 self.xmm_save(&xmm_using);
-                monoasm!(self.jit,
-                    // generic path
-                    movq rax, ([<cmp_ $op _values>]);
-                    call rax;
-                );
+                // generic path
+                self.call_binop([<cmp_ $op _values>] as _);
                 self.xmm_restore(&xmm_using);
+                self.handle_error(ctx, pc);
                 monoasm!(self.jit,
                     jmp  exit;
                 );
@@ -39,11 +38,9 @@ macro_rules! cmp_main {
                 self.jit.bind_label(exit);
                 self.jit.select_page(1);
                 self.jit.bind_label(generic);
-                monoasm!(self.jit,
-                    // generic path
-                    movq rax, ([<cmp_ $op _values>]);
-                    call rax;
-                );
+                // generic path
+                self.call_binop([<cmp_ $op _values>] as _);
+                self.vm_handle_error();
                 monoasm!(self.jit,
                     jmp  exit;
                 );
@@ -60,7 +57,8 @@ macro_rules! cmp_main {
 macro_rules! cmp_opt_main {
     (($op:ident, $rev_op:ident, $sop:ident, $rev_sop:ident)) => {
         paste! {
-            fn [<cmp_opt_int_ $sop>](&mut self, branch_dest: DestLabel, generic:DestLabel, brkind: BrKind, xmm_using: Vec<Xmm>) {
+            fn [<cmp_opt_int_ $sop>](&mut self, branch_dest: DestLabel, generic:DestLabel, brkind: BrKind, ctx: &BBContext, pc: BcPc) {
+                let xmm_using = ctx.get_xmm_using();
                 let cont = self.jit.label();
                 match brkind {
                     BrKind::BrIf => monoasm! { self.jit,
@@ -74,12 +72,10 @@ macro_rules! cmp_opt_main {
                 self.jit.select_page(1);
                 self.jit.bind_label(generic);
                 self.xmm_save(&xmm_using);
-                monoasm!(self.jit,
-                    // generic path
-                    movq rax, ([<cmp_ $sop _values>]);
-                    call rax;
-                );
+                // generic path
+                self.call_binop([<cmp_ $sop _values>] as _);
                 self.xmm_restore(&xmm_using);
+                self.handle_error(ctx, pc);
                 monoasm!(self.jit,
                     orq  rax, 0x10;
                     cmpq rax, (FALSE_VALUE);
@@ -137,7 +133,6 @@ impl Codegen {
         mode: BinOpMode,
         ctx: &BBContext,
     ) {
-        let xmm_using = ctx.get_xmm_using();
         let deopt = self.gen_side_deopt(pc, ctx);
         match kind {
             BinOpK::Add => {
@@ -208,58 +203,40 @@ impl Codegen {
             }
             BinOpK::Mul => {
                 self.load_binary_args_with_mode(&mode);
-                self.generic_binop(ret, mul_values as _, xmm_using, pc);
+                self.generic_binop(ctx, ret, mul_values as _, pc);
             }
             BinOpK::Div => {
                 self.load_binary_args_with_mode(&mode);
-                self.generic_binop(ret, div_values as _, xmm_using, pc);
+                self.generic_binop(ctx, ret, div_values as _, pc);
             }
             BinOpK::Rem => {
                 self.load_binary_args_with_mode(&mode);
-                self.generic_binop(ret, rem_values as _, xmm_using, pc);
+                self.generic_binop(ctx, ret, rem_values as _, pc);
             }
             BinOpK::Exp => {
-                self.xmm_save(&xmm_using);
+                self.load_binary_args_with_mode(&mode);
                 match mode {
-                    BinOpMode::RR(lhs, rhs) => {
-                        self.load_guard_binary_fixnum(lhs, rhs, deopt);
-                        monoasm!(self.jit,
-                            sarq rdi, 1;
-                            sarq rsi, 1;
-                        );
-                    }
-                    BinOpMode::RI(lhs, rhs) => {
-                        self.load_guard_rdi_fixnum(lhs, deopt);
-                        monoasm!(self.jit,
-                            sarq rdi, 1;
-                            movq rsi, (rhs);
-                        );
+                    // a negative exponent gives a Rational, and 0 ** -n raises an error.
+                    BinOpMode::RI(_, rhs) if rhs < 0 => {
+                        self.generic_binop(ctx, ret, pow_values as _, pc);
                     }
-                    BinOpMode::IR(lhs, rhs) => {
-                        self.load_guard_rsi_fixnum(rhs, deopt);
-                        monoasm!(self.jit,
-                            movq rdi, (lhs);
-                            sarq rsi, 1;
-                        );
+                    _ => {
+                        let generic = self.jit.label();
+                        self.guard_binary_fixnum_with_mode(generic, mode);
+                        self.gen_pow(ctx, generic, ret, pc);
                     }
                 }
-                monoasm!(self.jit,
-                    movq rax, (pow_ii as u64);
-                    call rax;
-                );
-                self.xmm_restore(&xmm_using);
-                self.store_rax(ret);
             }
             _ => {
                 let generic = self.jit.label();
                 self.load_binary_args_with_mode(&mode);
                 self.guard_binary_fixnum_with_mode(generic, mode);
                 match kind {
-                    BinOpK::BitOr => self.gen_bit_or(generic, ret, xmm_using, pc),
-                    BinOpK::BitAnd => self.gen_bit_and(generic, ret, xmm_using, pc),
-                    BinOpK::BitXor => self.gen_bit_xor(generic, ret, xmm_using, pc),
-                    BinOpK::Shr => self.gen_shr(generic, ret, xmm_using, pc),
-                    BinOpK::Shl => self.gen_shl(generic, ret, xmm_using, pc),
+                    BinOpK::BitOr => self.gen_bit_or(ctx, generic, ret, pc),
+                    BinOpK::BitAnd => self.gen_bit_and(ctx, generic, ret, pc),
+                    BinOpK::BitXor => self.gen_bit_xor(ctx, generic, ret, pc),
+                    BinOpK::Shr => self.gen_shr(ctx, generic, ret, pc),
+                    BinOpK::Shl => self.gen_shl(ctx, generic, ret, pc),
                     _ => unimplemented!(),
                 }
             }
@@ -273,6 +250,7 @@ impl Codegen {
         fret: Xmm,
         flhs: Xmm,
         frhs: Xmm,
+        div_by_zero: Option<DestLabel>,
     ) {
         let lhs = flhs.enc();
         let rhs = frhs.enc();
@@ -317,7 +295,7 @@ impl Codegen {
                 }
             }
             BinOpK::Div => {
-                let div_by_zero = self.div_by_zero;
+                let div_by_zero = div_by_zero.unwrap();
                 if ret == rhs {
                     monoasm!(self.jit,
                         movq  rax, xmm(ret);
@@ -328,11 +306,13 @@ impl Codegen {
                         movq  xmm(ret), xmm0;
                     );
                 } else {
-                    self.xmm_mov(flhs, fret);
                     monoasm!(self.jit,
                         movq  rax, xmm(rhs);
                         testq rax, rax;
                         jz    div_by_zero;
+                    );
+                    self.xmm_mov(flhs, fret);
+                    monoasm!(self.jit,
                         divsd xmm(ret), xmm(rhs);
                     );
                 }
@@ -362,6 +342,7 @@ impl Codegen {
         fret: Xmm,
         flhs: Xmm,
         rhs: i16,
+        div_by_zero: Option<DestLabel>,
     ) {
         let rhs_label = self.jit.const_f64(rhs as f64);
         let ret = fret.enc();
@@ -386,7 +367,7 @@ impl Codegen {
             }
             BinOpK::Div => {
                 if rhs == 0 {
-                    let div_by_zero = self.div_by_zero;
+                    let div_by_zero = div_by_zero.unwrap();
                     monoasm!(self.jit,
                         jmp   div_by_zero;
                     )
@@ -423,6 +404,7 @@ impl Codegen {
         fret: Xmm,
         lhs: i16,
         frhs: Xmm,
+        div_by_zero: Option<DestLabel>,
     ) {
         let lhs = self.jit.const_f64(lhs as f64);
         let rhs = frhs.enc();
@@ -467,13 +449,13 @@ impl Codegen {
                 }
             }
             BinOpK::Div => {
-                let div_by_zero = self.div_by_zero;
+                let div_by_zero = div_by_zero.unwrap();
                 if ret != rhs {
                     monoasm!(self.jit,
-                        movq  xmm(ret), [rip + lhs];
                         movq  rax, xmm(rhs);
                         testq rax, rax;
                         jeq   div_by_zero;
+                        movq  xmm(ret), [rip + lhs];
                         divsd xmm(ret), xmm(rhs);
                     );
                 } else {
@@ -512,8 +494,7 @@ impl Codegen {
         kind: BinOpK,
         ret: SlotId,
     ) {
-        let xmm_using = ctx.get_xmm_using();
-        self.generic_binop(ret, kind.generic_func() as _, xmm_using, pc);
+        self.generic_binop(ctx, ret, kind.generic_func() as _, pc);
     }
 
     pub(super) fn setflag_float(&mut self, kind: CmpKind) {
@@ -551,15 +532,15 @@ impl Codegen {
         generic: DestLabel,
         ret: SlotId,
         ctx: &BBContext,
+        pc: BcPc,
     ) {
-        let xmm_using = ctx.get_xmm_using();
         match kind {
-            CmpKind::Eq => self.cmp_eq(generic, xmm_using),
-            CmpKind::Ne => self.cmp_ne(generic, xmm_using),
-            CmpKind::Ge => self.cmp_ge(generic, xmm_using),
-            CmpKind::Gt => self.cmp_gt(generic, xmm_using),
-            CmpKind::Le => self.cmp_le(generic, xmm_using),
-            CmpKind::Lt => self.cmp_lt(generic, xmm_using),
+            CmpKind::Eq => self.cmp_eq(generic, ctx, pc),
+            CmpKind::Ne => self.cmp_ne(generic, ctx, pc),
+            CmpKind::Ge => self.cmp_ge(generic, ctx, pc),
+            CmpKind::Gt => self.cmp_gt(generic, ctx, pc),
+            CmpKind::Le => self.cmp_le(generic, ctx, pc),
+            CmpKind::Lt => self.cmp_lt(generic, ctx, pc),
             _ => unimplemented!(),
         }
         self.store_rax(ret);
@@ -584,15 +565,16 @@ impl Codegen {
         branch_dest: DestLabel,
         generic: DestLabel,
         brkind: BrKind,
-        xmm_using: Vec<Xmm>,
+        ctx: &BBContext,
+        pc: BcPc,
     ) {
         match kind {
-            CmpKind::Eq => self.cmp_opt_int_eq(branch_dest, generic, brkind, xmm_using),
-            CmpKind::Ne => self.cmp_opt_int_ne(branch_dest, generic, brkind, xmm_using),
-            CmpKind::Ge => self.cmp_opt_int_ge(branch_dest, generic, brkind, xmm_using),
-            CmpKind::Gt => self.cmp_opt_int_gt(branch_dest, generic, brkind, xmm_using),
-            CmpKind::Le => self.cmp_opt_int_le(branch_dest, generic, brkind, xmm_using),
-            CmpKind::Lt => self.cmp_opt_int_lt(branch_dest, generic, brkind, xmm_using),
+            CmpKind::Eq => self.cmp_opt_int_eq(branch_dest, generic, brkind, ctx, pc),
+            CmpKind::Ne => self.cmp_opt_int_ne(branch_dest, generic, brkind, ctx, pc),
+            CmpKind::Ge => self.cmp_opt_int_ge(branch_dest, generic, brkind, ctx, pc),
+            CmpKind::Gt => self.cmp_opt_int_gt(branch_dest, generic, brkind, ctx, pc),
+            CmpKind::Le => self.cmp_opt_int_le(branch_dest, generic, brkind, ctx, pc),
+            CmpKind::Lt => self.cmp_opt_int_lt(branch_dest, generic, brkind, ctx, pc),
             _ => unimplemented!(),
         }
     }
@@ -639,32 +621,52 @@ impl Codegen {
         self.guard_rsi_fixnum(deopt);
     }
 
-    fn gen_bit_or(&mut self, generic: DestLabel, ret: SlotId, xmm_using: UsingXmm, pc: BcPc) {
+    fn gen_bit_or(&mut self, ctx: &BBContext, generic: DestLabel, ret: SlotId, pc: BcPc) {
         monoasm!(self.jit,
             // fastpath
             orq rdi, rsi;
         );
         self.store_rdi(ret);
-        self.side_generic_op(generic, ret, bitor_values as _, xmm_using, pc);
+        self.side_generic_op(ctx, generic, ret, bitor_values as _, pc);
     }
 
-    fn gen_bit_and(&mut self, generic: DestLabel, ret: SlotId, xmm_using: UsingXmm, pc: BcPc) {
+    fn gen_bit_and(&mut self, ctx: &BBContext, generic: DestLabel, ret: SlotId, pc: BcPc) {
         monoasm!(self.jit,
             // fastpath
             andq rdi, rsi;
         );
         self.store_rdi(ret);
-        self.side_generic_op(generic, ret, bitand_values as _, xmm_using, pc);
+        self.side_generic_op(ctx, generic, ret, bitand_values as _, pc);
     }
 
-    fn gen_bit_xor(&mut self, generic: DestLabel, ret: SlotId, xmm_using: UsingXmm, pc: BcPc) {
+    fn gen_bit_xor(&mut self, ctx: &BBContext, generic: DestLabel, ret: SlotId, pc: BcPc) {
         monoasm!(self.jit,
             // fastpath
             xorq rdi, rsi;
             addq rdi, 1;
         );
         self.store_rdi(ret);
-        self.side_generic_op(generic, ret, bitxor_values as _, xmm_using, pc);
+        self.side_generic_op(ctx, generic, ret, bitxor_values as _, pc);
+    }
+
+    fn gen_pow(&mut self, ctx: &BBContext, generic: DestLabel, ret: SlotId, pc: BcPc) {
+        let xmm_using = ctx.get_xmm_using();
+        monoasm!(self.jit,
+            // a negative exponent is handled in the generic path.
+            testq rsi, rsi;
+            js generic;
+        );
+        self.xmm_save(&xmm_using);
+        monoasm!(self.jit,
+            // fastpath
+            sarq rdi, 1;
+            sarq rsi, 1;
+            movq rax, (pow_ii as u64);
+            call rax;
+        );
+        self.xmm_restore(&xmm_using);
+        self.store_rax(ret);
+        self.side_generic_op(ctx, generic, ret, pow_values as _, pc);
     }
 
     fn shift_under(&mut self, under: DestLabel, after: DestLabel) {
@@ -684,7 +686,7 @@ impl Codegen {
         self.jit.select_page(0);
     }
 
-    fn gen_shr(&mut self, generic: DestLabel, ret: SlotId, xmm_using: UsingXmm, pc: BcPc) {
+    fn gen_shr(&mut self, ctx: &BBContext, generic: DestLabel, ret: SlotId, pc: BcPc) {
         let shl = self.jit.label();
         let after = self.jit.label();
         let under = self.jit.label();
@@ -700,7 +702,7 @@ impl Codegen {
             orq rdi, 1;
         );
         self.store_rdi(ret);
-        self.side_generic_op(generic, ret, shr_values as _, xmm_using, pc);
+        self.side_generic_op(ctx, generic, ret, shr_values as _, pc);
         self.jit.select_page(1);
         monoasm!(self.jit,
         shl:
@@ -716,7 +718,7 @@ impl Codegen {
         self.shift_under(under, after);
     }
 
-    fn gen_shl(&mut self, generic: DestLabel, ret: SlotId, xmm_using: UsingXmm, pc: BcPc) {
+    fn gen_shl(&mut self, ctx: &BBContext, generic: DestLabel, ret: SlotId, pc: BcPc) {
         let shr = self.jit.label();
         let after = self.jit.label();
         let under = self.jit.label();
@@ -735,7 +737,7 @@ impl Codegen {
         );
         self.store_rdi(ret);
 
-        self.side_generic_op(generic, ret, shl_values as _, xmm_using, pc);
+        self.side_generic_op(ctx, generic, ret, shl_values as _, pc);
         self.jit.select_page(1);
         monoasm!(self.jit,
         shr:
@@ -751,28 +753,29 @@ impl Codegen {
 
     fn side_generic_op(
         &mut self,
+        ctx: &BBContext,
         generic: DestLabel,
         ret: SlotId,
         func: usize,
-        xmm_using: UsingXmm,
         pc: BcPc,
     ) {
         let exit = self.jit.label();
         self.jit.bind_label(exit);
         self.jit.select_page(1);
         self.jit.bind_label(generic);
-        self.generic_binop(ret, func, xmm_using, pc);
+        self.generic_binop(ctx, ret, func, pc);
         monoasm!(self.jit,
             jmp  exit;
         );
         self.jit.select_page(0);
     }
 
-    fn generic_binop(&mut self, ret: SlotId, func: usize, xmm_using: UsingXmm, pc: BcPc) {
+    fn generic_binop(&mut self, ctx: &BBContext, ret: SlotId, func: usize, pc: BcPc) {
+        let xmm_using = ctx.get_xmm_using();
         self.xmm_save(&xmm_using);
         self.call_binop(func);
         self.xmm_restore(&xmm_using);
-        self.handle_error(pc);
+        self.handle_error(ctx, pc);
         self.store_rax(ret);
     }
 
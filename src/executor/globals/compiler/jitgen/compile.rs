@@ -77,7 +77,7 @@ impl Codegen {
                 call rax;
             );
             self.xmm_restore(&xmm_using);
-            self.handle_error(pc);
+            self.handle_error(ctx, pc);
         }
         self.jit.bind_label(exit);
     }
@@ -102,7 +102,7 @@ impl Codegen {
             call rax;
         };
         self.xmm_restore(&xmm_using);
-        self.handle_error(pc);
+        self.handle_error(ctx, pc);
         self.store_rax(ret);
     }
 
@@ -126,20 +126,24 @@ impl Codegen {
             call rax;
         };
         self.xmm_restore(&xmm_using);
-        self.handle_error(pc);
+        self.handle_error(ctx, pc);
     }
 
     pub(super) fn jit_class_def(
         &mut self,
         ctx: &BBContext,
         ret: SlotId,
+        base: SlotId,
         superclass: SlotId,
         name: IdentId,
         func_id: FuncId,
+        func: usize,
+        pc: BcPc,
     ) {
         let xmm_using = ctx.get_xmm_using();
         self.xmm_save(&xmm_using);
-        let jit_return = self.vm_return;
+        let error = self.jit.label();
+        let body_error = self.jit.label();
         if superclass.is_zero() {
             monoasm! { self.jit,
                 xorq rcx, rcx;
@@ -149,14 +153,26 @@ impl Codegen {
                 movq rcx, [rbp - (conv(superclass))];  // rcx <- superclass: Option<Value>
             }
         }
+        if base.is_zero() {
+            monoasm! { self.jit,
+                xorq r8, r8;
+            }
+        } else {
+            monoasm! { self.jit,
+                movq r8, [rbp - (conv(base))];  // r8 <- base: Option<Value>
+            }
+        }
         monoasm! { self.jit,
             movl rdx, (name.get());  // rdx <- name
             movq rdi, rbx;  // &mut Interp
             movq rsi, r12;  // &mut Globals
-            movq rax, (define_class);
+            movq rax, (func);
             call rax;  // rax <- self: Value
-            testq rax, rax; // rax: Option<Value>
-            jeq  jit_return;
+            movq r13, ((pc + 1).get_u64());
+            testq rax, rax;
+            jeq error;
+        };
+        monoasm! { self.jit,
             movq r15, rax; // r15 <- self
             movl rsi, (func_id.0);  // rdx <- func_id
             //movq rdi, rbx;  // &mut Interp
@@ -193,7 +209,7 @@ impl Codegen {
             xorq rdi, rdi;
             call rax;
             testq rax, rax;
-            jeq jit_return;
+            jeq body_error;
         };
         if !ret.is_zero() {
             monoasm!(self.jit,
@@ -208,6 +224,18 @@ impl Codegen {
             call rax;
         );
         self.xmm_restore(&xmm_using);
+        self.jit.select_page(1);
+        monoasm!(self.jit,
+        body_error:
+            movq rdi, rbx; // &mut Interp
+            movq rsi, r12; // &mut Globals
+            movq rax, (pop_class_context);
+            call rax;
+            movq r13, ((pc + 1).get_u64());
+            jmp  error;
+        );
+        self.jit.select_page(0);
+        self.gen_error_exit(error, &xmm_using, ctx.get_write_back());
     }
 }
 
@@ -2,7 +2,6 @@ use super::*;
 
 impl Codegen {
     pub(super) fn jit_store_constant(&mut self, ctx: &BBContext, id: IdentId, src: SlotId) {
-        let const_version = self.const_version;
         let xmm_using = ctx.get_xmm_using();
         self.xmm_save(&xmm_using);
         monoasm!(self.jit,
@@ -10,7 +9,6 @@ impl Codegen {
           movq rcx, [rbp - (conv(src))];  // val: Value
           movq rdi, rbx;  // &mut Interp
           movq rsi, r12;  // &mut Globals
-          addq [rip + const_version], 1;
           movq rax, (set_constant);
           call rax;
         );
@@ -106,6 +104,6 @@ impl Codegen {
             call rax;
         );
         self.xmm_restore(&xmm_using);
-        self.handle_error(pc);
+        self.handle_error(ctx, pc);
     }
 }
@@ -28,9 +28,13 @@ impl Codegen {
         self.write_back_slot(ctx, recv);
         if let Some(codeptr) = callee_codeptr {
             let cached = InlineCached::new(pc + 1, codeptr);
-            if let Some(inline_id) = fnstore.inline.get(&cached.func_id()) {
-                self.gen_inlinable(ctx, &method_info, inline_id, ret, pc);
-                return;
+            match fnstore.inline.get(&cached.func_id()) {
+                // Range#each without a block is just a method call.
+                Some(InlineMethod::RangeEach) | None => {}
+                Some(inline_id) => {
+                    self.gen_inlinable(ctx, &method_info, inline_id, ret, pc);
+                    return;
+                }
             }
         }
         self.write_back_range(ctx, args, len);
@@ -87,6 +91,7 @@ impl Codegen {
                     movq xmm(fret.enc()), xmm0;
                 );
             }
+            InlineMethod::RangeEach => unreachable!(),
             InlineMethod::MathSin => {
                 let fsrc = self.xmm_read_assume_float(ctx, *args, pc);
                 let fret = ctx.xmm_write(ret);
@@ -122,10 +127,292 @@ impl Codegen {
         let wb = ctx.get_locals_write_back();
         self.gen_write_back(wb);
         ctx.dealloc_locals();
+        if let Some(codeptr) = method_info.callee_codeptr {
+            let cached = InlineCached::new(pc + 1, codeptr);
+            if fnstore.inline.get(&cached.func_id()) == Some(&InlineMethod::RangeEach) && len == 0 {
+                self.gen_range_each(ctx, &method_info, args, ret, pc);
+                return;
+            }
+        }
         method_info.args = args + 1;
         self.gen_call(fnstore, ctx, method_info, name, Some(args), ret, pc + 1);
     }
 
+    ///
+    /// Generate a loop which yields each fixnum of Range *recv* to the block in *block*.
+    ///
+    /// Falls back to the interpreter if the range does not consist of fixnums.
+    ///
+    fn gen_range_each(
+        &mut self,
+        ctx: &BBContext,
+        method_info: &MethodInfo,
+        block: SlotId,
+        ret: SlotId,
+        pc: BcPc,
+    ) {
+        let MethodInfo { recv, .. } = method_info;
+        let (class, version) = pc.class_version();
+        let deopt = self.gen_side_deopt(pc, ctx);
+        let setup = self.jit.label();
+        let exit = self.jit.label();
+        monoasm!(self.jit,
+            movq rdi, [rbp - (conv(*recv))];
+        );
+        if !recv.is_zero() {
+            self.guard_class(class, deopt);
+        }
+        self.guard_version(version, deopt);
+        // rdi: &Range
+        monoasm!(self.jit,
+            movq rsi, [rdi + 16];   // start
+            movq rdx, [rdi + 24];   // end
+            testq rsi, 0b1;
+            jz deopt;
+            testq rdx, 0b1;
+            jz deopt;
+            cmpl [rdi + 32], 0;     // exclude_end
+            jeq setup;
+            subq rdx, 2;
+            jo deopt;
+        setup:
+        );
+        let xmm_using = ctx.get_xmm_using();
+        self.xmm_save(&xmm_using);
+        monoasm!(self.jit,
+            // [rsp + 0]: counter
+            // [rsp + 8]: end
+            // [rsp + 16]: outer_cfp
+            // [rsp + 24]: &FuncData
+            subq rsp, 32;
+            movq [rsp], rsi;
+            movq [rsp + 8], rdx;
+            movq rdi, r12;
+            movq rsi, [rbp - (conv(block))];
+            movq rdx, rbx;
+            movq rax, (get_block_data);
+            call rax;
+            // rax <- outer_cfp, rdx <- &FuncData
+            movq [rsp + 16], rax;
+            movq [rsp + 24], rdx;
+        );
+        let head = self.jit.label();
+        monoasm!(self.jit,
+        head:
+            movq rax, [rsp];
+            cmpq rax, [rsp + 8];
+            jgt exit;
+            movq rax, [rsp + 16];
+            movq rdx, [rsp + 24];
+        );
+        self.push_frame(true);
+        monoasm! { self.jit,
+            // rsi <- CodePtr
+            movq rsi, [rdx + (FUNCDATA_OFFSET_CODEPTR)];
+            // set meta
+            movq rdi, [rdx + (FUNCDATA_OFFSET_META)];
+            movq [rsp -(16 + OFFSET_META)], rdi;
+            // set pc
+            movq r13, [rdx + (FUNCDATA_OFFSET_PC)];
+            // set block
+            movq [rsp - (16 + OFFSET_BLOCK)], 0;
+            // set an argument
+            movq rax, [rsp];
+            movq [rsp - (16 + OFFSET_ARG0)], rax;
+            movq rdi, 1;
+            call rsi;
+        };
+        self.pop_frame();
+        let error = self.jit.label();
+        monoasm!(self.jit,
+            movq r13, ((pc + 2).get_u64());
+            testq rax, rax;
+            jeq error;
+            movq rax, [rsp];
+            addq rax, 2;
+            jo exit;
+            movq [rsp], rax;
+            jmp head;
+        exit:
+            addq rsp, 32;
+        );
+        self.xmm_restore(&xmm_using);
+        if !ret.is_zero() {
+            monoasm!(self.jit,
+                movq rax, [rbp - (conv(*recv))];
+            );
+            self.store_rax(ret);
+        }
+
+        // the block raised an error.
+        let jit_return = self.vm_return;
+        self.jit.select_page(1);
+        monoasm!(self.jit,
+        error:
+            addq rsp, 32;
+        );
+        self.xmm_restore(&xmm_using);
+        self.gen_write_back(ctx.get_write_back());
+        monoasm!(self.jit,
+            jmp  jit_return;
+        );
+        self.jit.select_page(0);
+    }
+
+    pub(super) fn gen_super(
+        &mut self,
+        fnstore: &FnStore,
+        ctx: &mut BBContext,
+        mut method_info: MethodInfo,
+        ret: SlotId,
+        func_id: FuncId,
+        has_block: bool,
+        pc: BcPc,
+    ) {
+        let MethodInfo {
+            recv, args, len, ..
+        } = method_info;
+        ctx.dealloc_xmm(ret);
+        self.write_back_slot(ctx, recv);
+        let block = if has_block {
+            self.write_back_range(ctx, args, len + 1);
+            // We must write back and unlink all local vars since they may be accessed from block.
+            let wb = ctx.get_locals_write_back();
+            self.gen_write_back(wb);
+            ctx.dealloc_locals();
+            method_info.args = args + 1;
+            Some(args)
+        } else {
+            self.write_back_range(ctx, args, len);
+            None
+        };
+        match method_info.callee_codeptr {
+            // The receiver is always *self*, so a class guard is not needed.
+            // The owner class of the method is fixed unless the class version changes.
+            Some(codeptr) if pc.class_version().0 != ClassId::default() => {
+                let cached = InlineCached::new(pc + 1, codeptr);
+                self.gen_call_cached(fnstore, ctx, method_info, block, ret, cached, pc + 1);
+            }
+            _ => self.gen_super_not_cached(ctx, method_info, func_id, block, ret, pc + 1),
+        }
+    }
+
+    ///
+    /// generate JIT code for a `super` call which expands the rest array.
+    ///
+    /// This is not cached, and the method is looked up at runtime.
+    ///
+    pub(super) fn gen_super_splat(
+        &mut self,
+        ctx: &mut BBContext,
+        method_info: MethodInfo,
+        ret: SlotId,
+        func_id: FuncId,
+        has_block: bool,
+        pc: BcPc,
+    ) {
+        let MethodInfo { args, len, .. } = method_info;
+        ctx.dealloc_xmm(ret);
+        let func = if has_block {
+            self.write_back_range(ctx, args, len + 1);
+            // We must write back and unlink all local vars since they may be accessed from block.
+            let wb = ctx.get_locals_write_back();
+            self.gen_write_back(wb);
+            ctx.dealloc_locals();
+            super_splat_with_block as u64
+        } else {
+            self.write_back_range(ctx, args, len);
+            super_splat as u64
+        };
+        let xmm_using = ctx.get_xmm_using();
+        self.xmm_save(&xmm_using);
+        monoasm!(self.jit,
+            movq rdi, rbx;
+            movq rsi, r12;
+            movq rdx, [rbp - (OFFSET_SELF)]; // receiver: Value
+            movl rcx, (func_id.0); // FuncId
+            lea r8, [rbp - (conv(args))]; // args: Arg
+            movq r9, (len as u64); // len: usize
+            movq rax, (func);
+            call rax;
+        );
+        self.xmm_restore(&xmm_using);
+        self.handle_error(ctx, pc + 2);
+        if !ret.is_zero() {
+            self.store_rax(ret);
+        }
+    }
+
+    ///
+    /// generate JIT code for a `super` call which was not cached.
+    ///
+    /// The callee is searched every time.
+    ///
+    fn gen_super_not_cached(
+        &mut self,
+        ctx: &BBContext,
+        method_info: MethodInfo,
+        func_id: FuncId,
+        block: Option<SlotId>,
+        ret: SlotId,
+        pc: BcPc,
+    ) {
+        let MethodInfo { recv, len, .. } = method_info;
+        let not_found = self.jit.label();
+        let raise = self.jit.label();
+        let entry_return = self.vm_return;
+        let xmm_using = ctx.get_xmm_using();
+        self.xmm_save(&xmm_using);
+        monoasm!(self.jit,
+            movq rdi, r12;
+            movl rsi, (func_id.0);
+            movq rdx, (len as usize);
+            movq rcx, [rbp - (conv(recv))];
+            movq rax, (find_super);
+            call rax;
+            // rax <- Option<&FuncData>
+            testq rax, rax;
+            jeq not_found;
+            movq r15, rax;
+        );
+        self.push_frame(false);
+        self.set_self_and_args(method_info, block);
+        monoasm!(self.jit,
+            // set meta.
+            movq rax, [r15 + (FUNCDATA_OFFSET_META)];
+            movq [rsp - (16 + OFFSET_META)], rax;
+            // set pc.
+            movq r13, [r15 + (FUNCDATA_OFFSET_PC)];
+            movq rax, [r15 + (FUNCDATA_OFFSET_CODEPTR)];
+            call rax;
+        );
+        self.pop_frame();
+        self.xmm_restore(&xmm_using);
+        monoasm!(self.jit,
+            testq rax, rax;
+            jeq raise;
+        );
+        if !ret.is_zero() {
+            self.store_rax(ret);
+        }
+
+        self.jit.select_page(1);
+        monoasm!(self.jit,
+        not_found:
+        );
+        self.xmm_restore(&xmm_using);
+        // raise error.
+        monoasm!(self.jit,
+        raise:
+            movq r13, ((pc + 2).get_u64());
+        );
+        self.gen_write_back(ctx.get_write_back());
+        monoasm!(self.jit,
+            jmp entry_return;
+        );
+        self.jit.select_page(0);
+    }
+
     fn gen_call(
         &mut self,
         fnstore: &FnStore,
@@ -141,9 +428,14 @@ impl Codegen {
             recv,
             ..
         } = method_info;
+        // The inline cache of this call site was filled after the visibility check for this site,
+        // and the class version guard rejects the cache when any visibility has been changed.
+        // Protected methods are never cached, and the class of the cache is 0.
         if let Some(codeptr) = callee_codeptr {
             let cached = InlineCached::new(pc, codeptr);
-            if recv.is_zero() && ctx.self_class != cached.class_id {
+            if cached.class_id == ClassId::default()
+                || (recv.is_zero() && ctx.self_class != cached.class_id)
+            {
                 self.gen_call_not_cached(ctx, method_info, name, block, ret, pc);
             } else {
                 self.gen_call_cached(fnstore, ctx, method_info, block, ret, cached, pc);
@@ -216,7 +508,9 @@ impl Codegen {
         ret: SlotId,
         pc: BcPc,
     ) {
-        let MethodInfo { recv, len, .. } = method_info;
+        let MethodInfo {
+            recv, args, len, ..
+        } = method_info;
         // set arguments to a callee stack.
         //
         //       +-------------+
@@ -244,6 +538,8 @@ impl Codegen {
         let patch_adr = self.jit.label();
         let patch_pc = self.jit.label();
         let slow_path = self.jit.label();
+        let not_found = self.jit.label();
+        let returned = self.jit.label();
         let raise = self.jit.label();
         let cached_class_version = self.jit.const_i32(-1);
         let cached_recv_class = self.jit.const_i32(0);
@@ -293,6 +589,7 @@ impl Codegen {
             // patch point
             call entry_panic;
         patch_adr:
+        returned:
         );
 
         self.pop_frame();
@@ -310,13 +607,22 @@ impl Codegen {
         self.jit.select_page(1);
         monoasm!(self.jit,
         slow_path:
+            // A protected method is not cached, because its visibility also depends on the caller.
+            movq rdi, r12;
+            movl rsi, r15;
+            movq rdx, (u32::from(name));
+            movq rax, (method_cache_key);
+            call rax;
+            movl r15, rax; // r15: the key of the inline cache
             movq rsi, (u32::from(name)); // IdentId
             movq rdx, (len as usize); // args_len: usize
             movq rcx, [rbp - (conv(recv))]; // receiver: Value
+            movq r8, [rbp - (OFFSET_SELF)]; // caller: Value
+            movq r9, (recv.is_zero() as u64); // is_func_call: bool
             call entry_find_method;
             // absolute address was returned to rax.
             testq rax, rax;
-            jeq raise;
+            jeq not_found;
 
             lea rdi, [rip + patch_meta];
             subq rdi, 8;
@@ -340,11 +646,31 @@ impl Codegen {
             movl [rip + cached_recv_class], r15;
             jmp method_resolved;
         );
+        // the method was not found or not accessible.
+        let (func, args) = match block {
+            Some(block) => (method_missing_with_block as u64, block),
+            None => (method_missing as u64, args),
+        };
+        monoasm!(self.jit,
+        not_found:
+            movq rdi, rbx;
+            movq rsi, r12;
+            movq rdx, [rbp - (conv(recv))]; // receiver: Value
+            movq rcx, (u32::from(name)); // IdentId
+            lea r8, [rbp - (conv(args))]; // args: Arg
+            movq r9, (len as u64); // len: usize
+            movq rax, (func);
+            call rax;
+            jmp returned;
+        );
         let entry_return = self.vm_return;
         // raise error.
         monoasm!(self.jit,
         raise:
             movq r13, ((pc + 2).get_u64());
+        );
+        self.gen_write_back(ctx.get_write_back());
+        monoasm!(self.jit,
             jmp entry_return;
         );
         self.jit.select_page(0);
@@ -434,7 +760,7 @@ impl Codegen {
             call rax;
         );
         self.xmm_restore(&xmm_using);
-        self.handle_error(pc);
+        self.handle_error(ctx, pc);
         self.jit.bind_label(exit);
         if !ret.is_zero() {
             self.store_rax(ret);
@@ -505,10 +831,20 @@ impl Codegen {
             movq [rbp - (OFFSET_BLOCK)], r9;
             movq [rbp - (OFFSET_SELF)], rdx;
             subq rsp, ((OFFSET_SELF + 15) & !0xf);
+            pushq rcx;
+            pushq r8;
             movq rdi, rbx;  // &mut Interp
             movq rsi, r12;  // &mut Globals
             movq rax, (abs_address);
             call rax;
+            popq rsi;   // len
+            popq rdi;   // args
+            pushq rax;
+            pushq rax;
+            movq rax, (clear_keyword_arg);
+            call rax;
+            popq rax;
+            popq rax;
             leave;
             ret;
         );
@@ -538,7 +874,7 @@ impl Codegen {
         );
         self.pop_frame();
         self.xmm_restore(&xmm_using);
-        self.handle_error(pc);
+        self.handle_error(ctx, pc);
         if !ret.is_zero() {
             self.store_rax(ret);
         }
@@ -576,7 +912,7 @@ impl Codegen {
         );
         self.pop_frame();
         self.xmm_restore(&xmm_using);
-        self.handle_error(pc);
+        self.handle_error(ctx, pc);
         if !ret.is_zero() {
             self.store_rax(ret);
         }
@@ -628,7 +964,7 @@ impl Codegen {
         };
         self.pop_frame();
         self.xmm_restore(&xmm_using);
-        self.handle_error(pc);
+        self.handle_error(ctx, pc);
         if !ret.is_zero() {
             self.store_rax(ret);
         }
@@ -82,7 +82,9 @@ impl Codegen {
             // set block
             movq [rsp - (16 + OFFSET_BLOCK)], 0;
             movq [rsp - (16 + OFFSET_OUTER)], 0;
-            movq [rsp - (16 + OFFSET_CFP)], 0;
+            // push control frame
+            movq rax, [rbx];
+            movq [rsp - (16 + OFFSET_CFP)], rax;
             lea  rax, [rsp - (16 + OFFSET_CFP)];
             movq [rbx], rax;
             movq r13, [rdx + (FUNCDATA_OFFSET_PC)];    // r13: BcPc
@@ -110,8 +112,9 @@ impl Codegen {
             movq rax, [rdx + (FUNCDATA_OFFSET_CODEPTR)];
             xorq rdi, rdi;
             call rax;
-            // pop frame
-            movq [rbx], 0;
+            // pop control frame
+            movq rdi, [rsp - (16 + OFFSET_CFP)];
+            movq [rbx], rdi;
             addq rsp, 8;
             popq rbp;
             popq r15;
@@ -148,9 +151,8 @@ impl Codegen {
             movq rbp, rsp;
             movq rdx, rdi;
         };
-        let entry_fetch = self.jit.label();
+        let entry_fetch = self.vm_fetch;
         self.jit.bind_label(entry_fetch);
-        self.vm_fetch = entry_fetch;
         self.fetch_and_dispatch();
 
         self.vm_entry = entry;
@@ -206,12 +208,25 @@ impl Codegen {
         self.dispatch[15] = self.vm_loop_end();
         self.dispatch[16] = self.vm_load_ivar();
         self.dispatch[17] = self.vm_store_ivar();
-        self.dispatch[18] = self.vm_class_def();
+        self.dispatch[18] = self.vm_class_def(define_class as _);
         self.dispatch[19] = self.vm_method_call(true);
         self.dispatch[20] = self.vm_check_local(branch);
         self.dispatch[21] = self.vm_block_arg_proxy();
+        self.dispatch[22] = self.vm_class_def(define_module as _);
+        self.dispatch[23] = self.vm_block_arg();
+        self.dispatch[24] = self.vm_method_ret();
         self.dispatch[25] = self.vm_load_gvar();
         self.dispatch[26] = self.vm_store_gvar();
+        self.dispatch[27] = self.vm_raise();
+        self.dispatch[28] = self.vm_opt_case(branch);
+        self.dispatch[29] = self.vm_init_keyword();
+        self.dispatch[30] = self.vm_super(false);
+        self.dispatch[31] = self.vm_super(true);
+        self.dispatch[32] = self.vm_class_def(define_singleton_class as _);
+        self.dispatch[33] = self.vm_singleton_method_def();
+        self.dispatch[34] = self.vm_load_svar();
+        self.dispatch[35] = self.vm_super_splat(false);
+        self.dispatch[36] = self.vm_super_splat(true);
 
         self.dispatch[129] = self.vm_neg();
         self.dispatch[131] = self.vm_array();
@@ -257,6 +272,8 @@ impl Codegen {
         self.dispatch[170] = self.vm_init_method();
         self.dispatch[171] = self.vm_expand_array();
         self.dispatch[172] = self.vm_init_block();
+        self.dispatch[173] = self.vm_hash(gen_hash as _);
+        self.dispatch[174] = self.vm_hash(gen_kw_hash as _);
 
         self.dispatch[180] = add_ir;
         self.dispatch[181] = sub_ir;
@@ -290,6 +307,7 @@ impl Codegen {
             Value,
             *const Value,
             usize,
+            Option<Value>,
         ) -> Option<Value> =
             unsafe { std::mem::transmute(self.jit.get_current_address().as_ptr()) };
         // rdi: &mut Interp
@@ -298,8 +316,14 @@ impl Codegen {
         // rcx: receiver: Value
         // r8:  *args: *const Value
         // r9:  len: usize
+        // [rsp + 8]: block: Option<Value>
 
         self.gen_invoker_prologue(false);
+        monoasm! { self.jit,
+            // set block (5 registers were pushed in the prologue.)
+            movq rax, [rsp + 48];
+            movq [rsp - (16 + OFFSET_BLOCK)], rax;
+        };
         self.gen_invoker_prep();
         self.gen_invoker_epilogue();
 
@@ -460,7 +484,7 @@ impl Codegen {
         };
     }
 
-    fn vm_handle_error(&mut self) {
+    pub(super) fn vm_handle_error(&mut self) {
         let entry_return = self.vm_return;
         monoasm! { self.jit,
             testq rax, rax;
@@ -838,6 +862,24 @@ impl Codegen {
         label
     }
 
+    fn vm_hash(&mut self, func: usize) -> CodePtr {
+        let label = self.jit.get_current_address();
+        self.vm_get_addr_r15();
+        self.vm_get_addr_rdi();
+        monoasm! { self.jit,
+            movzxw rcx, rsi;  // len: usize
+            movq rdx, rdi;  // args: *const Value
+            movq rdi, rbx;  // &mut Executor
+            movq rsi, r12;  // &mut Globals
+            movq rax, (func);
+            call rax;
+        };
+        self.vm_handle_error();
+        self.vm_store_r15();
+        self.fetch_and_dispatch();
+        label
+    }
+
     fn vm_range(&mut self, exclude_end: bool) -> CodePtr {
         let label = self.jit.get_current_address();
         self.vm_get_addr_r15();
@@ -906,13 +948,15 @@ impl Codegen {
     //
     fn vm_block_arg_proxy(&mut self) -> CodePtr {
         let label = self.jit.get_current_address();
-        let panic = self.entry_panic;
+        let exit = self.jit.label();
         self.vm_get_addr_r15();
         monoasm! { self.jit,
             movq rax, [rbp - (OFFSET_BLOCK)];
+            // a Proc object or no block is passed as it is.
             testq rax, 0b1;
-            jeq panic;
+            jeq exit;
             addq rax, 0b10;
+        exit:
         };
         self.vm_store_r15();
         self.fetch_and_dispatch();
@@ -1114,22 +1158,54 @@ impl Codegen {
         label
     }
 
-    fn vm_class_def(&mut self) -> CodePtr {
+    fn vm_singleton_method_def(&mut self) -> CodePtr {
+        let label = self.jit.get_current_address();
+        let class_version = self.class_version;
+        let vm_return = self.vm_return;
+        self.vm_get_addr_r15();
+        monoasm! { self.jit,
+            movq r8, [r15];  // obj
+            movl rdx, [r13 - 8];  // name
+            movl rcx, [r13 - 4];  // func_id
+            movq rdi, rbx;  // &mut Interp
+            movq rsi, r12;  // &mut Globals
+            movq rax, (define_singleton_method);
+            call rax;
+            testq rax, rax;
+            jeq  vm_return;
+            addl [rip + class_version], 1;
+        };
+        self.fetch_and_dispatch();
+        label
+    }
+
+    fn vm_class_def(&mut self, func: usize) -> CodePtr {
         let label = self.jit.get_current_address();
         let vm_return = self.vm_return;
         let super_ = self.jit.label();
+        let base = self.jit.label();
+        let error = self.jit.label();
         monoasm! { self.jit,
+            movzxw rdi, [r13 - 14];  // rdi <- superclass
             cmpl rdi, 0;
             jeq super_;
         }
         self.vm_get_rdi();
         monoasm! { self.jit,
         super_:
-            movq rcx, rdi;
+            movq rcx, rdi;  // rcx <- superclass: Option<Value>
+            movzxw rdi, [r13 - 16];  // rdi <- base
+            cmpl rdi, 0;
+            jeq base;
+        }
+        self.vm_get_rdi();
+        monoasm! { self.jit,
+        base:
+            movq r8, rdi;  // r8 <- base: Option<Value>
             movl rdx, [r13 - 8];  // rdx <- name
             movq rdi, rbx;  // &mut Interp
             movq rsi, r12;  // &mut Globals
-            movq rax, (define_class);
+            movq rax, (func);
             call rax;  // rax <- self: Value
             pushq r13;
             pushq r15;
@@ -1173,7 +1249,7 @@ impl Codegen {
             popq r15;
             popq r13;
             testq rax, rax;
-            jeq vm_return;
+            jeq error;
         };
         let exit = self.jit.label();
         self.vm_store_r15_if_nonzero(exit);
@@ -1185,6 +1261,134 @@ impl Codegen {
             call rax;
         );
         self.fetch_and_dispatch();
+        self.jit.select_page(1);
+        monoasm!(self.jit,
+        error:
+            movq rdi, rbx; // &mut Interp
+            movq rsi, r12; // &mut Globals
+            movq rax, (pop_class_context);
+            call rax;
+            xorq rax, rax;
+            jmp  vm_return;
+        );
+        self.jit.select_page(0);
+        label
+    }
+
+    ///
+    /// Raise an exception.
+    ///
+    /// ~~~text
+    /// +---+---+---+---++---+---+---+---+
+    /// | op|src|       ||               |
+    /// +---+---+---+---++---+---+---+---+
+    ///
+    /// src: exception object
+    /// ~~~
+    fn vm_raise(&mut self) -> CodePtr {
+        let label = self.jit.get_current_address();
+        let vm_return = self.vm_return;
+        self.vm_get_addr_r15();
+        monoasm! { self.jit,
+            movq rdi, r12;  // &mut Globals
+            movq rsi, [r15];  // val: Value
+            movq rax, (raise_err);
+            call rax;
+            jmp  vm_return;
+        };
+        label
+    }
+
+    //
+    // +---+---+---+---++---+---+---+---+
+    // | op|   |funcId ||               |
+    // +---+---+---+---++---+---+---+---+
+    //
+    fn vm_init_keyword(&mut self) -> CodePtr {
+        let label = self.jit.get_current_address();
+        monoasm! { self.jit,
+            movq rdx, rdi;  // FuncId
+            movq rdi, rbx;  // &mut Executor
+            movq rsi, r12;  // &mut Globals
+            lea  rcx, [rbp - (OFFSET_SELF)];  // *mut Option<Value>
+            movq rax, (init_keyword);
+            call rax;
+        };
+        self.vm_handle_error();
+        self.fetch_and_dispatch();
+        label
+    }
+
+    //
+    // +---+---+---+---++---+---+---+---+
+    // | op|dst|  src  ||               |
+    // +---+---+---+---++---+---+---+---+
+    //
+    fn vm_block_arg(&mut self) -> CodePtr {
+        let label = self.jit.get_current_address();
+        let exit = self.jit.label();
+        self.vm_get_addr_r15();
+        self.vm_get_rdi();
+        monoasm! { self.jit,
+            // nil is passed as no block.
+            xorq rax, rax;
+            cmpq rdi, (NIL_VALUE);
+            jeq  exit;
+            movq rdx, rdi;  // val: Value
+            movq rdi, rbx;  // &mut Executor
+            movq rsi, r12;  // &mut Globals
+            movq rax, (to_block);
+            call rax;
+        };
+        self.vm_handle_error();
+        monoasm! { self.jit,
+        exit:
+        };
+        self.vm_store_r15();
+        self.fetch_and_dispatch();
+        label
+    }
+
+    //
+    // +---+---+---+---++---+---+---+---+
+    // | op|src|       ||               |
+    // +---+---+---+---++---+---+---+---+
+    //
+    fn vm_method_ret(&mut self) -> CodePtr {
+        let label = self.jit.get_current_address();
+        self.vm_get_addr_r15();
+        monoasm! { self.jit,
+            movq rdx, [r15];  // val: Value
+            movq rdi, rbx;  // &mut Executor
+            movq rsi, r12;  // &mut Globals
+            movq rax, (method_return);
+            call rax;
+        };
+        self.vm_handle_error();
+        monoasm! { self.jit,
+            leave;
+            ret;
+        };
+        label
+    }
+
+    //
+    // +---+---+---+---++---+---+---+---+
+    // | op|cnd|tableId||               |
+    // +---+---+---+---++---+---+---+---+
+    //
+    fn vm_opt_case(&mut self, branch: DestLabel) -> CodePtr {
+        let label = self.jit.get_current_address();
+        self.vm_get_addr_r15();
+        monoasm! { self.jit,
+            movq rdx, rdi;  // id: OptCaseId
+            movq rdi, r12;  // &Globals
+            movq rsi, [r15];  // cond: Value
+            movq rax, (opt_case);
+            call rax;
+            movq rdi, rax;
+            jmp  branch;
+        };
         label
     }
 
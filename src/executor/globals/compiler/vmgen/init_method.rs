@@ -51,6 +51,7 @@ impl Codegen {
         let fill_temp = self.jit.label();
         let exit = self.jit.label();
         let err = self.wrong_argument;
+        self.clear_keyword_flag();
         // in
         // r15: reg_num (except *self*)
         // rdi: pos_num
@@ -141,12 +142,17 @@ impl Codegen {
         let exit2 = self.jit.label();
         monoasm! { self.jit,
         exit:
+        // set block parameter.
             cmpw [r13 - 4], 0;
             jeq  exit2;
-            movzxw rax, [r13 - 4];
-            negq rax;
-            movq rdi, [rbp - (OFFSET_BLOCK)];
-            movq [rbp + rax * 8 - (OFFSET_SELF)], rdi;
+            movq rdi, rbx;
+            movq rsi, r12;
+            movq rdx, [rbp - (OFFSET_BLOCK)];
+            movq rax, (block_arg);
+            call rax;
+            movzxw rdi, [r13 - 4];
+            negq rdi;
+            movq [rbp + rdi * 8 - (OFFSET_SELF)], rax;
         exit2:
         };
     }
@@ -26,10 +26,82 @@ impl Codegen {
     /// code ptr: code pointer of the function
     /// ~~~
     pub(super) fn vm_method_call(&mut self, has_block: bool) -> CodePtr {
+        self.vm_call(has_block, false)
+    }
+
+    /// Call the method of the superclass
+    ///
+    /// ~~~text
+    /// Super
+    /// +---+---+---+---++---+---+---+---+
+    /// | op|ret|func_id||owner  |version|
+    /// +---+---+---+---++---+---+---+---+
+    /// MethodArgs
+    /// +---+---+---+---++---+---+---+---+
+    /// | op|rcv|arg|len||   code ptr    |
+    /// +---+---+---+---++---+---+---+---+
+    ///
+    /// operands
+    /// ret:     return register
+    /// func_id: the method in which `super` appears
+    /// rcv:     receiver register (always *self*)
+    /// arg:     the start of argument registers
+    /// len:     the number of argument registers
+    ///
+    /// inline method cache
+    /// owner:    the class in which the method of *func_id* was defined
+    /// version:  class version
+    /// code ptr: code pointer of the function
+    /// ~~~
+    pub(super) fn vm_super(&mut self, has_block: bool) -> CodePtr {
+        self.vm_call(has_block, true)
+    }
+
+    /// Call the method of the superclass, expanding the rest array
+    ///
+    /// The layout of the instruction is the same as `Super`, but the inline method cache is not used.
+    /// The last positional argument (followed by a keyword Hash if any) is the rest array,
+    /// which is expanded at runtime.
+    pub(super) fn vm_super_splat(&mut self, has_block: bool) -> CodePtr {
+        let label = self.jit.get_current_address();
+        let exit = self.jit.label();
+        let vm_return = self.vm_return;
+        let func = if has_block {
+            super_splat_with_block as u64
+        } else {
+            super_splat as u64
+        };
+        // rdi: FuncId
+        // r15: %ret
+        // [r13 +  0]; len
+        // [r13 +  2]; %args
+        monoasm! { self.jit,
+            movq rcx, rdi; // rcx: FuncId
+            movzxw rax, [r13 + 2];
+            negq rax;
+            lea r8, [rbp + rax * 8 - (OFFSET_SELF)]; // r8: *args
+            movzxw r9, [r13]; // r9: len
+            movq rdi, rbx;
+            movq rsi, r12;
+            movq rdx, [rbp - (OFFSET_SELF)]; // rdx: receiver:Value
+            movq rax, (func);
+            call rax; // rax <- Option<Value>
+            addq r13, 32;
+            testq rax, rax;
+            jeq vm_return;
+        };
+        self.vm_store_r15_if_nonzero(exit);
+        self.fetch_and_dispatch();
+        label
+    }
+
+    fn vm_call(&mut self, has_block: bool, is_super: bool) -> CodePtr {
         let label = self.jit.get_current_address();
         let exit = self.jit.label();
         let slowpath = self.jit.label();
         let exec = self.jit.label();
+        let not_found = self.jit.label();
+        let returned = self.jit.label();
         let vm_return = self.vm_return;
         let class_version = self.class_version;
         //
@@ -41,7 +113,7 @@ impl Codegen {
         //      |     Meta    |     PC      |
         //      +------+------+------+------+
         //
-        // rdi: IdentId
+        // rdi: IdentId (FuncId for super)
         // r15: %ret
         // [r13 -  8]: class_id
         // [r13 -  4]: class_version
@@ -65,11 +137,27 @@ impl Codegen {
             // rsp + 16:[pc]
             // rsp + 08:[method_name:IdentId]
             // rsp + 00:[recv:Value]
-
-            // rdi: receiver: Value
-            movq rax, (Value::get_class);
-            call rax;
-            movl r15, rax;
+        };
+        if is_super {
+            monoasm! { self.jit,
+                movq rdi, r12;
+                movq rsi, [rsp + 8];
+                movq rax, (super_cache_key);
+                call rax;
+                movl r15, rax;
+                // the callee can not be cached.
+                testq r15, r15;
+                jeq  slowpath;
+            };
+        } else {
+            monoasm! { self.jit,
+                // rdi: receiver: Value
+                movq rax, (Value::get_class);
+                call rax;
+                movl r15, rax;
+            };
+        }
+        monoasm! { self.jit,
             cmpl r15, [r13 - 8];
             jne  slowpath;
             movl rdi, [r13 - 4];
@@ -128,6 +216,7 @@ impl Codegen {
             popq r13;   // pop pc
             popq r15;   // pop %ret
             addq r13, 32;
+        returned:
             testq rax, rax;
             jeq vm_return;
         };
@@ -138,12 +227,57 @@ impl Codegen {
         let entry_find_method = self.entry_find_method;
         monoasm!(self.jit,
         slowpath:
-            movq rsi, [rsp + 8];  // rsi: IdentId
+        );
+        if !is_super {
+            // A protected method is not cached, because its visibility also depends on the caller.
+            monoasm!(self.jit,
+                movq rdi, r12;
+                movl rsi, r15;
+                movq rdx, [rsp + 8];
+                movq rax, (method_cache_key);
+                call rax;
+                movl r15, rax; // r15: the key of the inline cache
+            );
+        }
+        monoasm!(self.jit,
+            movq rsi, [rsp + 8];  // rsi: IdentId (FuncId for super)
             movzxw rdx, [r13];  // rdx: len
             movq rcx, [rsp]; // rcx: receiver:Value
-            call entry_find_method; // rax <- Option<&FuncData>
-            testq rax, rax;
-            jeq vm_return;
+        );
+        if is_super {
+            monoasm!(self.jit,
+                movq rdi, r12;
+                movq rax, (find_super);
+                call rax; // rax <- Option<&FuncData>
+            );
+        } else {
+            // The inline cache of each call site is filled only after the visibility check
+            // for the site, and invalidated when the visibility of any method is changed.
+            // Protected methods are never cached.
+            let not_self = self.jit.label();
+            monoasm!(self.jit,
+                movq r8, [rbp - (OFFSET_SELF)]; // r8: caller's self:Value
+                // r9: is_func_call:bool (the receiver is *self*)
+                xorq r9, r9;
+                cmpw [r13 + 4], 0;
+                jne  not_self;
+                movq r9, 1;
+            not_self:
+                call entry_find_method; // rax <- Option<&FuncData>
+            );
+        }
+        if is_super {
+            monoasm!(self.jit,
+                testq rax, rax;
+                jeq vm_return;
+            );
+        } else {
+            monoasm!(self.jit,
+                testq rax, rax;
+                jeq not_found;
+            );
+        }
+        monoasm!(self.jit,
             movl [r13 - 8], r15;
             movl rdi, [rip + class_version];
             movl [r13 - 4], rdi;
@@ -155,6 +289,32 @@ impl Codegen {
             movq [r13 + 24], rdi;
             jmp exec;
         );
+        if !is_super {
+            // the method was not found or not accessible.
+            let func = if has_block {
+                method_missing_with_block as u64
+            } else {
+                method_missing as u64
+            };
+            monoasm!(self.jit,
+            not_found:
+                movq rdi, rbx;
+                movq rsi, r12;
+                movq rdx, [rsp]; // rdx: receiver:Value
+                movq rcx, [rsp + 8]; // rcx: IdentId
+                movzxw rax, [r13 + 2];
+                negq rax;
+                lea r8, [rbp + rax * 8 - (OFFSET_SELF)]; // r8: *args
+                movzxw r9, [r13]; // r9: len
+                movq rax, (func);
+                call rax; // rax <- Option<Value>
+                addq rsp, 16;
+                popq r13;   // pop pc
+                popq r15;   // pop %ret
+                addq r13, 32;
+                jmp returned;
+            );
+        }
         self.jit.select_page(0);
 
         label
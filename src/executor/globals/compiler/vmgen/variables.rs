@@ -34,14 +34,12 @@ impl Codegen {
     //
     pub(super) fn vm_store_const(&mut self) -> CodePtr {
         let label = self.jit.get_current_address();
-        let const_version = self.const_version;
         self.vm_get_addr_r15();
         monoasm! { self.jit,
             movq rdx, rdi;  // name: IdentId
             movq rcx, [r15];  // val: Value
             movq rdi, rbx;  // &mut Interp
             movq rsi, r12;  // &mut Globals
-            addq [rip + const_version], 1;
             movq rax, (set_constant);
             call rax;
         };
@@ -115,6 +113,28 @@ impl Codegen {
         label
     }
 
+    /// Load special variable
+    ///
+    /// ~~~text
+    /// +---+---+---+---++---+---+---+---+
+    /// | op|dst|  id   ||               |
+    /// +---+---+---+---++---+---+---+---+
+    /// ~~~
+    pub(super) fn vm_load_svar(&mut self) -> CodePtr {
+        let label = self.jit.get_current_address();
+        self.vm_get_addr_r15();
+        monoasm! { self.jit,
+            movl rdx, rdi; // id
+            movq rdi, rbx; // &mut Executor
+            movq rsi, r12; // &mut Globals
+            movq rax, (get_special_var);
+            call rax;
+        };
+        self.vm_store_r15();
+        self.fetch_and_dispatch();
+        label
+    }
+
     /// Store global variable
     ///
     /// ~~~text
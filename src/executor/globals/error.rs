@@ -12,6 +12,44 @@ impl Globals {
         self.set_error(MonorubyErr::method_not_found(name, obj))
     }
 
+    pub(crate) fn err_no_super_method(&mut self, name: IdentId, obj: Value) {
+        self.set_error(MonorubyErr::no_super_method(name, obj))
+    }
+
+    ///
+    /// Set NoMethodError for calling a private or protected method *name* of *obj*.
+    ///
+    pub(crate) fn err_method_not_accessible(
+        &mut self,
+        name: IdentId,
+        obj: Value,
+        visibility: Visibility,
+    ) {
+        self.set_error(MonorubyErr::method_not_accessible(name, obj, visibility))
+    }
+
+    ///
+    /// Set NameError with message "undefined method `*name*' for class `*class_id*'".
+    ///
+    pub(crate) fn err_undefined_method_for_class(&mut self, name: IdentId, class_id: ClassId) {
+        self.set_error(MonorubyErr::nameerr(format!(
+            "undefined method `{}' for class `{}'",
+            IdentId::get_name(name),
+            class_id.get_name(self)
+        )));
+    }
+
+    ///
+    /// Set NameError with message "private constant *class_id*::*name* referenced".
+    ///
+    pub(crate) fn err_private_constant(&mut self, class_id: ClassId, name: IdentId) {
+        self.set_error(MonorubyErr::nameerr(format!(
+            "private constant {}::{} referenced",
+            class_id.get_name(self),
+            IdentId::get_name(name)
+        )));
+    }
+
     pub(crate) fn err_divide_by_zero(&mut self) {
         self.set_error(MonorubyErr::divide_by_zero());
     }
@@ -56,6 +94,30 @@ impl Globals {
         )));
     }
 
+    pub(crate) fn err_is_not_module(&mut self, name: IdentId) {
+        self.set_error(MonorubyErr::typeerr(format!(
+            "{} is not a module",
+            IdentId::get_name(name)
+        )));
+    }
+
+    pub(crate) fn err_is_not_class_or_module(&mut self, name: IdentId) {
+        self.set_error(MonorubyErr::typeerr(format!(
+            "{} is not a class/module",
+            IdentId::get_name(name)
+        )));
+    }
+
+    ///
+    /// Set NameError with message "wrong constant name *name*".
+    ///
+    pub(crate) fn err_wrong_constant_name(&mut self, name: IdentId) {
+        self.set_error(MonorubyErr::nameerr(format!(
+            "wrong constant name {}",
+            IdentId::get_name(name)
+        )));
+    }
+
     ///
     /// Set TypeError with message "superclass mismatch for class *name*".
     ///
@@ -97,6 +159,32 @@ impl Globals {
         )));
     }
 
+    ///
+    /// Set TypeError with message "*val* can't be coerced into *class of other*".
+    ///
+    pub(crate) fn err_cant_be_coerced(&mut self, val: Value, other: Value) {
+        let val = match val.unpack() {
+            RV::Nil | RV::Bool(_) => self.val_inspect(val),
+            _ => val.get_real_class_name(self),
+        };
+        self.set_error(MonorubyErr::typeerr(format!(
+            "{} can't be coerced into {}",
+            val,
+            other.get_real_class_name(self)
+        )));
+    }
+
+    ///
+    /// Set TypeError with message "wrong argument type *class of val* (expected *expected*)".
+    ///
+    pub(crate) fn err_wrong_argument_type(&mut self, val: Value, expected: ClassId) {
+        self.set_error(MonorubyErr::typeerr(format!(
+            "wrong argument type {} (expected {})",
+            val.get_real_class_name(self),
+            expected.get_name(self),
+        )));
+    }
+
     pub(crate) fn err_argument(&mut self, msg: &str) {
         self.set_error(MonorubyErr::argumenterr(msg.to_string()));
     }
@@ -126,6 +214,23 @@ impl Globals {
         )));
     }
 
+    ///
+    /// Set IndexError with message *msg*.
+    ///
+    pub(crate) fn err_index(&mut self, msg: String) {
+        self.set_error(MonorubyErr::indexerr(msg));
+    }
+
+    ///
+    /// Set KeyError with message "key not found: *key*".
+    ///
+    pub(crate) fn err_key_not_found(&mut self, key: Value) {
+        self.set_error(MonorubyErr::keyerr(format!(
+            "key not found: {}",
+            self.val_inspect(key)
+        )));
+    }
+
     ///
     /// Set FrozenError with message "can't modify frozen Integer: 5".
     ///
@@ -147,10 +252,124 @@ impl Globals {
         }));
     }
 
+    ///
+    /// Set RuntimeError with message *msg*.
+    ///
+    pub(crate) fn err_runtime(&mut self, msg: String) {
+        self.set_error(MonorubyErr::runtimeerr(msg));
+    }
+
+    ///
+    /// Set LocalJumpError with message *msg*.
+    ///
+    pub(crate) fn err_local_jump(&mut self, msg: String) {
+        self.set_error(MonorubyErr::localjumperr(msg));
+    }
+
+    ///
+    /// Set RegexpError with message *msg*.
+    ///
+    pub(crate) fn err_regex(&mut self, msg: String) {
+        self.set_error(MonorubyErr::regexerr(msg));
+    }
+
+    ///
+    /// Set StopIteration with message "iteration reached an end".
+    ///
+    pub(crate) fn err_stop_iteration(&mut self) {
+        self.set_error(MonorubyErr::stop_iteration(
+            "iteration reached an end".to_string(),
+        ));
+    }
+
+    ///
+    /// Set FiberError with message *msg*.
+    ///
+    pub(crate) fn err_fiber(&mut self, msg: &str) {
+        self.set_error(MonorubyErr::fibererr(msg.to_string()));
+    }
+
+    ///
+    /// Get the block *block*, or set LocalJumpError if no block was given.
+    ///
+    pub(crate) fn expect_block(&mut self, block: Option<Value>) -> Option<Value> {
+        if block.is_none() {
+            self.err_local_jump("no block given (yield)".to_string());
+        }
+        block
+    }
+
+    ///
+    /// Raise an exception object *val*.
+    ///
+    pub(crate) fn raise_exception(&mut self, val: Value) {
+        self.set_error(MonorubyErr::exception(val));
+    }
+
+    ///
+    /// If the current error is `return` from a block, get the frame (bp) to return from.
+    ///
+    /// An exception object for `return` re-raised by an ensure clause is converted back.
+    ///
+    pub(crate) fn method_return_target(&mut self) -> Option<usize> {
+        let err = self.error.as_mut()?;
+        if let MonorubyErrKind::Exception(val) = err.kind {
+            if let Some(inner) = val.is_exception() {
+                if let MonorubyErrKind::MethodReturn(..) = inner.kind {
+                    *err = inner.clone();
+                }
+            }
+        }
+        match err.kind {
+            MonorubyErrKind::MethodReturn(_, target) => Some(target),
+            _ => None,
+        }
+    }
+
+    ///
+    /// Check whether the current error is an instance of the class *class_id* or its subclasses.
+    ///
+    pub(crate) fn error_is_kind_of(&self, class_id: ClassId) -> bool {
+        match &self.error {
+            Some(err) => self.is_subclass_of(err.class_id(), class_id),
+            None => false,
+        }
+    }
+
+    ///
+    /// Check whether the current error was caused by a method which was not found or not
+    /// accessible, which is handled by `method_missing`.
+    ///
+    pub(crate) fn is_method_missing_error(&self) -> bool {
+        matches!(
+            self.error.as_ref().map(|err| &err.kind),
+            Some(MonorubyErrKind::MethodNotFound(..) | MonorubyErrKind::MethodNotAccessible(..))
+        )
+    }
+
     pub(crate) fn take_error(&mut self) -> Option<MonorubyErr> {
         std::mem::take(&mut self.error)
     }
 
+    ///
+    /// Take the current error and convert it to an exception object.
+    ///
+    /// If the error was raised with an exception object, the object itself is returned.
+    ///
+    pub(crate) fn take_error_obj(&mut self) -> Value {
+        let err = self.take_error().unwrap();
+        match err.kind {
+            MonorubyErrKind::Exception(mut val) => {
+                let inner = val.as_exception_mut();
+                if inner.loc.is_empty() {
+                    inner.loc = err.loc;
+                }
+                val
+            }
+            _ => without_gc(|| Value::new_exception(err.class_id(), err)),
+        }
+    }
+
     pub(crate) fn push_error_location(&mut self, loc: Loc, sourceinfo: SourceInfoRef) {
         match &mut self.error {
             Some(err) => {
@@ -171,17 +390,43 @@ pub struct MonorubyErr {
 pub enum MonorubyErrKind {
     //UndefinedLocal(String),
     MethodNotFound(IdentId, Value),
+    NoSuperMethod(IdentId, Value),
+    /// a private or protected method was called. (method name, receiver, visibility)
+    MethodNotAccessible(IdentId, Value, Visibility),
     Arguments(String),
     Syntax(ParseErrKind),
     Syntax2(String),
     Unimplemented(String),
     UninitConst(IdentId),
+    Name(String),
     DivideByZero,
     Range(String),
     Type(String),
     Index(String),
+    Key(String),
     Frozen(String),
     Load(String),
+    Runtime(String),
+    LocalJump(String),
+    Regex(String),
+    StopIteration(String),
+    Fiber(String),
+    /// `return` from a block. (return value, bp of the frame to return from)
+    MethodReturn(Value, usize),
+    Exception(Value),
+}
+
+impl GC<RValue> for MonorubyErr {
+    fn mark(&self, alloc: &mut Allocator<RValue>) {
+        match &self.kind {
+            MonorubyErrKind::MethodNotFound(_, obj)
+            | MonorubyErrKind::NoSuperMethod(_, obj)
+            | MonorubyErrKind::MethodNotAccessible(_, obj, _) => obj.mark(alloc),
+            MonorubyErrKind::MethodReturn(val, _) => val.mark(alloc),
+            MonorubyErrKind::Exception(obj) => obj.mark(alloc),
+            _ => {}
+        }
+    }
 }
 
 impl MonorubyErr {
@@ -224,6 +469,25 @@ impl MonorubyErr {
         self.show_loc();
     }
 
+    ///
+    /// Get the backtrace of this error as a list of "*file*:*line*" strings.
+    ///
+    pub(crate) fn get_backtrace(&self) -> Vec<String> {
+        self.loc
+            .iter()
+            .map(|(loc, sourceinfo)| {
+                let line = sourceinfo
+                    .code
+                    .get(..loc.0)
+                    .unwrap_or_default()
+                    .matches('\n')
+                    .count()
+                    + 1;
+                format!("{}:{}", sourceinfo.path.to_string_lossy(), line)
+            })
+            .collect()
+    }
+
     pub fn get_error_message(&self, globals: &Globals) -> String {
         match &self.kind {
             /*MonorubyErrKind::UndefinedLocal(ident) => {
@@ -237,6 +501,27 @@ impl MonorubyErr {
                     obj.get_real_class_name(globals)
                 )
             }
+            MonorubyErrKind::NoSuperMethod(name, obj) => {
+                format!(
+                    "super: no superclass method `{}' for {}:{}",
+                    IdentId::get_name(*name),
+                    obj.to_s(globals),
+                    obj.get_real_class_name(globals)
+                )
+            }
+            MonorubyErrKind::MethodNotAccessible(name, obj, visibility) => {
+                format!(
+                    "{} method `{}' called for {}:{}",
+                    match visibility {
+                        Visibility::Private => "private",
+                        Visibility::Protected => "protected",
+                        Visibility::Public => unreachable!(),
+                    },
+                    IdentId::get_name(*name),
+                    obj.to_s(globals),
+                    obj.get_real_class_name(globals)
+                )
+            }
             MonorubyErrKind::Arguments(name) => name.to_string(),
             MonorubyErrKind::Syntax(kind) => match kind {
                 ParseErrKind::SyntaxError(msg) => msg.to_string(),
@@ -247,12 +532,52 @@ impl MonorubyErr {
             MonorubyErrKind::UninitConst(name) => {
                 format!("uninitialized constant {}", IdentId::get_name(*name))
             }
+            MonorubyErrKind::Name(msg) => msg.to_string(),
             MonorubyErrKind::DivideByZero => "divided by 0".to_string(),
             MonorubyErrKind::Range(msg) => msg.to_string(),
             MonorubyErrKind::Type(msg) => msg.to_string(),
             MonorubyErrKind::Index(msg) => msg.to_string(),
+            MonorubyErrKind::Key(msg) => msg.to_string(),
             MonorubyErrKind::Frozen(msg) => msg.to_string(),
             MonorubyErrKind::Load(msg) => msg.to_string(),
+            MonorubyErrKind::Runtime(msg) => msg.to_string(),
+            MonorubyErrKind::LocalJump(msg) => msg.to_string(),
+            MonorubyErrKind::Regex(msg) => msg.to_string(),
+            MonorubyErrKind::StopIteration(msg) => msg.to_string(),
+            MonorubyErrKind::Fiber(msg) => msg.to_string(),
+            MonorubyErrKind::MethodReturn(..) => "unexpected return".to_string(),
+            MonorubyErrKind::Exception(obj) => {
+                obj.is_exception().unwrap().get_error_message(globals)
+            }
+        }
+    }
+
+    ///
+    /// Get the class of the exception object for this error.
+    ///
+    pub(crate) fn class_id(&self) -> ClassId {
+        match &self.kind {
+            MonorubyErrKind::MethodNotFound(..)
+            | MonorubyErrKind::NoSuperMethod(..)
+            | MonorubyErrKind::MethodNotAccessible(..) => NO_METHOD_ERROR_CLASS,
+            MonorubyErrKind::Arguments(_) => ARGUMENT_ERROR_CLASS,
+            MonorubyErrKind::UninitConst(_) | MonorubyErrKind::Name(_) => NAME_ERROR_CLASS,
+            MonorubyErrKind::DivideByZero => ZERO_DIVISION_ERROR_CLASS,
+            MonorubyErrKind::Range(_) => RANGE_ERROR_CLASS,
+            MonorubyErrKind::Type(_) => TYPE_ERROR_CLASS,
+            MonorubyErrKind::Index(_) => INDEX_ERROR_CLASS,
+            MonorubyErrKind::Key(_) => KEY_ERROR_CLASS,
+            MonorubyErrKind::Frozen(_) => FROZEN_ERROR_CLASS,
+            MonorubyErrKind::Load(_) => LOAD_ERROR_CLASS,
+            MonorubyErrKind::Runtime(_) => RUNTIME_ERROR_CLASS,
+            MonorubyErrKind::LocalJump(_) | MonorubyErrKind::MethodReturn(..) => {
+                LOCAL_JUMP_ERROR_CLASS
+            }
+            MonorubyErrKind::Regex(_) => REGEXP_ERROR_CLASS,
+            MonorubyErrKind::StopIteration(_) => STOP_ITERATION_CLASS,
+            MonorubyErrKind::Fiber(_) => FIBER_ERROR_CLASS,
+            MonorubyErrKind::Exception(obj) => obj.class_id(),
+            _ => STANDARD_ERROR_CLASS,
         }
     }
 }
@@ -310,26 +635,26 @@ impl MonorubyErr {
         )
     }
 
-    pub(crate) fn unsupported_feature(
-        msg: &str,
-        loc: Loc,
-        sourceinfo: SourceInfoRef,
-    ) -> MonorubyErr {
+    pub(crate) fn escape_from_eval(loc: Loc, sourceinfo: SourceInfoRef) -> MonorubyErr {
         MonorubyErr::new_with_loc(
-            MonorubyErrKind::Unimplemented(msg.to_string()),
+            MonorubyErrKind::Syntax2("can't escape from eval.".to_string()),
             loc,
             sourceinfo,
         )
     }
 
-    pub(crate) fn escape_from_eval(loc: Loc, sourceinfo: SourceInfoRef) -> MonorubyErr {
+    pub(crate) fn invalid_retry(loc: Loc, sourceinfo: SourceInfoRef) -> MonorubyErr {
         MonorubyErr::new_with_loc(
-            MonorubyErrKind::Syntax2("can't escape from eval.".to_string()),
+            MonorubyErrKind::Syntax2("Invalid retry".to_string()),
             loc,
             sourceinfo,
         )
     }
 
+    pub(crate) fn invalid_regexp(msg: String, loc: Loc, sourceinfo: SourceInfoRef) -> MonorubyErr {
+        MonorubyErr::new_with_loc(MonorubyErrKind::Syntax2(msg), loc, sourceinfo)
+    }
+
     /*pub(crate) fn undefined_local(
         ident: String,
         loc: Loc,
@@ -345,6 +670,18 @@ impl MonorubyErr {
         MonorubyErr::new(MonorubyErrKind::MethodNotFound(name, obj))
     }
 
+    pub(crate) fn no_super_method(name: IdentId, obj: Value) -> MonorubyErr {
+        MonorubyErr::new(MonorubyErrKind::NoSuperMethod(name, obj))
+    }
+
+    pub(crate) fn method_not_accessible(
+        name: IdentId,
+        obj: Value,
+        visibility: Visibility,
+    ) -> MonorubyErr {
+        MonorubyErr::new(MonorubyErrKind::MethodNotAccessible(name, obj, visibility))
+    }
+
     pub(crate) fn wrong_arguments(expected: usize, given: usize) -> MonorubyErr {
         MonorubyErr::new(MonorubyErrKind::Arguments(format!(
             "wrong number of arguments (given {given}, expected {expected})"
@@ -370,6 +707,10 @@ impl MonorubyErr {
         MonorubyErr::new(MonorubyErrKind::UninitConst(name))
     }
 
+    pub(crate) fn nameerr(msg: String) -> MonorubyErr {
+        MonorubyErr::new(MonorubyErrKind::Name(msg))
+    }
+
     pub(crate) fn typeerr(msg: String) -> MonorubyErr {
         MonorubyErr::new(MonorubyErrKind::Type(msg))
     }
@@ -382,6 +723,10 @@ impl MonorubyErr {
         MonorubyErr::new(MonorubyErrKind::Index(msg))
     }
 
+    pub(crate) fn keyerr(msg: String) -> MonorubyErr {
+        MonorubyErr::new(MonorubyErrKind::Key(msg))
+    }
+
     pub(crate) fn frozenerr(msg: String) -> MonorubyErr {
         MonorubyErr::new(MonorubyErrKind::Frozen(msg))
     }
@@ -389,4 +734,32 @@ impl MonorubyErr {
     pub(crate) fn loaderr(msg: String) -> MonorubyErr {
         MonorubyErr::new(MonorubyErrKind::Load(msg))
     }
+
+    pub(crate) fn runtimeerr(msg: String) -> MonorubyErr {
+        MonorubyErr::new(MonorubyErrKind::Runtime(msg))
+    }
+
+    pub(crate) fn localjumperr(msg: String) -> MonorubyErr {
+        MonorubyErr::new(MonorubyErrKind::LocalJump(msg))
+    }
+
+    pub(crate) fn regexerr(msg: String) -> MonorubyErr {
+        MonorubyErr::new(MonorubyErrKind::Regex(msg))
+    }
+
+    pub(crate) fn stop_iteration(msg: String) -> MonorubyErr {
+        MonorubyErr::new(MonorubyErrKind::StopIteration(msg))
+    }
+
+    pub(crate) fn fibererr(msg: String) -> MonorubyErr {
+        MonorubyErr::new(MonorubyErrKind::Fiber(msg))
+    }
+
+    pub(crate) fn method_return(val: Value, target: usize) -> MonorubyErr {
+        MonorubyErr::new(MonorubyErrKind::MethodReturn(val, target))
+    }
+
+    pub(crate) fn exception(val: Value) -> MonorubyErr {
+        MonorubyErr::new(MonorubyErrKind::Exception(val))
+    }
 }
@@ -25,6 +25,12 @@ pub struct ArgumentNames {
     pub req_num: usize,
     pub block_param: Option<String>,
     pub names: Vec<Option<String>>,
+    /// keyword parameters. (name, required)
+    pub keyword: Vec<(String, bool)>,
+    /// keyword rest parameter.
+    pub kw_rest: Option<String>,
+    /// true if the rest parameter was implicitly added to receive keyword arguments.
+    pub hidden_rest: bool,
 }
 
 impl ArgumentNames {
@@ -50,6 +56,36 @@ pub(crate) struct OptionalInfo {
     pub(crate) initializer: Node,
 }
 
+///
+/// Keyword parameters of a function.
+///
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct KeywordInfo {
+    /// (name, slot, required)
+    pub(crate) params: Vec<(IdentId, SlotId, bool)>,
+    /// the slot of the keyword rest parameter.
+    pub(crate) kw_rest: Option<SlotId>,
+    /// the slot of the rest parameter.
+    pub(crate) rest: SlotId,
+    /// true if the rest parameter was implicitly added to receive keyword arguments.
+    pub(crate) hidden_rest: bool,
+}
+
+///
+/// An entry of the exception table.
+///
+/// An exception raised in the bytecode of *range* is caught, stored in the register *err_reg*,
+/// and then the execution is resumed at *dest*.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ExceptionMapEntry {
+    pub(crate) range: std::ops::Range<usize>,
+    pub(crate) dest: usize,
+    pub(crate) err_reg: SlotId,
+    /// true if this entry is for an ensure clause.
+    pub(crate) ensure: bool,
+}
+
 #[derive(Clone, PartialEq)]
 pub(crate) struct Funcs(Vec<FuncInfo>);
 
@@ -190,6 +226,8 @@ fn handle_args(
     let mut req_num = 0;
     let mut rest = 0;
     let mut block_param = None;
+    let mut keyword = vec![];
+    let mut kw_rest = None;
     for param in params {
         match param.kind {
             ParamKind::Param(name) => {
@@ -217,6 +255,12 @@ fn handle_args(
             ParamKind::Block(name) => {
                 block_param = Some(name);
             }
+            ParamKind::Keyword(name, initializer) => {
+                keyword.push((name, initializer));
+            }
+            ParamKind::KWRest(name) => {
+                kw_rest = Some(name);
+            }
             _ => {
                 return Err(MonorubyErr::unsupported_parameter_kind(
                     param.kind,
@@ -226,6 +270,12 @@ fn handle_args(
             }
         }
     }
+    // keyword arguments are passed as a trailing Hash, which is received by the rest parameter.
+    let hidden_rest = rest == 0 && (!keyword.is_empty() || kw_rest.is_some());
+    if hidden_rest {
+        args.push(Some("/rest".to_string()));
+        rest = 1;
+    }
     let pos_num = args.len() - rest;
     let expand: Vec<_> = expand
         .into_iter()
@@ -236,6 +286,20 @@ fn handle_args(
         })
         .collect();
     args.append(&mut destruct_args);
+    // keyword parameters are placed after the block parameter.
+    let kw_pos = args.len() + block_param.is_some() as usize;
+    let keyword = keyword
+        .into_iter()
+        .enumerate()
+        .map(|(i, (name, initializer))| {
+            let required = initializer.is_none();
+            if let Some(box initializer) = initializer {
+                let local = BcLocal((kw_pos + i) as u16);
+                optional.push(OptionalInfo { local, initializer });
+            }
+            (name, required)
+        })
+        .collect();
     Ok((
         ArgumentNames {
             names: args,
@@ -243,6 +307,9 @@ fn handle_args(
             pos_num,
             req_num,
             block_param,
+            keyword,
+            kw_rest,
+            hidden_rest,
         },
         expand,
         optional,
@@ -257,6 +324,8 @@ pub struct ConstSiteInfo {
     pub prefix: Vec<IdentId>,
     /// Is toplevel?. (e.g. ::Foo)
     pub toplevel: bool,
+    /// The function in which this constant site appears.
+    pub func_id: FuncId,
     /// Inline constant cache.
     pub cache: (usize, Option<Value>), //(version, value)
 }
@@ -265,12 +334,70 @@ pub struct ConstSiteInfo {
 #[repr(transparent)]
 pub struct ConstSiteId(pub u32);
 
+///
+/// Jump table for case expressions whose *when* clauses are all Integer or Symbol literals.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OptCaseInfo {
+    /// true for Symbol keys, false for Integer keys.
+    pub symbol: bool,
+    /// The smallest key.
+    pub min: i64,
+    /// Branch displacements (from the next bytecode) for keys in min..min + len.
+    pub branch_table: Box<[i32]>,
+}
+
+impl OptCaseInfo {
+    ///
+    /// Get a branch displacement for *cond*.
+    ///
+    /// Return None if *cond* is not in the table.
+    ///
+    pub fn find(&self, cond: Value) -> Option<i32> {
+        let key = if self.symbol {
+            cond.is_symbol()?.get() as i64
+        } else {
+            cond.try_fixnum()?
+        };
+        let i = usize::try_from(key.checked_sub(self.min)?).ok()?;
+        self.branch_table.get(i).cloned()
+    }
+
+    ///
+    /// Get bytecode indices of the branch destinations for OptCase at *idx*.
+    ///
+    pub fn dests(&self, idx: usize) -> Vec<usize> {
+        let mut dests: Vec<_> = self
+            .branch_table
+            .iter()
+            .map(|disp| ((idx + 1) as i32 + disp) as usize)
+            .collect();
+        dests.sort_unstable();
+        dests.dedup();
+        dests
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct OptCaseId(pub u32);
+
 #[derive(Clone, PartialEq)]
 pub(crate) struct FnStore {
     pub(crate) functions: Funcs,
     pub(crate) inline: HashMap<FuncId, InlineMethod>,
     /// const access site info.
     constsite_info: Vec<ConstSiteInfo>,
+    /// jump tables for case expressions.
+    optcase_info: Vec<OptCaseInfo>,
+    /// functions for `&:method` block arguments.
+    symbol_proc: HashMap<IdentId, FuncId>,
+    /// the block function for `Executor::collect_each`.
+    enum_yielder: Option<FuncId>,
+    /// the block function for the external iteration of Enumerators.
+    fiber_yielder: Option<FuncId>,
+    /// the block function for `Executor::each_with_index_by`.
+    index_yielder: Option<FuncId>,
 }
 
 impl std::ops::Index<FuncId> for FnStore {
@@ -299,12 +426,39 @@ impl std::ops::IndexMut<ConstSiteId> for FnStore {
     }
 }
 
+impl std::ops::Index<OptCaseId> for FnStore {
+    type Output = OptCaseInfo;
+    fn index(&self, index: OptCaseId) -> &OptCaseInfo {
+        &self.optcase_info[index.0 as usize]
+    }
+}
+
+impl GC<RValue> for FnStore {
+    fn mark(&self, alloc: &mut Allocator<RValue>) {
+        self.functions.0.iter().for_each(|info| {
+            if let FuncKind::ISeq(iseq) = &info.kind {
+                iseq.mark(alloc);
+            }
+        });
+        self.constsite_info.iter().for_each(|info| {
+            if let Some(v) = &info.cache.1 {
+                v.mark(alloc);
+            }
+        });
+    }
+}
+
 impl FnStore {
     pub(super) fn new() -> Self {
         Self {
             functions: Funcs::default(),
             inline: HashMap::default(),
             constsite_info: vec![],
+            optcase_info: vec![],
+            symbol_proc: HashMap::default(),
+            enum_yielder: None,
+            fiber_yielder: None,
+            index_yielder: None,
         }
     }
 
@@ -319,6 +473,24 @@ impl FnStore {
 }
 
 impl FnStore {
+    ///
+    /// Get the lexical class context of the method which *func_id* belongs to.
+    ///
+    /// Return None for top-level and class definition bodies, which use the current class context.
+    ///
+    pub(crate) fn get_lexical_context(&self, mut func_id: FuncId) -> Option<Vec<ClassId>> {
+        loop {
+            let info = match &self[func_id].kind {
+                FuncKind::ISeq(info) => info,
+                _ => return None,
+            };
+            match info.outer {
+                Some(outer) => func_id = outer,
+                None => return info.lexical_context.clone(),
+            }
+        }
+    }
+
     pub(super) fn compile_script(
         &mut self,
         ast: Node,
@@ -337,7 +509,10 @@ impl FnStore {
         let mut fid = main_fid;
 
         while self.len() > fid.0 as usize {
-            self.compile_func(fid)?;
+            // builtin functions may be added during compilation. (e.g. for `&:method`)
+            if let FuncKind::ISeq(_) = self[fid].kind {
+                self.compile_func(fid)?;
+            }
             fid = FuncId(fid.0 + 1);
         }
 
@@ -366,6 +541,58 @@ impl FnStore {
         self.functions.add_native_func(name, address, arity)
     }
 
+    ///
+    /// Get FuncId of the block function for `&:method`.
+    ///
+    /// The function is shared among the block arguments with the same *method*.
+    ///
+    pub(crate) fn symbol_proc(&mut self, method: IdentId) -> FuncId {
+        if let Some(func_id) = self.symbol_proc.get(&method) {
+            return *func_id;
+        }
+        let func_id = self.add_builtin_func(IdentId::get_name(method), call_symbol, -1);
+        self.symbol_proc.insert(method, func_id);
+        func_id
+    }
+
+    ///
+    /// Get FuncId of the block function which collects yielded values in `Executor::collect_each`.
+    ///
+    pub(crate) fn enum_yielder(&mut self) -> FuncId {
+        if let Some(func_id) = self.enum_yielder {
+            return func_id;
+        }
+        let func_id = self.add_builtin_func("each".to_string(), enum_yielder, -1);
+        self.enum_yielder = Some(func_id);
+        func_id
+    }
+
+    ///
+    /// Get FuncId of the block function which passes yielded values to the resumer of the Fiber
+    /// running an Enumerator.
+    ///
+    pub(crate) fn fiber_yielder(&mut self) -> FuncId {
+        if let Some(func_id) = self.fiber_yielder {
+            return func_id;
+        }
+        let func_id = self.add_builtin_func("each".to_string(), fiber_yielder, -1);
+        self.fiber_yielder = Some(func_id);
+        func_id
+    }
+
+    ///
+    /// Get FuncId of the block function which calls a block with indexes in
+    /// `Executor::each_with_index_by`.
+    ///
+    pub(crate) fn index_yielder(&mut self) -> FuncId {
+        if let Some(func_id) = self.index_yielder {
+            return func_id;
+        }
+        let func_id = self.add_builtin_func("with_index".to_string(), index_yielder, -1);
+        self.index_yielder = Some(func_id);
+        func_id
+    }
+
     pub(super) fn add_attr_reader(&mut self, name: String, ivar_name: IdentId) -> FuncId {
         self.functions.add_attr_reader(name, ivar_name)
     }
@@ -504,6 +731,13 @@ impl FuncInfo {
         self.arity
     }
 
+    pub(crate) fn is_ruby_func(&self) -> Option<&ISeqInfo> {
+        match &self.kind {
+            FuncKind::ISeq(info) => Some(info),
+            _ => None,
+        }
+    }
+
     pub(crate) fn as_ruby_func(&self) -> &ISeqInfo {
         match &self.kind {
             FuncKind::ISeq(info) => info,
@@ -511,7 +745,7 @@ impl FuncInfo {
         }
     }
 
-    fn as_ruby_func_mut(&mut self) -> &mut ISeqInfo {
+    pub(crate) fn as_ruby_func_mut(&mut self) -> &mut ISeqInfo {
         match &mut self.kind {
             FuncKind::ISeq(info) => info,
             _ => unreachable!(),
@@ -536,7 +770,7 @@ impl FuncInfo {
             self.data.meta,
         );
         let mut skip = false;
-        let bb_info = info.get_bb_info();
+        let bb_info = info.get_bb_info(&globals.func);
         for (i, pc) in info.bytecode().iter().enumerate() {
             let pc = BcPc::from(pc);
             if skip {
@@ -557,6 +791,24 @@ impl FuncInfo {
     }
 }
 
+/// The name of the hidden local variable which holds `$~`.
+const SPECIAL_VAR: &str = "$~";
+
+///
+/// Check whether the code in *loc* may refer to `$~` and its derivatives.
+///
+fn uses_special_var(code: &str, loc: Loc) -> bool {
+    let end = std::cmp::min(loc.1 + 1, code.len());
+    let code = match code.get(std::cmp::min(loc.0, end)..end) {
+        Some(code) => code,
+        None => return true,
+    };
+    code.contains("$~")
+        || code.contains("$&")
+        || code.contains("last_match")
+        || (1..=9).any(|i| code.contains(&format!("${}", i)))
+}
+
 ///
 /// Information of instruction sequences.
 ///
@@ -570,12 +822,20 @@ pub(crate) struct ISeqInfo {
     pub(super) bytecode: Option<Pin<Box<[Bc]>>>,
     /// Source map.
     pub sourcemap: Vec<Loc>,
+    /// Exception table. Inner handlers precede outer ones.
+    pub(crate) exception_map: Vec<ExceptionMapEntry>,
     /// the name of arguments.
     args: ArgumentNames,
     /// expand array info. (src_reg, dst_reg, len)
     pub(crate) expand: Vec<ExpandInfo>,
     /// optional parameters initializer
     pub(crate) optional: Vec<OptionalInfo>,
+    /// keyword parameters.
+    pub(crate) keyword: Option<KeywordInfo>,
+    /// lexical class context in which this method was defined. (innermost last)
+    pub(crate) lexical_context: Option<Vec<ClassId>>,
+    /// the class or module in which this method was defined.
+    pub(crate) owner: Option<ClassId>,
     /// local variables.
     locals: HashMap<String, u16>,
     /// outer local variables.
@@ -590,6 +850,17 @@ pub(crate) struct ISeqInfo {
     pub(crate) is_block: bool,
 }
 
+impl GC<RValue> for ISeqInfo {
+    fn mark(&self, alloc: &mut Allocator<RValue>) {
+        if let Some(bytecode) = &self.bytecode {
+            bytecode
+                .iter()
+                .filter_map(|bc| bc.embedded_value())
+                .for_each(|v| v.mark(alloc));
+        }
+    }
+}
+
 impl std::fmt::Debug for ISeqInfo {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -613,15 +884,20 @@ impl ISeqInfo {
         sourceinfo: SourceInfoRef,
         is_block: bool,
     ) -> Self {
+        let uses_special_var = !is_block && uses_special_var(&sourceinfo.code, body.loc);
         let mut info = ISeqInfo {
             id,
             outer,
             name,
             bytecode: None,
             sourcemap: vec![],
+            exception_map: vec![],
             args: args.clone(),
             expand,
             optional,
+            keyword: None,
+            lexical_context: None,
+            owner: None,
             locals: HashMap::default(),
             outer_locals,
             temp: 0,
@@ -636,6 +912,29 @@ impl ISeqInfo {
         if let Some(name) = args.block_param {
             info.add_local(name);
         }
+        if !args.keyword.is_empty() || args.kw_rest.is_some() {
+            let params = args
+                .keyword
+                .into_iter()
+                .map(|(name, required)| {
+                    let ident = IdentId::get_ident_id(&name);
+                    let slot = SlotId::new(info.add_local(name).0 + 1);
+                    (ident, slot, required)
+                })
+                .collect();
+            let kw_rest = args
+                .kw_rest
+                .map(|name| SlotId::new(info.add_local(name).0 + 1));
+            info.keyword = Some(KeywordInfo {
+                params,
+                kw_rest,
+                rest: SlotId::new(args.pos_num as u16 + 1),
+                hidden_rest: args.hidden_rest,
+            });
+        }
+        if uses_special_var {
+            info.add_local(SPECIAL_VAR.to_string());
+        }
         info
     }
 
@@ -721,6 +1020,58 @@ impl ISeqInfo {
         self.args.pos_num
     }
 
+    ///
+    /// Get the arity of the Proc object for this block.
+    ///
+    /// Optional parameters are counted only for lambdas.
+    ///
+    pub(crate) fn proc_arity(&self, is_lambda: bool) -> i64 {
+        let args = &self.args;
+        let kw_required = args.keyword.iter().any(|(_, required)| *required);
+        let req = args.req_num as i64 + if kw_required { 1 } else { 0 };
+        let optional = args.pos_num != args.req_num
+            || (!kw_required && (!args.keyword.is_empty() || args.kw_rest.is_some()));
+        let rest = args.arg_num != args.pos_num && !args.hidden_rest;
+        if rest || (is_lambda && optional) {
+            -(req + 1)
+        } else {
+            req
+        }
+    }
+
+    /// get the parameters of this function.
+    pub(crate) fn args(&self) -> &ArgumentNames {
+        &self.args
+    }
+
+    ///
+    /// Get the method in which this function is lexically enclosed, and the depth of the block nesting.
+    ///
+    /// For a method, its own FuncId and 0 are returned.
+    ///
+    pub(crate) fn method_context(&self, store: &FnStore) -> (FuncId, usize) {
+        let mut func_id = self.id;
+        let mut outer = self.outer;
+        let mut depth = 0;
+        while let Some(id) = outer {
+            func_id = id;
+            outer = store[id].as_ruby_func().outer;
+            depth += 1;
+        }
+        (func_id, depth)
+    }
+
+    ///
+    /// Get the register which holds `$~` of this method.
+    ///
+    /// Returns None if this function is a block or does not use `$~`.
+    ///
+    pub(crate) fn special_var_reg(&self) -> Option<usize> {
+        self.locals
+            .get(SPECIAL_VAR)
+            .map(|local| *local as usize + 1)
+    }
+
     /// get a position of a block argument.
     pub(crate) fn block_pos(&self) -> usize {
         if self.args.block_param.is_some() {
@@ -754,6 +1105,28 @@ impl ISeqInfo {
         BcPc::from(&self.bytecode()[idx])
     }
 
+    ///
+    /// Get the handler for an exception raised at the bytecode index *idx*.
+    ///
+    /// Returns the index of the handler and the register to store the exception object.
+    ///
+    pub(crate) fn get_exception_dest(&self, idx: usize) -> Option<(usize, SlotId)> {
+        self.exception_map
+            .iter()
+            .find(|entry| entry.range.contains(&idx))
+            .map(|entry| (entry.dest, entry.err_reg))
+    }
+
+    ///
+    /// Get the ensure clause for *idx*, which is executed when `return` from a block unwinds this frame.
+    ///
+    pub(crate) fn get_ensure_dest(&self, idx: usize) -> Option<(usize, SlotId)> {
+        self.exception_map
+            .iter()
+            .find(|entry| entry.ensure && entry.range.contains(&idx))
+            .map(|entry| (entry.dest, entry.err_reg))
+    }
+
     pub(crate) fn get_pc_index(&self, pc: Option<BcPc>) -> usize {
         if let Some(pos) = pc {
             pos - self.get_pc(0)
@@ -853,7 +1226,7 @@ impl ISeqInfo {
     ///
     /// Some((basic_block_id, Vec of source bytecodes)) => a start bytecode of a basic block.
     ///
-    pub(crate) fn get_bb_info(&self) -> Vec<Option<(usize, Vec<usize>)>> {
+    pub(crate) fn get_bb_info(&self, store: &FnStore) -> Vec<Option<(usize, Vec<usize>)>> {
         let mut info = vec![vec![]; self.bytecode_len() + 1];
         let mut skip = false;
         for (idx, pc) in self.bytecode().iter().enumerate() {
@@ -871,6 +1244,11 @@ impl ISeqInfo {
                 | TraceIr::CheckLocal(_, disp) => {
                     info[((idx + 1) as i32 + disp) as usize].push(idx);
                 }
+                TraceIr::OptCase(_, id) => {
+                    for dest in store[id].dests(idx) {
+                        info[dest].push(idx);
+                    }
+                }
                 _ => {}
             }
         }
@@ -901,7 +1279,7 @@ impl ISeqInfo {
                         elem.1.push(idx);
                     }
                 }
-                TraceIr::Br(_) | TraceIr::Ret(_) => {}
+                TraceIr::Br(_) | TraceIr::Ret(_) | TraceIr::MethodRet(_) | TraceIr::Raise(_) => {}
                 _ => {
                     if let Some(ref mut elem) = bb_info[idx + 1] {
                         elem.1.push(idx);
@@ -944,10 +1322,28 @@ impl ISeqInfo {
             name,
             prefix,
             toplevel,
+            func_id: self.id,
             cache: (usize::MAX, None),
         };
         let id = store.constsite_info.len();
         store.constsite_info.push(info);
         ConstSiteId(id as u32)
     }
+
+    pub(crate) fn add_optcase(
+        &self,
+        store: &mut FnStore,
+        symbol: bool,
+        min: i64,
+        branch_table: Box<[i32]>,
+    ) -> OptCaseId {
+        let info = OptCaseInfo {
+            symbol,
+            min,
+            branch_table,
+        };
+        let id = store.optcase_info.len();
+        store.optcase_info.push(info);
+        OptCaseId(id as u32)
+    }
 }
@@ -152,6 +152,27 @@ impl BcPc {
             TraceIr::Br(disp) => {
                 format!("br =>:{:05}", i as i32 + 1 + disp)
             }
+            TraceIr::OptCase(reg, id) => {
+                let OptCaseInfo {
+                    symbol,
+                    min,
+                    branch_table,
+                } = &globals.func[id];
+                let table: Vec<_> = branch_table
+                    .iter()
+                    .enumerate()
+                    .map(|(key, disp)| {
+                        let key = *min + key as i64;
+                        let key = if *symbol {
+                            format!(":{}", IdentId::get_name(IdentId::from(key as u32)))
+                        } else {
+                            key.to_string()
+                        };
+                        format!("{key}=>:{:05}", i as i32 + 1 + disp)
+                    })
+                    .collect();
+                format!("opt_case {:?} {{{}}}", reg, table.join(", "))
+            }
             TraceIr::CondBr(reg, disp, opt, kind) => {
                 format!(
                     "cond{}br {}{:?} =>:{:05}",
@@ -181,6 +202,10 @@ impl BcPc {
             TraceIr::Array(ret, src, len) => {
                 format!("{:?} = array[{:?}; {}]", ret, src, len)
             }
+            TraceIr::Hash { ret, args, len, kw } => {
+                let kind = if kw { "kw_hash" } else { "hash" };
+                format!("{:?} = {kind}{{{:?}: {}}}", ret, args, len)
+            }
             TraceIr::Index(ret, base, idx) => {
                 let op1 = format!("{:?} = {:?}.[{:?}]", ret, base, idx);
                 format!(
@@ -221,6 +246,9 @@ impl BcPc {
             TraceIr::BlockArgProxy(dst) => {
                 format!("{:?} = block_arg", dst)
             }
+            TraceIr::BlockArg(dst, src) => {
+                format!("{:?} = &{:?}", dst, src)
+            }
             TraceIr::LoadDynVar(ret, src) => {
                 format!("{:?} = {:?}", ret, src)
             }
@@ -248,6 +276,9 @@ impl BcPc {
             TraceIr::LoadGvar { ret, name } => {
                 format!("{:?} = ${}", ret, IdentId::get_name(name),)
             }
+            TraceIr::LoadSvar { ret, id } => {
+                format!("{:?} = {}", ret, special_var_name(id))
+            }
             TraceIr::StoreGvar { val, name } => {
                 format!("${} = {:?}", IdentId::get_name(name), val)
             }
@@ -337,6 +368,9 @@ impl BcPc {
             }
 
             TraceIr::Ret(reg) => format!("ret {:?}", reg),
+            TraceIr::MethodRet(reg) => format!("method_ret {:?}", reg),
+            TraceIr::Raise(reg) => format!("raise {:?}", reg),
+            TraceIr::InitKeyword(func_id) => format!("init_keyword {:?}", func_id),
             TraceIr::Mov(dst, src) => format!("{:?} = {:?}", dst, src),
             TraceIr::MethodCall {
                 ret, name, class, ..
@@ -402,25 +436,80 @@ impl BcPc {
                 };
                 format!("{:36} [{}]", op1, class.get_name(globals))
             }
+            TraceIr::Super {
+                ret,
+                class,
+                has_block,
+                splat,
+                ..
+            } => {
+                let args_pc = *self + 1;
+                let (args, len) = match args_pc.op1() {
+                    TraceIr::MethodArgs(MethodInfo { args, len, .. }) => (args, len),
+                    _ => unreachable!(),
+                };
+                let op1 = match (has_block, len) {
+                    (false, 0) => format!("{} = super()", ret.ret_str()),
+                    (false, _) => format!("{} = super({:?}; {})", ret.ret_str(), args, len),
+                    (true, 0) => format!("{} = super(&{:?})", ret.ret_str(), args),
+                    (true, _) => format!(
+                        "{} = super({:?}; {} &{:?})",
+                        ret.ret_str(),
+                        args + 1,
+                        len,
+                        args
+                    ),
+                };
+                let op1 = if splat { format!("{} *", op1) } else { op1 };
+                format!("{:36} [{}]", op1, class.get_name(globals))
+            }
             TraceIr::MethodArgs(..) => return None,
             TraceIr::MethodDef(name, func_id) => {
                 let name = IdentId::get_name(name);
                 format!("method_def {:?}: {:?}", name, func_id)
             }
+            TraceIr::SingletonMethodDef { obj, name, func_id } => {
+                let name = IdentId::get_name(name);
+                format!("singleton_method_def {:?}.{:?}: {:?}", obj, name, func_id)
+            }
+            TraceIr::SingletonClassDef { ret, base, func_id } => {
+                format!(
+                    "{} = singleton_class_def << {:?}: {:?}",
+                    ret.ret_str(),
+                    base,
+                    func_id
+                )
+            }
             TraceIr::ClassDef {
                 ret,
+                base,
                 superclass,
                 name,
                 func_id,
+                is_module,
             } => {
                 let name = IdentId::get_name(name);
-                format!(
-                    "{} = class_def {:?} < {}: {:?}",
-                    ret.ret_str(),
-                    name,
-                    superclass.ret_str(),
-                    func_id
-                )
+                let base = if base.is_zero() {
+                    String::new()
+                } else {
+                    format!("{:?}::", base)
+                };
+                if is_module {
+                    format!(
+                        "{} = module_def {base}{:?}: {:?}",
+                        ret.ret_str(),
+                        name,
+                        func_id
+                    )
+                } else {
+                    format!(
+                        "{} = class_def {base}{:?} < {}: {:?}",
+                        ret.ret_str(),
+                        name,
+                        superclass.ret_str(),
+                        func_id
+                    )
+                }
             }
             TraceIr::ConcatStr(ret, args, len) => {
                 format!("{} = concat({:?}; {})", ret.ret_str(), args, len)
@@ -548,6 +637,13 @@ pub(super) enum BcIr {
     Symbol(BcReg, IdentId),
     Literal(BcReg, Value),
     Array(BcReg, BcReg, u16),
+    Hash {
+        ret: BcReg,
+        args: BcReg,
+        len: u16,
+        /// true if the hash is keyword arguments of a method call.
+        kw: bool,
+    },
     Range {
         ret: BcReg,
         start: BcReg,
@@ -562,11 +658,17 @@ pub(super) enum BcIr {
         ret: BcReg,
         name: IdentId,
     },
+    /// load special variable. (`$~`, `$&`, `$1`..`$9`)
+    LoadSvar {
+        ret: BcReg,
+        id: u32,
+    },
     StoreGvar {
         val: BcReg,
         name: IdentId,
     },
     BlockArgProxy(BcReg),
+    BlockArg(BcReg, BcReg), // dst, src  - %dst = &%src
     LoadDynVar {
         /// return register of the current frame.
         ret: BcReg,
@@ -595,9 +697,26 @@ pub(super) enum BcIr {
     CheckLocal(BcReg, usize),
     Br(usize),
     CondBr(BcReg, usize, bool, BrKind),
+    OptCase {
+        cond: BcReg,
+        symbol: bool,
+        min: i64,
+        table: Vec<usize>,
+    },
     Ret(BcReg),
+    MethodRet(BcReg),
+    Raise(BcReg),
+    InitKeyword,
     MethodCall(Option<BcReg>, IdentId),      // (ret, id)
     MethodCallBlock(Option<BcReg>, IdentId), // (ret, id)
+    /// `super` in the method *func_id*.
+    Super {
+        ret: Option<BcReg>,
+        func_id: FuncId,
+        has_block: bool,
+        /// true if the last positional argument is a rest array to be expanded.
+        splat: bool,
+    },
     Yield {
         ret: Option<BcReg>,
         args: BcReg,
@@ -608,11 +727,23 @@ pub(super) enum BcIr {
     MethodArgs(BcReg, BcReg, usize), // (recv, args, args_len)
     InlineCache,
     MethodDef(IdentId, FuncId),
+    SingletonMethodDef {
+        obj: BcReg,
+        name: IdentId,
+        func_id: FuncId,
+    },
     ClassDef {
         ret: Option<BcReg>,
+        base: Option<BcReg>,
         superclass: Option<BcReg>,
         name: IdentId,
         func_id: FuncId,
+        is_module: bool,
+    },
+    SingletonClassDef {
+        ret: Option<BcReg>,
+        base: BcReg,
+        func_id: FuncId,
     },
     ConcatStr(Option<BcReg>, BcTemp, usize), // (ret, args, args_len)
     ExpandArray(BcReg, BcReg, u16),          // (src, dst, len)
@@ -674,6 +805,17 @@ impl Bc {
         }
     }
 
+    ///
+    /// Get a Value embedded in this bytecode (literals and inline constant caches).
+    ///
+    pub(crate) fn embedded_value(&self) -> Option<Value> {
+        match (self.op1 >> 48) as u16 {
+            // Literal / LoadConst
+            7 | 10 => self.value(),
+            _ => None,
+        }
+    }
+
     pub(crate) fn from_with_func_name_id(op1: u64, name: IdentId, func_id: FuncId) -> Self {
         Self {
             op1,
@@ -819,6 +961,9 @@ impl std::fmt::Debug for Bc {
             TraceIr::Br(disp) => {
                 write!(f, "br => {}", disp_str(disp))
             }
+            TraceIr::OptCase(reg, id) => {
+                write!(f, "opt_case {:?} table[{}]", reg, id.0)
+            }
             TraceIr::CondBr(reg, disp, opt, kind) => {
                 write!(
                     f,
@@ -839,6 +984,10 @@ impl std::fmt::Debug for Bc {
             TraceIr::Array(ret, src, len) => {
                 write!(f, "{:?} = array[{:?}; {}]", ret, src, len)
             }
+            TraceIr::Hash { ret, args, len, kw } => {
+                let kind = if kw { "kw_hash" } else { "hash" };
+                write!(f, "{:?} = {kind}{{{:?}: {}}}", ret, args, len)
+            }
             TraceIr::Range {
                 ret,
                 start,
@@ -867,6 +1016,9 @@ impl std::fmt::Debug for Bc {
             TraceIr::BlockArgProxy(dst) => {
                 write!(f, "{:?} = block_arg", dst)
             }
+            TraceIr::BlockArg(dst, src) => {
+                write!(f, "{:?} = &{:?}", dst, src)
+            }
             TraceIr::LoadDynVar(ret, src) => {
                 write!(f, "{:?} = {:?}", ret, src)
             }
@@ -882,6 +1034,9 @@ impl std::fmt::Debug for Bc {
             TraceIr::LoadGvar { ret, name } => {
                 write!(f, "{:?} = ${}", ret, IdentId::get_name(name),)
             }
+            TraceIr::LoadSvar { ret, id } => {
+                write!(f, "{:?} = {}", ret, special_var_name(id))
+            }
             TraceIr::StoreGvar { val, name } => {
                 write!(f, "${} = {:?}", IdentId::get_name(name), val)
             }
@@ -953,6 +1108,9 @@ impl std::fmt::Debug for Bc {
             }
 
             TraceIr::Ret(reg) => write!(f, "ret {:?}", reg),
+            TraceIr::MethodRet(reg) => write!(f, "method_ret {:?}", reg),
+            TraceIr::Raise(reg) => write!(f, "raise {:?}", reg),
+            TraceIr::InitKeyword(func_id) => write!(f, "init_keyword {:?}", func_id),
             TraceIr::Mov(dst, src) => write!(f, "{:?} = {:?}", dst, src),
             TraceIr::MethodCall {
                 ret, name, class, ..
@@ -966,6 +1124,10 @@ impl std::fmt::Debug for Bc {
                 let op1 = format!("{} = call {:?}", ret.ret_str(), name,);
                 write!(f, "{:28} {:?}", op1, class)
             }
+            TraceIr::Super { ret, class, .. } => {
+                let op1 = format!("{} = super", ret.ret_str());
+                write!(f, "{:28} {:?}", op1, class)
+            }
             TraceIr::Yield { ret, args, len } => {
                 let op1 = format!("{} = yield ({:?}; {})", ret.ret_str(), args, len);
                 write!(f, "{:28}", op1)
@@ -980,8 +1142,20 @@ impl std::fmt::Debug for Bc {
             TraceIr::MethodDef(name, _) => {
                 write!(f, "method_def {:?}", name)
             }
-            TraceIr::ClassDef { ret, name, .. } => {
-                write!(f, "{} = class_def {:?}", ret.ret_str(), name)
+            TraceIr::SingletonMethodDef { obj, name, .. } => {
+                write!(f, "singleton_method_def {:?}.{:?}", obj, name)
+            }
+            TraceIr::ClassDef {
+                ret,
+                name,
+                is_module,
+                ..
+            } => {
+                let kind = if is_module { "module_def" } else { "class_def" };
+                write!(f, "{} = {kind} {:?}", ret.ret_str(), name)
+            }
+            TraceIr::SingletonClassDef { ret, base, .. } => {
+                write!(f, "{} = singleton_class_def << {:?}", ret.ret_str(), base)
             }
             TraceIr::ConcatStr(ret, args, len) => {
                 write!(f, "{} = concat({:?}; {})", ret.ret_str(), args, len)
@@ -1044,6 +1218,13 @@ pub(super) enum TraceIr {
     Literal(SlotId, Value),
     /// array(%ret, %src, len)
     Array(SlotId, SlotId, u16),
+    /// hash(%ret, %args, len, kw)
+    Hash {
+        ret: SlotId,
+        args: SlotId,
+        len: u16,
+        kw: bool,
+    },
     Range {
         ret: SlotId,
         start: SlotId,
@@ -1059,12 +1240,18 @@ pub(super) enum TraceIr {
     LoadDynVar(SlotId, DynVar),
     StoreDynVar(DynVar, SlotId),
     BlockArgProxy(SlotId),
+    /// block argument for `&src`(%dst, %src)
+    BlockArg(SlotId, SlotId),
     LoadIvar(SlotId, IdentId, ClassId, IvarId), // ret, id  - %ret = @id
     StoreIvar(SlotId, IdentId, ClassId, IvarId), // src, id  - @id = %src
     LoadGvar {
         ret: SlotId,
         name: IdentId,
     },
+    LoadSvar {
+        ret: SlotId,
+        id: u32,
+    },
     StoreGvar {
         val: SlotId,
         name: IdentId,
@@ -1112,6 +1299,14 @@ pub(super) enum TraceIr {
     Cmpri(CmpKind, SlotId, SlotId, i16, bool),
     /// return(%ret)
     Ret(SlotId),
+    /// return from the method in which the block was defined(%ret)
+    MethodRet(SlotId),
+    /// raise(%exception)
+    Raise(SlotId),
+    /// branch through the jump table(%cond, table_id)
+    OptCase(SlotId, OptCaseId),
+    /// set keyword parameters from the keyword arguments(func_id)
+    InitKeyword(FuncId),
     /// move(%dst, %src)
     Mov(SlotId, SlotId),
     /// initialize_method
@@ -1153,6 +1348,24 @@ pub(super) enum TraceIr {
         class: ClassId,
         _version: u32,
     },
+    //                0       4       8       12      16
+    //                +-------+-------+-------+-------+
+    // Super          |   |ret|func_id| owner |version|
+    //                +-------+-------+-------+-------+
+    // MethodArgs     |   |rcv|arg|len|    CodePtr    |
+    //                +-------+-------+-------+-------+
+    //                |      Meta     |      PC       |
+    //                +-------+-------+-------+-------+
+    /// `super` call(%ret, the method in which `super` appears)
+    Super {
+        ret: SlotId,
+        func_id: FuncId,
+        class: ClassId,
+        _version: u32,
+        has_block: bool,
+        /// true if the last positional argument is a rest array to be expanded.
+        splat: bool,
+    },
     Yield {
         ret: SlotId,
         args: SlotId,
@@ -1162,12 +1375,26 @@ pub(super) enum TraceIr {
     MethodArgs(MethodInfo),
     /// method definition(method_name, func_id)
     MethodDef(IdentId, FuncId),
-    /// class definition(method_name, func_id)
+    /// singleton method definition(obj, method_name, func_id)
+    SingletonMethodDef {
+        obj: SlotId,
+        name: IdentId,
+        func_id: FuncId,
+    },
+    /// class or module definition(method_name, func_id)
     ClassDef {
         ret: SlotId,
+        base: SlotId,
         superclass: SlotId,
         name: IdentId,
         func_id: FuncId,
+        is_module: bool,
+    },
+    /// singleton class definition(`class << base`)
+    SingletonClassDef {
+        ret: SlotId,
+        base: SlotId,
+        func_id: FuncId,
     },
     /// concatenate strings(ret, args, args_len)
     ConcatStr(SlotId, SlotId, u16),
@@ -1219,6 +1446,17 @@ impl BrKind {
     }
 }
 
+///
+/// Get the name of the special variable *id*. (See `get_special_var()`)
+///
+fn special_var_name(id: u32) -> String {
+    match id {
+        0 => "$~".to_string(),
+        1 => "$&".to_string(),
+        n => format!("${}", n - 1),
+    }
+}
+
 fn dec_wl(op: u64) -> (u16, u32) {
     ((op >> 32) as u16, op as u32)
 }
@@ -1282,11 +1520,13 @@ impl TraceIr {
                         IvarId::new(ivar),
                     )
                 }
-                18 => Self::ClassDef {
+                18 | 22 => Self::ClassDef {
                     ret: SlotId::new(op1),
-                    superclass: SlotId::new(op2 as u16),
+                    base: SlotId::new(op2 as u16),
+                    superclass: SlotId::new((op2 >> 16) as u16),
                     name: IdentId::from((pc.op2.0) as u32),
                     func_id: FuncId((pc.op2.0 >> 32) as u32),
+                    is_module: opcode == 22,
                 },
                 19 => {
                     let (class, _version) = pc.class_version();
@@ -1299,6 +1539,8 @@ impl TraceIr {
                 }
                 20 => Self::CheckLocal(SlotId::new(op1), op2 as i32),
                 21 => Self::BlockArgProxy(SlotId::new(op1)),
+                23 => Self::BlockArg(SlotId::new(op1), SlotId::new(op2 as u16)),
+                24 => Self::MethodRet(SlotId::new(op1)),
                 25 => Self::LoadGvar {
                     ret: SlotId::new(op1),
                     name: IdentId::from(op2),
@@ -1307,6 +1549,34 @@ impl TraceIr {
                     val: SlotId::new(op1),
                     name: IdentId::from(op2),
                 },
+                27 => Self::Raise(SlotId::new(op1)),
+                28 => Self::OptCase(SlotId::new(op1), OptCaseId(op2)),
+                29 => Self::InitKeyword(FuncId(op2)),
+                32 => Self::SingletonClassDef {
+                    ret: SlotId::new(op1),
+                    base: SlotId::new(op2 as u16),
+                    func_id: FuncId((pc.op2.0 >> 32) as u32),
+                },
+                34 => Self::LoadSvar {
+                    ret: SlotId::new(op1),
+                    id: op2,
+                },
+                33 => Self::SingletonMethodDef {
+                    obj: SlotId::new(op1),
+                    name: IdentId::from((pc.op2.0) as u32),
+                    func_id: FuncId((pc.op2.0 >> 32) as u32),
+                },
+                30 | 31 | 35 | 36 => {
+                    let (class, _version) = pc.class_version();
+                    Self::Super {
+                        ret: SlotId::new(op1),
+                        func_id: FuncId(op2),
+                        class,
+                        _version,
+                        has_block: opcode == 31 || opcode == 36,
+                        splat: opcode >= 35,
+                    }
+                }
                 _ => unreachable!("{:016x}", op),
             }
         } else {
@@ -1399,6 +1669,12 @@ impl TraceIr {
                     stack_offset: op3 as usize,
                 },
                 171 => Self::ExpandArray(SlotId::new(op1), SlotId::new(op2), op3),
+                173 | 174 => Self::Hash {
+                    ret: SlotId::new(op1),
+                    args: SlotId::new(op2),
+                    len: op3,
+                    kw: opcode == 174,
+                },
                 180..=199 => Self::BinOpIr {
                     kind: BinOpK::from(opcode - 180),
                     ret: SlotId::new(op1),
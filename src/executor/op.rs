@@ -1,6 +1,6 @@
 use super::*;
 
-use num::{BigInt, Integer, ToPrimitive, Zero};
+use num::{BigInt, BigRational, Integer, ToPrimitive, Zero};
 use paste::paste;
 use std::ops::{Add, BitAnd, BitOr, BitXor, Div, Mul, Rem, Shl, Shr, Sub};
 
@@ -33,7 +33,7 @@ macro_rules! binop_values {
                     (RV::BigInt(lhs), RV::Float(rhs)) => Value::new_float(lhs.to_f64().unwrap().$op(&rhs)),
                     (RV::Float(lhs), RV::Float(rhs)) => Value::new_float(lhs.$op(&rhs)),
                     _ => {
-                        return interp.invoke_method(globals, $op_str, lhs, &[rhs]);
+                        return interp.coerce_binop(globals, $op_str, lhs, rhs);
                     }
                 };
                 Some(v)
@@ -46,11 +46,11 @@ macro_rules! binop_values {
     };
 }
 
+///
+/// *lhs* ** *rhs* for a non-negative exponent *rhs*.
+///
 pub(super) extern "C" fn pow_ii(lhs: i64, rhs: i64) -> Value {
     if let Ok(rhs) = i32::try_from(rhs) {
-        if rhs < 0 {
-            unimplemented!("a**b: b<0 in not supported yet.")
-        }
         let rhs = rhs as u32;
         match lhs.checked_pow(rhs) {
             Some(res) => Value::new_integer(res),
@@ -61,6 +61,19 @@ pub(super) extern "C" fn pow_ii(lhs: i64, rhs: i64) -> Value {
     }
 }
 
+///
+/// *base* ** *exp* as a Rational.
+///
+/// Raise ZeroDivisionError for 0 ** -n.
+///
+pub(crate) fn pow_rational(globals: &mut Globals, base: BigRational, exp: i32) -> Option<Value> {
+    if exp < 0 && base.is_zero() {
+        globals.err_divide_by_zero();
+        return None;
+    }
+    Some(Value::new_rational(num::traits::Pow::pow(base, exp)))
+}
+
 fn pow_ff(lhs: f64, rhs: f64) -> Value {
     Value::new_float(lhs.powf(rhs))
 }
@@ -69,7 +82,6 @@ pub(super) extern "C" fn pow_ff_f(lhs: f64, rhs: f64) -> f64 {
     lhs.powf(rhs)
 }
 
-// TODO: support rhs < 0.
 pub(super) extern "C" fn pow_values(
     interp: &mut Executor,
     globals: &mut Globals,
@@ -77,7 +89,12 @@ pub(super) extern "C" fn pow_values(
     rhs: Value,
 ) -> Option<Value> {
     let v = match (lhs.unpack(), rhs.unpack()) {
-        (RV::Integer(lhs), RV::Integer(rhs)) => pow_ii(lhs, rhs),
+        (RV::Integer(lhs), RV::Integer(rhs)) => match i32::try_from(rhs) {
+            Ok(rhs) if rhs < 0 => {
+                return pow_rational(globals, BigRational::from_integer(BigInt::from(lhs)), rhs)
+            }
+            _ => pow_ii(lhs, rhs),
+        },
         (RV::Integer(lhs), RV::BigInt(rhs)) => {
             if let Ok(rhs) = rhs.try_into() {
                 Value::new_bigint(BigInt::from(lhs).pow(rhs))
@@ -88,6 +105,9 @@ pub(super) extern "C" fn pow_values(
         (RV::Integer(lhs), RV::Float(rhs)) => pow_ff(lhs as f64, rhs),
         (RV::BigInt(lhs), RV::Integer(rhs)) => {
             if let Ok(rhs) = i32::try_from(rhs) {
+                if rhs < 0 {
+                    return pow_rational(globals, BigRational::from_integer(lhs.clone()), rhs);
+                }
                 let rhs = rhs as u32;
                 Value::new_bigint(lhs.pow(rhs))
             } else {
@@ -112,7 +132,7 @@ pub(super) extern "C" fn pow_values(
         (RV::Float(lhs), RV::BigInt(rhs)) => pow_ff(lhs, rhs.to_f64().unwrap()),
         (RV::Float(lhs), RV::Float(rhs)) => pow_ff(lhs, rhs),
         _ => {
-            return interp.invoke_method(globals, IdentId::_POW, lhs, &[rhs]);
+            return interp.coerce_binop(globals, IdentId::_POW, lhs, rhs);
         }
     };
     Some(v)
@@ -196,7 +216,7 @@ pub(super) extern "C" fn div_values(
             Value::new_float(lhs.div(&rhs))
         }
         _ => {
-            return interp.invoke_method(globals, IdentId::_DIV, lhs, &[rhs]);
+            return interp.coerce_binop(globals, IdentId::_DIV, lhs, rhs);
         }
     };
     Some(v)
@@ -217,7 +237,7 @@ macro_rules! int_binop_values {
                     (RV::BigInt(lhs), RV::Integer(rhs)) => Value::new_bigint(lhs.$op(BigInt::from(rhs))),
                     (RV::BigInt(lhs), RV::BigInt(rhs)) => Value::new_bigint(lhs.$op(rhs)),
                     _ => {
-                        return interp.invoke_method(globals, $op_str, lhs, &[rhs]);
+                        return interp.coerce_binop(globals, $op_str, lhs, rhs);
                     }
                 };
                 Some(v)
@@ -258,7 +278,7 @@ pub(super) extern "C" fn shr_values(
             }
         }
         _ => {
-            return interp.invoke_method(globals, IdentId::_SHR, lhs, &[rhs]);
+            return interp.coerce_binop(globals, IdentId::_SHR, lhs, rhs);
         }
     };
     Some(v)
@@ -286,7 +306,7 @@ pub(super) extern "C" fn shl_values(
             }
         }
         _ => {
-            return interp.invoke_method(globals, IdentId::_SHL, lhs, &[rhs]);
+            return interp.coerce_binop(globals, IdentId::_SHL, lhs, rhs);
         }
     };
     Some(v)
@@ -315,9 +335,14 @@ fn bigint_shl(lhs: &BigInt, rhs: u32) -> Value {
 }
 
 macro_rules! cmp_values {
-    ($op:ident) => {
+    (($op:ident, $op_str:expr)) => {
         paste! {
-            pub(super) extern "C" fn [<cmp_ $op _values>](lhs: Value, rhs: Value) -> Value {
+            pub(super) extern "C" fn [<cmp_ $op _values>](
+                interp: &mut Executor,
+                globals: &mut Globals,
+                lhs: Value,
+                rhs: Value
+            ) -> Option<Value> {
                 let b = match (lhs.unpack(), rhs.unpack()) {
                     (RV::Integer(lhs), RV::Integer(rhs)) => lhs.$op(&rhs),
                     (RV::Integer(lhs), RV::BigInt(rhs)) => BigInt::from(lhs).$op(&rhs),
@@ -328,48 +353,490 @@ macro_rules! cmp_values {
                     (RV::Float(lhs), RV::Integer(rhs)) => lhs.$op(&(rhs as f64)),
                     (RV::Float(lhs), RV::BigInt(rhs)) => lhs.$op(&(rhs.to_f64().unwrap())),
                     (RV::Float(lhs), RV::Float(rhs)) => lhs.$op(&rhs),
-                    (lhs, rhs) => unreachable!("{:?} cmp {:?}", lhs, rhs),
+                    (RV::String(lhs), RV::String(rhs)) => lhs.$op(&rhs),
+                    _ => {
+                        return interp.coerce_binop(globals, $op_str, lhs, rhs);
+                    }
                 };
-                Value::bool(b)
+                Some(Value::bool(b))
             }
         }
     };
-    ($op1:ident, $($op2:ident),+) => {
-        cmp_values!($op1);
-        cmp_values!($($op2),+);
+    (($op1:ident, $op_str1:expr), $(($op2:ident, $op_str2:expr)),+) => {
+        cmp_values!(($op1, $op_str1));
+        cmp_values!($(($op2, $op_str2)),+);
     };
 }
 
-cmp_values!(ge, gt, le, lt);
+cmp_values!(
+    (ge, IdentId::_GE),
+    (gt, IdentId::_GT),
+    (le, IdentId::_LE),
+    (lt, IdentId::_LT)
+);
 
-macro_rules! eq_values {
-    ($op:ident) => {
-        paste! {
-            pub(super) extern "C" fn [<cmp_ $op _values>](lhs: Value, rhs: Value) -> Value {
-                let b = match (lhs.unpack(), rhs.unpack()) {
-                    (RV::Integer(lhs), RV::Integer(rhs)) => lhs.$op(&rhs),
-                    (RV::Integer(lhs), RV::BigInt(rhs)) => BigInt::from(lhs).$op(&rhs),
-                    (RV::Integer(lhs), RV::Float(rhs)) => (lhs as f64).$op(&rhs),
-                    (RV::BigInt(lhs), RV::Integer(rhs)) => lhs.$op(&BigInt::from(rhs)),
-                    (RV::BigInt(lhs), RV::BigInt(rhs)) => lhs.$op(&rhs),
-                    (RV::BigInt(lhs), RV::Float(rhs)) => lhs.to_f64().unwrap().$op(&rhs),
-                    (RV::Float(lhs), RV::Integer(rhs)) => lhs.$op(&(rhs as f64)),
-                    (RV::Float(lhs), RV::BigInt(rhs)) => lhs.$op(&(rhs.to_f64().unwrap())),
-                    (RV::Float(lhs), RV::Float(rhs)) => lhs.$op(&rhs),
-                    (RV::Bool(lhs), RV::Bool(rhs)) => lhs.$op(&rhs),
-                    _ => unreachable!("{:?} {:?}", lhs, rhs),
+pub(super) extern "C" fn cmp_eq_values(
+    interp: &mut Executor,
+    globals: &mut Globals,
+    lhs: Value,
+    rhs: Value,
+) -> Option<Value> {
+    Some(Value::bool(interp.eq_values(globals, lhs, rhs)?))
+}
+
+pub(super) extern "C" fn cmp_ne_values(
+    interp: &mut Executor,
+    globals: &mut Globals,
+    lhs: Value,
+    rhs: Value,
+) -> Option<Value> {
+    Some(Value::bool(!interp.eq_values(globals, lhs, rhs)?))
+}
+
+impl Executor {
+    ///
+    /// Execute the binary operator *op* for *lhs* and *rhs*.
+    ///
+    /// Operators for numerics are executed natively, and methods are called for other values.
+    ///
+    pub(crate) fn binary_op(
+        &mut self,
+        globals: &mut Globals,
+        op: IdentId,
+        lhs: Value,
+        rhs: Value,
+    ) -> Option<Value> {
+        let func = match op {
+            IdentId::_ADD => add_values,
+            IdentId::_SUB => sub_values,
+            IdentId::_MUL => mul_values,
+            IdentId::_DIV => div_values,
+            IdentId::_REM => rem_values,
+            IdentId::_POW => pow_values,
+            IdentId::_EQ => cmp_eq_values,
+            IdentId::_NEQ => cmp_ne_values,
+            IdentId::_LT => cmp_lt_values,
+            IdentId::_LE => cmp_le_values,
+            IdentId::_GT => cmp_gt_values,
+            IdentId::_GE => cmp_ge_values,
+            IdentId::_BOR => bitor_values,
+            IdentId::_BAND => bitand_values,
+            IdentId::_BXOR => bitxor_values,
+            IdentId::_SHL => shl_values,
+            IdentId::_SHR => shr_values,
+            _ => return self.invoke_method(globals, op, lhs, &[rhs]),
+        };
+        func(self, globals, lhs, rhs)
+    }
+
+    ///
+    /// Execute the binary operator *op* for *lhs* and *rhs* which can not be handled natively.
+    ///
+    /// When *lhs* is a numeric, *rhs* is converted with `rhs.coerce(lhs)` and *op* is applied
+    /// to the result. Otherwise, the method *op* of *lhs* is called.
+    ///
+    pub(crate) fn coerce_binop(
+        &mut self,
+        globals: &mut Globals,
+        op: IdentId,
+        lhs: Value,
+        rhs: Value,
+    ) -> Option<Value> {
+        if let Some(res) = self.complex_binop(globals, op, lhs, rhs) {
+            return res;
+        }
+        if let Some(res) = self.rational_binop(globals, op, lhs, rhs) {
+            return res;
+        }
+        if !lhs.is_numeric() && lhs.is_rational().is_none() && lhs.is_complex().is_none() {
+            return self.invoke_method(globals, op, lhs, &[rhs]);
+        }
+        if rhs.is_numeric() || rhs.is_rational().is_some() {
+            // e.g. `1.5 | 1` or `1 << 1.5`
+            if lhs.class_id() == INTEGER_CLASS {
+                globals.err_no_implict_conv(rhs, INTEGER_CLASS);
+            } else {
+                globals.err_method_not_found(op, lhs);
+            }
+            return None;
+        }
+        let (lhs, rhs) = match self.coerce(globals, lhs, rhs)? {
+            Some(pair) => pair,
+            None => {
+                match op {
+                    IdentId::_LT | IdentId::_LE | IdentId::_GT | IdentId::_GE => {
+                        let msg = format!(
+                            "comparison of {} with {} failed",
+                            lhs.get_real_class_name(globals),
+                            match rhs.unpack() {
+                                RV::Nil | RV::Bool(_) => globals.val_inspect(rhs),
+                                _ => rhs.get_real_class_name(globals),
+                            }
+                        );
+                        globals.err_argument(&msg);
+                    }
+                    _ => globals.err_cant_be_coerced(rhs, lhs),
+                }
+                return None;
+            }
+        };
+        self.binary_op(globals, op, lhs, rhs)
+    }
+
+    ///
+    /// Execute arithmetic or comparison operator *op* when *lhs* or *rhs* is a Rational.
+    ///
+    /// Rational is mixed with Integer into Rational, and with Float into Float.
+    /// Returns None if neither is a Rational, or the other is not a numeric.
+    ///
+    fn rational_binop(
+        &mut self,
+        globals: &mut Globals,
+        op: IdentId,
+        lhs: Value,
+        rhs: Value,
+    ) -> Option<Option<Value>> {
+        if lhs.is_rational().is_none() && rhs.is_rational().is_none() {
+            return None;
+        }
+        match op {
+            IdentId::_ADD
+            | IdentId::_SUB
+            | IdentId::_MUL
+            | IdentId::_DIV
+            | IdentId::_REM
+            | IdentId::_POW
+            | IdentId::_LT
+            | IdentId::_LE
+            | IdentId::_GT
+            | IdentId::_GE => {}
+            _ => return None,
+        }
+        let (l, r) = match (lhs.try_rational(), rhs.try_rational()) {
+            (Some(l), Some(r)) => (l, r),
+            _ => {
+                let l = lhs.try_float()?;
+                let r = rhs.try_float()?;
+                return Some(self.binary_op(globals, op, Value::new_float(l), Value::new_float(r)));
+            }
+        };
+        let v = match op {
+            IdentId::_ADD => Value::new_rational(l + r),
+            IdentId::_SUB => Value::new_rational(l - r),
+            IdentId::_MUL => Value::new_rational(l * r),
+            IdentId::_DIV | IdentId::_REM if r.is_zero() => {
+                globals.err_divide_by_zero();
+                return Some(None);
+            }
+            IdentId::_DIV => Value::new_rational(l / r),
+            IdentId::_REM => Value::new_rational(&l - (&l / &r).floor() * &r),
+            IdentId::_POW => {
+                if r.is_integer() {
+                    if let Some(exp) = r.to_integer().to_i32() {
+                        return Some(pow_rational(globals, l, exp));
+                    }
+                }
+                pow_ff(l.to_f64().unwrap(), r.to_f64().unwrap())
+            }
+            IdentId::_LT => Value::bool(l < r),
+            IdentId::_LE => Value::bool(l <= r),
+            IdentId::_GT => Value::bool(l > r),
+            IdentId::_GE => Value::bool(l >= r),
+            _ => unreachable!(),
+        };
+        Some(Some(v))
+    }
+
+    ///
+    /// Execute the binary operator *op* when *lhs* or *rhs* is a Complex.
+    ///
+    /// Integer, Float and Rational are treated as a Complex whose imaginary part is 0.
+    /// Returns None if neither is a Complex, or the other is not a numeric.
+    ///
+    fn complex_binop(
+        &mut self,
+        globals: &mut Globals,
+        op: IdentId,
+        lhs: Value,
+        rhs: Value,
+    ) -> Option<Option<Value>> {
+        if lhs.is_complex().is_none() && rhs.is_complex().is_none() {
+            return None;
+        }
+        let lhs_parts = complex_parts(lhs)?;
+        let rhs_parts = complex_parts(rhs)?;
+        Some(match op {
+            IdentId::_ADD | IdentId::_SUB | IdentId::_MUL | IdentId::_DIV => self
+                .complex_op(globals, op, lhs_parts, rhs_parts)
+                .map(|(re, im)| Value::new_complex(re, im)),
+            IdentId::_POW => self.complex_pow(globals, lhs_parts, rhs_parts, rhs),
+            _ => {
+                let lhs = match lhs_parts {
+                    (re, None) => Value::new_complex(re, Value::new_integer(0)),
+                    _ => lhs,
+                };
+                globals.err_method_not_found(op, lhs);
+                None
+            }
+        })
+    }
+
+    ///
+    /// Apply +, -, * or / to complex numbers given as (real, imaginary).
+    ///
+    /// The imaginary part None means 0.
+    ///
+    fn complex_op(
+        &mut self,
+        globals: &mut Globals,
+        op: IdentId,
+        (a, b): (Value, Option<Value>),
+        (c, d): (Value, Option<Value>),
+    ) -> Option<(Value, Value)> {
+        let zero = Value::new_integer(0);
+        let res = match op {
+            IdentId::_ADD | IdentId::_SUB => {
+                let re = self.binary_op(globals, op, a, c)?;
+                let b = b.unwrap_or(zero);
+                let im = match d {
+                    Some(d) => self.binary_op(globals, op, b, d)?,
+                    None => b,
                 };
-                Value::bool(b)
+                (re, im)
+            }
+            IdentId::_MUL => match (b, d) {
+                (Some(b), Some(d)) => {
+                    let ac = self.binary_op(globals, IdentId::_MUL, a, c)?;
+                    let bd = self.binary_op(globals, IdentId::_MUL, b, d)?;
+                    let ad = self.binary_op(globals, IdentId::_MUL, a, d)?;
+                    let bc = self.binary_op(globals, IdentId::_MUL, b, c)?;
+                    (
+                        self.binary_op(globals, IdentId::_SUB, ac, bd)?,
+                        self.binary_op(globals, IdentId::_ADD, ad, bc)?,
+                    )
+                }
+                (Some(b), None) => (
+                    self.binary_op(globals, IdentId::_MUL, a, c)?,
+                    self.binary_op(globals, IdentId::_MUL, b, c)?,
+                ),
+                (None, Some(d)) => (
+                    self.binary_op(globals, IdentId::_MUL, a, c)?,
+                    self.binary_op(globals, IdentId::_MUL, a, d)?,
+                ),
+                (None, None) => unreachable!(),
+            },
+            IdentId::_DIV => {
+                let b = b.unwrap_or(zero);
+                match d {
+                    Some(d) => {
+                        // (a + bi) / (c + di) = ((ac + bd) + (bc - ad)i) / (c^2 + d^2)
+                        let cc = self.binary_op(globals, IdentId::_MUL, c, c)?;
+                        let dd = self.binary_op(globals, IdentId::_MUL, d, d)?;
+                        let den = self.binary_op(globals, IdentId::_ADD, cc, dd)?;
+                        let ac = self.binary_op(globals, IdentId::_MUL, a, c)?;
+                        let bd = self.binary_op(globals, IdentId::_MUL, b, d)?;
+                        let bc = self.binary_op(globals, IdentId::_MUL, b, c)?;
+                        let ad = self.binary_op(globals, IdentId::_MUL, a, d)?;
+                        let re = self.binary_op(globals, IdentId::_ADD, ac, bd)?;
+                        let im = self.binary_op(globals, IdentId::_SUB, bc, ad)?;
+                        (self.quo(globals, re, den)?, self.quo(globals, im, den)?)
+                    }
+                    None => (self.quo(globals, a, c)?, self.quo(globals, b, c)?),
+                }
             }
+            _ => unreachable!(),
+        };
+        Some(res)
+    }
+
+    ///
+    /// Divide *lhs* by *rhs*, giving a Rational if both are exact numbers.
+    ///
+    fn quo(&mut self, globals: &mut Globals, lhs: Value, rhs: Value) -> Option<Value> {
+        match (lhs.try_rational(), rhs.try_rational()) {
+            (Some(l), Some(r)) => {
+                if r.is_zero() {
+                    globals.err_divide_by_zero();
+                    return None;
+                }
+                Some(Value::new_rational(l / r))
+            }
+            _ => self.binary_op(globals, IdentId::_DIV, lhs, rhs),
         }
-    };
-    ($op1:ident, $($op2:ident),+) => {
-        eq_values!($op1);
-        eq_values!($($op2),+);
-    };
+    }
+
+    ///
+    /// Complex ** *exp*.
+    ///
+    /// An Integer exponent is calculated exactly by repeated multiplication, and others are
+    /// calculated in Float.
+    ///
+    fn complex_pow(
+        &mut self,
+        globals: &mut Globals,
+        base: (Value, Option<Value>),
+        exp_parts: (Value, Option<Value>),
+        exp: Value,
+    ) -> Option<Value> {
+        if let (RV::Integer(n), Some(_)) = (exp.unpack(), base.1) {
+            let one = (Value::new_integer(1), Some(Value::new_integer(0)));
+            let mut res = one;
+            let mut square = base;
+            let mut i = n.unsigned_abs();
+            while i != 0 {
+                if i & 1 == 1 {
+                    let (re, im) = self.complex_op(globals, IdentId::_MUL, res, square)?;
+                    res = (re, Some(im));
+                }
+                i >>= 1;
+                if i != 0 {
+                    let (re, im) = self.complex_op(globals, IdentId::_MUL, square, square)?;
+                    square = (re, Some(im));
+                }
+            }
+            if n < 0 {
+                let (re, im) = self.complex_op(globals, IdentId::_DIV, one, res)?;
+                res = (re, Some(im));
+            }
+            return Some(Value::new_complex(res.0, res.1.unwrap()));
+        }
+        let to_c64 = |(re, im): (Value, Option<Value>)| {
+            num::complex::Complex64::new(
+                re.try_float().unwrap(),
+                im.map_or(0.0, |im| im.try_float().unwrap()),
+            )
+        };
+        let res = to_c64(base).powc(to_c64(exp_parts));
+        Some(Value::new_complex(
+            Value::new_float(res.re),
+            Value::new_float(res.im),
+        ))
+    }
+
+    ///
+    /// Convert *lhs* and *rhs* by `rhs.coerce(lhs)`.
+    ///
+    /// Returns Some(None) if *rhs* does not respond to `coerce`.
+    ///
+    pub(crate) fn coerce(
+        &mut self,
+        globals: &mut Globals,
+        lhs: Value,
+        rhs: Value,
+    ) -> Option<Option<(Value, Value)>> {
+        let coerce = IdentId::get_ident_id("coerce");
+        if globals.find_method(rhs, coerce).is_none() {
+            return Some(None);
+        }
+        let res = self.invoke_method(globals, coerce, rhs, &[lhs])?;
+        match res.is_array() {
+            Some(ary) if ary.len() == 2 => Some(Some((ary[0], ary[1]))),
+            _ => {
+                globals.set_error(MonorubyErr::typeerr(
+                    "coerce must return [x, y]".to_string(),
+                ));
+                None
+            }
+        }
+    }
+
+    ///
+    /// Check whether *lhs* == *rhs*.
+    ///
+    /// `#==` is called for values which can not be compared natively.
+    ///
+    pub(crate) fn eq_values(
+        &mut self,
+        globals: &mut Globals,
+        lhs: Value,
+        rhs: Value,
+    ) -> Option<bool> {
+        let b = match (lhs.unpack(), rhs.unpack()) {
+            (RV::Integer(lhs), RV::Integer(rhs)) => lhs.eq(&rhs),
+            (RV::Integer(lhs), RV::BigInt(rhs)) => BigInt::from(lhs).eq(rhs),
+            (RV::Integer(lhs), RV::Float(rhs)) => (lhs as f64).eq(&rhs),
+            (RV::BigInt(lhs), RV::Integer(rhs)) => lhs.eq(&BigInt::from(rhs)),
+            (RV::BigInt(lhs), RV::BigInt(rhs)) => lhs.eq(rhs),
+            (RV::BigInt(lhs), RV::Float(rhs)) => lhs.to_f64().unwrap().eq(&rhs),
+            (RV::Float(lhs), RV::Integer(rhs)) => lhs.eq(&(rhs as f64)),
+            (RV::Float(lhs), RV::BigInt(rhs)) => lhs.eq(&(rhs.to_f64().unwrap())),
+            (RV::Float(lhs), RV::Float(rhs)) => lhs.eq(&rhs),
+            (RV::Bool(lhs), RV::Bool(rhs)) => lhs.eq(&rhs),
+            (RV::Nil, RV::Nil) => true,
+            (RV::Symbol(lhs), RV::Symbol(rhs)) => lhs.eq(&rhs),
+            (RV::String(lhs), RV::String(rhs)) => lhs.eq(rhs),
+            _ => {
+                return Some(
+                    self.invoke_method(globals, IdentId::_EQ, lhs, &[rhs])?
+                        .as_bool(),
+                )
+            }
+        };
+        Some(b)
+    }
+
+    ///
+    /// Compare *lhs* and *rhs*.
+    ///
+    /// `#<=>` is called for values which can not be compared natively.
+    /// Raise ArgumentError if they are not comparable.
+    ///
+    pub(crate) fn compare_values(
+        &mut self,
+        globals: &mut Globals,
+        lhs: Value,
+        rhs: Value,
+    ) -> Option<std::cmp::Ordering> {
+        let ord = match (lhs.unpack(), rhs.unpack()) {
+            (RV::Integer(lhs), RV::Integer(rhs)) => lhs.partial_cmp(&rhs),
+            (RV::Integer(lhs), RV::BigInt(rhs)) => BigInt::from(lhs).partial_cmp(rhs),
+            (RV::Integer(lhs), RV::Float(rhs)) => (lhs as f64).partial_cmp(&rhs),
+            (RV::BigInt(lhs), RV::Integer(rhs)) => lhs.partial_cmp(&BigInt::from(rhs)),
+            (RV::BigInt(lhs), RV::BigInt(rhs)) => lhs.partial_cmp(rhs),
+            (RV::BigInt(lhs), RV::Float(rhs)) => lhs.to_f64().unwrap().partial_cmp(&rhs),
+            (RV::Float(lhs), RV::Integer(rhs)) => lhs.partial_cmp(&(rhs as f64)),
+            (RV::Float(lhs), RV::BigInt(rhs)) => lhs.partial_cmp(&(rhs.to_f64().unwrap())),
+            (RV::Float(lhs), RV::Float(rhs)) => lhs.partial_cmp(&rhs),
+            (RV::String(lhs), RV::String(rhs)) => lhs.partial_cmp(rhs),
+            _ => match globals.find_method(lhs, IdentId::_CMP) {
+                Some(_) => self
+                    .invoke_method(globals, IdentId::_CMP, lhs, &[rhs])?
+                    .try_fixnum()
+                    .map(|i| i.cmp(&0)),
+                None => None,
+            },
+        };
+        match ord {
+            Some(ord) => Some(ord),
+            None => {
+                let rhs = match rhs.unpack() {
+                    RV::Nil | RV::Bool(_) | RV::Float(_) => globals.val_inspect(rhs),
+                    _ => rhs.get_real_class_name(globals),
+                };
+                let msg = format!(
+                    "comparison of {} with {} failed",
+                    lhs.get_real_class_name(globals),
+                    rhs
+                );
+                globals.err_argument(&msg);
+                None
+            }
+        }
+    }
 }
 
-eq_values!(eq, ne);
+///
+/// Split a numeric *val* into (real, imaginary). The imaginary part of a real number is None.
+///
+fn complex_parts(val: Value) -> Option<(Value, Option<Value>)> {
+    if let Some(complex) = val.is_complex() {
+        Some((complex.re, Some(complex.im)))
+    } else if val.is_numeric() || val.is_rational().is_some() {
+        Some((val, None))
+    } else {
+        None
+    }
+}
 
 pub(super) extern "C" fn neg_value(
     interp: &mut Executor,
@@ -504,6 +971,46 @@ pub extern "C" fn set_global_var(globals: &mut Globals, name: IdentId, val: Valu
     globals.set_gvar(name, val);
 }
 
+///
+/// Get special variable.
+///
+/// *id* is 0 for `$~`, and n + 1 for `$n` (`$&` for n = 0).
+///
+/// rax: Value
+///
+pub extern "C" fn get_special_var(vm: &mut Executor, globals: &mut Globals, id: u32) -> Value {
+    let last_match = vm.get_last_match(globals);
+    if id == 0 {
+        return last_match;
+    }
+    match last_match.is_match_data() {
+        Some(md) => match md.group(id as usize - 1) {
+            Some(bytes) => Value::new_string_from_slice(bytes),
+            None => Value::nil(),
+        },
+        None => Value::nil(),
+    }
+}
+
+///
+/// Raise an exception object *val*.
+///
+/// rax: None
+///
+pub extern "C" fn raise_err(globals: &mut Globals, val: Value) -> Option<Value> {
+    globals.raise_exception(val);
+    None
+}
+
+///
+/// Look up the jump table of a case expression.
+///
+/// rax: displacement to the matched clause (0 if not found)
+///
+pub extern "C" fn opt_case(globals: &Globals, cond: Value, id: OptCaseId) -> i64 {
+    globals.func[id].find(cond).unwrap_or(0) as i64
+}
+
 pub extern "C" fn define_method(
     interp: &mut Executor,
     globals: &mut Globals,
@@ -511,7 +1018,35 @@ pub extern "C" fn define_method(
     func: FuncId,
 ) {
     let parent = interp.get_class_context();
-    globals.add_method(parent, name, func);
+    let context = interp.class_context_stack().to_vec();
+    let info = globals.func[func].as_ruby_func_mut();
+    info.lexical_context = Some(context);
+    info.owner = Some(parent);
+    let (visibility, module_function) = interp.get_def_visibility();
+    globals.add_method_with_visibility(parent, name, func, visibility);
+    if module_function {
+        let singleton = globals.get_singleton_id(parent);
+        globals.add_method(singleton, name, func);
+    }
+}
+
+///
+/// Define a singleton method *func* with *name* on *obj*.
+///
+pub extern "C" fn define_singleton_method(
+    interp: &mut Executor,
+    globals: &mut Globals,
+    name: IdentId,
+    func: FuncId,
+    obj: Value,
+) -> Option<Value> {
+    let class_id = globals.get_singleton_class(obj)?;
+    let context = interp.class_context_stack().to_vec();
+    let info = globals.func[func].as_ruby_func_mut();
+    info.lexical_context = Some(context);
+    info.owner = Some(class_id);
+    globals.add_method(class_id, name, func);
+    Some(Value::nil())
 }
 
 pub extern "C" fn _dump_stacktrace(interp: &mut Executor, globals: &mut Globals) {
@@ -82,6 +82,10 @@ impl IdentId {
     pub const _BAND: IdentId = id!(33);
     pub const _BXOR: IdentId = id!(34);
     pub const _UMINUS: IdentId = id!(35);
+    pub const _MATCH: IdentId = id!(36);
+    pub const _MEMBERS: IdentId = id!(37);
+    pub const _KEYWORD_INIT: IdentId = id!(38);
+    pub const _RESPOND_TO_MISSING: IdentId = id!(39);
 }
 
 impl IdentId {
@@ -174,6 +178,10 @@ impl IdentifierTable {
         table.set_ident_id("&", IdentId::_BAND);
         table.set_ident_id("^", IdentId::_BXOR);
         table.set_ident_id("-@", IdentId::_UMINUS);
+        table.set_ident_id("=~", IdentId::_MATCH);
+        table.set_ident_id("/members", IdentId::_MEMBERS);
+        table.set_ident_id("/keyword_init", IdentId::_KEYWORD_INIT);
+        table.set_ident_id("respond_to_missing?", IdentId::_RESPOND_TO_MISSING);
         table
     }
 
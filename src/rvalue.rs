@@ -1,8 +1,17 @@
 use crate::*;
-use num::BigInt;
+use num::{BigInt, BigRational};
 use smallvec::SmallVec;
 use std::mem::ManuallyDrop;
 
+mod enumerator;
+mod hash;
+mod procedure;
+mod regexp;
+pub use enumerator::*;
+pub use hash::*;
+pub use procedure::*;
+pub use regexp::*;
+
 pub const OBJECT_INLINE_IVAR: usize = 6;
 
 /// Heap-allocated objects.
@@ -39,6 +48,15 @@ impl std::fmt::Debug for RValue {
                     7 => format!("TIME({:?})", self.kind.time),
                     8 => format!("ARRAY({:?})", self.kind.array),
                     9 => format!("RANGE({:?})", self.kind.range),
+                    10 => format!("EXCEPTION({:?})", self.kind.exception),
+                    11 => format!("HASH({:?})", self.kind.hash),
+                    12 => format!("PROC({:?})", self.kind.proc),
+                    13 => format!("REGEXP({:?})", self.kind.regexp.source()),
+                    14 => format!("MATCHDATA({:?})", self.kind.match_data),
+                    15 => format!("RATIONAL({:?})", self.kind.rational),
+                    16 => format!("COMPLEX({:?})", self.kind.complex),
+                    17 => format!("FIBER({:?})", self.kind.fiber),
+                    18 => format!("ENUMERATOR({:?})", self.kind.enumerator),
                     _ => unreachable!(),
                 }
             },
@@ -64,11 +82,71 @@ impl GC<RValue> for RValue {
                 }
             });
         }
+        match self.kind() {
+            ObjKind::INVALID => panic!("Invalid rvalue. (maybe GC problem) {:?}", &self),
+            // class objects are marked via ClassStore.
+            ObjKind::CLASS | ObjKind::MODULE => {}
+            ObjKind::OBJECT => self.as_object().iter().for_each(|v| {
+                if let Some(v) = v {
+                    v.mark(alloc)
+                }
+            }),
+            ObjKind::BIGNUM
+            | ObjKind::FLOAT
+            | ObjKind::BYTES
+            | ObjKind::TIME
+            | ObjKind::RATIONAL => {}
+            ObjKind::ARRAY => self.as_array().iter().for_each(|v| v.mark(alloc)),
+            ObjKind::RANGE => {
+                let range = self.as_range();
+                range.start.mark(alloc);
+                range.end.mark(alloc);
+            }
+            ObjKind::COMPLEX => {
+                let complex = self.as_complex();
+                complex.re.mark(alloc);
+                complex.im.mark(alloc);
+            }
+            ObjKind::EXCEPTION => self.as_exception().mark(alloc),
+            ObjKind::HASH => self.as_hash().mark(alloc),
+            ObjKind::PROC => self.as_proc().mark(alloc),
+            ObjKind::REGEXP => {}
+            ObjKind::MATCHDATA => self.as_match_data().mark(alloc),
+            ObjKind::FIBER => self.as_fiber().mark(alloc),
+            ObjKind::ENUMERATOR => self.as_enumerator().mark(alloc),
+            _ => unreachable!("mark()"),
+        }
     }
 }
 
 impl GCBox for RValue {
-    fn free(&mut self) {}
+    fn free(&mut self) {
+        if !self.is_valid() {
+            // already freed.
+            return;
+        }
+        unsafe {
+            match self.kind() {
+                ObjKind::BIGNUM => ManuallyDrop::drop(&mut self.kind.bignum),
+                ObjKind::BYTES => ManuallyDrop::drop(&mut self.kind.string),
+                ObjKind::TIME => ManuallyDrop::drop(&mut self.kind.time),
+                ObjKind::ARRAY => ManuallyDrop::drop(&mut self.kind.array),
+                ObjKind::RANGE => ManuallyDrop::drop(&mut self.kind.range),
+                ObjKind::EXCEPTION => ManuallyDrop::drop(&mut self.kind.exception),
+                ObjKind::HASH => ManuallyDrop::drop(&mut self.kind.hash),
+                ObjKind::PROC => ManuallyDrop::drop(&mut self.kind.proc),
+                ObjKind::REGEXP => ManuallyDrop::drop(&mut self.kind.regexp),
+                ObjKind::MATCHDATA => ManuallyDrop::drop(&mut self.kind.match_data),
+                ObjKind::RATIONAL => ManuallyDrop::drop(&mut self.kind.rational),
+                ObjKind::COMPLEX => ManuallyDrop::drop(&mut self.kind.complex),
+                ObjKind::FIBER => ManuallyDrop::drop(&mut self.kind.fiber),
+                ObjKind::ENUMERATOR => ManuallyDrop::drop(&mut self.kind.enumerator),
+                _ => {}
+            }
+        }
+        self.var_table = None;
+        self.flags = RVFlag { next: None };
+    }
 
     fn next(&self) -> Option<std::ptr::NonNull<RValue>> {
         let next = unsafe { self.flags.next };
@@ -98,6 +176,13 @@ impl RValue {
         self as *const RValue as u64
     }
 
+    ///
+    /// Check whether `self` is a live object (not in the free list).
+    ///
+    pub(crate) fn is_valid(&self) -> bool {
+        unsafe { self.flags.flag.flag & 0b1 == 1 }
+    }
+
     pub(crate) fn class(&self) -> ClassId {
         self.flags.class()
     }
@@ -110,6 +195,14 @@ impl RValue {
         self.flags.change_class(new_class_id);
     }
 
+    pub(crate) fn is_frozen(&self) -> bool {
+        self.flags.is_frozen()
+    }
+
+    pub(crate) fn set_frozen(&mut self) {
+        self.flags.set_frozen();
+    }
+
     pub(crate) fn deep_copy(&self) -> Self {
         RValue {
             flags: self.flags,
@@ -124,7 +217,7 @@ impl RValue {
             },
             kind: match self.kind() {
                 ObjKind::INVALID => panic!("Invalid rvalue. (maybe GC problem) {:?}", &self),
-                ObjKind::CLASS => ObjKind::class(self.as_class()),
+                ObjKind::CLASS | ObjKind::MODULE => ObjKind::class(self.as_class()),
                 ObjKind::OBJECT => ObjKind::object(),
                 ObjKind::BIGNUM => ObjKind::bignum(self.as_bignum().clone()),
                 ObjKind::FLOAT => ObjKind {
@@ -146,6 +239,24 @@ impl RValue {
                         lhs.exclude_end != 0,
                     )
                 }
+                ObjKind::EXCEPTION => ObjKind::exception(self.as_exception().clone()),
+                ObjKind::HASH => {
+                    // keys are shared, since hash values of copied keys must not change.
+                    let mut hash = self.as_hash().clone();
+                    hash.deep_copy_values();
+                    ObjKind::hash(hash)
+                }
+                ObjKind::PROC => ObjKind::proc(self.as_proc().clone()),
+                ObjKind::REGEXP => ObjKind::regexp(self.as_regexp().clone()),
+                ObjKind::MATCHDATA => ObjKind::match_data(self.as_match_data().clone()),
+                ObjKind::RATIONAL => ObjKind::rational(self.as_rational().clone()),
+                ObjKind::COMPLEX => {
+                    let lhs = self.as_complex();
+                    ObjKind::complex(Value::deep_copy(lhs.re), Value::deep_copy(lhs.im))
+                }
+                // a copy of a Fiber starts from the beginning of the body.
+                ObjKind::FIBER => ObjKind::fiber(FiberInner::new(self.as_fiber().body())),
+                ObjKind::ENUMERATOR => ObjKind::enumerator(self.as_enumerator().dup()),
                 _ => unreachable!("clone()"),
             },
         }
@@ -233,6 +344,17 @@ impl RValue {
         }
     }
 
+    ///
+    /// Create new module object with *class_id*.
+    ///
+    pub(crate) fn new_module(id: ClassId) -> Self {
+        RValue {
+            flags: RVFlag::new(MODULE_CLASS, ObjKind::MODULE),
+            kind: ObjKind::class(id),
+            var_table: None,
+        }
+    }
+
     ///
     /// Create new instance object of class *class_id*.
     ///
@@ -264,6 +386,14 @@ impl RValue {
         }
     }
 
+    pub(crate) fn new_string_from_inner(inner: StringInner) -> Self {
+        RValue {
+            flags: RVFlag::new(STRING_CLASS, ObjKind::BYTES),
+            kind: ObjKind::string(inner),
+            var_table: None,
+        }
+    }
+
     pub(crate) fn new_array(ary: ArrayInner) -> Self {
         RValue {
             flags: RVFlag::new(ARRAY_CLASS, ObjKind::ARRAY),
@@ -295,6 +425,82 @@ impl RValue {
             var_table: None,
         }
     }
+
+    pub(crate) fn new_exception(class_id: ClassId, err: MonorubyErr) -> Self {
+        RValue {
+            flags: RVFlag::new(class_id, ObjKind::EXCEPTION),
+            kind: ObjKind::exception(err),
+            var_table: None,
+        }
+    }
+
+    pub(crate) fn new_hash(hash: HashInner) -> Self {
+        Self::new_hash_with_class(hash, HASH_CLASS)
+    }
+
+    pub(crate) fn new_hash_with_class(hash: HashInner, class_id: ClassId) -> Self {
+        RValue {
+            flags: RVFlag::new(class_id, ObjKind::HASH),
+            kind: ObjKind::hash(hash),
+            var_table: None,
+        }
+    }
+
+    pub(crate) fn new_proc(proc: ProcInner) -> Self {
+        RValue {
+            flags: RVFlag::new(PROC_CLASS, ObjKind::PROC),
+            kind: ObjKind::proc(proc),
+            var_table: None,
+        }
+    }
+
+    pub(crate) fn new_regexp(regexp: RegexpInner) -> Self {
+        RValue {
+            flags: RVFlag::new(REGEXP_CLASS, ObjKind::REGEXP),
+            kind: ObjKind::regexp(regexp),
+            var_table: None,
+        }
+    }
+
+    pub(crate) fn new_match_data(match_data: MatchDataInner) -> Self {
+        RValue {
+            flags: RVFlag::new(MATCHDATA_CLASS, ObjKind::MATCHDATA),
+            kind: ObjKind::match_data(match_data),
+            var_table: None,
+        }
+    }
+
+    pub(crate) fn new_rational(rational: BigRational) -> Self {
+        RValue {
+            flags: RVFlag::new(RATIONAL_CLASS, ObjKind::RATIONAL),
+            kind: ObjKind::rational(rational),
+            var_table: None,
+        }
+    }
+
+    pub(crate) fn new_complex(re: Value, im: Value) -> Self {
+        RValue {
+            flags: RVFlag::new(COMPLEX_CLASS, ObjKind::COMPLEX),
+            kind: ObjKind::complex(re, im),
+            var_table: None,
+        }
+    }
+
+    pub(crate) fn new_fiber(fiber: FiberInner) -> Self {
+        RValue {
+            flags: RVFlag::new(FIBER_CLASS, ObjKind::FIBER),
+            kind: ObjKind::fiber(fiber),
+            var_table: None,
+        }
+    }
+
+    pub(crate) fn new_enumerator(enumerator: EnumeratorInner, class_id: ClassId) -> Self {
+        RValue {
+            flags: RVFlag::new(class_id, ObjKind::ENUMERATOR),
+            kind: ObjKind::enumerator(enumerator),
+            var_table: None,
+        }
+    }
 }
 
 impl RValue {
@@ -326,9 +532,13 @@ impl RValue {
         unsafe { String::from_utf8_lossy(&self.kind.string.0).to_string() }
     }
 
-    /*pub(crate) fn as_string_mut(&mut self) -> &mut InnerVec {
-        unsafe { &mut *self.kind.bytes }
-    }*/
+    pub(crate) fn as_string_inner(&self) -> &StringInner {
+        unsafe { &self.kind.string }
+    }
+
+    pub(crate) fn as_string_inner_mut(&mut self) -> &mut StringInner {
+        unsafe { &mut self.kind.string }
+    }
 
     pub(crate) fn as_array(&self) -> &ArrayInner {
         unsafe { &self.kind.array }
@@ -346,6 +556,62 @@ impl RValue {
         unsafe { &self.kind.time }
     }
 
+    pub(crate) fn as_exception(&self) -> &MonorubyErr {
+        unsafe { &self.kind.exception }
+    }
+
+    pub(crate) fn as_exception_mut(&mut self) -> &mut MonorubyErr {
+        unsafe { &mut self.kind.exception }
+    }
+
+    pub(crate) fn as_hash(&self) -> &HashInner {
+        unsafe { &self.kind.hash }
+    }
+
+    pub(crate) fn as_hash_mut(&mut self) -> &mut HashInner {
+        unsafe { &mut self.kind.hash }
+    }
+
+    pub(crate) fn as_proc(&self) -> &ProcInner {
+        unsafe { &self.kind.proc }
+    }
+
+    pub(crate) fn as_proc_mut(&mut self) -> &mut ProcInner {
+        unsafe { &mut self.kind.proc }
+    }
+
+    pub(crate) fn as_regexp(&self) -> &RegexpInner {
+        unsafe { &self.kind.regexp }
+    }
+
+    pub(crate) fn as_fiber(&self) -> &FiberInner {
+        unsafe { &self.kind.fiber }
+    }
+
+    pub(crate) fn as_fiber_mut(&mut self) -> &mut FiberInner {
+        unsafe { &mut self.kind.fiber }
+    }
+
+    pub(crate) fn as_enumerator(&self) -> &EnumeratorInner {
+        unsafe { &self.kind.enumerator }
+    }
+
+    pub(crate) fn as_enumerator_mut(&mut self) -> &mut EnumeratorInner {
+        unsafe { &mut self.kind.enumerator }
+    }
+
+    pub(crate) fn as_match_data(&self) -> &MatchDataInner {
+        unsafe { &self.kind.match_data }
+    }
+
+    pub(crate) fn as_rational(&self) -> &BigRational {
+        unsafe { &self.kind.rational }
+    }
+
+    pub(crate) fn as_complex(&self) -> &ComplexInner {
+        unsafe { &self.kind.complex }
+    }
+
     /*pub(crate) fn as_time_mut(&mut self) -> &mut TimeInfo {
         unsafe { &mut *self.kind.time }
     }*/
@@ -356,8 +622,15 @@ impl RValue {
     ///
     /// This method consumes `self` and allocates it on the heap, returning `Value`,
     /// a wrapped raw pointer.  
+    ///
+    /// This is a safe point of GC.
     pub(crate) fn pack(self) -> Value {
-        let ptr = ALLOC.with(|alloc| alloc.borrow_mut().alloc(self));
+        let ptr = ALLOC.with(|alloc| {
+            let mut alloc = alloc.borrow_mut();
+            let ptr = alloc.alloc(self);
+            alloc.check_gc_on_alloc(ptr);
+            ptr
+        });
         Value::from_ptr(ptr)
     }
 }
@@ -401,6 +674,14 @@ impl RVFlag {
     fn change_class(&mut self, class: ClassId) {
         self.flag.class = class;
     }
+
+    fn is_frozen(&self) -> bool {
+        unsafe { self.flag.flag & 0b10 != 0 }
+    }
+
+    fn set_frozen(&mut self) {
+        unsafe { self.flag.flag |= 0b10 };
+    }
 }
 
 #[repr(C)]
@@ -414,12 +695,21 @@ pub union ObjKind {
     pub time: ManuallyDrop<TimeInfo>,
     pub array: ManuallyDrop<ArrayInner>,
     pub range: ManuallyDrop<Range>,
+    pub exception: ManuallyDrop<Box<MonorubyErr>>,
+    pub hash: ManuallyDrop<Box<HashInner>>,
+    pub proc: ManuallyDrop<Box<ProcInner>>,
+    pub regexp: ManuallyDrop<Box<RegexpInner>>,
+    pub match_data: ManuallyDrop<Box<MatchDataInner>>,
+    pub rational: ManuallyDrop<Box<BigRational>>,
+    pub complex: ManuallyDrop<ComplexInner>,
+    pub fiber: ManuallyDrop<Box<FiberInner>>,
+    pub enumerator: ManuallyDrop<Box<EnumeratorInner>>,
 }
 
 impl ObjKind {
     pub const INVALID: u8 = 0;
     pub const CLASS: u8 = 1;
-    //pub const MODULE: u8 = 2;
+    pub const MODULE: u8 = 2;
     pub const OBJECT: u8 = 3;
     pub const BIGNUM: u8 = 4;
     pub const FLOAT: u8 = 5;
@@ -427,12 +717,159 @@ impl ObjKind {
     pub const TIME: u8 = 7;
     pub const ARRAY: u8 = 8;
     pub const RANGE: u8 = 9;
+    pub const EXCEPTION: u8 = 10;
+    pub const HASH: u8 = 11;
+    pub const PROC: u8 = 12;
+    pub const REGEXP: u8 = 13;
+    pub const MATCHDATA: u8 = 14;
+    pub const RATIONAL: u8 = 15;
+    pub const COMPLEX: u8 = 16;
+    pub const FIBER: u8 = 17;
+    pub const ENUMERATOR: u8 = 18;
 }
 
-#[derive(Clone)]
+#[derive(Clone, Default)]
 #[repr(transparent)]
 pub struct StringInner(SmallVec<[u8; STRING_INLINE_CAP]>);
 
+impl std::ops::Deref for StringInner {
+    type Target = [u8];
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl StringInner {
+    pub fn from_slice(slice: &[u8]) -> Self {
+        StringInner(SmallVec::from_slice(slice))
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        StringInner(SmallVec::with_capacity(capacity))
+    }
+
+    pub fn push(&mut self, byte: u8) {
+        self.0.push(byte);
+    }
+
+    pub fn extend_from_slice(&mut self, slice: &[u8]) {
+        self.0.extend_from_slice(slice);
+    }
+
+    pub fn push_char(&mut self, ch: char) {
+        let mut buf = [0u8; 4];
+        self.extend_from_slice(ch.encode_utf8(&mut buf).as_bytes());
+    }
+
+    ///
+    /// Iterate over the characters in `self` as byte slices.
+    ///
+    /// A byte which is not a part of a valid UTF-8 sequence is treated as a single character.
+    ///
+    pub fn chars(&self) -> CharSlices<'_> {
+        CharSlices(&self.0)
+    }
+
+    ///
+    /// Get the character which starts at the byte index *byte_pos*.
+    ///
+    pub fn char_at(&self, byte_pos: usize) -> Option<&[u8]> {
+        CharSlices(self.0.get(byte_pos..)?).next()
+    }
+
+    ///
+    /// The number of characters in `self`.
+    ///
+    pub fn char_length(&self) -> usize {
+        self.chars().count()
+    }
+
+    ///
+    /// Convert the character index *char_pos* to the byte index.
+    ///
+    /// Returns None if *char_pos* is beyond the end of `self`.
+    ///
+    pub fn byte_index(&self, char_pos: usize) -> Option<usize> {
+        let mut byte_pos = 0;
+        let mut chars = self.chars();
+        for _ in 0..char_pos {
+            byte_pos += chars.next()?.len();
+        }
+        Some(byte_pos)
+    }
+
+    ///
+    /// Convert the byte index *byte_pos* to the character index.
+    ///
+    pub fn char_index(&self, byte_pos: usize) -> usize {
+        CharSlices(&self.0[..byte_pos]).count()
+    }
+
+    ///
+    /// Get the substring of `self` from the character index *start* with *len* characters.
+    ///
+    /// A negative *start* counts from the end of `self`.
+    /// Returns None if *start* is out of range or *len* is negative.
+    ///
+    pub fn substr(&self, start: i64, len: i64) -> Option<&[u8]> {
+        let char_len = self.char_length() as i64;
+        let start = if start < 0 { start + char_len } else { start };
+        if start < 0 || start > char_len || len < 0 {
+            return None;
+        }
+        let end = std::cmp::min(start.saturating_add(len), char_len);
+        let start = self.byte_index(start as usize).unwrap();
+        let end = self.byte_index(end as usize).unwrap();
+        Some(&self.0[start..end])
+    }
+
+    ///
+    /// Find the first occurence of *pat* in `self` at or after the byte index *from*,
+    /// and return its byte index.
+    ///
+    pub fn find(&self, pat: &[u8], from: usize) -> Option<usize> {
+        if from > self.len() {
+            return None;
+        }
+        if pat.is_empty() {
+            return Some(from);
+        }
+        self.0[from..]
+            .windows(pat.len())
+            .position(|w| w == pat)
+            .map(|pos| pos + from)
+    }
+}
+
+///
+/// An iterator over the characters of a byte string.
+///
+#[derive(Clone)]
+pub struct CharSlices<'a>(&'a [u8]);
+
+impl<'a> Iterator for CharSlices<'a> {
+    type Item = &'a [u8];
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.0.is_empty() {
+            return None;
+        }
+        let len = match self.0[0] {
+            0xc2..=0xdf => 2,
+            0xe0..=0xef => 3,
+            0xf0..=0xf4 => 4,
+            _ => 1,
+        };
+        let len = if len > self.0.len() || self.0[1..len].iter().any(|b| b & 0xc0 != 0x80) {
+            1
+        } else {
+            len
+        };
+        let (ch, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Some(ch)
+    }
+}
+
 #[derive(Debug, PartialEq)]
 #[repr(C)]
 pub struct Range {
@@ -447,6 +884,16 @@ impl Range {
     }
 }
 
+///
+/// A complex number. Both parts are real numerics (Integer, Float or Rational).
+///
+#[derive(Debug)]
+#[repr(C)]
+pub struct ComplexInner {
+    pub re: Value,
+    pub im: Value,
+}
+
 impl ObjKind {
     fn invalid() -> Self {
         Self { invalid: () }
@@ -478,6 +925,12 @@ impl ObjKind {
         }
     }
 
+    fn string(inner: StringInner) -> Self {
+        Self {
+            string: ManuallyDrop::new(inner),
+        }
+    }
+
     fn bytes_from_vec(vec: Vec<u8>) -> Self {
         Self {
             string: ManuallyDrop::new(StringInner(SmallVec::from_vec(vec))),
@@ -505,6 +958,60 @@ impl ObjKind {
             time: ManuallyDrop::new(info),
         }
     }
+
+    fn exception(err: MonorubyErr) -> Self {
+        Self {
+            exception: ManuallyDrop::new(Box::new(err)),
+        }
+    }
+
+    fn hash(hash: HashInner) -> Self {
+        Self {
+            hash: ManuallyDrop::new(Box::new(hash)),
+        }
+    }
+
+    fn proc(proc: ProcInner) -> Self {
+        Self {
+            proc: ManuallyDrop::new(Box::new(proc)),
+        }
+    }
+
+    fn regexp(regexp: RegexpInner) -> Self {
+        Self {
+            regexp: ManuallyDrop::new(Box::new(regexp)),
+        }
+    }
+
+    fn match_data(match_data: MatchDataInner) -> Self {
+        Self {
+            match_data: ManuallyDrop::new(Box::new(match_data)),
+        }
+    }
+
+    fn rational(rational: BigRational) -> Self {
+        Self {
+            rational: ManuallyDrop::new(Box::new(rational)),
+        }
+    }
+
+    fn complex(re: Value, im: Value) -> Self {
+        Self {
+            complex: ManuallyDrop::new(ComplexInner { re, im }),
+        }
+    }
+
+    fn fiber(fiber: FiberInner) -> Self {
+        Self {
+            fiber: ManuallyDrop::new(Box::new(fiber)),
+        }
+    }
+
+    fn enumerator(enumerator: EnumeratorInner) -> Self {
+        Self {
+            enumerator: ManuallyDrop::new(Box::new(enumerator)),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -536,11 +1043,11 @@ impl ArrayInner {
         self.0.push(value);
     }
 
-    /*pub fn pop(&mut self) -> Option<Value> {
+    pub fn pop(&mut self) -> Option<Value> {
         self.0.pop()
     }
 
-    pub fn truncate(&mut self, new_len: usize) {
+    /*pub fn truncate(&mut self, new_len: usize) {
         self.0.truncate(new_len);
     }
 
@@ -556,9 +1063,17 @@ impl ArrayInner {
         self.0.extend_from_slice(slice);
     }
 
-    /*pub fn drain(&mut self, range: std::ops::Range<usize>) -> Vec<Value> {
+    pub fn drain(&mut self, range: std::ops::Range<usize>) -> Vec<Value> {
         self.0.drain(range).collect()
-    }*/
+    }
+
+    pub fn insert_slice(&mut self, idx: usize, slice: &[Value]) {
+        self.0.splice(idx..idx, slice.iter().cloned());
+    }
+
+    pub fn retain(&mut self, f: impl FnMut(&Value) -> bool) {
+        self.0.retain(f);
+    }
 }
 
 impl ArrayInner {
@@ -585,6 +1100,21 @@ impl ArrayInner {
         return Some(src);
     }
 
+    ///
+    /// Get the elements of *len* from *start*.
+    ///
+    /// Return nil if *start* is out of range.
+    ///
+    pub fn get_elements(&self, start: i64, len: i64) -> Value {
+        let ary_len = self.len() as i64;
+        let start = if start < 0 { start + ary_len } else { start };
+        if start < 0 || start > ary_len || len < 0 {
+            return Value::nil();
+        }
+        let end = std::cmp::min(start.saturating_add(len), ary_len);
+        Value::new_array_from_vec(self[start as usize..end as usize].to_vec())
+    }
+
     pub fn get_index(&self, idx: i64) -> Option<Value> {
         return Some(if idx >= 0 {
             self.get(idx as usize).cloned().unwrap_or_default()
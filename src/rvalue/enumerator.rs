@@ -0,0 +1,112 @@
+use crate::*;
+
+///
+/// Enumerator object.
+///
+/// An Enumerator iterates by calling *method* of *obj* with *args*. For the Enumerators
+/// created by `Enumerator.new`, *obj* is the generator block, which is called with
+/// an Enumerator::Yielder.
+///
+/// External iteration (`next`, `peek`) runs the iteration in a Fiber, which is suspended
+/// every time a value is yielded.
+///
+#[derive(Debug)]
+pub struct EnumeratorInner {
+    pub(crate) obj: Value,
+    pub(crate) method: IdentId,
+    pub(crate) args: Vec<Value>,
+    /// true if *obj* is the generator block given to `Enumerator.new`.
+    pub(crate) is_generator: bool,
+    /// the size given to `Enumerator.new`.
+    pub(crate) size: Option<Value>,
+    /// operations applied to the yielded values by Enumerator::Lazy.
+    pub(crate) lazy_ops: Vec<LazyOp>,
+    /// the Fiber running the external iteration.
+    pub(crate) fiber: Option<Value>,
+    /// the value fetched in advance by `peek`.
+    pub(crate) peeked: Option<Value>,
+}
+
+///
+/// An operation of Enumerator::Lazy, which holds the block as a Proc.
+///
+#[derive(Debug, Clone, Copy)]
+pub enum LazyOp {
+    Map(Value),
+    Select(Value),
+    Reject(Value),
+    TakeWhile(Value),
+    Take(usize),
+}
+
+impl GC<RValue> for EnumeratorInner {
+    fn mark(&self, alloc: &mut Allocator<RValue>) {
+        self.obj.mark(alloc);
+        self.args.iter().for_each(|v| v.mark(alloc));
+        if let Some(size) = self.size {
+            size.mark(alloc);
+        }
+        for op in &self.lazy_ops {
+            match op {
+                LazyOp::Map(proc)
+                | LazyOp::Select(proc)
+                | LazyOp::Reject(proc)
+                | LazyOp::TakeWhile(proc) => proc.mark(alloc),
+                LazyOp::Take(_) => {}
+            }
+        }
+        if let Some(fiber) = self.fiber {
+            fiber.mark(alloc);
+        }
+        if let Some(peeked) = self.peeked {
+            peeked.mark(alloc);
+        }
+    }
+}
+
+impl EnumeratorInner {
+    pub(crate) fn new(obj: Value, method: IdentId, args: Vec<Value>) -> Self {
+        Self {
+            obj,
+            method,
+            args,
+            is_generator: false,
+            size: None,
+            lazy_ops: vec![],
+            fiber: None,
+            peeked: None,
+        }
+    }
+
+    pub(crate) fn new_generator(proc: Value, size: Option<Value>) -> Self {
+        Self {
+            is_generator: true,
+            size,
+            ..Self::new(proc, IdentId::EACH, vec![])
+        }
+    }
+
+    ///
+    /// Duplicate this Enumerator. The state of the external iteration is not copied.
+    ///
+    pub(crate) fn dup(&self) -> Self {
+        Self {
+            obj: self.obj,
+            method: self.method,
+            args: self.args.clone(),
+            is_generator: self.is_generator,
+            size: self.size,
+            lazy_ops: self.lazy_ops.clone(),
+            fiber: None,
+            peeked: None,
+        }
+    }
+
+    ///
+    /// Reset the state of the external iteration.
+    ///
+    pub(crate) fn rewind(&mut self) {
+        self.fiber = None;
+        self.peeked = None;
+    }
+}
@@ -0,0 +1,404 @@
+use crate::*;
+use fxhash::FxHasher;
+use smallvec::SmallVec;
+use std::hash::{Hash, Hasher};
+
+///
+/// Insertion-ordered hash table keyed by `Value`.
+///
+/// Keys of builtin types (immediates, String, Bignum, Float, Array and Range) are
+/// hashed and compared natively. Keys of other objects use their `#hash` and `#eql?`.
+///
+#[derive(Debug, Clone, Default)]
+pub struct HashInner {
+    /// (hash, key, value) in insertion order. `None` is a deleted entry.
+    entries: Vec<Option<(u64, Value, Value)>>,
+    /// map from a hash value to indices of *entries*.
+    index: HashMap<u64, SmallVec<[usize; 1]>>,
+    /// the number of live entries.
+    len: usize,
+    /// the value returned for absent keys.
+    default: Value,
+    /// the Proc called for absent keys with the hash and the key.
+    default_proc: Option<Value>,
+    /// true if the hash holds keyword arguments of a method call.
+    keyword: bool,
+}
+
+impl GC<RValue> for HashInner {
+    fn mark(&self, alloc: &mut Allocator<RValue>) {
+        self.iter().for_each(|(k, v)| {
+            k.mark(alloc);
+            v.mark(alloc);
+        });
+        self.default.mark(alloc);
+        if let Some(proc) = self.default_proc {
+            proc.mark(alloc);
+        }
+    }
+}
+
+impl HashInner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Value, Value)> + '_ {
+        self.entries
+            .iter()
+            .filter_map(|e| e.map(|(_, k, v)| (k, v)))
+    }
+
+    pub fn keys(&self) -> Vec<Value> {
+        self.iter().map(|(k, _)| k).collect()
+    }
+
+    pub fn values(&self) -> Vec<Value> {
+        self.iter().map(|(_, v)| v).collect()
+    }
+
+    pub fn default(&self) -> Value {
+        self.default
+    }
+
+    pub fn set_default(&mut self, default: Value) {
+        self.default = default;
+        self.default_proc = None;
+    }
+
+    pub fn set_default_proc(&mut self, proc: Value) {
+        self.default = Value::nil();
+        self.default_proc = Some(proc);
+    }
+
+    pub fn is_keyword(&self) -> bool {
+        self.keyword
+    }
+
+    pub fn set_keyword(&mut self) {
+        self.keyword = true;
+    }
+
+    pub fn clear_keyword(&mut self) {
+        self.keyword = false;
+    }
+
+    ///
+    /// Get the value for *key*, or None if *key* is not found.
+    ///
+    /// Return None on error.
+    ///
+    pub fn get(
+        &self,
+        vm: &mut Executor,
+        globals: &mut Globals,
+        key: Value,
+    ) -> Option<Option<Value>> {
+        let hash = Self::hash_key(vm, globals, key)?;
+        let res = self
+            .find(vm, globals, hash, key)?
+            .map(|i| self.entries[i].unwrap().2);
+        Some(res)
+    }
+
+    ///
+    /// Get the value for *key* of the Hash *hash*, or the default value if *key* is not found.
+    ///
+    /// If *hash* has a default proc, the result of the proc called with *hash* and *key* is
+    /// returned for an absent key.
+    ///
+    pub fn index(
+        hash: Value,
+        vm: &mut Executor,
+        globals: &mut Globals,
+        key: Value,
+    ) -> Option<Value> {
+        let inner = hash.as_hash();
+        if let Some(val) = inner.get(vm, globals, key)? {
+            return Some(val);
+        }
+        match inner.default_proc {
+            Some(proc) => vm.invoke_block(globals, proc, hash, &[hash, key]),
+            None => Some(inner.default),
+        }
+    }
+
+    pub fn insert(
+        &mut self,
+        vm: &mut Executor,
+        globals: &mut Globals,
+        key: Value,
+        val: Value,
+    ) -> Option<()> {
+        let hash = Self::hash_key(vm, globals, key)?;
+        match self.find(vm, globals, hash, key)? {
+            Some(i) => self.entries[i].as_mut().unwrap().2 = val,
+            None => {
+                // a String key is copied so that later changes to it do not affect the hash.
+                let key = match key.unpack() {
+                    RV::String(s) => Value::new_string_from_slice(s),
+                    _ => key,
+                };
+                self.push(hash, key, val);
+            }
+        }
+        Some(())
+    }
+
+    ///
+    /// Remove *key* and return its value, or None if *key* is not found.
+    ///
+    /// Return None on error.
+    ///
+    pub fn remove(
+        &mut self,
+        vm: &mut Executor,
+        globals: &mut Globals,
+        key: Value,
+    ) -> Option<Option<Value>> {
+        let hash = Self::hash_key(vm, globals, key)?;
+        let i = match self.find(vm, globals, hash, key)? {
+            Some(i) => i,
+            None => return Some(None),
+        };
+        let (_, _, val) = self.entries[i].take().unwrap();
+        let bucket = self.index.get_mut(&hash).unwrap();
+        bucket.retain(|j| *j != i);
+        if bucket.is_empty() {
+            self.index.remove(&hash);
+        }
+        self.len -= 1;
+        if self.entries.len() > self.len * 2 + 8 {
+            self.compact();
+        }
+        Some(Some(val))
+    }
+
+    pub(crate) fn deep_copy_values(&mut self) {
+        self.entries
+            .iter_mut()
+            .flatten()
+            .for_each(|(_, _, v)| *v = Value::deep_copy(*v));
+    }
+
+    fn push(&mut self, hash: u64, key: Value, val: Value) {
+        let i = self.entries.len();
+        self.entries.push(Some((hash, key, val)));
+        self.index.entry(hash).or_default().push(i);
+        self.len += 1;
+    }
+
+    fn find(
+        &self,
+        vm: &mut Executor,
+        globals: &mut Globals,
+        hash: u64,
+        key: Value,
+    ) -> Option<Option<usize>> {
+        let candidates = match self.index.get(&hash) {
+            Some(bucket) => bucket.clone(),
+            None => return Some(None),
+        };
+        for i in candidates {
+            let (_, k, _) = self.entries[i].unwrap();
+            if Self::eql(vm, globals, k, key)? {
+                return Some(Some(i));
+            }
+        }
+        Some(None)
+    }
+
+    /// Remove deleted entries and rebuild the index.
+    fn compact(&mut self) {
+        let entries = std::mem::take(&mut self.entries);
+        self.index.clear();
+        self.len = 0;
+        for (hash, key, val) in entries.into_iter().flatten() {
+            self.push(hash, key, val);
+        }
+    }
+}
+
+impl HashInner {
+    ///
+    /// Calculate the hash value of *key*.
+    ///
+    /// `#hash` is called for keys which can not be hashed natively.
+    ///
+    fn hash_key(vm: &mut Executor, globals: &mut Globals, key: Value) -> Option<u64> {
+        if let Some(hash) = Self::native_hash(key) {
+            return Some(hash);
+        }
+        if let Some(ary) = key.is_array() {
+            let mut hasher = FxHasher::default();
+            for elem in ary.to_vec() {
+                Self::hash_key(vm, globals, elem)?.hash(&mut hasher);
+            }
+            return Some(hasher.finish());
+        }
+        let hash = vm.invoke_method(globals, IdentId::get_ident_id("hash"), key, &[])?;
+        Some(match hash.unpack() {
+            RV::Integer(i) => i as u64,
+            RV::BigInt(b) => {
+                let mut hasher = FxHasher::default();
+                b.hash(&mut hasher);
+                hasher.finish()
+            }
+            _ => hash.get(),
+        })
+    }
+
+    ///
+    /// Check whether *lhs* and *rhs* are the same key.
+    ///
+    /// `#eql?` is called for keys which can not be compared natively.
+    ///
+    fn eql(vm: &mut Executor, globals: &mut Globals, lhs: Value, rhs: Value) -> Option<bool> {
+        if let Some(b) = Self::native_eql(lhs, rhs) {
+            return Some(b);
+        }
+        if let (Some(l), Some(r)) = (lhs.is_array(), rhs.is_array()) {
+            if l.len() != r.len() {
+                return Some(false);
+            }
+            for (l, r) in l.to_vec().into_iter().zip(r.to_vec()) {
+                if !Self::eql(vm, globals, l, r)? {
+                    return Some(false);
+                }
+            }
+            return Some(true);
+        }
+        let res = vm.invoke_method(globals, IdentId::get_ident_id("eql?"), lhs, &[rhs])?;
+        Some(res.as_bool())
+    }
+
+    ///
+    /// Calculate the hash value of *key* without calling Ruby methods.
+    ///
+    /// Return None if *key* needs `#hash` to be called.
+    ///
+    pub(crate) fn native_hash(key: Value) -> Option<u64> {
+        let mut hasher = FxHasher::default();
+        Self::native_hash_into(key, &mut hasher)?;
+        Some(hasher.finish())
+    }
+
+    fn native_hash_into(key: Value, hasher: &mut FxHasher) -> Option<()> {
+        match key.unpack() {
+            RV::BigInt(b) => b.hash(hasher),
+            // 0.0 and -0.0 are the same key.
+            RV::Float(f) => (if f == 0.0 { 0.0f64 } else { f }).to_bits().hash(hasher),
+            RV::String(s) => s.hash(hasher),
+            RV::Object(rv) => match rv.kind() {
+                ObjKind::CLASS | ObjKind::MODULE => key.get().hash(hasher),
+                ObjKind::ARRAY => {
+                    let ary = rv.as_array();
+                    ary.len().hash(hasher);
+                    for elem in ary.iter() {
+                        Self::native_hash_into(*elem, hasher)?;
+                    }
+                }
+                ObjKind::RANGE => {
+                    let range = rv.as_range();
+                    Self::native_hash_into(range.start, hasher)?;
+                    Self::native_hash_into(range.end, hasher)?;
+                    range.exclude_end.hash(hasher);
+                }
+                ObjKind::RATIONAL => rv.as_rational().hash(hasher),
+                ObjKind::COMPLEX => {
+                    let complex = rv.as_complex();
+                    Self::native_hash_into(complex.re, hasher)?;
+                    Self::native_hash_into(complex.im, hasher)?;
+                }
+                _ => return None,
+            },
+            _ => key.get().hash(hasher),
+        }
+        Some(())
+    }
+
+    ///
+    /// Check whether *lhs* and *rhs* are the same key without calling Ruby methods.
+    ///
+    /// Return None if `#eql?` needs to be called.
+    ///
+    pub(crate) fn native_eql(lhs: Value, rhs: Value) -> Option<bool> {
+        if lhs == rhs {
+            return Some(true);
+        }
+        match (lhs.unpack(), rhs.unpack()) {
+            (RV::BigInt(l), RV::BigInt(r)) => Some(l == r),
+            (RV::Float(l), RV::Float(r)) => Some(l == r),
+            (RV::String(l), RV::String(r)) => Some(l == r),
+            (RV::Object(l), RV::Object(r)) => match (l.kind(), r.kind()) {
+                (ObjKind::ARRAY, ObjKind::ARRAY) => {
+                    let (l, r) = (l.as_array(), r.as_array());
+                    if l.len() != r.len() {
+                        return Some(false);
+                    }
+                    for (l, r) in l.iter().zip(r.iter()) {
+                        if !Self::native_eql(*l, *r)? {
+                            return Some(false);
+                        }
+                    }
+                    Some(true)
+                }
+                (ObjKind::RANGE, ObjKind::RANGE) => {
+                    let (l, r) = (l.as_range(), r.as_range());
+                    Some(
+                        l.exclude_end == r.exclude_end
+                            && Self::native_eql(l.start, r.start)?
+                            && Self::native_eql(l.end, r.end)?,
+                    )
+                }
+                (ObjKind::RATIONAL, ObjKind::RATIONAL) => Some(l.as_rational() == r.as_rational()),
+                (ObjKind::COMPLEX, ObjKind::COMPLEX) => {
+                    let (l, r) = (l.as_complex(), r.as_complex());
+                    Some(Self::native_eql(l.re, r.re)? && Self::native_eql(l.im, r.im)?)
+                }
+                (ObjKind::CLASS | ObjKind::MODULE, _) | (_, ObjKind::CLASS | ObjKind::MODULE) => {
+                    Some(false)
+                }
+                (ObjKind::ARRAY | ObjKind::RANGE, _) | (_, ObjKind::ARRAY | ObjKind::RANGE) => {
+                    Some(false)
+                }
+                _ => None,
+            },
+            (RV::Object(rv), _) | (_, RV::Object(rv)) => match rv.kind() {
+                ObjKind::CLASS | ObjKind::MODULE | ObjKind::ARRAY | ObjKind::RANGE => Some(false),
+                _ => None,
+            },
+            _ => Some(false),
+        }
+    }
+
+    ///
+    /// Build a hash from natively hashable key-value pairs.
+    ///
+    #[cfg(test)]
+    pub(crate) fn from_native_pairs(pairs: Vec<(Value, Value)>) -> Self {
+        let mut hash = HashInner::new();
+        for (key, val) in pairs {
+            let h = Self::native_hash(key).unwrap();
+            let found = hash.index.get(&h).and_then(|bucket| {
+                bucket
+                    .iter()
+                    .find(|i| Self::native_eql(hash.entries[**i].unwrap().1, key).unwrap())
+                    .cloned()
+            });
+            match found {
+                Some(i) => hash.entries[i].as_mut().unwrap().2 = val,
+                None => hash.push(h, key, val),
+            }
+        }
+        hash
+    }
+}
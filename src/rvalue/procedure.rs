@@ -0,0 +1,62 @@
+use crate::*;
+
+///
+/// Proc object.
+///
+/// A Proc holds the block function and the environment (outer frames) captured by the block.
+///
+#[derive(Debug, Clone)]
+pub struct ProcInner {
+    /// the function of the block.
+    func_id: FuncId,
+    /// the environment captured by the block.
+    env: Env,
+    /// true if this is a lambda.
+    is_lambda: bool,
+    /// FuncData with the lambda flag, which is used to invoke a lambda.
+    lambda_data: FuncData,
+}
+
+impl GC<RValue> for ProcInner {
+    fn mark(&self, alloc: &mut Allocator<RValue>) {
+        self.env.mark(alloc);
+    }
+}
+
+impl ProcInner {
+    pub(crate) fn new(func_id: FuncId, env: Env, is_lambda: bool) -> Self {
+        Self {
+            func_id,
+            env,
+            is_lambda,
+            lambda_data: FuncData::default(),
+        }
+    }
+
+    pub(crate) fn func_id(&self) -> FuncId {
+        self.func_id
+    }
+
+    pub(crate) fn env(&self) -> &Env {
+        &self.env
+    }
+
+    pub(crate) fn is_lambda(&self) -> bool {
+        self.is_lambda
+    }
+
+    ///
+    /// Get FuncData to invoke this Proc.
+    ///
+    /// For lambdas, a copy of *data* with the lambda flag is returned.
+    ///
+    pub(crate) fn func_data(&mut self, data: &FuncData) -> *const FuncData {
+        if self.is_lambda {
+            self.lambda_data = data.clone();
+            self.lambda_data.meta.set_lambda();
+            &self.lambda_data
+        } else {
+            data
+        }
+    }
+}
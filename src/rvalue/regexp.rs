@@ -0,0 +1,199 @@
+use super::*;
+use std::rc::Rc;
+
+mod matcher;
+mod parser;
+pub use matcher::{Captures, Regex};
+
+///
+/// Regexp object.
+///
+#[derive(Debug, Clone)]
+pub struct RegexpInner {
+    /// the source string of the pattern.
+    source: String,
+    /// option flags. (IGNORECASE | EXTENDED | MULTILINE)
+    options: u32,
+    regex: Rc<Regex>,
+}
+
+impl PartialEq for RegexpInner {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source && self.options == other.options
+    }
+}
+
+impl RegexpInner {
+    pub const IGNORECASE: u32 = 1;
+    pub const EXTENDED: u32 = 2;
+    pub const MULTILINE: u32 = 4;
+
+    ///
+    /// Compile *source* with *options*.
+    ///
+    /// Returns the message for RegexpError on failure.
+    ///
+    pub fn new(source: String, options: u32) -> Result<Self, String> {
+        let options = options & (Self::IGNORECASE | Self::EXTENDED | Self::MULTILINE);
+        match Regex::new(
+            &source,
+            options & Self::IGNORECASE != 0,
+            options & Self::MULTILINE != 0,
+            options & Self::EXTENDED != 0,
+        ) {
+            Ok(regex) => Ok(RegexpInner {
+                source,
+                options,
+                regex: Rc::new(regex),
+            }),
+            Err(msg) => Err(format!("{}: /{}/", msg, source)),
+        }
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn options(&self) -> u32 {
+        self.options
+    }
+
+    pub fn regex(&self) -> &Regex {
+        &self.regex
+    }
+
+    ///
+    /// Search the first match in *text* starting at or after the byte index *start*.
+    ///
+    pub fn search(&self, text: &[u8], start: usize) -> Option<Captures> {
+        self.regex.search(text, start)
+    }
+
+    fn option_letters(&self, on: bool) -> String {
+        [
+            (Self::MULTILINE, 'm'),
+            (Self::IGNORECASE, 'i'),
+            (Self::EXTENDED, 'x'),
+        ]
+        .iter()
+        .filter(|(bit, _)| (self.options & bit != 0) == on)
+        .map(|(_, c)| *c)
+        .collect()
+    }
+
+    ///
+    /// `/source/opts`
+    ///
+    pub fn inspect(&self) -> String {
+        let mut s = String::from("/");
+        let mut escape = false;
+        for c in self.source.chars() {
+            match c {
+                '/' if !escape => s.push_str("\\/"),
+                '\n' => s.push_str("\\n"),
+                c => s.push(c),
+            }
+            escape = !escape && c == '\\';
+        }
+        s.push('/');
+        s += &self.option_letters(true);
+        s
+    }
+
+    ///
+    /// `(?opts-opts:source)`
+    ///
+    pub fn tos(&self) -> String {
+        let on = self.option_letters(true);
+        let off = self.option_letters(false);
+        if off.is_empty() {
+            format!("(?{}:{})", on, self.source)
+        } else {
+            format!("(?{}-{}:{})", on, off, self.source)
+        }
+    }
+}
+
+///
+/// MatchData object.
+///
+#[derive(Debug, Clone)]
+pub struct MatchDataInner {
+    /// the Regexp object.
+    regexp: Value,
+    /// the target string (a frozen copy).
+    string: Value,
+    captures: Captures,
+}
+
+impl GC<RValue> for MatchDataInner {
+    fn mark(&self, alloc: &mut Allocator<RValue>) {
+        self.regexp.mark(alloc);
+        self.string.mark(alloc);
+    }
+}
+
+impl MatchDataInner {
+    pub(crate) fn new(regexp: Value, string: Value, captures: Captures) -> Self {
+        MatchDataInner {
+            regexp,
+            string,
+            captures,
+        }
+    }
+
+    pub(crate) fn regexp(&self) -> Value {
+        self.regexp
+    }
+
+    pub(crate) fn string(&self) -> Value {
+        self.string
+    }
+
+    /// the number of groups including the whole match.
+    pub(crate) fn len(&self) -> usize {
+        self.captures.len()
+    }
+
+    /// the byte range of the *index*-th group.
+    pub(crate) fn pos(&self, index: usize) -> Option<(usize, usize)> {
+        self.captures.get(index).cloned().flatten()
+    }
+
+    /// the byte slice of the *index*-th group.
+    pub(crate) fn group(&self, index: usize) -> Option<&[u8]> {
+        let (start, end) = self.pos(index)?;
+        Some(&self.string.as_bytes()[start..end])
+    }
+
+    ///
+    /// Get the group index for *name*.
+    ///
+    /// When several groups have the same name, the last matched one is returned.
+    ///
+    pub(crate) fn name_to_index(&self, name: &str) -> Option<usize> {
+        let (_, indices) = self
+            .regexp
+            .as_regexp()
+            .regex()
+            .names()
+            .iter()
+            .find(|(n, _)| n == name)?;
+        indices
+            .iter()
+            .rev()
+            .find(|i| self.pos(**i).is_some())
+            .or_else(|| indices.last())
+            .cloned()
+    }
+
+    pub(crate) fn pre_match(&self) -> &[u8] {
+        let (start, _) = self.pos(0).unwrap();
+        &self.string.as_bytes()[..start]
+    }
+
+    pub(crate) fn post_match(&self) -> &[u8] {
+        let (_, end) = self.pos(0).unwrap();
+        &self.string.as_bytes()[end..]
+    }
+}
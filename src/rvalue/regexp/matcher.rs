@@ -0,0 +1,531 @@
+use super::parser::*;
+use super::CharSlices;
+
+#[derive(Debug, Clone)]
+enum Inst {
+    Char(char),
+    /// a case-folded character.
+    CharIc(char),
+    /// any character. (multiline)
+    Any(bool),
+    /// a character class. (class, ignore_case)
+    Class(Box<CharClass>, bool),
+    Assert(Assertion),
+    /// try the first branch, and then the second one on failure.
+    Split(usize, usize),
+    Jmp(usize),
+    /// save the current position to the slot.
+    Save(usize),
+    /// save the current position to the mark slot.
+    SetMark(usize),
+    /// fail if the position does not advance from the mark.
+    CheckProgress(usize),
+    /// a back reference. (group numbers, ignore_case)
+    Backref(Vec<usize>, bool),
+    /// run the sub-program which follows this instruction, and continue from *next*.
+    Look {
+        behind: bool,
+        negate: bool,
+        next: usize,
+    },
+    /// run the sub-program which follows this instruction, and continue from *next*
+    /// without backtracking into it.
+    Atomic {
+        next: usize,
+    },
+    Match,
+}
+
+///
+/// Compiled regular expression.
+///
+#[derive(Debug)]
+pub struct Regex {
+    prog: Vec<Inst>,
+    /// the number of capture groups including the whole match.
+    captures: usize,
+    /// the number of mark slots for empty loop checks.
+    marks: usize,
+    names: Vec<(String, Vec<usize>)>,
+}
+
+///
+/// Positions (byte index) of capture groups. The first is the whole match.
+///
+pub type Captures = Vec<Option<(usize, usize)>>;
+
+enum Backtrack {
+    Alt { pc: usize, pos: usize },
+    Restore { slot: usize, val: Option<usize> },
+}
+
+struct Context<'a> {
+    text: &'a [u8],
+    search_start: usize,
+}
+
+impl Regex {
+    pub fn new(
+        pattern: &str,
+        ignore_case: bool,
+        multiline: bool,
+        extended: bool,
+    ) -> Result<Self, String> {
+        let parsed = parse(pattern, ignore_case, multiline, extended)?;
+        let mut compiler = Compiler {
+            prog: vec![],
+            marks: 0,
+        };
+        compiler.compile(&parsed.node);
+        compiler.prog.push(Inst::Match);
+        Ok(Regex {
+            prog: compiler.prog,
+            captures: parsed.captures,
+            marks: compiler.marks,
+            names: parsed.names,
+        })
+    }
+
+    /// the number of capture groups including the whole match.
+    pub fn captures_len(&self) -> usize {
+        self.captures
+    }
+
+    pub fn names(&self) -> &[(String, Vec<usize>)] {
+        &self.names
+    }
+
+    ///
+    /// Search the first match in *text* starting at or after the byte index *start*.
+    ///
+    pub fn search(&self, text: &[u8], start: usize) -> Option<Captures> {
+        if start > text.len() {
+            return None;
+        }
+        let ctx = Context {
+            text,
+            search_start: start,
+        };
+        let mut slots = vec![None; self.captures * 2 + self.marks];
+        let mut pos = start;
+        loop {
+            slots.iter_mut().for_each(|s| *s = None);
+            if let Some(end) = self.run(&ctx, 0, pos, &mut slots, None) {
+                let mut captures: Captures = vec![Some((pos, end))];
+                for i in 1..self.captures {
+                    captures.push(match (slots[i * 2], slots[i * 2 + 1]) {
+                        (Some(s), Some(e)) => Some((s, e)),
+                        _ => None,
+                    });
+                }
+                return Some(captures);
+            }
+            match CharSlices(&text[pos..]).next() {
+                Some(ch) => pos += ch.len(),
+                None => return None,
+            }
+        }
+    }
+
+    fn run(
+        &self,
+        ctx: &Context,
+        mut pc: usize,
+        mut pos: usize,
+        slots: &mut [Option<usize>],
+        target: Option<usize>,
+    ) -> Option<usize> {
+        let text = ctx.text;
+        let mut stack: Vec<Backtrack> = vec![];
+        loop {
+            let ok = match &self.prog[pc] {
+                Inst::Match => {
+                    if target.map_or(true, |t| t == pos) {
+                        return Some(pos);
+                    }
+                    false
+                }
+                Inst::Char(c) => match char_at(text, pos) {
+                    Some((ch, len)) if ch == *c => {
+                        pos += len;
+                        pc += 1;
+                        true
+                    }
+                    _ => false,
+                },
+                Inst::CharIc(c) => match char_at(text, pos) {
+                    Some((ch, len)) if fold_case(ch) == *c => {
+                        pos += len;
+                        pc += 1;
+                        true
+                    }
+                    _ => false,
+                },
+                Inst::Any(multiline) => match char_at(text, pos) {
+                    Some((ch, len)) if *multiline || ch != '\n' => {
+                        pos += len;
+                        pc += 1;
+                        true
+                    }
+                    _ => false,
+                },
+                Inst::Class(class, ignore_case) => match char_at(text, pos) {
+                    Some((ch, len)) if class.matches(ch, *ignore_case) => {
+                        pos += len;
+                        pc += 1;
+                        true
+                    }
+                    _ => false,
+                },
+                Inst::Assert(assertion) => {
+                    pc += 1;
+                    check_assertion(ctx, *assertion, pos)
+                }
+                Inst::Split(first, second) => {
+                    stack.push(Backtrack::Alt { pc: *second, pos });
+                    pc = *first;
+                    true
+                }
+                Inst::Jmp(dest) => {
+                    pc = *dest;
+                    true
+                }
+                Inst::Save(slot) => {
+                    save_slot(slots, *slot, pos, &mut stack);
+                    pc += 1;
+                    true
+                }
+                Inst::SetMark(mark) => {
+                    save_slot(slots, self.captures * 2 + *mark, pos, &mut stack);
+                    pc += 1;
+                    true
+                }
+                Inst::CheckProgress(mark) => {
+                    pc += 1;
+                    slots[self.captures * 2 + *mark] != Some(pos)
+                }
+                Inst::Backref(groups, ignore_case) => {
+                    let group =
+                        groups
+                            .iter()
+                            .rev()
+                            .find_map(|i| match (slots[i * 2], slots[i * 2 + 1]) {
+                                (Some(s), Some(e)) => Some((s, e)),
+                                _ => None,
+                            });
+                    match group {
+                        Some((s, e)) => match match_backref(text, &text[s..e], pos, *ignore_case) {
+                            Some(len) => {
+                                pos += len;
+                                pc += 1;
+                                true
+                            }
+                            None => false,
+                        },
+                        None => false,
+                    }
+                }
+                Inst::Look {
+                    behind,
+                    negate,
+                    next,
+                } => {
+                    let mut sub = slots.to_vec();
+                    let found = if *behind {
+                        let mut start = pos;
+                        loop {
+                            if self.run(ctx, pc + 1, start, &mut sub, Some(pos)).is_some() {
+                                break true;
+                            }
+                            if start == 0 {
+                                break false;
+                            }
+                            start -= 1;
+                        }
+                    } else {
+                        self.run(ctx, pc + 1, pos, &mut sub, None).is_some()
+                    };
+                    if found != *negate {
+                        if found {
+                            // captures in a positive look-around are kept.
+                            apply_slots(slots, &sub, &mut stack);
+                        }
+                        pc = *next;
+                        true
+                    } else {
+                        false
+                    }
+                }
+                Inst::Atomic { next } => {
+                    let mut sub = slots.to_vec();
+                    match self.run(ctx, pc + 1, pos, &mut sub, None) {
+                        Some(end) => {
+                            apply_slots(slots, &sub, &mut stack);
+                            pos = end;
+                            pc = *next;
+                            true
+                        }
+                        None => false,
+                    }
+                }
+            };
+            if !ok {
+                // backtrack.
+                loop {
+                    match stack.pop() {
+                        None => return None,
+                        Some(Backtrack::Restore { slot, val }) => slots[slot] = val,
+                        Some(Backtrack::Alt { pc: p, pos: q }) => {
+                            pc = p;
+                            pos = q;
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn save_slot(slots: &mut [Option<usize>], slot: usize, pos: usize, stack: &mut Vec<Backtrack>) {
+    stack.push(Backtrack::Restore {
+        slot,
+        val: slots[slot],
+    });
+    slots[slot] = Some(pos);
+}
+
+fn apply_slots(slots: &mut [Option<usize>], sub: &[Option<usize>], stack: &mut Vec<Backtrack>) {
+    for (i, val) in sub.iter().enumerate() {
+        if slots[i] != *val {
+            stack.push(Backtrack::Restore {
+                slot: i,
+                val: slots[i],
+            });
+            slots[i] = *val;
+        }
+    }
+}
+
+///
+/// Decode the character at the byte index *pos*.
+///
+/// An invalid byte is decoded as U+FFFD.
+///
+fn char_at(text: &[u8], pos: usize) -> Option<(char, usize)> {
+    let bytes = CharSlices(text.get(pos..)?).next()?;
+    let ch = std::str::from_utf8(bytes)
+        .ok()
+        .and_then(|s| s.chars().next())
+        .unwrap_or(char::REPLACEMENT_CHARACTER);
+    Some((ch, bytes.len()))
+}
+
+///
+/// Decode the character which ends at the byte index *pos*.
+///
+fn char_before(text: &[u8], pos: usize) -> Option<char> {
+    let mut start = pos.checked_sub(1)?;
+    while start > 0 && pos - start < 4 && text[start] & 0xc0 == 0x80 {
+        start -= 1;
+    }
+    match char_at(text, start) {
+        Some((ch, len)) if start + len == pos => Some(ch),
+        _ => Some(char::REPLACEMENT_CHARACTER),
+    }
+}
+
+fn check_assertion(ctx: &Context, assertion: Assertion, pos: usize) -> bool {
+    let text = ctx.text;
+    match assertion {
+        Assertion::LineStart => pos == 0 || text[pos - 1] == b'\n',
+        Assertion::LineEnd => pos == text.len() || text[pos] == b'\n',
+        Assertion::TextStart => pos == 0,
+        Assertion::TextEnd => pos == text.len(),
+        Assertion::TextEndNewline => {
+            pos == text.len() || (pos + 1 == text.len() && text[pos] == b'\n')
+        }
+        Assertion::WordBoundary | Assertion::NotWordBoundary => {
+            let before = char_before(text, pos).map_or(false, is_word_char);
+            let after = char_at(text, pos).map_or(false, |(c, _)| is_word_char(c));
+            (before != after) == (assertion == Assertion::WordBoundary)
+        }
+        Assertion::SearchStart => pos == ctx.search_start,
+    }
+}
+
+///
+/// Match *group* at *pos* in *text*, and return the matched length.
+///
+fn match_backref(text: &[u8], group: &[u8], pos: usize, ignore_case: bool) -> Option<usize> {
+    if !ignore_case {
+        return if text[pos..].starts_with(group) {
+            Some(group.len())
+        } else {
+            None
+        };
+    }
+    let mut len = 0;
+    let mut i = 0;
+    while i < group.len() {
+        let (c1, l1) = char_at(group, i)?;
+        let (c2, l2) = char_at(text, pos + len)?;
+        if fold_case(c1) != fold_case(c2) {
+            return None;
+        }
+        i += l1;
+        len += l2;
+    }
+    Some(len)
+}
+
+struct Compiler {
+    prog: Vec<Inst>,
+    marks: usize,
+}
+
+impl Compiler {
+    fn push(&mut self, inst: Inst) -> usize {
+        self.prog.push(inst);
+        self.prog.len() - 1
+    }
+
+    fn pc(&self) -> usize {
+        self.prog.len()
+    }
+
+    fn compile(&mut self, node: &Node) {
+        match node {
+            Node::Empty => {}
+            Node::Char(c, ignore_case) => {
+                if *ignore_case {
+                    self.push(Inst::CharIc(fold_case(*c)));
+                } else {
+                    self.push(Inst::Char(*c));
+                }
+            }
+            Node::Any(multiline) => {
+                self.push(Inst::Any(*multiline));
+            }
+            Node::Class(class, ignore_case) => {
+                self.push(Inst::Class(class.clone(), *ignore_case));
+            }
+            Node::Assert(assertion) => {
+                self.push(Inst::Assert(*assertion));
+            }
+            Node::Capture(node, index) => {
+                self.push(Inst::Save(index * 2));
+                self.compile(node);
+                self.push(Inst::Save(index * 2 + 1));
+            }
+            Node::Concat(nodes) => nodes.iter().for_each(|node| self.compile(node)),
+            Node::Alt(nodes) => {
+                let mut jumps = vec![];
+                for (i, node) in nodes.iter().enumerate() {
+                    if i == nodes.len() - 1 {
+                        self.compile(node);
+                    } else {
+                        let split = self.push(Inst::Split(0, 0));
+                        self.compile(node);
+                        jumps.push(self.push(Inst::Jmp(0)));
+                        self.prog[split] = Inst::Split(split + 1, self.pc());
+                    }
+                }
+                let end = self.pc();
+                for jmp in jumps {
+                    self.prog[jmp] = Inst::Jmp(end);
+                }
+            }
+            Node::Repeat {
+                node,
+                min,
+                max,
+                kind,
+            } => {
+                if *kind == RepeatKind::Possessive {
+                    let atomic = Node::Atomic(Box::new(Node::Repeat {
+                        node: node.clone(),
+                        min: *min,
+                        max: *max,
+                        kind: RepeatKind::Greedy,
+                    }));
+                    self.compile(&atomic);
+                    return;
+                }
+                let greedy = *kind == RepeatKind::Greedy;
+                for _ in 0..*min {
+                    self.compile(node);
+                }
+                match max {
+                    None => {
+                        let mark = if node.can_be_empty() {
+                            self.marks += 1;
+                            Some(self.marks - 1)
+                        } else {
+                            None
+                        };
+                        let split = self.push(Inst::Split(0, 0));
+                        if let Some(mark) = mark {
+                            self.push(Inst::SetMark(mark));
+                        }
+                        self.compile(node);
+                        if let Some(mark) = mark {
+                            self.push(Inst::CheckProgress(mark));
+                        }
+                        self.push(Inst::Jmp(split));
+                        let exit = self.pc();
+                        self.prog[split] = self.split(greedy, split + 1, exit);
+                    }
+                    Some(max) => {
+                        let mut splits = vec![];
+                        for _ in *min..*max {
+                            splits.push(self.push(Inst::Split(0, 0)));
+                            self.compile(node);
+                        }
+                        let exit = self.pc();
+                        for split in splits {
+                            self.prog[split] = self.split(greedy, split + 1, exit);
+                        }
+                    }
+                }
+            }
+            Node::Backref(groups, ignore_case) => {
+                self.push(Inst::Backref(groups.clone(), *ignore_case));
+            }
+            Node::Look {
+                node,
+                behind,
+                negate,
+            } => {
+                let look = self.push(Inst::Look {
+                    behind: *behind,
+                    negate: *negate,
+                    next: 0,
+                });
+                self.compile(node);
+                self.push(Inst::Match);
+                let next = self.pc();
+                self.prog[look] = Inst::Look {
+                    behind: *behind,
+                    negate: *negate,
+                    next,
+                };
+            }
+            Node::Atomic(node) => {
+                let atomic = self.push(Inst::Atomic { next: 0 });
+                self.compile(node);
+                self.push(Inst::Match);
+                let next = self.pc();
+                self.prog[atomic] = Inst::Atomic { next };
+            }
+        }
+    }
+
+    fn split(&self, greedy: bool, body: usize, exit: usize) -> Inst {
+        if greedy {
+            Inst::Split(body, exit)
+        } else {
+            Inst::Split(exit, body)
+        }
+    }
+}
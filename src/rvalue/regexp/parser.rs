@@ -0,0 +1,848 @@
+///
+/// AST of a regular expression.
+///
+#[derive(Debug, Clone)]
+pub(super) enum Node {
+    Empty,
+    /// a literal character. (char, ignore_case)
+    Char(char, bool),
+    /// `.` (multiline)
+    Any(bool),
+    /// a character class. (class, ignore_case)
+    Class(Box<CharClass>, bool),
+    Assert(Assertion),
+    /// a capture group. (body, group number)
+    Capture(Box<Node>, usize),
+    Concat(Vec<Node>),
+    Alt(Vec<Node>),
+    Repeat {
+        node: Box<Node>,
+        min: usize,
+        max: Option<usize>,
+        kind: RepeatKind,
+    },
+    /// a back reference. (group numbers, ignore_case)
+    Backref(Vec<usize>, bool),
+    /// look-ahead / look-behind assertions.
+    Look {
+        node: Box<Node>,
+        behind: bool,
+        negate: bool,
+    },
+    /// `(?>...)`
+    Atomic(Box<Node>),
+}
+
+impl Node {
+    pub(super) fn can_be_empty(&self) -> bool {
+        match self {
+            Node::Empty | Node::Assert(_) | Node::Backref(..) | Node::Look { .. } => true,
+            Node::Char(..) | Node::Any(_) | Node::Class(..) => false,
+            Node::Capture(node, _) | Node::Atomic(node) => node.can_be_empty(),
+            Node::Concat(nodes) => nodes.iter().all(|n| n.can_be_empty()),
+            Node::Alt(nodes) => nodes.iter().any(|n| n.can_be_empty()),
+            Node::Repeat { node, min, .. } => *min == 0 || node.can_be_empty(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum RepeatKind {
+    Greedy,
+    Lazy,
+    Possessive,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Assertion {
+    /// `^`
+    LineStart,
+    /// `$`
+    LineEnd,
+    /// `\A`
+    TextStart,
+    /// `\z`
+    TextEnd,
+    /// `\Z`
+    TextEndNewline,
+    /// `\b`
+    WordBoundary,
+    /// `\B`
+    NotWordBoundary,
+    /// `\G`
+    SearchStart,
+}
+
+///
+/// Character class.
+///
+#[derive(Debug, Clone, Default)]
+pub(super) struct CharClass {
+    negated: bool,
+    items: Vec<ClassItem>,
+}
+
+#[derive(Debug, Clone)]
+enum ClassItem {
+    Range(char, char),
+    Digit(bool),
+    Word(bool),
+    Space(bool),
+    Hex(bool),
+    Posix(PosixClass, bool),
+    Class(CharClass),
+    Intersection(Box<CharClass>, Box<CharClass>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PosixClass {
+    Alnum,
+    Alpha,
+    Ascii,
+    Blank,
+    Cntrl,
+    Digit,
+    Graph,
+    Lower,
+    Print,
+    Punct,
+    Space,
+    Upper,
+    Xdigit,
+    Word,
+}
+
+impl PosixClass {
+    fn from_name(name: &str) -> Option<Self> {
+        let class = match name {
+            "alnum" => Self::Alnum,
+            "alpha" => Self::Alpha,
+            "ascii" => Self::Ascii,
+            "blank" => Self::Blank,
+            "cntrl" => Self::Cntrl,
+            "digit" => Self::Digit,
+            "graph" => Self::Graph,
+            "lower" => Self::Lower,
+            "print" => Self::Print,
+            "punct" => Self::Punct,
+            "space" => Self::Space,
+            "upper" => Self::Upper,
+            "xdigit" => Self::Xdigit,
+            "word" => Self::Word,
+            _ => return None,
+        };
+        Some(class)
+    }
+
+    fn matches(self, c: char) -> bool {
+        match self {
+            Self::Alnum => c.is_alphanumeric(),
+            Self::Alpha => c.is_alphabetic(),
+            Self::Ascii => c.is_ascii(),
+            Self::Blank => c == ' ' || c == '\t',
+            Self::Cntrl => c.is_control(),
+            Self::Digit => c.is_ascii_digit(),
+            Self::Graph => !c.is_whitespace() && !c.is_control(),
+            Self::Lower => c.is_lowercase(),
+            Self::Print => !c.is_control(),
+            Self::Punct => c.is_ascii_punctuation(),
+            Self::Space => c.is_whitespace(),
+            Self::Upper => c.is_uppercase(),
+            Self::Xdigit => c.is_ascii_hexdigit(),
+            Self::Word => is_word_char(c),
+        }
+    }
+}
+
+pub(super) fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+fn is_space_char(c: char) -> bool {
+    matches!(c, ' ' | '\t' | '\n' | '\x0b' | '\x0c' | '\r')
+}
+
+///
+/// Simple case folding.
+///
+pub(super) fn fold_case(c: char) -> char {
+    let mut lower = c.to_lowercase();
+    match (lower.next(), lower.next()) {
+        (Some(l), None) => l,
+        _ => c,
+    }
+}
+
+fn upper_case(c: char) -> char {
+    let mut upper = c.to_uppercase();
+    match (upper.next(), upper.next()) {
+        (Some(u), None) => u,
+        _ => c,
+    }
+}
+
+impl ClassItem {
+    fn matches(&self, c: char, ignore_case: bool) -> bool {
+        match self {
+            ClassItem::Range(start, end) => *start <= c && c <= *end,
+            ClassItem::Digit(neg) => c.is_ascii_digit() != *neg,
+            ClassItem::Word(neg) => is_word_char(c) != *neg,
+            ClassItem::Space(neg) => is_space_char(c) != *neg,
+            ClassItem::Hex(neg) => c.is_ascii_hexdigit() != *neg,
+            ClassItem::Posix(class, neg) => class.matches(c) != *neg,
+            ClassItem::Class(class) => class.matches(c, ignore_case),
+            ClassItem::Intersection(lhs, rhs) => {
+                lhs.matches(c, ignore_case) && rhs.matches(c, ignore_case)
+            }
+        }
+    }
+}
+
+impl CharClass {
+    pub(super) fn matches(&self, c: char, ignore_case: bool) -> bool {
+        let hit = |c: char| self.items.iter().any(|item| item.matches(c, ignore_case));
+        let found = hit(c) || (ignore_case && (hit(fold_case(c)) || hit(upper_case(c))));
+        found != self.negated
+    }
+
+    fn single(item: ClassItem) -> Self {
+        CharClass {
+            negated: false,
+            items: vec![item],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Flags {
+    ignore_case: bool,
+    multiline: bool,
+    extended: bool,
+}
+
+///
+/// The result of parsing.
+///
+pub(super) struct Parsed {
+    pub node: Node,
+    /// the number of capture groups including the whole match.
+    pub captures: usize,
+    /// named groups.
+    pub names: Vec<(String, Vec<usize>)>,
+}
+
+pub(super) fn parse(
+    pattern: &str,
+    ignore_case: bool,
+    multiline: bool,
+    extended: bool,
+) -> Result<Parsed, String> {
+    let mut parser = Parser {
+        chars: pattern.chars().collect(),
+        pos: 0,
+        flags: Flags {
+            ignore_case,
+            multiline,
+            extended,
+        },
+        captures: 1,
+        names: vec![],
+        named_only: has_named_group(pattern),
+    };
+    let node = parser.parse_alt()?;
+    if parser.pos < parser.chars.len() {
+        // must be ')'
+        return Err("unmatched close parenthesis".to_string());
+    }
+    Ok(Parsed {
+        node,
+        captures: parser.captures,
+        names: parser.names,
+    })
+}
+
+///
+/// If a pattern contains named groups, plain parentheses do not capture.
+///
+fn has_named_group(pattern: &str) -> bool {
+    let chars: Vec<char> = pattern.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => i += 1,
+            '(' if chars.get(i + 1) == Some(&'?') => match chars.get(i + 2) {
+                Some('\'') => return true,
+                Some('<') => {
+                    if !matches!(chars.get(i + 3), Some('=') | Some('!')) {
+                        return true;
+                    }
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+        i += 1;
+    }
+    false
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+    flags: Flags,
+    /// the number of capture groups so far (including the whole match).
+    captures: usize,
+    names: Vec<(String, Vec<usize>)>,
+    named_only: bool,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).cloned()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).cloned()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += 1;
+        Some(c)
+    }
+
+    fn consume(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn consume_str(&mut self, s: &str) -> bool {
+        let len = s.chars().count();
+        if self.chars.len() >= self.pos + len
+            && self.chars[self.pos..self.pos + len]
+                .iter()
+                .cloned()
+                .eq(s.chars())
+        {
+            self.pos += len;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// skip whitespaces and comments in extended mode.
+    fn skip_extended(&mut self) {
+        if !self.flags.extended {
+            return;
+        }
+        loop {
+            match self.peek() {
+                Some(c) if c.is_whitespace() => self.pos += 1,
+                Some('#') => {
+                    while let Some(c) = self.next() {
+                        if c == '\n' {
+                            break;
+                        }
+                    }
+                }
+                _ => return,
+            }
+        }
+    }
+
+    fn parse_alt(&mut self) -> Result<Node, String> {
+        let mut branches = vec![self.parse_concat()?];
+        while self.consume('|') {
+            branches.push(self.parse_concat()?);
+        }
+        if branches.len() == 1 {
+            Ok(branches.pop().unwrap())
+        } else {
+            Ok(Node::Alt(branches))
+        }
+    }
+
+    fn parse_concat(&mut self) -> Result<Node, String> {
+        let mut nodes = vec![];
+        loop {
+            self.skip_extended();
+            match self.peek() {
+                None | Some('|') | Some(')') => break,
+                Some('*') | Some('+') | Some('?') => {
+                    return Err("target of repeat operator is not specified".to_string())
+                }
+                _ => {}
+            }
+            let atom = match self.parse_atom()? {
+                Some(atom) => atom,
+                // inline option or comment.
+                None => continue,
+            };
+            let atom = self.parse_quantifier(atom)?;
+            nodes.push(atom);
+        }
+        match nodes.len() {
+            0 => Ok(Node::Empty),
+            1 => Ok(nodes.pop().unwrap()),
+            _ => Ok(Node::Concat(nodes)),
+        }
+    }
+
+    fn parse_quantifier(&mut self, mut atom: Node) -> Result<Node, String> {
+        loop {
+            self.skip_extended();
+            let (min, max) = match self.peek() {
+                Some('*') => {
+                    self.pos += 1;
+                    (0, None)
+                }
+                Some('+') => {
+                    self.pos += 1;
+                    (1, None)
+                }
+                Some('?') => {
+                    self.pos += 1;
+                    (0, Some(1))
+                }
+                Some('{') => match self.parse_interval()? {
+                    Some(interval) => interval,
+                    None => return Ok(atom),
+                },
+                _ => return Ok(atom),
+            };
+            let kind = if self.consume('?') {
+                RepeatKind::Lazy
+            } else if self.consume('+') {
+                RepeatKind::Possessive
+            } else {
+                RepeatKind::Greedy
+            };
+            if matches!(atom, Node::Assert(_) | Node::Look { .. }) {
+                return Err("target of repeat operator is invalid".to_string());
+            }
+            atom = Node::Repeat {
+                node: Box::new(atom),
+                min,
+                max,
+                kind,
+            };
+        }
+    }
+
+    ///
+    /// Parse `{n}`, `{n,}`, `{,m}` or `{n,m}`.
+    ///
+    /// Returns None (and does not consume anything) if it is not a valid interval.
+    ///
+    fn parse_interval(&mut self) -> Result<Option<(usize, Option<usize>)>, String> {
+        let save = self.pos;
+        self.pos += 1;
+        let min = self.parse_number();
+        let max = if self.consume(',') {
+            self.parse_number()
+        } else {
+            Some(min.unwrap_or(0))
+        };
+        if !self.consume('}') || (min.is_none() && max.is_none()) {
+            self.pos = save;
+            return Ok(None);
+        }
+        let min = min.unwrap_or(0);
+        if min > 100000 || max.unwrap_or(0) > 100000 {
+            return Err("too big number for repeat range".to_string());
+        }
+        if let Some(max) = max {
+            if max < min {
+                return Err("upper bound must be greater than lower bound".to_string());
+            }
+        }
+        Ok(Some((min, max)))
+    }
+
+    fn parse_number(&mut self) -> Option<usize> {
+        let start = self.pos;
+        let mut n: usize = 0;
+        while let Some(d) = self.peek().and_then(|c| c.to_digit(10)) {
+            n = n.saturating_mul(10).saturating_add(d as usize);
+            self.pos += 1;
+        }
+        if self.pos == start {
+            None
+        } else {
+            Some(n)
+        }
+    }
+
+    fn parse_atom(&mut self) -> Result<Option<Node>, String> {
+        let c = self.next().unwrap();
+        let node = match c {
+            '(' => return self.parse_group(),
+            '[' => Node::Class(Box::new(self.parse_class()?), self.flags.ignore_case),
+            '.' => Node::Any(self.flags.multiline),
+            '^' => Node::Assert(Assertion::LineStart),
+            '$' => Node::Assert(Assertion::LineEnd),
+            '\\' => self.parse_escape()?,
+            c => Node::Char(c, self.flags.ignore_case),
+        };
+        Ok(Some(node))
+    }
+
+    fn parse_group(&mut self) -> Result<Option<Node>, String> {
+        if !self.consume('?') {
+            let node = if self.named_only {
+                self.parse_group_body()?
+            } else {
+                let index = self.new_capture();
+                Node::Capture(Box::new(self.parse_group_body()?), index)
+            };
+            return Ok(Some(node));
+        }
+        let node = match self.peek() {
+            Some(':') => {
+                self.pos += 1;
+                self.parse_group_body()?
+            }
+            Some('=') | Some('!') => {
+                let negate = self.next() == Some('!');
+                Node::Look {
+                    node: Box::new(self.parse_group_body()?),
+                    behind: false,
+                    negate,
+                }
+            }
+            Some('>') => {
+                self.pos += 1;
+                Node::Atomic(Box::new(self.parse_group_body()?))
+            }
+            Some('<') if matches!(self.peek_at(1), Some('=') | Some('!')) => {
+                self.pos += 1;
+                let negate = self.next() == Some('!');
+                Node::Look {
+                    node: Box::new(self.parse_group_body()?),
+                    behind: true,
+                    negate,
+                }
+            }
+            Some('<') | Some('\'') => {
+                let close = if self.next() == Some('<') { '>' } else { '\'' };
+                let name = self.parse_name(close)?;
+                let index = self.new_capture();
+                match self.names.iter_mut().find(|(n, _)| n == &name) {
+                    Some((_, indices)) => indices.push(index),
+                    None => self.names.push((name, vec![index])),
+                }
+                Node::Capture(Box::new(self.parse_group_body()?), index)
+            }
+            Some('#') => {
+                loop {
+                    match self.next() {
+                        Some(')') => break,
+                        Some(_) => {}
+                        None => return Err("end pattern in group".to_string()),
+                    }
+                }
+                return Ok(None);
+            }
+            _ => {
+                // (?imx-imx) or (?imx-imx:subexp)
+                let mut flags = self.flags;
+                let mut on = true;
+                loop {
+                    match self.next() {
+                        Some('i') => flags.ignore_case = on,
+                        Some('m') => flags.multiline = on,
+                        Some('x') => flags.extended = on,
+                        Some('-') if on => on = false,
+                        Some(')') => {
+                            // applies to the rest of the enclosing group.
+                            self.flags = flags;
+                            return Ok(None);
+                        }
+                        Some(':') => break,
+                        Some(_) => return Err("undefined group option".to_string()),
+                        None => return Err("end pattern in group".to_string()),
+                    }
+                }
+                let save = self.flags;
+                self.flags = flags;
+                let node = self.parse_group_body();
+                self.flags = save;
+                node?
+            }
+        };
+        Ok(Some(node))
+    }
+
+    fn new_capture(&mut self) -> usize {
+        let index = self.captures;
+        self.captures += 1;
+        index
+    }
+
+    fn parse_group_body(&mut self) -> Result<Node, String> {
+        let save = self.flags;
+        let node = self.parse_alt()?;
+        self.flags = save;
+        if !self.consume(')') {
+            return Err("end pattern with unmatched parenthesis".to_string());
+        }
+        Ok(node)
+    }
+
+    fn parse_name(&mut self, close: char) -> Result<String, String> {
+        let mut name = String::new();
+        loop {
+            match self.next() {
+                Some(c) if c == close => break,
+                Some(c) if is_word_char(c) => name.push(c),
+                _ => return Err("invalid group name".to_string()),
+            }
+        }
+        if name.is_empty() {
+            return Err("group name is empty".to_string());
+        }
+        Ok(name)
+    }
+
+    fn parse_escape(&mut self) -> Result<Node, String> {
+        let ic = self.flags.ignore_case;
+        let c = match self.next() {
+            Some(c) => c,
+            None => return Err("too short escape sequence".to_string()),
+        };
+        let class = |item: ClassItem| -> Result<Node, String> {
+            Ok(Node::Class(Box::new(CharClass::single(item)), false))
+        };
+        match c {
+            'd' => class(ClassItem::Digit(false)),
+            'D' => class(ClassItem::Digit(true)),
+            'w' => class(ClassItem::Word(false)),
+            'W' => class(ClassItem::Word(true)),
+            's' => class(ClassItem::Space(false)),
+            'S' => class(ClassItem::Space(true)),
+            'h' => class(ClassItem::Hex(false)),
+            'H' => class(ClassItem::Hex(true)),
+            'A' => Ok(Node::Assert(Assertion::TextStart)),
+            'z' => Ok(Node::Assert(Assertion::TextEnd)),
+            'Z' => Ok(Node::Assert(Assertion::TextEndNewline)),
+            'b' => Ok(Node::Assert(Assertion::WordBoundary)),
+            'B' => Ok(Node::Assert(Assertion::NotWordBoundary)),
+            'G' => Ok(Node::Assert(Assertion::SearchStart)),
+            '1'..='9' => {
+                self.pos -= 1;
+                let n = self.parse_number().unwrap();
+                if n >= self.captures {
+                    return Err("invalid backref number/name".to_string());
+                }
+                Ok(Node::Backref(vec![n], ic))
+            }
+            'k' if self.peek() == Some('<') || self.peek() == Some('\'') => {
+                let close = if self.next() == Some('<') { '>' } else { '\'' };
+                let name = self.parse_name(close)?;
+                if let Ok(n) = name.parse::<usize>() {
+                    if n == 0 || n >= self.captures {
+                        return Err("invalid backref number/name".to_string());
+                    }
+                    return Ok(Node::Backref(vec![n], ic));
+                }
+                match self.names.iter().find(|(n, _)| n == &name) {
+                    Some((_, indices)) => Ok(Node::Backref(indices.clone(), ic)),
+                    None => Err(format!("undefined name <{}> reference", name)),
+                }
+            }
+            c => Ok(Node::Char(self.escaped_char(c)?, ic)),
+        }
+    }
+
+    ///
+    /// Convert an escaped character (after `\`) into a literal character.
+    ///
+    fn escaped_char(&mut self, c: char) -> Result<char, String> {
+        let c = match c {
+            'n' => '\n',
+            't' => '\t',
+            'r' => '\r',
+            'f' => '\x0c',
+            'v' => '\x0b',
+            'a' => '\x07',
+            'e' => '\x1b',
+            '0' => {
+                let mut n = 0;
+                for _ in 0..2 {
+                    match self.peek().and_then(|c| c.to_digit(8)) {
+                        Some(d) => {
+                            n = n * 8 + d;
+                            self.pos += 1;
+                        }
+                        None => break,
+                    }
+                }
+                char::from_u32(n).unwrap()
+            }
+            'x' => {
+                if self.consume('{') {
+                    self.parse_hex_until('}')?
+                } else {
+                    self.parse_hex(2)?
+                }
+            }
+            'u' => {
+                if self.consume('{') {
+                    self.parse_hex_until('}')?
+                } else {
+                    self.parse_hex(4)?
+                }
+            }
+            c => c,
+        };
+        Ok(c)
+    }
+
+    fn parse_hex(&mut self, max_len: usize) -> Result<char, String> {
+        let mut n = 0;
+        let mut len = 0;
+        while len < max_len {
+            match self.peek().and_then(|c| c.to_digit(16)) {
+                Some(d) => {
+                    n = n * 16 + d;
+                    self.pos += 1;
+                    len += 1;
+                }
+                None => break,
+            }
+        }
+        if len == 0 {
+            return Err("invalid hex escape".to_string());
+        }
+        char::from_u32(n).ok_or_else(|| "invalid Unicode range".to_string())
+    }
+
+    fn parse_hex_until(&mut self, close: char) -> Result<char, String> {
+        let c = self.parse_hex(8)?;
+        if !self.consume(close) {
+            return Err("invalid Unicode escape".to_string());
+        }
+        Ok(c)
+    }
+
+    ///
+    /// Parse a character class. The opening `[` is already consumed.
+    ///
+    fn parse_class(&mut self) -> Result<CharClass, String> {
+        let mut class = CharClass {
+            negated: self.consume('^'),
+            items: vec![],
+        };
+        let mut first = true;
+        loop {
+            let c = match self.next() {
+                Some(c) => c,
+                None => return Err("premature end of char-class".to_string()),
+            };
+            match c {
+                ']' if !first => break,
+                '[' if self.consume(':') => {
+                    let negate = self.consume('^');
+                    let start = self.pos;
+                    while let Some(c) = self.peek() {
+                        if !c.is_ascii_alphabetic() {
+                            break;
+                        }
+                        self.pos += 1;
+                    }
+                    let name: String = self.chars[start..self.pos].iter().collect();
+                    if !self.consume_str(":]") {
+                        return Err("invalid POSIX bracket type".to_string());
+                    }
+                    match PosixClass::from_name(&name) {
+                        Some(posix) => class.items.push(ClassItem::Posix(posix, negate)),
+                        None => return Err("invalid POSIX bracket type".to_string()),
+                    }
+                }
+                '[' => {
+                    let nested = self.parse_class()?;
+                    class.items.push(ClassItem::Class(nested));
+                }
+                '&' if self.consume('&') => {
+                    let lhs = CharClass {
+                        negated: false,
+                        items: std::mem::take(&mut class.items),
+                    };
+                    // the rest of the class (up to the closing `]`) is the right hand side.
+                    let rhs = self.parse_class()?;
+                    class
+                        .items
+                        .push(ClassItem::Intersection(Box::new(lhs), Box::new(rhs)));
+                    break;
+                }
+                c => {
+                    let start = match self.class_atom(c)? {
+                        ClassAtom::Char(c) => c,
+                        ClassAtom::Item(item) => {
+                            class.items.push(item);
+                            first = false;
+                            continue;
+                        }
+                    };
+                    if self.peek() == Some('-')
+                        && self.peek_at(1).is_some()
+                        && self.peek_at(1) != Some(']')
+                    {
+                        self.pos += 1;
+                        let c = self.next().unwrap();
+                        match self.class_atom(c)? {
+                            ClassAtom::Char(end) => {
+                                if end < start {
+                                    return Err("empty range in char class".to_string());
+                                }
+                                class.items.push(ClassItem::Range(start, end));
+                            }
+                            ClassAtom::Item(item) => {
+                                class.items.push(ClassItem::Range(start, start));
+                                class.items.push(ClassItem::Range('-', '-'));
+                                class.items.push(item);
+                            }
+                        }
+                    } else {
+                        class.items.push(ClassItem::Range(start, start));
+                    }
+                }
+            }
+            first = false;
+        }
+        Ok(class)
+    }
+
+    fn class_atom(&mut self, c: char) -> Result<ClassAtom, String> {
+        if c != '\\' {
+            return Ok(ClassAtom::Char(c));
+        }
+        let c = match self.next() {
+            Some(c) => c,
+            None => return Err("premature end of char-class".to_string()),
+        };
+        let item = match c {
+            'd' => ClassItem::Digit(false),
+            'D' => ClassItem::Digit(true),
+            'w' => ClassItem::Word(false),
+            'W' => ClassItem::Word(true),
+            's' => ClassItem::Space(false),
+            'S' => ClassItem::Space(true),
+            'h' => ClassItem::Hex(false),
+            'H' => ClassItem::Hex(true),
+            'b' => return Ok(ClassAtom::Char('\x08')),
+            c => return Ok(ClassAtom::Char(self.escaped_char(c)?)),
+        };
+        Ok(ClassAtom::Item(item))
+    }
+}
+
+enum ClassAtom {
+    Char(char),
+    Item(ClassItem),
+}
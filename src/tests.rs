@@ -294,6 +294,16 @@ mod test {
         run_test("90.78**-43.2");
         run_test("-90.78**43.2");
         run_test("-90.78**-43.2");
+        run_test(
+            r#"
+            def f(a, b)
+              [a ** b, a ** 2, 3 ** b, (a + 11) ** -2]
+            end
+            res = []
+            30.times { |i| res << f(i - 10, i % 7) << f(i + 2, i - 15) << f(2.5, 3) }
+            res
+        "#,
+        );
     }
 
     #[test]
@@ -1073,6 +1083,294 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_const_scope() {
+        run_test(
+            r#"
+            X = 1
+            class A
+              X = 2
+              class B
+                def f; X; end
+              end
+              def f; X; end
+            end
+            class A::B
+              def g; X; end
+            end
+            class C < A
+              def f; X; end
+            end
+            [X, A::X, A.new.f, A::B.new.f, A::B.new.g, C.new.f, A::B, ::X]
+        "#,
+        );
+        run_test(
+            r#"
+            module M
+              Y = 10
+              module N
+                def nesting; Module.nesting; end
+              end
+            end
+            module M::N
+              def nesting2; Module.nesting; end
+            end
+            class D
+              include M
+              include M::N
+              def f; Y; end
+            end
+            d = D.new
+            [d.f, d.nesting, d.nesting2, Module.nesting]
+        "#,
+        );
+        run_test(
+            r#"
+            Z = 1
+            class A
+              def f; Z; end
+            end
+            res = []
+            a = A.new
+            3.times { res << a.f }
+            class A; Z = 2; end
+            3.times { res << a.f }
+            res
+        "#,
+        );
+    }
+
+    #[test]
+    fn test_const_scope_error() {
+        run_test_error(
+            r#"
+            class A; end
+            class A::B::C; end
+        "#,
+        );
+        run_test_error(
+            r#"
+            X = 3
+            class X::B; end
+        "#,
+        );
+        run_test_error(
+            r#"
+            class A
+              Y = 1
+            end
+            class B
+              def f; Y; end
+            end
+            B.new.f
+        "#,
+        );
+    }
+
+    #[test]
+    fn test_super() {
+        run_test(
+            r#"
+            class A
+              def initialize(x, y = 2)
+                @x = x
+                @y = y
+              end
+              def f(a, k: 1)
+                [a, k, @x, @y]
+              end
+              def g
+                yield 5
+              end
+            end
+            class B < A
+              def initialize(x)
+                super(x * 10)
+              end
+              def f(a, k: 3)
+                super + super(a + 1) + super(a, k: 9)
+              end
+              def g
+                super { |x| x * 3 } + super
+              end
+            end
+            b = B.new(4)
+            [b.f(7), b.f(7, k: 5), b.g { |x| x + 1 }]
+        "#,
+        );
+        run_test(
+            r#"
+            module M
+              def f(x)
+                "M" + super
+              end
+            end
+            class C
+              def f(x)
+                "C" + x
+              end
+            end
+            class D < C
+              include M
+              def f(x)
+                "D" + super
+              end
+            end
+            class E < C
+              prepend M
+              def f(x)
+                "E" + super
+              end
+            end
+            res = []
+            3.times { res << D.new.f("!") << E.new.f("?") }
+            res
+        "#,
+        );
+        run_test(
+            r#"
+            class A
+              def f(x)
+                x * 2
+              end
+            end
+            class B < A
+              def f(x)
+                res = 0
+                3.times { |i| res += super }
+                res
+              end
+            end
+            B.new.f(5)
+        "#,
+        );
+        run_test(
+            r#"
+            class A
+              def f(a, *r, k: 0)
+                [a, r, k, block_given? ? yield : nil]
+              end
+              def g(a, (b, c), d)
+                [a, b, c, d]
+              end
+            end
+            class B < A
+              def f(a, *r, k: 0)
+                r = r.map { |x| x * 2 } if a == 0
+                super
+              end
+              def g(a, (b, c), d)
+                [1].map { super }
+              end
+            end
+            b = B.new
+            res = []
+            30.times do |i|
+              res << b.f(i, i, 3, k: 5) << b.f(0, 1, 2) { :blk } << b.f(7)
+              res << b.g(i, [2, 3], 4) << b.g(1, 2, 3)
+            end
+            res
+        "#,
+        );
+    }
+
+    #[test]
+    fn test_super_error() {
+        run_test_error(
+            r#"
+            class A
+              def f
+                super
+              end
+            end
+            A.new.f
+        "#,
+        );
+        run_test_error(
+            r#"
+            class A
+              def f(x); x; end
+            end
+            class B < A
+              def f(x)
+                super(x, x)
+              end
+            end
+            B.new.f(1)
+        "#,
+        );
+    }
+
+    #[test]
+    fn test_singleton_method() {
+        run_test(
+            r#"
+            class Foo
+              def self.create(x)
+                new(x)
+              end
+              class << self
+                attr_accessor :count
+                def make
+                  create(5)
+                end
+              end
+              def initialize(x)
+                @x = x
+              end
+              def x
+                @x
+              end
+            end
+            class Bar < Foo
+              def self.create(x)
+                super(x + 1)
+              end
+            end
+            o = Object.new
+            def o.hello
+              "hello"
+            end
+            class << o
+              def bye
+                "bye " + hello
+              end
+            end
+            Foo.count = 3
+            [Foo.create(3).x, Foo.make.x, Bar.create(3).x, Bar.make.class, o.hello, o.bye,
+             Foo.count, o.class]
+        "#,
+        );
+        run_test(
+            r#"
+            res = []
+            a = Object.new
+            b = Object.new
+            def a.f; 1; end
+            def b.f; 2; end
+            4.times { |i| res << (i % 2 == 0 ? a : b).f }
+            res
+        "#,
+        );
+    }
+
+    #[test]
+    fn test_singleton_method_error() {
+        run_test_error(
+            r#"
+            def 1.foo
+            end
+        "#,
+        );
+        run_test_error(
+            r#"
+            o = Object.new
+            def o.foo; end
+            Object.new.foo
+        "#,
+        );
+    }
+
     #[test]
     fn test_string() {
         run_test(
@@ -1401,6 +1699,139 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_method_keyword() {
+        run_test_with_prelude(
+            r#"
+        [f(1), f(1, b: 5), f(1, c: 7, b: 4), f(2, 3, c: 9)]
+        "#,
+            r#"
+        def f(x, y = 10, b: 2, c: b * 3)
+            [x, y, b, c]
+        end
+        "#,
+        );
+        run_test_with_prelude(
+            r#"
+        [f(a: 1), f(b: 3, a: 2), f(a: 1, c: 3, d: 4)]
+        "#,
+            r#"
+        def f(a:, b: 2, **opts)
+            [a, b, opts.to_a]
+        end
+        "#,
+        );
+        run_test_with_prelude(
+            r#"
+        h = {b: 5, c: 6}
+        [f(1, **h), f(1, b: 2, **h), f(1, **h, c: 7), f(1, 2, 3, b: 4), f({b: 1})[2]]
+        "#,
+            r#"
+        def f(a, *r, b: 10, c: 20)
+            [a, r, b, c]
+        end
+        "#,
+        );
+        run_test_with_prelude(
+            r#"
+        h = {a: 1}
+        [g({a: 2}), g(h), g(a: 3), k(b: 3).to_a, k.to_a]
+        "#,
+            r#"
+        def g(x)
+            x[:a]
+        end
+        def k(**kw)
+            kw
+        end
+        "#,
+        );
+        run_test_with_prelude(
+            r#"
+        [f { |a, b: 2| [a, b] }, f { |a, k: 3, **r| [a, k, r.to_a] }]
+        "#,
+            r#"
+        def f
+            yield 1, b: 5
+        end
+        "#,
+        );
+        run_test(r#"[Integer("12", exception: false), Integer("x", exception: false)]"#);
+        run_test2(
+            r#"
+        def f(x, step: 1)
+            x + step
+        end
+        a = 0
+        for i in 0..30
+          a = f(a, step: i) + f(a)
+        end
+        a
+        "#,
+        );
+        // a keyword Hash bound to a positional or rest parameter is an ordinary Hash.
+        run_test_with_prelude(
+            r#"
+        a = []
+        a.push(k: 3)
+        [g(f(a: 1)), g(r(a: 2)[0]), g(a[0]), g(y { |x| x }), g({c: 4})]
+        "#,
+            r#"
+        def f(h)
+            h
+        end
+        def r(*rest)
+            rest
+        end
+        def y
+            yield(b: 5)
+        end
+        def g(h = nil, **kw)
+            [h, kw]
+        end
+        "#,
+        );
+    }
+
+    #[test]
+    fn test_method_keyword_error() {
+        run_test_error(
+            r#"
+        def f(a:)
+        end
+        f
+        "#,
+        );
+        run_test_error(
+            r#"
+        def f(a: 1)
+        end
+        f(b: 2)
+        "#,
+        );
+        run_test_error(
+            r#"
+        def f(x, a: 1)
+        end
+        f(a: 2)
+        "#,
+        );
+        run_test_error(
+            r#"
+        def f(x, a: 1)
+        end
+        f(1, 2, a: 2)
+        "#,
+        );
+        run_test_error(
+            r#"
+        def f(a: 1)
+        end
+        f(**3)
+        "#,
+        );
+    }
+
     #[test]
     fn test_block_param() {
         run_test_with_prelude(
@@ -1439,4 +1870,345 @@ mod test {
         "#,
         )
     }
+
+    #[test]
+    fn test_rescue() {
+        run_test("begin; 3; rescue; 5; else; 4; end");
+        run_test("begin; raise 'x'; 3; rescue; 5; else; 4; end");
+        run_test("begin; raise 'x'; rescue => e; e.class.to_s; end");
+        run_test("begin; raise RuntimeError, 'x'; rescue StandardError => e; e.class.to_s; end");
+        run_test("begin; raise 'x'; rescue; $!.class.to_s; end");
+        run_test("begin; raise 'x'; rescue; end; $!");
+        run_test("begin; begin; raise 'x'; rescue; raise; end; rescue => e; e.message; end");
+        run_test(
+            "begin; begin; raise TypeError, 'y'; rescue => e; raise; end; rescue => f; [f.class.to_s, f.message]; end",
+        );
+        run_test("begin; raise; rescue => e; [e.class.to_s, e.message]; end");
+        run_test2(
+            r#"
+        a = []
+        for i in 0..10
+          begin
+            a << 100 / (5 - i)
+          rescue => e
+            a << :err
+          end
+        end
+        a
+        "#,
+        );
+        run_test2(
+            r#"
+        $x = []
+        def f(x)
+          begin
+            raise RuntimeError, "x" if x == 1
+            raise StandardError if x == 2
+            $x << :body
+          rescue RuntimeError => e
+            $x << e.class.to_s
+          rescue Integer, StandardError
+            $x << :std
+          else
+            $x << :else
+          ensure
+            $x << :ensure
+          end
+        end
+        f(0); f(1); f(2)
+        $x
+        "#,
+        );
+    }
+
+    #[test]
+    fn test_rescue_nested() {
+        run_test2(
+            r#"
+        def f(x)
+          raise "err" if x == 3
+          x
+        end
+        def iter
+          yield 1
+          yield 3
+          yield 5
+        end
+        def g
+          a = []
+          iter do |x|
+            begin
+              a << f(x)
+            rescue
+              a << :rescued
+            end
+          end
+          a
+        end
+        g
+        "#,
+        );
+        run_test2(
+            r#"
+        a = []
+        begin
+          begin
+            raise "inner"
+          rescue Integer
+            a << :no
+          ensure
+            a << :inner_ensure
+          end
+        rescue => e
+          a << e.class.to_s
+        end
+        a
+        "#,
+        );
+        run_test_error("begin; raise 'x'; rescue Integer; end");
+        run_test_error("begin; 1; ensure; raise 'e'; end");
+    }
+
+    #[test]
+    fn test_ensure() {
+        run_test2(
+            r#"
+        $x = []
+        def f
+          begin
+            return 1
+          ensure
+            $x << 2
+          end
+        end
+        def g
+          i = 0
+          while true
+            begin
+              i += 1
+              break if i == 3
+            ensure
+              $x << i
+            end
+          end
+          i
+        end
+        def h
+          begin
+            begin
+              raise "err"
+            ensure
+              $x << :inner
+            end
+          ensure
+            $x << :outer
+          end
+        end
+        begin
+          h
+        rescue
+          $x << :rescued
+        end
+        [f, g, $x]
+        "#,
+        );
+    }
+
+    #[test]
+    fn test_rescue_jit() {
+        run_test2(
+            r#"
+        $x = []
+        def f(i)
+          f = i * 0.5
+          begin
+            raise "err" if i % 7 == 3
+            f += 10 / (i % 5)
+          rescue ZeroDivisionError
+            $x << [:zero, i, f]
+          rescue => e
+            $x << [e.message, i, f]
+          ensure
+            f += 0.25
+          end
+          f
+        end
+        a = 0.0
+        for i in 0..40
+          a += f(i)
+        end
+        [a, $x]
+        "#,
+        );
+        run_test2(
+            r#"
+        a = []
+        f = 0.0
+        i = 0
+        while i < 50
+          begin
+            f += 1.5
+            raise "err" if i % 10 == 9
+            a << 100 / (i % 6)
+          rescue => e
+            a << [e.class.to_s, f]
+          end
+          i += 1
+        end
+        [a, f]
+        "#,
+        );
+    }
+
+    #[test]
+    fn test_retry() {
+        run_test2(
+            r#"
+        a = []
+        i = 0
+        begin
+          i += 1
+          a << i
+          raise "err" if i < 3
+          a << :done
+        rescue
+          a << :rescued
+          retry
+        ensure
+          a << :ensure
+        end
+        a
+        "#,
+        );
+        run_test2(
+            r#"
+        def f
+          n = 0
+          begin
+            n += 1
+            raise "err" if n < 5
+            n
+          rescue
+            begin
+              retry if n < 4
+            ensure
+              $x << n
+            end
+            -1
+          end
+        end
+        $x = []
+        [f, $x, $!]
+        "#,
+        );
+        run_test_error("retry");
+        run_test_error("begin; 1; rescue; end; retry");
+    }
+
+    #[test]
+    fn test_case() {
+        run_test("case 3; when 1, 2 then :a; when 3 then :b; else :c; end");
+        run_test("case 5; when 1, 2 then :a; when 3 then :b; end");
+        run_test("case 7.0; when 5 then :a; when 7 then :b; end");
+        run_test(r#"case "foo"; when "bar" then 1; when "foo" then 2; end"#);
+        run_test("case :c; when :a, :b then 1; when :c then 2; else 3; end");
+        run_test("case 50; when 0...50 then :low; when 50..100 then :high; end");
+        run_test("case 1.5; when 0..1 then :a; when 1..2 then :b; end");
+        run_test(r#"case "x"; when Integer then :int; when String then :str; end"#);
+        run_test("case [1]; when Array then :ary; end");
+        run_test("x = 10; case; when x < 5 then :a; when x < 20 then :b; else :c; end");
+        run_test2(
+            r#"
+        def f(x)
+          case x
+          when 0 then :zero
+          when 1, 3, 5, 7, 9 then :odd
+          when 2, 4, 6, 8 then :even
+          when Integer then :large
+          when :a, :b then :sym
+          else :other
+          end
+        end
+        def h(x)
+          case x
+          when 1 then :one
+          when 2, 3 then :two_three
+          when 5 then :five
+          else :else
+          end
+        end
+        a = []
+        for i in -1..12
+          a << f(i) << h(i)
+        end
+        a << f(3.0) << f(:a) << f("a") << f(nil) << h(2.0) << h(:a)
+        a
+        "#,
+        );
+        run_test2(
+            r#"
+        def g(x)
+          r = case x
+              when :foo then 1
+              when :bar, :baz then 2
+              when :qux then 3
+              end
+          [r, x]
+        end
+        a = []
+        for i in 0..30
+          a << g(:foo) << g(:baz) << g(:qux) << g(:quux) << g(i)
+        end
+        a
+        "#,
+        );
+        run_test(
+            r#"
+        class Pt
+          attr_reader :x
+          def initialize(x); @x = x; end
+          def ==(other); other.is_a?(Pt) && x == other.x; end
+        end
+        a = []
+        [Pt.new(1), Pt.new(2), 3].each do |v|
+          a << case v
+               when Pt.new(2) then :two
+               when Pt.new(1) then :one
+               else :other
+               end
+        end
+        a
+        "#,
+        );
+    }
+
+    #[test]
+    fn test_special_var() {
+        run_test(
+            r#"
+        def f(s)
+          s =~ /(\d+)/
+          $1
+        end
+        "x" =~ /(x)/
+        a = []
+        for i in 0..30
+          a << f("a#{i}") << $1
+        end
+        a << $~[0]
+        a
+        "#,
+        );
+        run_test(
+            r#"
+        def g
+          res = []
+          ["a1", "b2"].each { |s| s =~ /\d/; res << $& }
+          res << $&
+          res
+        end
+        g
+        "#,
+        );
+    }
 }
@@ -1,5 +1,5 @@
 use crate::*;
-use num::{BigInt, ToPrimitive};
+use num::{BigInt, BigRational, ToPrimitive};
 use ruruby_parse::{Node, NodeKind};
 
 use crate::alloc::{Allocator, GC};
@@ -56,6 +56,20 @@ impl Value {
                             .all(|(lhs, rhs)| Value::eq(*lhs, *rhs))
                 }
                 (ObjKind::RANGE, ObjKind::RANGE) => lhs.as_range() == rhs.as_range(),
+                (ObjKind::RATIONAL, ObjKind::RATIONAL) => lhs.as_rational() == rhs.as_rational(),
+                (ObjKind::COMPLEX, ObjKind::COMPLEX) => {
+                    let (lhs, rhs) = (lhs.as_complex(), rhs.as_complex());
+                    Value::eq(lhs.re, rhs.re) && Value::eq(lhs.im, rhs.im)
+                }
+                (ObjKind::HASH, ObjKind::HASH) => {
+                    let lhs = lhs.as_hash();
+                    let rhs = rhs.as_hash();
+                    lhs.len() == rhs.len()
+                        && lhs
+                            .iter()
+                            .zip(rhs.iter())
+                            .all(|((k1, v1), (k2, v2))| Value::eq(k1, k2) && Value::eq(v1, v2))
+                }
                 _ => false,
             },
             _ => false,
@@ -103,10 +117,22 @@ impl Value {
         }
     }
 
-    pub(crate) fn get_singleton(self, globals: &mut Globals) -> Value {
-        let original_id = self.as_class();
-        let singleton = globals.get_singleton_id(original_id);
-        globals.get_class_obj(singleton)
+    pub(crate) fn is_frozen(&self) -> bool {
+        match self.try_rvalue() {
+            Some(rv) => rv.is_frozen(),
+            None => true,
+        }
+    }
+
+    pub(crate) fn set_frozen(&mut self) {
+        if let Some(rv) = self.try_rvalue_mut() {
+            rv.set_frozen();
+        }
+    }
+
+    pub(crate) fn get_singleton(self, globals: &mut Globals) -> Option<Value> {
+        let singleton = globals.get_singleton_class(self)?;
+        Some(globals.get_class_obj(singleton))
     }
 
     pub(crate) fn get_real_class_id(self, globals: &Globals) -> ClassId {
@@ -135,7 +161,8 @@ impl Value {
 
     pub(crate) extern "C" fn deep_copy(val: Value) -> Self {
         if let Some(rv) = val.try_rvalue() {
-            rv.deep_copy().pack()
+            // copied children are not rooted until the copy of *val* is packed.
+            without_gc(|| rv.deep_copy().pack())
         } else {
             val
         }
@@ -153,6 +180,10 @@ impl Value {
         Value(unsafe { std::num::NonZeroU64::new_unchecked(NIL_VALUE) })
     }
 
+    pub(crate) fn is_nil(&self) -> bool {
+        self.get() == NIL_VALUE
+    }
+
     pub(crate) fn bool(b: bool) -> Self {
         if b {
             Value::from(TRUE_VALUE)
@@ -207,6 +238,10 @@ impl Value {
         RValue::new_class(id).pack()
     }
 
+    pub(crate) fn new_empty_module(id: ClassId) -> Self {
+        RValue::new_module(id).pack()
+    }
+
     pub(crate) fn new_object(class_id: ClassId) -> Self {
         RValue::new_object(class_id).pack()
     }
@@ -223,6 +258,10 @@ impl Value {
         RValue::new_bytes_from_slice(b).pack()
     }
 
+    pub(crate) fn new_string_from_inner(inner: StringInner) -> Self {
+        RValue::new_string_from_inner(inner).pack()
+    }
+
     pub(crate) fn new_array(ary: ArrayInner) -> Self {
         RValue::new_array(ary).pack()
     }
@@ -247,6 +286,57 @@ impl Value {
         RValue::new_time(time).pack()
     }
 
+    pub(crate) fn new_exception(class_id: ClassId, err: MonorubyErr) -> Self {
+        RValue::new_exception(class_id, err).pack()
+    }
+
+    pub(crate) fn new_hash(hash: HashInner) -> Self {
+        RValue::new_hash(hash).pack()
+    }
+
+    pub(crate) fn new_hash_with_class(hash: HashInner, class_id: ClassId) -> Self {
+        RValue::new_hash_with_class(hash, class_id).pack()
+    }
+
+    pub(crate) fn new_proc(proc: ProcInner) -> Self {
+        RValue::new_proc(proc).pack()
+    }
+
+    pub(crate) fn new_regexp(regexp: RegexpInner) -> Self {
+        RValue::new_regexp(regexp).pack()
+    }
+
+    pub(crate) fn new_match_data(match_data: MatchDataInner) -> Self {
+        RValue::new_match_data(match_data).pack()
+    }
+
+    pub(crate) fn new_rational(rational: BigRational) -> Self {
+        RValue::new_rational(rational).pack()
+    }
+
+    pub(crate) fn new_complex(re: Value, im: Value) -> Self {
+        RValue::new_complex(re, im).pack()
+    }
+
+    pub(crate) fn new_fiber(body: FiberBody) -> Self {
+        RValue::new_fiber(FiberInner::new(body)).pack()
+    }
+
+    ///
+    /// Create an Enumerator which iterates by calling *method* of *obj* with *args*.
+    ///
+    pub(crate) fn new_enumerator(obj: Value, method: &str, args: Vec<Value>) -> Self {
+        let method = IdentId::get_ident_id(method);
+        RValue::new_enumerator(EnumeratorInner::new(obj, method, args), ENUMERATOR_CLASS).pack()
+    }
+
+    pub(crate) fn new_enumerator_with_class(
+        enumerator: EnumeratorInner,
+        class_id: ClassId,
+    ) -> Self {
+        RValue::new_enumerator(enumerator, class_id).pack()
+    }
+
     pub(crate) fn unpack(&self) -> RV {
         if let Some(i) = self.try_fixnum() {
             RV::Integer(i)
@@ -386,12 +476,16 @@ impl Value {
         unsafe { &mut *(self.get() as *mut RValue) }
     }
 
-    /*pub(crate) fn is_symbol(&self) -> Option<IdentId> {
+    pub(crate) fn is_symbol(&self) -> Option<IdentId> {
         match self.unpack() {
             RV::Symbol(sym) => Some(sym),
             _ => None,
         }
-    }*/
+    }
+
+    pub(crate) fn as_symbol(&self) -> IdentId {
+        self.is_symbol().unwrap()
+    }
 
     pub(crate) fn as_array(&self) -> &ArrayInner {
         assert_eq!(ObjKind::ARRAY, self.rvalue().kind());
@@ -411,6 +505,125 @@ impl Value {
         }
     }
 
+    pub(crate) fn as_hash(&self) -> &HashInner {
+        assert_eq!(ObjKind::HASH, self.rvalue().kind());
+        self.rvalue().as_hash()
+    }
+
+    pub(crate) fn as_hash_mut(&mut self) -> &mut HashInner {
+        assert_eq!(ObjKind::HASH, self.rvalue().kind());
+        self.rvalue_mut().as_hash_mut()
+    }
+
+    pub(crate) fn is_hash(&self) -> Option<&HashInner> {
+        let rv = self.try_rvalue()?;
+        match rv.kind() {
+            ObjKind::HASH => Some(rv.as_hash()),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_proc(&self) -> &ProcInner {
+        assert_eq!(ObjKind::PROC, self.rvalue().kind());
+        self.rvalue().as_proc()
+    }
+
+    pub(crate) fn as_proc_mut(&mut self) -> &mut ProcInner {
+        assert_eq!(ObjKind::PROC, self.rvalue().kind());
+        self.rvalue_mut().as_proc_mut()
+    }
+
+    pub(crate) fn is_proc(&self) -> Option<&ProcInner> {
+        let rv = self.try_rvalue()?;
+        match rv.kind() {
+            ObjKind::PROC => Some(rv.as_proc()),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_regexp(&self) -> &RegexpInner {
+        assert_eq!(ObjKind::REGEXP, self.rvalue().kind());
+        self.rvalue().as_regexp()
+    }
+
+    pub(crate) fn is_regexp(&self) -> Option<&RegexpInner> {
+        let rv = self.try_rvalue()?;
+        match rv.kind() {
+            ObjKind::REGEXP => Some(rv.as_regexp()),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_match_data(&self) -> &MatchDataInner {
+        assert_eq!(ObjKind::MATCHDATA, self.rvalue().kind());
+        self.rvalue().as_match_data()
+    }
+
+    pub(crate) fn is_match_data(&self) -> Option<&MatchDataInner> {
+        let rv = self.try_rvalue()?;
+        match rv.kind() {
+            ObjKind::MATCHDATA => Some(rv.as_match_data()),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_rational(&self) -> &BigRational {
+        assert_eq!(ObjKind::RATIONAL, self.rvalue().kind());
+        self.rvalue().as_rational()
+    }
+
+    pub(crate) fn is_rational(&self) -> Option<&BigRational> {
+        let rv = self.try_rvalue()?;
+        match rv.kind() {
+            ObjKind::RATIONAL => Some(rv.as_rational()),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_fiber_mut(&mut self) -> &mut FiberInner {
+        assert_eq!(ObjKind::FIBER, self.rvalue().kind());
+        self.rvalue_mut().as_fiber_mut()
+    }
+
+    pub(crate) fn is_fiber(&self) -> Option<&FiberInner> {
+        let rv = self.try_rvalue()?;
+        match rv.kind() {
+            ObjKind::FIBER => Some(rv.as_fiber()),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_enumerator(&self) -> &EnumeratorInner {
+        assert_eq!(ObjKind::ENUMERATOR, self.rvalue().kind());
+        self.rvalue().as_enumerator()
+    }
+
+    pub(crate) fn as_enumerator_mut(&mut self) -> &mut EnumeratorInner {
+        assert_eq!(ObjKind::ENUMERATOR, self.rvalue().kind());
+        self.rvalue_mut().as_enumerator_mut()
+    }
+
+    pub(crate) fn is_enumerator(&self) -> Option<&EnumeratorInner> {
+        let rv = self.try_rvalue()?;
+        match rv.kind() {
+            ObjKind::ENUMERATOR => Some(rv.as_enumerator()),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_complex(&self) -> &ComplexInner {
+        assert_eq!(ObjKind::COMPLEX, self.rvalue().kind());
+        self.rvalue().as_complex()
+    }
+
+    pub(crate) fn is_complex(&self) -> Option<&ComplexInner> {
+        let rv = self.try_rvalue()?;
+        match rv.kind() {
+            ObjKind::COMPLEX => Some(rv.as_complex()),
+            _ => None,
+        }
+    }
+
     pub(crate) fn is_class(&self) -> Option<ClassId> {
         let rv = self.try_rvalue()?;
         match rv.kind() {
@@ -419,8 +632,28 @@ impl Value {
         }
     }
 
+    pub(crate) fn is_module(&self) -> Option<ClassId> {
+        let rv = self.try_rvalue()?;
+        match rv.kind() {
+            ObjKind::MODULE => Some(rv.as_class()),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn is_class_or_module(&self) -> Option<ClassId> {
+        let rv = self.try_rvalue()?;
+        match rv.kind() {
+            ObjKind::CLASS | ObjKind::MODULE => Some(rv.as_class()),
+            _ => None,
+        }
+    }
+
+    /// Get *ClassId* of the class or module object.
     pub(crate) fn as_class(&self) -> ClassId {
-        assert_eq!(ObjKind::CLASS, self.rvalue().kind());
+        assert!(matches!(
+            self.rvalue().kind(),
+            ObjKind::CLASS | ObjKind::MODULE
+        ));
         self.rvalue().as_class()
     }
 
@@ -434,6 +667,30 @@ impl Value {
         }
     }
 
+    pub(crate) fn expect_module(&self, name: IdentId, globals: &mut Globals) -> Option<ClassId> {
+        match self.is_module() {
+            Some(module) => Some(module),
+            None => {
+                globals.err_is_not_module(name);
+                None
+            }
+        }
+    }
+
+    pub(crate) fn expect_class_or_module(
+        &self,
+        name: IdentId,
+        globals: &mut Globals,
+    ) -> Option<ClassId> {
+        match self.is_class_or_module() {
+            Some(class) => Some(class),
+            None => {
+                globals.err_is_not_class_or_module(name);
+                None
+            }
+        }
+    }
+
     pub(crate) fn expect_symbol_or_string(&self, globals: &mut Globals) -> Option<IdentId> {
         match self.unpack() {
             RV::Symbol(sym) => return Some(sym),
@@ -447,6 +704,75 @@ impl Value {
         None
     }
 
+    pub(crate) fn is_numeric(&self) -> bool {
+        matches!(self.unpack(), RV::Integer(_) | RV::BigInt(_) | RV::Float(_))
+    }
+
+    pub(crate) fn as_float(&self) -> f64 {
+        match self.unpack() {
+            RV::Float(f) => f,
+            _ => unreachable!(),
+        }
+    }
+
+    ///
+    /// Convert a numeric value (Integer, Float or Rational) to f64.
+    ///
+    pub(crate) fn try_float(&self) -> Option<f64> {
+        match self.unpack() {
+            RV::Integer(i) => Some(i as f64),
+            RV::BigInt(b) => Some(b.to_f64().unwrap()),
+            RV::Float(f) => Some(f),
+            _ => self.is_rational().map(|r| r.to_f64().unwrap()),
+        }
+    }
+
+    ///
+    /// Convert Integer or Rational to BigRational.
+    ///
+    pub(crate) fn try_rational(&self) -> Option<BigRational> {
+        match self.unpack() {
+            RV::Integer(i) => Some(BigRational::from_integer(BigInt::from(i))),
+            RV::BigInt(b) => Some(BigRational::from_integer(b.clone())),
+            _ => self.is_rational().cloned(),
+        }
+    }
+
+    ///
+    /// Convert a numeric value to f64.
+    ///
+    /// Set TypeError for other values.
+    ///
+    pub(crate) fn expect_float(&self, globals: &mut Globals) -> Option<f64> {
+        match self.try_float() {
+            Some(f) => Some(f),
+            None => {
+                globals.err_cant_conert_into_float(*self);
+                None
+            }
+        }
+    }
+
+    pub(crate) fn expect_integer(&self, globals: &mut Globals) -> Option<i64> {
+        match self.try_fixnum() {
+            Some(i) => Some(i),
+            None => {
+                globals.err_no_implict_conv(*self, INTEGER_CLASS);
+                None
+            }
+        }
+    }
+
+    pub(crate) fn expect_string_inner(&self, globals: &mut Globals) -> Option<&StringInner> {
+        match self.is_string() {
+            Some(s) => Some(s),
+            None => {
+                globals.err_no_implict_conv(*self, STRING_CLASS);
+                None
+            }
+        }
+    }
+
     pub(crate) fn expect_string(&self, globals: &mut Globals) -> Option<String> {
         match self.unpack() {
             RV::String(s) => {
@@ -469,10 +795,49 @@ impl Value {
         self.rvalue().as_string()
     }
 
+    pub(crate) fn as_string_inner(&self) -> &StringInner {
+        assert_eq!(ObjKind::BYTES, self.rvalue().kind());
+        self.rvalue().as_string_inner()
+    }
+
+    pub(crate) fn as_string_inner_mut(&mut self) -> &mut StringInner {
+        assert_eq!(ObjKind::BYTES, self.rvalue().kind());
+        self.rvalue_mut().as_string_inner_mut()
+    }
+
+    pub(crate) fn is_string(&self) -> Option<&StringInner> {
+        let rv = self.try_rvalue()?;
+        match rv.kind() {
+            ObjKind::BYTES => Some(rv.as_string_inner()),
+            _ => None,
+        }
+    }
+
     pub(crate) fn as_range(&self) -> &Range {
         assert_eq!(ObjKind::RANGE, self.rvalue().kind());
         self.rvalue().as_range()
     }
+
+    pub(crate) fn is_range(&self) -> Option<&Range> {
+        let rv = self.try_rvalue()?;
+        match rv.kind() {
+            ObjKind::RANGE => Some(rv.as_range()),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn is_exception(&self) -> Option<&MonorubyErr> {
+        let rv = self.try_rvalue()?;
+        match rv.kind() {
+            ObjKind::EXCEPTION => Some(rv.as_exception()),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_exception_mut(&mut self) -> &mut MonorubyErr {
+        assert_eq!(ObjKind::EXCEPTION, self.rvalue().kind());
+        self.rvalue_mut().as_exception_mut()
+    }
 }
 
 impl Value {
@@ -516,6 +881,13 @@ impl Value {
                 let end = Self::from_ast(end, globals);
                 Value::new_range(start, end, *exclude_end)
             }
+            NodeKind::Hash(v, ..) => {
+                let v = v
+                    .iter()
+                    .map(|(k, v)| (Self::from_ast(k, globals), Self::from_ast(v, globals)))
+                    .collect();
+                Value::new_hash(HashInner::from_native_pairs(v))
+            }
             _ => unreachable!(),
         }
     }